@@ -74,6 +74,15 @@ impl<F> std::fmt::Display for ACVMStatus<F> {
     }
 }
 
+/// A sample of the call stack taken for each Brillig opcode executed while profiling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfilingSample {
+    /// The locations of the call stack, ending with the location of the executed opcode.
+    pub call_stack: Vec<OpcodeLocation>,
+}
+
+pub type ProfilingSamples = Vec<ProfilingSample>;
+
 pub enum StepResult<'a, F, B: BlackBoxFunctionSolver<F>> {
     Status(ACVMStatus<F>),
     IntoBrillig(BrilligSolver<'a, F, B>),
@@ -181,6 +190,12 @@ pub struct ACVM<'a, F, B: BlackBoxFunctionSolver<F>> {
     unconstrained_functions: &'a [BrilligBytecode<F>],
 
     assertion_payloads: &'a [(OpcodeLocation, AssertionPayload<F>)],
+
+    /// Whether to sample the call stack of each executed Brillig opcode
+    profiling_active: bool,
+
+    /// The samples collected while profiling
+    profiling_samples: ProfilingSamples,
 }
 
 impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
@@ -190,6 +205,7 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
         initial_witness: WitnessMap<F>,
         unconstrained_functions: &'a [BrilligBytecode<F>],
         assertion_payloads: &'a [(OpcodeLocation, AssertionPayload<F>)],
+        profiling_active: bool,
     ) -> Self {
         let status = if opcodes.is_empty() { ACVMStatus::Solved } else { ACVMStatus::InProgress };
         ACVM {
@@ -205,6 +221,8 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
             acir_call_results: Vec::default(),
             unconstrained_functions,
             assertion_payloads,
+            profiling_active,
+            profiling_samples: Vec::new(),
         }
     }
 
@@ -248,6 +266,11 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
         &self.status
     }
 
+    /// Takes the samples of the Brillig call stacks collected so far while profiling.
+    pub fn take_profiling_samples(&mut self) -> ProfilingSamples {
+        std::mem::take(&mut self.profiling_samples)
+    }
+
     /// Sets the VM status to [ACVMStatus::Failure] using the provided `error`.
     /// Returns the new status.
     fn fail(&mut self, error: OpcodeResolutionError<F>) -> ACVMStatus<F> {
@@ -478,10 +501,15 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
                 &self.unconstrained_functions[*id as usize].bytecode,
                 self.backend,
                 self.instruction_pointer,
+                self.profiling_active,
             )?,
         };
 
-        let result = solver.solve().map_err(|err| self.map_brillig_error(err))?;
+        let result = solver.solve();
+        if self.profiling_active {
+            self.profiling_samples.extend(solver.take_profiling_samples());
+        }
+        let result = result.map_err(|err| self.map_brillig_error(err))?;
 
         match result {
             BrilligSolverStatus::ForeignCallWait(foreign_call) => {
@@ -549,6 +577,7 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
             &self.unconstrained_functions[*id as usize].bytecode,
             self.backend,
             self.instruction_pointer,
+            self.profiling_active,
         );
         match solver {
             Ok(solver) => StepResult::IntoBrillig(solver),
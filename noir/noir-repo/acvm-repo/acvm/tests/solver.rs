@@ -39,7 +39,8 @@ fn bls12_381_circuit() {
     ])
     .into();
 
-    let mut acvm = ACVM::new(&StubbedBlackBoxSolver, &opcodes, witness_assignments, &[], &[]);
+    let mut acvm =
+        ACVM::new(&StubbedBlackBoxSolver, &opcodes, witness_assignments, &[], &[], false);
     // use the partial witness generation solver with our acir program
     let solver_status = acvm.solve();
     assert_eq!(solver_status, ACVMStatus::Solved, "should be fully solved");
@@ -146,6 +147,7 @@ fn inversion_brillig_oracle_equivalence() {
         witness_assignments,
         &unconstrained_functions,
         &[],
+        false,
     );
     // use the partial witness generation solver with our acir program
     let solver_status = acvm.solve();
@@ -291,6 +293,7 @@ fn double_inversion_brillig_oracle() {
         witness_assignments,
         &unconstrained_functions,
         &[],
+        false,
     );
 
     // use the partial witness generation solver with our acir program
@@ -428,6 +431,7 @@ fn oracle_dependent_execution() {
         witness_assignments,
         &unconstrained_functions,
         &[],
+        false,
     );
 
     // use the partial witness generation solver with our acir program
@@ -540,6 +544,7 @@ fn brillig_oracle_predicate() {
         witness_assignments,
         &unconstrained_functions,
         &[],
+        false,
     );
     let solver_status = acvm.solve();
     assert_eq!(solver_status, ACVMStatus::Solved, "should be fully solved");
@@ -576,7 +581,7 @@ fn unsatisfied_opcode_resolved() {
     let opcodes = vec![Opcode::AssertZero(opcode_a)];
     let unconstrained_functions = vec![];
     let mut acvm =
-        ACVM::new(&StubbedBlackBoxSolver, &opcodes, values, &unconstrained_functions, &[]);
+        ACVM::new(&StubbedBlackBoxSolver, &opcodes, values, &unconstrained_functions, &[], false);
     let solver_status = acvm.solve();
     assert_eq!(
         solver_status,
@@ -666,7 +671,7 @@ fn unsatisfied_opcode_resolved_brillig() {
     ];
     let unconstrained_functions = vec![brillig_bytecode];
     let mut acvm =
-        ACVM::new(&StubbedBlackBoxSolver, &opcodes, values, &unconstrained_functions, &[]);
+        ACVM::new(&StubbedBlackBoxSolver, &opcodes, values, &unconstrained_functions, &[], false);
     let solver_status = acvm.solve();
     assert_eq!(
         solver_status,
@@ -714,8 +719,14 @@ fn memory_operations() {
 
     let opcodes = vec![init, read_op, expression];
     let unconstrained_functions = vec![];
-    let mut acvm =
-        ACVM::new(&StubbedBlackBoxSolver, &opcodes, initial_witness, &unconstrained_functions, &[]);
+    let mut acvm = ACVM::new(
+        &StubbedBlackBoxSolver,
+        &opcodes,
+        initial_witness,
+        &unconstrained_functions,
+        &[],
+        false,
+    );
     let solver_status = acvm.solve();
     assert_eq!(solver_status, ACVMStatus::Solved);
     let witness_map = acvm.finalize();
@@ -179,6 +179,7 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ProgramExecutor<'a, B> {
                 initial_witness,
                 self.unconstrained_functions,
                 &circuit.assert_messages,
+                false,
             );
 
             loop {
@@ -32,6 +32,15 @@ mod memory;
 /// The error call stack contains the opcode indexes of the call stack at the time of failure, plus the index of the opcode that failed.
 pub type ErrorCallStack = Vec<usize>;
 
+/// A sample of the call stack taken for each opcode executed while profiling.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BrilligProfilingSample {
+    /// The opcode indexes of the call stack, plus the index of the executed opcode.
+    pub call_stack: Vec<usize>,
+}
+
+pub type BrilligProfilingSamples = Vec<BrilligProfilingSample>;
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum FailureReason {
     Trap { revert_data_offset: usize, revert_data_size: usize },
@@ -89,6 +98,10 @@ pub struct VM<'a, F, B: BlackBoxFunctionSolver<F>> {
     black_box_solver: &'a B,
     // The solver for big integers
     bigint_solver: BrilligBigintSolver,
+    // Whether to sample the call stack of each executed opcode
+    profiling_active: bool,
+    // The samples collected while profiling
+    profiling_samples: BrilligProfilingSamples,
 }
 
 impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
@@ -98,6 +111,7 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
         bytecode: &'a [Opcode<F>],
         foreign_call_results: Vec<ForeignCallResult<F>>,
         black_box_solver: &'a B,
+        profiling_active: bool,
     ) -> Self {
         Self {
             calldata,
@@ -110,6 +124,8 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
             call_stack: Vec::new(),
             black_box_solver,
             bigint_solver: Default::default(),
+            profiling_active,
+            profiling_samples: Vec::new(),
         }
     }
 
@@ -195,8 +211,28 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
         self.call_stack.iter().copied().chain(std::iter::once(self.program_counter)).collect()
     }
 
+    /// Takes the call stack samples collected so far while profiling.
+    pub fn take_profiling_samples(&mut self) -> BrilligProfilingSamples {
+        std::mem::take(&mut self.profiling_samples)
+    }
+
     /// Process a single opcode and modify the program counter.
     pub fn process_opcode(&mut self) -> VMStatus<F> {
+        if !self.profiling_active {
+            return self.process_opcode_internal();
+        }
+
+        let call_stack = self.get_call_stack();
+        let status = self.process_opcode_internal();
+        // A foreign call is processed again once its result has been resolved, so it is only
+        // sampled then
+        if !matches!(status, VMStatus::ForeignCallWait { .. }) {
+            self.profiling_samples.push(BrilligProfilingSample { call_stack });
+        }
+        status
+    }
+
+    fn process_opcode_internal(&mut self) -> VMStatus<F> {
         let opcode = &self.bytecode[self.program_counter];
         match opcode {
             Opcode::BinaryFieldOp { op, lhs, rhs, destination: result } => {
@@ -732,7 +768,7 @@ mod tests {
 
         // Start VM
         let opcodes = [calldata_copy];
-        let mut vm = VM::new(calldata, &opcodes, vec![], &StubbedBlackBoxSolver);
+        let mut vm = VM::new(calldata, &opcodes, vec![], &StubbedBlackBoxSolver, false);
 
         // Process a single VM opcode
         //
@@ -749,6 +785,24 @@ mod tests {
         assert_eq!(output_value.to_field(), FieldElement::from(27u128));
     }
 
+    #[test]
+    fn profiling_samples_call_stacks() {
+        let opcodes = [
+            Opcode::Call { location: 2 },
+            Opcode::Stop { return_data_offset: 0, return_data_size: 0 },
+            Opcode::Return,
+        ];
+        let mut vm = VM::new(vec![], &opcodes, vec![], &StubbedBlackBoxSolver, true);
+
+        let status = vm.process_opcodes();
+        assert_eq!(status, VMStatus::Finished { return_data_offset: 0, return_data_size: 0 });
+
+        let call_stacks: Vec<_> =
+            vm.take_profiling_samples().into_iter().map(|sample| sample.call_stack).collect();
+        assert_eq!(call_stacks, vec![vec![0], vec![0, 2], vec![1]]);
+        assert!(vm.take_profiling_samples().is_empty());
+    }
+
     #[test]
     fn jmpif_opcode() {
         let mut calldata: Vec<FieldElement> = vec![];
@@ -776,7 +830,7 @@ mod tests {
         opcodes.push(Opcode::Jump { location: 3 });
         opcodes.push(Opcode::JumpIf { condition: destination, location: 4 });
 
-        let mut vm = VM::new(calldata, &opcodes, vec![], &StubbedBlackBoxSolver);
+        let mut vm = VM::new(calldata, &opcodes, vec![], &StubbedBlackBoxSolver, false);
 
         let status = vm.process_opcode();
         assert_eq!(status, VMStatus::InProgress);
@@ -833,7 +887,7 @@ mod tests {
             jump_if_not_opcode,
             add_opcode,
         ];
-        let mut vm = VM::new(calldata, &opcodes, vec![], &StubbedBlackBoxSolver);
+        let mut vm = VM::new(calldata, &opcodes, vec![], &StubbedBlackBoxSolver, false);
         let status = vm.process_opcode();
         assert_eq!(status, VMStatus::InProgress);
 
@@ -881,7 +935,7 @@ mod tests {
             },
             Opcode::Stop { return_data_offset: 1, return_data_size: 1 },
         ];
-        let mut vm = VM::new(calldata, opcodes, vec![], &StubbedBlackBoxSolver);
+        let mut vm = VM::new(calldata, opcodes, vec![], &StubbedBlackBoxSolver, false);
 
         let status = vm.process_opcode();
         assert_eq!(status, VMStatus::InProgress);
@@ -912,7 +966,7 @@ mod tests {
             Opcode::Mov { destination: MemoryAddress::from(2), source: MemoryAddress::from(0) };
 
         let opcodes = &[calldata_copy, mov_opcode];
-        let mut vm = VM::new(calldata, opcodes, vec![], &StubbedBlackBoxSolver);
+        let mut vm = VM::new(calldata, opcodes, vec![], &StubbedBlackBoxSolver, false);
 
         let status = vm.process_opcode();
         assert_eq!(status, VMStatus::InProgress);
@@ -969,7 +1023,7 @@ mod tests {
                 condition: MemoryAddress(1),
             },
         ];
-        let mut vm = VM::new(calldata, opcodes, vec![], &StubbedBlackBoxSolver);
+        let mut vm = VM::new(calldata, opcodes, vec![], &StubbedBlackBoxSolver, false);
 
         let status = vm.process_opcode();
         assert_eq!(status, VMStatus::InProgress);
@@ -1052,7 +1106,7 @@ mod tests {
             .chain(cast_opcodes)
             .chain([equal_opcode, not_equal_opcode, less_than_opcode, less_than_equal_opcode])
             .collect();
-        let mut vm = VM::new(calldata, &opcodes, vec![], &StubbedBlackBoxSolver);
+        let mut vm = VM::new(calldata, &opcodes, vec![], &StubbedBlackBoxSolver, false);
 
         // Calldata copy
         let status = vm.process_opcode();
@@ -1348,7 +1402,7 @@ mod tests {
         calldata: Vec<F>,
         opcodes: &[Opcode<F>],
     ) -> VM<'_, F, StubbedBlackBoxSolver> {
-        let mut vm = VM::new(calldata, opcodes, vec![], &StubbedBlackBoxSolver);
+        let mut vm = VM::new(calldata, opcodes, vec![], &StubbedBlackBoxSolver, false);
         brillig_execute(&mut vm);
         assert_eq!(vm.call_stack, vec![]);
         vm
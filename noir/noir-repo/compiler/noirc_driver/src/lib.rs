@@ -15,15 +15,16 @@ use noirc_evaluator::errors::RuntimeError;
 use noirc_evaluator::ssa::SsaProgramArtifact;
 use noirc_frontend::debug::build_debug_crate_file;
 use noirc_frontend::graph::{CrateId, CrateName};
+use noirc_frontend::hir::def_collector::dc_crate::CompilationError;
 use noirc_frontend::hir::def_map::{Contract, CrateDefMap};
 use noirc_frontend::hir::Context;
 use noirc_frontend::macros_api::MacroProcessor;
 use noirc_frontend::monomorphization::{
-    errors::MonomorphizationError, monomorphize, monomorphize_debug,
+    auto_fold::auto_fold, errors::MonomorphizationError, monomorphize, monomorphize_debug,
 };
 use noirc_frontend::node_interner::FuncId;
 use noirc_frontend::token::SecondaryAttribute;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tracing::info;
 
 mod abi_gen;
@@ -36,6 +37,8 @@ use debug::filter_relevant_files;
 
 pub use contract::{CompiledContract, CompiledContractOutputs, ContractFunction};
 pub use debug::DebugFile;
+pub use noirc_evaluator::brillig::BrilligRegisterUsage;
+pub use noirc_frontend::monomorphization::auto_fold::AutoFoldedFunction;
 pub use program::CompiledProgram;
 
 const STD_CRATE_NAME: &str = "std";
@@ -99,6 +102,11 @@ pub struct CompileOptions {
     #[arg(long, hide = true)]
     pub force_brillig: bool,
 
+    /// Automatically compile large functions which are called many times as separate ACIR functions,
+    /// as if they were marked `#[fold]`
+    #[arg(long)]
+    pub auto_fold: bool,
+
     /// Use the deprecated name resolution & type checking passes instead of the elaborator
     #[arg(long, hide = true)]
     pub use_legacy: bool,
@@ -106,6 +114,71 @@ pub struct CompileOptions {
     /// Outputs the paths to any modified artifacts
     #[arg(long, hide = true)]
     pub show_artifact_paths: bool,
+
+    /// Interpret the SSA before and after each optimization pass and report any pass which changes its behavior
+    #[arg(long, hide = true)]
+    pub check_ssa_passes: bool,
+
+    /// Check that the SSA is well formed after each optimization pass (always done in debug builds)
+    #[arg(long, hide = true)]
+    pub validate_ssa: bool,
+
+    /// Write the SSA after each pass to numbered files in `<DIR>/<entry point>/`, where the entry
+    /// point is `main` or the name of a contract function. `<target>/ssa` is used by default.
+    #[arg(long, value_name = "DIR", hide = true)]
+    pub dump_ssa: Option<Option<PathBuf>>,
+
+    /// Don't run the named SSA optimization pass. May be repeated.
+    #[arg(long, value_name = "PASS", value_parser = parse_optional_ssa_pass, hide = true)]
+    pub skip_ssa_pass: Vec<String>,
+
+    /// Don't run any SSA optimization passes after the named pass.
+    /// Passes which are needed to generate ACIR still run.
+    #[arg(long, value_name = "PASS", value_parser = parse_ssa_pass, hide = true)]
+    pub stop_after_ssa_pass: Option<String>,
+
+    /// Print the time taken by each SSA pass and how it changed the number of instructions
+    #[arg(long, hide = true)]
+    pub show_ssa_pass_stats: bool,
+}
+
+impl CompileOptions {
+    /// Returns these options with `--dump-ssa` writing to `<target_dir>/ssa` if no directory
+    /// was given, so that the dumps end up beside the package's other build artifacts.
+    pub fn with_ssa_dump_target_dir(&self, target_dir: &Path) -> CompileOptions {
+        let mut options = self.clone();
+        if let Some(None) = options.dump_ssa {
+            options.dump_ssa = Some(Some(target_dir.join("ssa")));
+        }
+        options
+    }
+
+    /// The directory SSA dumps should be written to, if any. Without a target directory
+    /// from `with_ssa_dump_target_dir`, this falls back to `target/ssa`.
+    fn ssa_dump_directory(&self) -> Option<PathBuf> {
+        let directory = self.dump_ssa.as_ref()?;
+        Some(directory.clone().unwrap_or_else(|| Path::new("target").join("ssa")))
+    }
+}
+
+fn parse_ssa_pass(input: &str) -> Result<String, String> {
+    let names = noirc_evaluator::ssa::ssa_pass_names();
+    if names.contains(&input) {
+        Ok(input.to_string())
+    } else {
+        Err(format!("unknown SSA pass, expected one of: {}", names.join(", ")))
+    }
+}
+
+fn parse_optional_ssa_pass(input: &str) -> Result<String, String> {
+    let names = noirc_evaluator::ssa::optional_ssa_pass_names();
+    if names.contains(&input) {
+        Ok(input.to_string())
+    } else if noirc_evaluator::ssa::ssa_pass_names().contains(&input) {
+        Err("this SSA pass is needed to generate ACIR, so it can't be skipped".to_string())
+    } else {
+        Err(format!("unknown SSA pass, expected one of: {}", names.join(", ")))
+    }
 }
 
 fn parse_expression_width(input: &str) -> Result<ExpressionWidth, std::io::Error> {
@@ -259,15 +332,10 @@ pub fn check_crate(
     disable_macros: bool,
     use_legacy: bool,
 ) -> CompilationResult<()> {
-    let macros: &[&dyn MacroProcessor] =
-        if disable_macros { &[] } else { &[&aztec_macros::AztecMacro as &dyn MacroProcessor] };
-
-    let mut errors = vec![];
-    let diagnostics = CrateDefMap::collect_defs(crate_id, context, use_legacy, macros);
-    errors.extend(diagnostics.into_iter().map(|(error, file_id)| {
-        let diagnostic = CustomDiagnostic::from(&error);
-        diagnostic.in_file(file_id)
-    }));
+    let errors = vecmap(
+        collect_crate_errors(context, crate_id, disable_macros, use_legacy),
+        |(error, file_id)| CustomDiagnostic::from(&error).in_file(file_id),
+    );
 
     if has_errors(&errors, deny_warnings) {
         Err(errors)
@@ -276,6 +344,20 @@ pub fn check_crate(
     }
 }
 
+/// Same as [check_crate], but returns the errors and warnings as reported by the frontend
+/// so that tooling can inspect them rather than only display them.
+pub fn collect_crate_errors(
+    context: &mut Context,
+    crate_id: CrateId,
+    disable_macros: bool,
+    use_legacy: bool,
+) -> Vec<(CompilationError, FileId)> {
+    let macros: &[&dyn MacroProcessor] =
+        if disable_macros { &[] } else { &[&aztec_macros::AztecMacro as &dyn MacroProcessor] };
+
+    CrateDefMap::collect_defs(crate_id, context, use_legacy, macros)
+}
+
 pub fn compute_function_abi(
     context: &Context,
     crate_id: &CrateId,
@@ -522,13 +604,21 @@ pub fn compile_no_check(
     cached_program: Option<CompiledProgram>,
     force_compile: bool,
 ) -> Result<CompiledProgram, CompileError> {
-    let program = if options.instrument_debug {
+    let mut program = if options.instrument_debug {
         monomorphize_debug(main_function, &mut context.def_interner, &context.debug_instrumenter)?
     } else {
         monomorphize(main_function, &mut context.def_interner)?
     };
 
-    let hash = fxhash::hash64(&program);
+    // Folding is decided before hashing the program so that the decisions are reflected in its hash.
+    let auto_folded = if options.auto_fold { auto_fold(&mut program) } else { Vec::new() };
+
+    let mut hash = fxhash::hash64(&program);
+    // A program compiled without some of its optimizations must not be reused by a normal compilation
+    let skips_passes = !options.skip_ssa_pass.is_empty() || options.stop_after_ssa_pass.is_some();
+    if skips_passes {
+        hash = fxhash::hash64(&(hash, &options.skip_ssa_pass, &options.stop_after_ssa_pass));
+    }
     let hashes_match = cached_program.as_ref().map_or(false, |program| program.hash == hash);
     if options.show_monomorphized {
         println!("{program}");
@@ -536,8 +626,15 @@ pub fn compile_no_check(
 
     // If user has specified that they want to see intermediate steps printed then we should
     // force compilation even if the program hasn't changed.
-    let force_compile =
-        force_compile || options.print_acir || options.show_brillig || options.show_ssa;
+    let force_compile = force_compile
+        || options.print_acir
+        || options.show_brillig
+        || options.show_ssa
+        || options.check_ssa_passes
+        || options.validate_ssa
+        || options.dump_ssa.is_some()
+        || options.show_ssa_pass_stats
+        || skips_passes;
 
     if !force_compile && hashes_match {
         info!("Program matches existing artifact, returning early");
@@ -549,10 +646,26 @@ pub fn compile_no_check(
         enable_brillig_logging: options.show_brillig,
         force_brillig_output: options.force_brillig,
         print_codegen_timings: options.benchmark_codegen,
+        check_ssa_passes: options.check_ssa_passes,
+        validate_ssa: options.validate_ssa,
+        ssa_dump_directory: options.ssa_dump_directory().map(|directory| {
+            // The dumps of each program entry point are kept separate as they contain the whole program
+            directory.join(context.function_name(&main_function))
+        }),
+        skip_ssa_passes: options.skip_ssa_pass.clone(),
+        stop_after_ssa_pass: options.stop_after_ssa_pass.clone(),
+        show_ssa_pass_statistics: options.show_ssa_pass_stats,
     };
 
-    let SsaProgramArtifact { program, debug, warnings, names, error_types, .. } =
-        create_program(program, &ssa_evaluator_options)?;
+    let SsaProgramArtifact {
+        program,
+        debug,
+        warnings,
+        names,
+        error_types,
+        brillig_register_usage,
+        ..
+    } = create_program(program, &ssa_evaluator_options)?;
 
     let abi = abi_gen::gen_abi(context, &main_function, return_visibility, error_types);
     let file_map = filter_relevant_files(&debug, &context.file_manager);
@@ -566,5 +679,7 @@ pub fn compile_no_check(
         noir_version: NOIR_ARTIFACT_VERSION_STRING.to_string(),
         warnings,
         names,
+        auto_folded,
+        brillig_register_usage,
     })
 }
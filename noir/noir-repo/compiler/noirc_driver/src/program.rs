@@ -7,7 +7,7 @@ use noirc_errors::debug_info::DebugInfo;
 use noirc_evaluator::errors::SsaReport;
 use serde::{Deserialize, Serialize};
 
-use super::debug::DebugFile;
+use super::{debug::DebugFile, AutoFoldedFunction, BrilligRegisterUsage};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CompiledProgram {
@@ -29,4 +29,10 @@ pub struct CompiledProgram {
     pub warnings: Vec<SsaReport>,
     /// Names of the functions in the program. These are used for more informative debugging and benchmarking.
     pub names: Vec<String>,
+    /// Functions which were compiled as separate ACIR functions due to the `--auto-fold` option.
+    #[serde(default)]
+    pub auto_folded: Vec<AutoFoldedFunction>,
+    /// The number of registers needed by each unconstrained function of the program.
+    #[serde(default)]
+    pub brillig_register_usage: Vec<BrilligRegisterUsage>,
 }
@@ -0,0 +1,80 @@
+use std::path::Path;
+
+use noirc_driver::{
+    add_dep, file_manager_with_stdlib, prepare_crate, prepare_dependency, ErrorsAndWarnings,
+};
+use noirc_errors::FileDiagnostic;
+use noirc_frontend::hir::{def_map::parse_file, Context};
+
+// A library re-exporting `bar` and `baz` from one of its modules
+const LIBRARY_SOURCE: &str = "
+mod foo {
+    pub fn bar() -> Field { 1 }
+    pub fn baz() -> Field { 2 }
+}
+
+use foo::bar;
+use foo::baz;
+";
+
+/// Type-checks a program depending on `LIBRARY_SOURCE` as `lib`, returning its warnings
+fn check_program_with_library(source: &str) -> Result<Vec<FileDiagnostic>, ErrorsAndWarnings> {
+    let root = Path::new("");
+    let file_name = Path::new("main.nr");
+    let library_file_name = Path::new("lib/lib.nr");
+    let mut file_manager = file_manager_with_stdlib(root);
+    file_manager.add_file_with_source(file_name, source.to_owned()).expect(
+        "Adding source buffer to file manager should never fail when file manager is empty",
+    );
+    file_manager
+        .add_file_with_source(library_file_name, LIBRARY_SOURCE.to_owned())
+        .expect("Adding source buffer to file manager should never fail");
+    let parsed_files = file_manager
+        .as_file_map()
+        .all_file_ids()
+        .map(|&file_id| (file_id, parse_file(&file_manager, file_id)))
+        .collect();
+
+    let mut context = Context::new(file_manager, parsed_files);
+    let root_crate_id = prepare_crate(&mut context, file_name);
+    let library_crate_id = prepare_dependency(&mut context, library_file_name);
+    add_dep(&mut context, root_crate_id, library_crate_id, "lib".parse().unwrap());
+
+    let ((), warnings) =
+        noirc_driver::check_crate(&mut context, root_crate_id, false, false, false)?;
+    Ok(warnings)
+}
+
+#[test]
+fn imports_of_dependencies_are_not_reported_as_unused() -> Result<(), ErrorsAndWarnings> {
+    // Only `bar` is used through the library's import, `baz` isn't used at all
+    let source = "
+use lib::bar;
+
+fn main() -> pub Field {
+    bar()
+}";
+
+    let warnings = check_program_with_library(source)?;
+    assert_eq!(warnings, Vec::new(), "expected no warnings, got {warnings:?}");
+
+    Ok(())
+}
+
+#[test]
+fn unused_imports_of_dependencies_are_reported() -> Result<(), ErrorsAndWarnings> {
+    let source = "
+use lib::bar;
+use lib::baz;
+
+fn main() -> pub Field {
+    bar()
+}";
+
+    let warnings = check_program_with_library(source)?;
+    let messages: Vec<_> =
+        warnings.iter().map(|warning| warning.diagnostic.message.as_str()).collect();
+    assert_eq!(messages, vec!["unused import baz"]);
+
+    Ok(())
+}
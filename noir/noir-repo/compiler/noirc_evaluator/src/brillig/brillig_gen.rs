@@ -27,5 +27,65 @@ pub(crate) fn convert_ssa_function(
         BrilligBlock::compile(&mut function_context, &mut brillig_context, block, &func.dfg);
     }
 
+    brillig_context.report_peak_register_count(func.name());
+
     brillig_context.artifact()
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::brillig::brillig_gen::convert_ssa_function;
+    use crate::ssa::function_builder::FunctionBuilder;
+    use crate::ssa::ir::function::RuntimeType;
+    use crate::ssa::ir::instruction::BinaryOp;
+    use crate::ssa::ir::map::Id;
+    use crate::ssa::ir::types::Type;
+
+    /// Generates a function summing `value_count` products of its parameters, each alongside an unused product.
+    /// The sum is either accumulated as the products are computed, or once all of them have been computed.
+    fn peak_register_count(value_count: usize, accumulate: bool) -> usize {
+        // brillig fn main f0 {
+        //     b0(v0: u32, v1: u32):
+        //       v2 = mul v0, v1
+        //       v3 = mul v1, v0
+        //       v4 = add v0, v2
+        //       ...
+        //       return v4
+        //   }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id);
+        builder.set_runtime(RuntimeType::Brillig);
+
+        let v0 = builder.add_parameter(Type::unsigned(32));
+        let v1 = builder.add_parameter(Type::unsigned(32));
+
+        let mut sum = v0;
+        let mut products = Vec::new();
+        for _ in 0..value_count {
+            let product = builder.insert_binary(v0, BinaryOp::Mul, v1);
+            builder.insert_binary(v1, BinaryOp::Mul, v0);
+            if accumulate {
+                sum = builder.insert_binary(sum, BinaryOp::Add, product);
+            } else {
+                products.push(product);
+            }
+        }
+        for product in products {
+            sum = builder.insert_binary(sum, BinaryOp::Add, product);
+        }
+        builder.terminate_with_return(vec![sum]);
+
+        let ssa = builder.finish();
+        convert_ssa_function(ssa.main(), false).peak_register_count
+    }
+
+    #[test]
+    fn short_lived_values_reuse_registers() {
+        // Unused and already summed products are freed, so the peak doesn't grow with the number of products
+        assert_eq!(peak_register_count(20, true), peak_register_count(5, true));
+
+        // Keeping the products alive until the end needs a register for each of them
+        assert!(peak_register_count(20, false) >= 20);
+        assert!(peak_register_count(20, true) < peak_register_count(20, false));
+    }
+}
@@ -39,6 +39,8 @@ pub(crate) struct BrilligBlock<'block> {
     pub(crate) variables: BlockVariables,
     /// For each instruction, the set of values that are not used anymore after it.
     pub(crate) last_uses: HashMap<InstructionId, HashSet<ValueId>>,
+    /// The values passed to the jump's destination which are defined in the registers of the parameter they are passed to.
+    pub(crate) coalesced_arguments: HashMap<ValueId, ValueId>,
 }
 
 impl<'block> BrilligBlock<'block> {
@@ -60,9 +62,17 @@ impl<'block> BrilligBlock<'block> {
                 .collect(),
         );
         let last_uses = function_context.liveness.get_last_uses(&block_id).clone();
-
-        let mut brillig_block =
-            BrilligBlock { function_context, block_id, brillig_context, variables, last_uses };
+        let coalesced_arguments =
+            function_context.liveness.get_coalesced_arguments(&block_id).clone();
+
+        let mut brillig_block = BrilligBlock {
+            function_context,
+            block_id,
+            brillig_context,
+            variables,
+            last_uses,
+            coalesced_arguments,
+        };
 
         brillig_block.convert_block(dfg);
     }
@@ -163,7 +173,11 @@ impl<'block> BrilligBlock<'block> {
                 BrilligVariable::SingleAddr(source_var),
                 BrilligVariable::SingleAddr(destination_var),
             ) => {
-                self.brillig_context.mov_instruction(destination_var.address, source_var.address);
+                // Arguments defined in the registers of the parameter they are passed to are already in place
+                if destination_var.address != source_var.address {
+                    self.brillig_context
+                        .mov_instruction(destination_var.address, source_var.address);
+                }
             }
             (
                 BrilligVariable::BrilligArray(BrilligArray {
@@ -239,12 +253,8 @@ impl<'block> BrilligBlock<'block> {
 
         match instruction {
             Instruction::Binary(binary) => {
-                let result_var = self.variables.define_single_addr_variable(
-                    self.function_context,
-                    self.brillig_context,
-                    dfg.instruction_results(instruction_id)[0],
-                    dfg,
-                );
+                let result_var =
+                    self.define_single_addr_result(dfg.instruction_results(instruction_id)[0], dfg);
                 self.convert_ssa_binary(binary, dfg, result_var);
             }
             Instruction::Constrain(lhs, rhs, assert_message) => {
@@ -331,12 +341,8 @@ impl<'block> BrilligBlock<'block> {
             }
             Instruction::Not(value) => {
                 let condition_register = self.convert_ssa_single_addr_value(*value, dfg);
-                let result_register = self.variables.define_single_addr_variable(
-                    self.function_context,
-                    self.brillig_context,
-                    dfg.instruction_results(instruction_id)[0],
-                    dfg,
-                );
+                let result_register =
+                    self.define_single_addr_result(dfg.instruction_results(instruction_id)[0], dfg);
                 self.brillig_context.not_instruction(condition_register, result_register);
             }
             Instruction::Call { func, arguments } => match &dfg[*func] {
@@ -594,12 +600,7 @@ impl<'block> BrilligBlock<'block> {
             },
             Instruction::Truncate { value, bit_size, .. } => {
                 let result_ids = dfg.instruction_results(instruction_id);
-                let destination_register = self.variables.define_single_addr_variable(
-                    self.function_context,
-                    self.brillig_context,
-                    result_ids[0],
-                    dfg,
-                );
+                let destination_register = self.define_single_addr_result(result_ids[0], dfg);
                 let source_register = self.convert_ssa_single_addr_value(*value, dfg);
                 self.brillig_context.codegen_truncate(
                     destination_register,
@@ -609,12 +610,7 @@ impl<'block> BrilligBlock<'block> {
             }
             Instruction::Cast(value, _) => {
                 let result_ids = dfg.instruction_results(instruction_id);
-                let destination_variable = self.variables.define_single_addr_variable(
-                    self.function_context,
-                    self.brillig_context,
-                    result_ids[0],
-                    dfg,
-                );
+                let destination_variable = self.define_single_addr_result(result_ids[0], dfg);
                 let source_variable = self.convert_ssa_single_addr_value(*value, dfg);
                 self.convert_cast(destination_variable, source_variable);
             }
@@ -743,6 +739,27 @@ impl<'block> BrilligBlock<'block> {
         self.brillig_context.set_call_stack(CallStack::new());
     }
 
+    /// Defines the single register result of an instruction. If the result is passed to a block parameter
+    /// which is not used anymore, it's defined in the parameter's register.
+    fn define_single_addr_result(
+        &mut self,
+        result: ValueId,
+        dfg: &DataFlowGraph,
+    ) -> SingleAddrVariable {
+        if let Some(parameter) = self.coalesced_arguments.get(&result) {
+            self.variables
+                .define_variable_in_place_of(self.function_context, result, *parameter, dfg)
+                .extract_single_addr()
+        } else {
+            self.variables.define_single_addr_variable(
+                self.function_context,
+                self.brillig_context,
+                result,
+                dfg,
+            )
+        }
+    }
+
     fn convert_ssa_function_call(
         &mut self,
         func_id: FunctionId,
@@ -67,6 +67,27 @@ impl BlockVariables {
         variable
     }
 
+    /// Defines a variable in the registers already allocated to another variable, and returns the shared allocation.
+    /// The other variable's value must not be used while the new one is alive.
+    pub(crate) fn define_variable_in_place_of(
+        &mut self,
+        function_context: &mut FunctionContext,
+        value_id: ValueId,
+        existing_value_id: ValueId,
+        dfg: &DataFlowGraph,
+    ) -> BrilligVariable {
+        let value_id = dfg.resolve(value_id);
+        let variable = self.get_allocation(function_context, existing_value_id, dfg);
+
+        if function_context.ssa_value_allocations.insert(value_id, variable).is_some() {
+            unreachable!("ICE: ValueId {value_id:?} was already in cache");
+        }
+
+        self.available_variables.insert(value_id);
+
+        variable
+    }
+
     /// Defines a variable that fits in a single register and returns the allocated register.
     pub(crate) fn define_single_addr_variable(
         &mut self,
@@ -394,6 +394,7 @@ mod tests {
             brillig_context,
             variables,
             last_uses: Default::default(),
+            coalesced_arguments: Default::default(),
         }
     }
 
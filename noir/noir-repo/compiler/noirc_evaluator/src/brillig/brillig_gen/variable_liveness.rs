@@ -6,8 +6,9 @@ use crate::ssa::ir::{
     dfg::DataFlowGraph,
     dom::DominatorTree,
     function::Function,
-    instruction::{Instruction, InstructionId},
+    instruction::{Instruction, InstructionId, TerminatorInstruction},
     post_order::PostOrder,
+    types::Type,
     value::{Value, ValueId},
 };
 
@@ -120,6 +121,9 @@ fn compute_used_before_def(
 
 type LastUses = HashMap<InstructionId, Variables>;
 
+/// Maps values passed to a block to the block parameters they can share registers with
+type CoalescedArguments = HashMap<ValueId, ValueId>;
+
 /// A struct representing the liveness of variables throughout a function.
 pub(crate) struct VariableLiveness {
     cfg: ControlFlowGraph,
@@ -130,6 +134,8 @@ pub(crate) struct VariableLiveness {
     last_uses: HashMap<BasicBlockId, LastUses>,
     /// The list of block params the given block is defining. The order matters for the entry block, so it's a vec.
     param_definitions: HashMap<BasicBlockId, Vec<ValueId>>,
+    /// The arguments of each block's jump which can be defined directly in the parameter they are passed to
+    coalesced_arguments: HashMap<BasicBlockId, CoalescedArguments>,
 }
 
 impl VariableLiveness {
@@ -144,6 +150,7 @@ impl VariableLiveness {
             live_in: HashMap::default(),
             last_uses: HashMap::default(),
             param_definitions: HashMap::default(),
+            coalesced_arguments: HashMap::default(),
         };
 
         instance.compute_block_param_definitions(func);
@@ -152,6 +159,8 @@ impl VariableLiveness {
 
         instance.compute_last_uses(func);
 
+        instance.compute_coalesced_arguments(func);
+
         instance
     }
 
@@ -174,6 +183,15 @@ impl VariableLiveness {
         self.last_uses.get(block_id).expect("Last uses should have been calculated")
     }
 
+    /// A map of the values passed by the block's terminator to the block parameters whose registers they can be defined in.
+    /// The parameter's previous value is not used after the value is defined, so storing the value directly in the parameter
+    /// avoids both a separate allocation and a move when jumping.
+    pub(crate) fn get_coalesced_arguments(&self, block_id: &BasicBlockId) -> &CoalescedArguments {
+        self.coalesced_arguments
+            .get(block_id)
+            .expect("Coalesced arguments should have been calculated")
+    }
+
     /// Retrieves the list of block params the given block is defining.
     /// Block params are defined before the block that owns them (since they are used by the predecessor blocks). They must be defined in the immediate dominator.
     /// This is the last point where the block param can be allocated without it being allocated in different places in different branches.
@@ -314,18 +332,107 @@ impl VariableLiveness {
             // Then, handle the instructions in reverse order to find the last use
             for instruction_id in block.instructions().iter().rev() {
                 let instruction = &func.dfg[*instruction_id];
-                let instruction_last_uses = variables_used_in_instruction(instruction, &func.dfg)
-                    .into_iter()
-                    .filter(|id| !used_after.contains(id) && !live_out.contains(id))
-                    .collect();
+                let mut instruction_last_uses: Variables =
+                    variables_used_in_instruction(instruction, &func.dfg)
+                        .into_iter()
+                        .filter(|id| !used_after.contains(id) && !live_out.contains(id))
+                        .collect();
 
                 used_after.extend(&instruction_last_uses);
+
+                // Results that are never used die as soon as they are defined
+                let unused_results =
+                    func.dfg.instruction_results(*instruction_id).iter().filter(|result| {
+                        func.dfg.resolve(**result) == **result
+                            && !used_after.contains(*result)
+                            && !live_out.contains(*result)
+                    });
+                instruction_last_uses.extend(unused_results);
+
                 block_last_uses.insert(*instruction_id, instruction_last_uses);
             }
 
             self.last_uses.insert(block_id, block_last_uses);
         }
     }
+
+    fn compute_coalesced_arguments(&mut self, func: &Function) {
+        for block_id in func.reachable_blocks() {
+            let coalesced = self.compute_coalesced_arguments_of_block(func, block_id);
+            self.coalesced_arguments.insert(block_id, coalesced);
+        }
+    }
+
+    /// An argument `v` passed to the parameter `p` of the jump's destination can be defined in `p`'s registers if:
+    /// - `v` is the single register result of an instruction in this block which writes it after reading its operands.
+    /// - `v` is only used by the jump, where it is passed a single time.
+    /// - `p` is not used by that instruction, any later instruction or the jump, so overwriting it early is not observable.
+    fn compute_coalesced_arguments_of_block(
+        &self,
+        func: &Function,
+        block_id: BasicBlockId,
+    ) -> CoalescedArguments {
+        let dfg = &func.dfg;
+        let block = &dfg[block_id];
+        let mut coalesced = CoalescedArguments::default();
+
+        let (destination, arguments) = match block.terminator() {
+            Some(TerminatorInstruction::Jmp { destination, arguments, .. }) => {
+                (destination, arguments)
+            }
+            _ => return coalesced,
+        };
+
+        let mut argument_variables: HashMap<ValueId, usize> = HashMap::default();
+        for argument in arguments {
+            for variable in collect_variables_of_value(*argument, dfg) {
+                *argument_variables.entry(variable).or_default() += 1;
+            }
+        }
+        let live_out = self.get_live_out(&block_id);
+
+        let mut candidates: CoalescedArguments = arguments
+            .iter()
+            .zip(dfg[*destination].parameters())
+            .map(|(argument, parameter)| (dfg.resolve(*argument), *parameter))
+            .filter(|(argument, parameter)| {
+                matches!(dfg[*argument], Value::Instruction { .. })
+                    && matches!(dfg.type_of_value(*argument), Type::Numeric(_))
+                    && argument_variables.get(argument) == Some(&1)
+                    && !argument_variables.contains_key(parameter)
+                    && !live_out.contains(argument)
+            })
+            .collect();
+
+        let mut used_after = Variables::default();
+        for instruction_id in block.instructions().iter().rev() {
+            let instruction = &dfg[*instruction_id];
+            let used = variables_used_in_instruction(instruction, dfg);
+            let writes_result_last = matches!(
+                instruction,
+                Instruction::Binary(_)
+                    | Instruction::Cast(..)
+                    | Instruction::Not(_)
+                    | Instruction::Truncate { .. }
+            );
+
+            if let [result] = dfg.instruction_results(*instruction_id) {
+                if let Some(parameter) = candidates.remove(result) {
+                    if writes_result_last
+                        && !used_after.contains(result)
+                        && !used_after.contains(&parameter)
+                        && !used.contains(&parameter)
+                    {
+                        coalesced.insert(*result, parameter);
+                    }
+                }
+            }
+
+            used_after.extend(used);
+        }
+
+        coalesced
+    }
 }
 
 #[cfg(test)]
@@ -632,4 +739,66 @@ mod test {
         assert_eq!(liveness.get_live_in(&b1), &FxHashSet::from_iter([v1, v2].into_iter()));
         assert_eq!(liveness.get_live_in(&b2), &FxHashSet::from_iter([v1, v2].into_iter()));
     }
+
+    #[test]
+    fn unused_results_and_coalesced_arguments() {
+        // brillig fn main f0 {
+        //     b0(v0: u32, v1: u1):
+        //       jmpif v1 then: b1, else: b2
+        //     b1():
+        //       v2 = add v0, u32 1
+        //       v3 = mul v0, u32 2
+        //       jmp b3(v2)
+        //     b3(v4: u32):
+        //       return v4
+        //     b2():
+        //       jmp b3(v0)
+        //   }
+
+        let main_id = Id::test_new(1);
+        let mut builder = FunctionBuilder::new("main".into(), main_id);
+        builder.set_runtime(RuntimeType::Brillig);
+
+        let v0 = builder.add_parameter(Type::unsigned(32));
+        let v1 = builder.add_parameter(Type::bool());
+
+        let b1 = builder.insert_block();
+        let b2 = builder.insert_block();
+        let b3 = builder.insert_block();
+
+        builder.terminate_with_jmpif(v1, b1, b2);
+
+        builder.switch_to_block(b1);
+        let one = builder.numeric_constant(1u128, Type::unsigned(32));
+        let two = builder.numeric_constant(2u128, Type::unsigned(32));
+        let v2 = builder.insert_binary(v0, BinaryOp::Add, one);
+        let v3 = builder.insert_binary(v0, BinaryOp::Mul, two);
+        builder.terminate_with_jmp(b3, vec![v2]);
+
+        builder.switch_to_block(b3);
+        let v4 = builder.add_block_parameter(b3, Type::unsigned(32));
+        builder.terminate_with_return(vec![v4]);
+
+        builder.switch_to_block(b2);
+        builder.terminate_with_jmp(b3, vec![v0]);
+
+        let ssa = builder.finish();
+        let func = ssa.main();
+        let liveness = VariableLiveness::from_function(func);
+
+        // The unused result dies right after it is defined
+        let block_1 = &func.dfg[b1];
+        assert_eq!(
+            liveness.get_last_uses(&b1).get(&block_1.instructions()[0]),
+            Some(&FxHashSet::default())
+        );
+        assert_eq!(
+            liveness.get_last_uses(&b1).get(&block_1.instructions()[1]),
+            Some(&FxHashSet::from_iter([v0, v3].into_iter()))
+        );
+
+        // The sum can be stored directly in the parameter, but the function parameter is still alive
+        assert_eq!(liveness.get_coalesced_arguments(&b1).get(&v2), Some(&v4));
+        assert!(liveness.get_coalesced_arguments(&b2).is_empty());
+    }
 }
@@ -22,6 +22,7 @@ mod codegen_memory;
 mod codegen_stack;
 mod entry_point;
 mod instructions;
+mod optimizer;
 
 pub(crate) use instructions::BrilligBinaryOp;
 
@@ -82,6 +83,8 @@ pub(crate) struct BrilligContext<F> {
     obj: BrilligArtifact<F>,
     /// Tracks register allocations
     registers: BrilligRegistersContext,
+    /// The largest number of registers used by the blocks generated before the current one
+    peak_register_count: usize,
     /// Context label, must be unique with respect to the function
     /// being linked.
     context_label: String,
@@ -99,6 +102,7 @@ impl<F: AcirField + DebugToString> BrilligContext<F> {
         BrilligContext {
             obj: BrilligArtifact::default(),
             registers: BrilligRegistersContext::new(),
+            peak_register_count: 0,
             context_label: String::default(),
             section_label: 0,
             next_section: 1,
@@ -113,7 +117,7 @@ impl<F: AcirField + DebugToString> BrilligContext<F> {
 
     /// Returns the artifact
     pub(crate) fn artifact(self) -> BrilligArtifact<F> {
-        self.obj
+        BrilligArtifact { peak_register_count: self.peak_register_count(), ..self.obj }
     }
 
     /// Sets a current call stack that the next pushed opcodes will be associated with.
@@ -216,7 +220,7 @@ pub(crate) mod tests {
         calldata: Vec<FieldElement>,
         bytecode: &[BrilligOpcode<FieldElement>],
     ) -> (VM<'_, FieldElement, DummyBlackBoxSolver>, usize, usize) {
-        let mut vm = VM::new(calldata, bytecode, vec![], &DummyBlackBoxSolver);
+        let mut vm = VM::new(calldata, bytecode, vec![], &DummyBlackBoxSolver, false);
 
         let status = vm.process_opcodes();
         if let VMStatus::Finished { return_data_offset, return_data_size } = status {
@@ -282,6 +286,7 @@ pub(crate) mod tests {
             &bytecode,
             vec![ForeignCallResult { values: vec![ForeignCallParam::Array(number_sequence)] }],
             &DummyBlackBoxSolver,
+            false,
         );
         let status = vm.process_opcodes();
         assert_eq!(status, VMStatus::Finished { return_data_offset: 0, return_data_size: 0 });
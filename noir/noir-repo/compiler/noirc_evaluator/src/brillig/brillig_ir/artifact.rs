@@ -49,6 +49,8 @@ pub(crate) struct BrilligArtifact<F> {
     locations: BTreeMap<OpcodeLocation, CallStack>,
     /// The current call stack. All opcodes that are pushed will be associated with this call stack.
     call_stack: CallStack,
+    /// The largest number of registers, excluding the reserved ones, used at once by the bytecode.
+    pub(crate) peak_register_count: usize,
 }
 
 /// A pointer to a location in the opcode.
@@ -244,6 +244,16 @@ impl DebugShow {
         }
     }
 
+    /// Debug function for the peak register count of a function, printed once it has been generated
+    pub(crate) fn peak_register_count(&self, function_name: &str, count: usize) {
+        debug_println!(
+            self.enable_debug_trace,
+            "; {} uses at most {} registers",
+            function_name,
+            count
+        );
+    }
+
     /// Debug function for jump_instruction
     pub(crate) fn jump_instruction(&self, target_label: String) {
         debug_println!(self.enable_debug_trace, "  JUMP_TO {}", target_label);
@@ -19,6 +19,7 @@ impl<F: AcirField + DebugToString> BrilligContext<F> {
         let mut context = BrilligContext {
             obj: BrilligArtifact::default(),
             registers: BrilligRegistersContext::new(),
+            peak_register_count: 0,
             context_label: String::default(),
             section_label: 0,
             next_section: 1,
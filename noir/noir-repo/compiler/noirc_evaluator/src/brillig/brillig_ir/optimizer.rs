@@ -0,0 +1,351 @@
+//! Optimizations over the final Brillig bytecode, once all functions have been linked together
+//! and their jumps resolved.
+//!
+//! Code generation works one SSA instruction at a time, so it leaves behind opcodes which a view
+//! of the whole bytecode shows to be unnecessary:
+//! - Opcodes which can't be reached from the start of the bytecode, such as unused procedures.
+//! - Jumps to the opcode which follows them.
+//! - `Mov`s to a register which already holds the value being moved, and `Const`s which load
+//!   a value into a register that already holds it. These are found by tracking the contents of
+//!   registers within each basic block.
+//!
+//! Removing an opcode shifts the opcodes after it, so jump targets and the opcode locations of
+//! the call stacks and assertion messages are remapped to match.
+use acvm::{
+    acir::brillig::{MemoryAddress, Opcode as BrilligOpcode},
+    AcirField,
+};
+use std::collections::{BTreeMap, HashMap};
+
+use super::artifact::{GeneratedBrillig, OpcodeLocation};
+
+impl<F: AcirField> GeneratedBrillig<F> {
+    /// Removes unreachable and redundant opcodes from the bytecode
+    pub(crate) fn optimize(mut self) -> Self {
+        // Removing opcodes may expose more to remove, e.g. a jump over unreachable code
+        // becomes a jump to the next opcode.
+        loop {
+            let keep = opcodes_to_keep(&self.byte_code);
+            if keep.iter().all(|keep| *keep) {
+                return self;
+            }
+            self = self.remove_opcodes(&keep);
+        }
+    }
+
+    fn remove_opcodes(self, keep: &[bool]) -> Self {
+        // Maps each location to the first kept opcode at or after it
+        let mut new_locations = Vec::with_capacity(keep.len() + 1);
+        let mut kept_count = 0;
+        for keep in keep {
+            new_locations.push(kept_count);
+            kept_count += usize::from(*keep);
+        }
+        new_locations.push(kept_count);
+        let remap = |location: OpcodeLocation| new_locations[location];
+
+        let byte_code = self
+            .byte_code
+            .into_iter()
+            .zip(keep)
+            .filter(|(_, keep)| **keep)
+            .map(|(opcode, _)| match opcode {
+                BrilligOpcode::Jump { location } => {
+                    BrilligOpcode::Jump { location: remap(location) }
+                }
+                BrilligOpcode::JumpIf { condition, location } => {
+                    BrilligOpcode::JumpIf { condition, location: remap(location) }
+                }
+                BrilligOpcode::JumpIfNot { condition, location } => {
+                    BrilligOpcode::JumpIfNot { condition, location: remap(location) }
+                }
+                BrilligOpcode::Call { location } => {
+                    BrilligOpcode::Call { location: remap(location) }
+                }
+                opcode => opcode,
+            })
+            .collect();
+
+        GeneratedBrillig {
+            byte_code,
+            locations: remap_locations(self.locations, keep, remap),
+            assert_messages: remap_locations(self.assert_messages, keep, remap),
+        }
+    }
+}
+
+fn remap_locations<T>(
+    map: BTreeMap<OpcodeLocation, T>,
+    keep: &[bool],
+    remap: impl Fn(OpcodeLocation) -> OpcodeLocation,
+) -> BTreeMap<OpcodeLocation, T> {
+    map.into_iter()
+        .filter(|(location, _)| keep[*location])
+        .map(|(location, value)| (remap(location), value))
+        .collect()
+}
+
+fn opcodes_to_keep<F: AcirField>(byte_code: &[BrilligOpcode<F>]) -> Vec<bool> {
+    let reachable = reachable_opcodes(byte_code);
+    let redundant = redundant_opcodes(byte_code);
+
+    byte_code
+        .iter()
+        .enumerate()
+        .map(|(index, opcode)| {
+            let jumps_to_next = match opcode {
+                BrilligOpcode::Jump { location }
+                | BrilligOpcode::JumpIf { location, .. }
+                | BrilligOpcode::JumpIfNot { location, .. } => *location == index + 1,
+                _ => false,
+            };
+            reachable[index] && !redundant[index] && !jumps_to_next
+        })
+        .collect()
+}
+
+/// Marks the opcodes which can be reached from the start of the bytecode
+fn reachable_opcodes<F>(byte_code: &[BrilligOpcode<F>]) -> Vec<bool> {
+    let mut reachable = vec![false; byte_code.len()];
+    let mut stack = vec![0];
+
+    while let Some(location) = stack.pop() {
+        if location >= byte_code.len() || reachable[location] {
+            continue;
+        }
+        reachable[location] = true;
+
+        match &byte_code[location] {
+            BrilligOpcode::Jump { location: target } => stack.push(*target),
+            // A call returns to the opcode after it
+            BrilligOpcode::JumpIf { location: target, .. }
+            | BrilligOpcode::JumpIfNot { location: target, .. }
+            | BrilligOpcode::Call { location: target } => {
+                stack.push(*target);
+                stack.push(location + 1);
+            }
+            BrilligOpcode::Return | BrilligOpcode::Stop { .. } | BrilligOpcode::Trap { .. } => (),
+            _ => stack.push(location + 1),
+        }
+    }
+    reachable
+}
+
+/// What a register is known to hold
+#[derive(Clone, PartialEq)]
+enum Contents<F> {
+    Constant {
+        bit_size: u32,
+        value: F,
+    },
+    /// The same value as another register
+    CopyOf(MemoryAddress),
+}
+
+/// Marks the `Mov`s and `Const`s which write a value to a register that already holds it
+fn redundant_opcodes<F: AcirField>(byte_code: &[BrilligOpcode<F>]) -> Vec<bool> {
+    let mut jump_targets = vec![false; byte_code.len() + 1];
+    for opcode in byte_code {
+        if let BrilligOpcode::Jump { location }
+        | BrilligOpcode::JumpIf { location, .. }
+        | BrilligOpcode::JumpIfNot { location, .. }
+        | BrilligOpcode::Call { location } = opcode
+        {
+            if let Some(target) = jump_targets.get_mut(*location) {
+                *target = true;
+            }
+        }
+    }
+
+    let mut redundant = vec![false; byte_code.len()];
+    let mut registers = RegisterContents { known: HashMap::new() };
+
+    for (index, opcode) in byte_code.iter().enumerate() {
+        // Only the contents known from within the current basic block can be relied on
+        let starts_block = index == 0
+            || jump_targets[index]
+            || matches!(
+                byte_code[index - 1],
+                BrilligOpcode::Jump { .. }
+                    | BrilligOpcode::Return
+                    | BrilligOpcode::Stop { .. }
+                    | BrilligOpcode::Trap { .. }
+            );
+        if starts_block {
+            registers.clear();
+        }
+
+        match opcode {
+            BrilligOpcode::Mov { destination, source } => {
+                let contents = registers.contents(*source);
+                if registers.contents(*destination) == contents {
+                    redundant[index] = true;
+                } else {
+                    registers.set(*destination, contents);
+                }
+            }
+            BrilligOpcode::Const { destination, bit_size, value } => {
+                let contents = Contents::Constant { bit_size: *bit_size, value: *value };
+                if registers.contents(*destination) == contents {
+                    redundant[index] = true;
+                } else {
+                    registers.set(*destination, contents);
+                }
+            }
+            BrilligOpcode::BinaryFieldOp { destination, .. }
+            | BrilligOpcode::BinaryIntOp { destination, .. }
+            | BrilligOpcode::Cast { destination, .. }
+            | BrilligOpcode::ConditionalMov { destination, .. }
+            | BrilligOpcode::Load { destination, .. } => registers.invalidate(*destination),
+            BrilligOpcode::JumpIf { .. } | BrilligOpcode::JumpIfNot { .. } => (),
+            // These may write to any memory, including every register
+            _ => registers.clear(),
+        }
+    }
+    redundant
+}
+
+struct RegisterContents<F> {
+    known: HashMap<MemoryAddress, Contents<F>>,
+}
+
+impl<F: AcirField> RegisterContents<F> {
+    fn contents(&self, register: MemoryAddress) -> Contents<F> {
+        self.known.get(&register).cloned().unwrap_or(Contents::CopyOf(register))
+    }
+
+    fn set(&mut self, register: MemoryAddress, contents: Contents<F>) {
+        self.invalidate(register);
+        self.known.insert(register, contents);
+    }
+
+    /// Forgets the contents of a register which is written to, along with any copies of it
+    fn invalidate(&mut self, register: MemoryAddress) {
+        self.known.remove(&register);
+        self.known.retain(|_, contents| *contents != Contents::CopyOf(register));
+    }
+
+    fn clear(&mut self) {
+        self.known.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::{
+        acir::brillig::{BinaryIntOp, HeapArray, MemoryAddress, Opcode as BrilligOpcode},
+        FieldElement,
+    };
+
+    use crate::{brillig::brillig_ir::artifact::GeneratedBrillig, ssa::ir::dfg::CallStack};
+
+    fn generated_brillig(
+        byte_code: Vec<BrilligOpcode<FieldElement>>,
+    ) -> GeneratedBrillig<FieldElement> {
+        let locations = (0..byte_code.len()).map(|location| (location, CallStack::new())).collect();
+        GeneratedBrillig { byte_code, locations, assert_messages: Default::default() }
+    }
+
+    fn constant(destination: usize, value: u128) -> BrilligOpcode<FieldElement> {
+        BrilligOpcode::Const {
+            destination: MemoryAddress(destination),
+            bit_size: 32,
+            value: FieldElement::from(value),
+        }
+    }
+
+    fn mov(destination: usize, source: usize) -> BrilligOpcode<FieldElement> {
+        BrilligOpcode::Mov {
+            destination: MemoryAddress(destination),
+            source: MemoryAddress(source),
+        }
+    }
+
+    fn add(destination: usize, lhs: usize, rhs: usize) -> BrilligOpcode<FieldElement> {
+        BrilligOpcode::BinaryIntOp {
+            destination: MemoryAddress(destination),
+            op: BinaryIntOp::Add,
+            bit_size: 32,
+            lhs: MemoryAddress(lhs),
+            rhs: MemoryAddress(rhs),
+        }
+    }
+
+    fn stop() -> BrilligOpcode<FieldElement> {
+        BrilligOpcode::Stop { return_data_offset: 0, return_data_size: 0 }
+    }
+
+    #[test]
+    fn removes_redundant_moves_and_constants() {
+        let brillig = generated_brillig(vec![
+            constant(1, 5),
+            constant(1, 5),
+            mov(2, 1),
+            mov(1, 2),
+            mov(3, 3),
+            add(1, 1, 2),
+            // Register 1 was overwritten, so this is still needed
+            mov(2, 1),
+            constant(2, 5),
+            stop(),
+        ]);
+
+        let optimized = brillig.optimize();
+        assert_eq!(
+            optimized.byte_code,
+            vec![constant(1, 5), mov(2, 1), add(1, 1, 2), mov(2, 1), constant(2, 5), stop()]
+        );
+        assert_eq!(optimized.locations.keys().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn does_not_rely_on_register_contents_across_blocks() {
+        let brillig = generated_brillig(vec![
+            constant(1, 5),
+            BrilligOpcode::JumpIf { condition: MemoryAddress(0), location: 3 },
+            constant(1, 6),
+            // Register 1 may hold either 5 or 6 here
+            constant(1, 5),
+            stop(),
+        ]);
+
+        let optimized = brillig.optimize();
+        assert_eq!(optimized.byte_code.len(), 5);
+    }
+
+    #[test]
+    fn removes_unreachable_code_and_jumps_to_the_next_opcode() {
+        let trap =
+            BrilligOpcode::Trap { revert_data: HeapArray { pointer: MemoryAddress(0), size: 0 } };
+        let mut brillig = generated_brillig(vec![
+            BrilligOpcode::JumpIfNot { condition: MemoryAddress(0), location: 5 },
+            BrilligOpcode::Jump { location: 3 },
+            // Unreachable
+            constant(1, 1),
+            BrilligOpcode::Call { location: 6 },
+            stop(),
+            trap.clone(),
+            add(1, 1, 1),
+            BrilligOpcode::Return,
+            // Unreachable procedure
+            constant(2, 2),
+            BrilligOpcode::Return,
+        ]);
+        brillig.assert_messages.insert(5, "assertion failed".to_string());
+
+        let optimized = brillig.optimize();
+        assert_eq!(
+            optimized.byte_code,
+            vec![
+                BrilligOpcode::JumpIfNot { condition: MemoryAddress(0), location: 3 },
+                BrilligOpcode::Call { location: 4 },
+                stop(),
+                trap,
+                add(1, 1, 1),
+                BrilligOpcode::Return,
+            ]
+        );
+        assert_eq!(optimized.assert_messages.get(&3).map(String::as_str), Some("assertion failed"));
+        assert_eq!(optimized.locations.len(), 6);
+    }
+}
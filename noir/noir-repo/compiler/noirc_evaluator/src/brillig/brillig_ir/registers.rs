@@ -74,12 +74,15 @@ impl BrilligRegistersContext {
     }
 
     /// Push a register to the deallocation list, ready for reuse.
-    /// TODO(AD): currently, register deallocation is only done with immediate values.
-    /// TODO(AD): See https://github.com/noir-lang/noir/issues/1720
     pub(crate) fn deallocate_register(&mut self, register_index: MemoryAddress) {
         assert!(!self.deallocated_registers.contains(&register_index));
         self.deallocated_registers.push(register_index);
     }
+
+    /// The number of registers, excluding the reserved ones, that have been used in this context.
+    pub(crate) fn used_register_count(&self) -> usize {
+        self.next_free_register_index - ReservedRegisters::len()
+    }
 }
 
 impl<F> BrilligContext<F> {
@@ -94,9 +97,21 @@ impl<F> BrilligContext<F> {
     }
 
     pub(crate) fn set_allocated_registers(&mut self, allocated_registers: Vec<MemoryAddress>) {
+        self.peak_register_count = self.peak_register_count();
         self.registers = BrilligRegistersContext::from_preallocated_registers(allocated_registers);
     }
 
+    /// The largest number of registers, excluding the reserved ones, used so far by the generated code.
+    /// This is the size of the register space that is saved and restored around calls.
+    pub(crate) fn peak_register_count(&self) -> usize {
+        self.peak_register_count.max(self.registers.used_register_count())
+    }
+
+    /// Prints the peak register count of the function being generated when debug tracing is enabled.
+    pub(crate) fn report_peak_register_count(&self, function_name: &str) {
+        self.debug_show.peak_register_count(function_name, self.peak_register_count());
+    }
+
     /// Push a register to the deallocation list, ready for reuse.
     pub(crate) fn deallocate_register(&mut self, register_index: MemoryAddress) {
         self.registers.deallocate_register(register_index);
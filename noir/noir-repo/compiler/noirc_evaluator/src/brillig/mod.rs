@@ -2,6 +2,7 @@ pub(crate) mod brillig_gen;
 pub(crate) mod brillig_ir;
 
 use acvm::FieldElement;
+use iter_extended::vecmap;
 
 use self::{
     brillig_gen::{brillig_fn::FunctionContext, convert_ssa_function},
@@ -11,6 +12,7 @@ use crate::ssa::{
     ir::function::{Function, FunctionId, RuntimeType},
     ssa_gen::Ssa,
 };
+use serde::{Deserialize, Serialize};
 use std::collections::{BTreeSet, HashMap};
 
 /// Context structure for the brillig pass.
@@ -21,6 +23,15 @@ pub struct Brillig {
     ssa_function_to_brillig: HashMap<FunctionId, BrilligArtifact<FieldElement>>,
 }
 
+/// The number of registers an unconstrained function needs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BrilligRegisterUsage {
+    pub name: String,
+    /// The largest number of registers, excluding the reserved ones, used at once by the function.
+    /// These are saved and restored around each call the function makes.
+    pub peak_registers: usize,
+}
+
 impl Brillig {
     /// Compiles a function into brillig and store the compilation artifacts
     pub(crate) fn compile(&mut self, func: &Function, enable_debug_trace: bool) {
@@ -28,6 +39,16 @@ impl Brillig {
         self.ssa_function_to_brillig.insert(func.id(), obj);
     }
 
+    /// The register usage of each compiled function, in the order the functions were declared
+    pub(crate) fn register_usage(&self, ssa: &Ssa) -> Vec<BrilligRegisterUsage> {
+        let mut function_ids: Vec<_> = self.ssa_function_to_brillig.keys().copied().collect();
+        function_ids.sort();
+        vecmap(function_ids, |function_id| BrilligRegisterUsage {
+            name: ssa.functions[&function_id].name().to_string(),
+            peak_registers: self.ssa_function_to_brillig[&function_id].peak_register_count,
+        })
+    }
+
     /// Finds a brillig function artifact by its function label
     pub(crate) fn find_by_function_label(
         &self,
@@ -7,9 +7,16 @@
 //! This module heavily borrows from Cranelift
 #![allow(dead_code)]
 
-use std::collections::{BTreeMap, BTreeSet};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
-use crate::errors::{RuntimeError, SsaReport};
+use crate::{
+    brillig::BrilligRegisterUsage,
+    errors::{RuntimeError, SsaReport},
+};
 use acvm::{
     acir::{
         circuit::{
@@ -38,11 +45,13 @@ use self::{
 mod acir_gen;
 mod checks;
 pub(super) mod function_builder;
+mod interpreter;
 pub mod ir;
 mod opt;
+mod parser;
 pub mod ssa_gen;
 
-pub(crate) struct ArtifactsAndWarnings(Artifacts, Vec<SsaReport>);
+pub(crate) struct ArtifactsAndWarnings(Artifacts, Vec<SsaReport>, Vec<BrilligRegisterUsage>);
 
 /// Optimize the given program by converting it into SSA
 /// form and performing optimizations there. When finished,
@@ -55,52 +64,164 @@ pub(crate) fn optimize_into_acir(
 ) -> Result<ArtifactsAndWarnings, RuntimeError> {
     let ssa_gen_span = span!(Level::TRACE, "ssa_generation");
     let ssa_gen_span_guard = ssa_gen_span.enter();
-    let mut ssa = SsaBuilder::new(
-        program,
-        options.enable_ssa_logging,
-        options.force_brillig_output,
-        options.print_codegen_timings,
-    )?
-    .run_pass(Ssa::defunctionalize, "After Defunctionalization:")
-    .run_pass(Ssa::remove_paired_rc, "After Removing Paired rc_inc & rc_decs:")
-    .run_pass(Ssa::separate_runtime, "After Runtime Separation:")
-    .run_pass(Ssa::resolve_is_unconstrained, "After Resolving IsUnconstrained:")
-    .run_pass(Ssa::inline_functions, "After Inlining:")
-    // Run mem2reg with the CFG separated into blocks
-    .run_pass(Ssa::mem2reg, "After Mem2Reg:")
-    .run_pass(Ssa::as_slice_optimization, "After `as_slice` optimization")
-    .try_run_pass(
-        Ssa::evaluate_static_assert_and_assert_constant,
-        "After `static_assert` and `assert_constant`:",
-    )?
-    .try_run_pass(Ssa::unroll_loops_iteratively, "After Unrolling:")?
-    .run_pass(Ssa::simplify_cfg, "After Simplifying:")
-    .run_pass(Ssa::flatten_cfg, "After Flattening:")
-    .run_pass(Ssa::remove_bit_shifts, "After Removing Bit Shifts:")
-    // Run mem2reg once more with the flattened CFG to catch any remaining loads/stores
-    .run_pass(Ssa::mem2reg, "After Mem2Reg:")
-    // Run the inlining pass again to handle functions with `InlineType::NoPredicates`.
-    // Before flattening is run, we treat functions marked with the `InlineType::NoPredicates` as an entry point.
-    // This pass must come immediately following `mem2reg` as the succeeding passes
-    // may create an SSA which inlining fails to handle.
-    .run_pass(Ssa::inline_functions_with_no_predicates, "After Inlining:")
-    .run_pass(Ssa::remove_if_else, "After Remove IfElse:")
-    .run_pass(Ssa::fold_constants, "After Constant Folding:")
-    .run_pass(Ssa::remove_enable_side_effects, "After EnableSideEffects removal:")
-    .run_pass(Ssa::fold_constants_using_constraints, "After Constraint Folding:")
-    .run_pass(Ssa::dead_instruction_elimination, "After Dead Instruction Elimination:")
-    .run_pass(Ssa::array_set_optimization, "After Array Set Optimizations:")
-    .finish();
+    let mut builder = SsaBuilder::new(program, options)?;
+    for (index, pass) in ssa_passes().into_iter().enumerate() {
+        builder = builder.run_pass(index + 1, pass)?;
+    }
+    let mut ssa = builder.finish();
 
     let ssa_level_warnings = ssa.check_for_underconstrained_values();
     let brillig = time("SSA to Brillig", options.print_codegen_timings, || {
         ssa.to_brillig(options.enable_brillig_logging)
     });
+    let register_usage = brillig.register_usage(&ssa);
 
     drop(ssa_gen_span_guard);
 
     let artifacts = time("SSA to ACIR", options.print_codegen_timings, || ssa.into_acir(&brillig))?;
-    Ok(ArtifactsAndWarnings(artifacts, ssa_level_warnings))
+    Ok(ArtifactsAndWarnings(artifacts, ssa_level_warnings, register_usage))
+}
+
+/// A step of the SSA pipeline
+struct SsaPass {
+    /// The name used to refer to the pass from the command line
+    name: &'static str,
+    /// The message printed along with the SSA after the pass has run
+    msg: &'static str,
+    run: PassFunction,
+    /// Whether the pass only optimizes the SSA. Other passes are needed to generate ACIR.
+    optional: bool,
+    /// Whether ACIR functions are expected to contain no loops or references after this pass
+    flattens_acir: bool,
+}
+
+enum PassFunction {
+    Infallible(fn(Ssa) -> Ssa),
+    Fallible(fn(Ssa) -> Result<Ssa, RuntimeError>),
+}
+
+impl SsaPass {
+    /// A pass which is needed to generate ACIR
+    fn required(name: &'static str, msg: &'static str, run: fn(Ssa) -> Ssa) -> Self {
+        let run = PassFunction::Infallible(run);
+        SsaPass { name, msg, run, optional: false, flattens_acir: false }
+    }
+
+    /// A pass which is needed to generate ACIR, and may fail
+    fn fallible(
+        name: &'static str,
+        msg: &'static str,
+        run: fn(Ssa) -> Result<Ssa, RuntimeError>,
+    ) -> Self {
+        let run = PassFunction::Fallible(run);
+        SsaPass { name, msg, run, optional: false, flattens_acir: false }
+    }
+
+    /// A pass which only optimizes the SSA, so it may be skipped
+    fn optimization(name: &'static str, msg: &'static str, run: fn(Ssa) -> Ssa) -> Self {
+        SsaPass { optional: true, ..Self::required(name, msg, run) }
+    }
+
+    fn flattening_acir(self) -> Self {
+        SsaPass { flattens_acir: true, ..self }
+    }
+}
+
+/// The passes which are run on the SSA, in order
+fn ssa_passes() -> Vec<SsaPass> {
+    vec![
+        SsaPass::required("defunctionalize", "After Defunctionalization:", Ssa::defunctionalize),
+        SsaPass::optimization(
+            "remove_paired_rc",
+            "After Removing Paired rc_inc & rc_decs:",
+            Ssa::remove_paired_rc,
+        ),
+        SsaPass::required("separate_runtime", "After Runtime Separation:", Ssa::separate_runtime),
+        SsaPass::required(
+            "resolve_is_unconstrained",
+            "After Resolving IsUnconstrained:",
+            Ssa::resolve_is_unconstrained,
+        ),
+        SsaPass::required("inline", "After Inlining:", Ssa::inline_functions),
+        // Run mem2reg with the CFG separated into blocks
+        SsaPass::optimization("mem2reg", "After Mem2Reg:", Ssa::mem2reg),
+        SsaPass::optimization(
+            "as_slice",
+            "After `as_slice` optimization",
+            Ssa::as_slice_optimization,
+        ),
+        SsaPass::fallible(
+            "static_assert",
+            "After `static_assert` and `assert_constant`:",
+            Ssa::evaluate_static_assert_and_assert_constant,
+        ),
+        SsaPass::fallible("unroll", "After Unrolling:", Ssa::unroll_loops_iteratively),
+        SsaPass::optimization("simplify_cfg", "After Simplifying:", Ssa::simplify_cfg),
+        SsaPass::optimization(
+            "global_value_numbering",
+            "After Global Value Numbering:",
+            Ssa::global_value_numbering,
+        ),
+        SsaPass::required("flatten_cfg", "After Flattening:", Ssa::flatten_cfg),
+        SsaPass::required(
+            "remove_bit_shifts",
+            "After Removing Bit Shifts:",
+            Ssa::remove_bit_shifts,
+        ),
+        // Run mem2reg once more with the flattened CFG to catch any remaining loads/stores
+        SsaPass::required("mem2reg_flattened", "After Mem2Reg:", Ssa::mem2reg).flattening_acir(),
+        // Run the inlining pass again to handle functions with `InlineType::NoPredicates`.
+        // Before flattening is run, we treat functions marked with the `InlineType::NoPredicates` as an entry point.
+        // This pass must come immediately following `mem2reg` as the succeeding passes
+        // may create an SSA which inlining fails to handle.
+        SsaPass::required(
+            "inline_no_predicates",
+            "After Inlining:",
+            Ssa::inline_functions_with_no_predicates,
+        ),
+        SsaPass::required("remove_if_else", "After Remove IfElse:", Ssa::remove_if_else),
+        SsaPass::optimization("fold_constants", "After Constant Folding:", Ssa::fold_constants),
+        SsaPass::optimization(
+            "remove_enable_side_effects",
+            "After EnableSideEffects removal:",
+            Ssa::remove_enable_side_effects,
+        ),
+        SsaPass::optimization(
+            "range_analysis",
+            "After Range Analysis:",
+            Ssa::remove_redundant_range_checks,
+        ),
+        SsaPass::optimization(
+            "fold_constraints",
+            "After Constraint Folding:",
+            Ssa::fold_constants_using_constraints,
+        ),
+        SsaPass::optimization(
+            "loop_invariant_code_motion",
+            "After Loop Invariant Code Motion:",
+            Ssa::loop_invariant_code_motion,
+        ),
+        SsaPass::optimization(
+            "dead_instruction_elimination",
+            "After Dead Instruction Elimination:",
+            Ssa::dead_instruction_elimination,
+        ),
+        SsaPass::optimization(
+            "array_set",
+            "After Array Set Optimizations:",
+            Ssa::array_set_optimization,
+        ),
+    ]
+}
+
+/// The names of the SSA passes, in the order they are run
+pub fn ssa_pass_names() -> Vec<&'static str> {
+    ssa_passes().into_iter().map(|pass| pass.name).collect()
+}
+
+/// The names of the SSA passes which only optimize the program, and so may be skipped
+pub fn optional_ssa_pass_names() -> Vec<&'static str> {
+    ssa_passes().into_iter().filter(|pass| pass.optional).map(|pass| pass.name).collect()
 }
 
 // Helper to time SSA passes
@@ -125,6 +246,8 @@ pub struct SsaProgramArtifact {
     pub main_return_witnesses: Vec<Witness>,
     pub names: Vec<String>,
     pub error_types: BTreeMap<ErrorSelector, HirType>,
+    /// The number of registers needed by each unconstrained function
+    pub brillig_register_usage: Vec<BrilligRegisterUsage>,
 }
 
 impl SsaProgramArtifact {
@@ -141,6 +264,7 @@ impl SsaProgramArtifact {
             main_return_witnesses: Vec::default(),
             names: Vec::default(),
             error_types,
+            brillig_register_usage: Vec::default(),
         }
     }
 
@@ -171,6 +295,25 @@ pub struct SsaEvaluatorOptions {
 
     /// Pretty print benchmark times of each code generation pass
     pub print_codegen_timings: bool,
+
+    /// Interpret the SSA on sample inputs before and after each pass,
+    /// panicking if a pass changes the behavior of the program
+    pub check_ssa_passes: bool,
+
+    /// Check that the SSA is well formed after each pass. This is always done in debug builds.
+    pub validate_ssa: bool,
+
+    /// Write the SSA after each pass to a numbered file in this directory
+    pub ssa_dump_directory: Option<PathBuf>,
+
+    /// The names of optional SSA passes which should not be run
+    pub skip_ssa_passes: Vec<String>,
+
+    /// Skip all optional SSA passes after the pass with this name
+    pub stop_after_ssa_pass: Option<String>,
+
+    /// Print a table of the time taken by each SSA pass and how it changed the number of instructions
+    pub show_ssa_pass_statistics: bool,
 }
 
 /// Compiles the [`Program`] into [`ACIR``][acvm::acir::circuit::Program].
@@ -188,8 +331,11 @@ pub fn create_program(
     let func_sigs = program.function_signatures.clone();
 
     let recursive = program.recursive;
-    let ArtifactsAndWarnings((generated_acirs, generated_brillig, error_types), ssa_level_warnings) =
-        optimize_into_acir(program, options)?;
+    let ArtifactsAndWarnings(
+        (generated_acirs, generated_brillig, error_types),
+        ssa_level_warnings,
+        brillig_register_usage,
+    ) = optimize_into_acir(program, options)?;
     assert_eq!(
         generated_acirs.len(),
         func_sigs.len(),
@@ -197,6 +343,7 @@ pub fn create_program(
     );
 
     let mut program_artifact = SsaProgramArtifact::new(generated_brillig, error_types);
+    program_artifact.brillig_register_usage = brillig_register_usage;
 
     // Add warnings collected at the Ssa stage
     program_artifact.add_warnings(ssa_level_warnings);
@@ -326,39 +473,157 @@ struct SsaBuilder {
     ssa: Ssa,
     print_ssa_passes: bool,
     print_codegen_timings: bool,
+    check_ssa_passes: bool,
+    validate_ssa: bool,
+
+    /// Set once ACIR functions are expected to contain no loops or references
+    acir_flattened: bool,
+
+    dump_directory: Option<PathBuf>,
+    skip_passes: Vec<String>,
+    stop_after_pass: Option<String>,
+
+    /// Set once the pass named by `stop_after_pass` has run
+    stopped: bool,
+
+    /// The statistics for each pass which has run, if they should be printed
+    statistics: Option<Vec<PassStatistics>>,
+}
+
+struct PassStatistics {
+    name: &'static str,
+    duration: Duration,
+    instructions: usize,
 }
 
 impl SsaBuilder {
-    fn new(
-        program: Program,
-        print_ssa_passes: bool,
-        force_brillig_runtime: bool,
-        print_codegen_timings: bool,
-    ) -> Result<SsaBuilder, RuntimeError> {
-        let ssa = ssa_gen::generate_ssa(program, force_brillig_runtime)?;
-        Ok(SsaBuilder { print_ssa_passes, print_codegen_timings, ssa }.print("Initial SSA:"))
+    fn new(program: Program, options: &SsaEvaluatorOptions) -> Result<SsaBuilder, RuntimeError> {
+        let start = Instant::now();
+        let ssa = ssa_gen::generate_ssa(program, options.force_brillig_output)?;
+        let statistics = options.show_ssa_pass_statistics.then(|| {
+            let instructions = count_instructions(&ssa);
+            vec![PassStatistics { name: "ssa_gen", duration: start.elapsed(), instructions }]
+        });
+        let builder = SsaBuilder {
+            print_ssa_passes: options.enable_ssa_logging,
+            print_codegen_timings: options.print_codegen_timings,
+            check_ssa_passes: options.check_ssa_passes,
+            validate_ssa: options.validate_ssa || cfg!(debug_assertions),
+            acir_flattened: false,
+            dump_directory: options.ssa_dump_directory.clone(),
+            skip_passes: options.skip_ssa_passes.clone(),
+            stop_after_pass: options.stop_after_ssa_pass.clone(),
+            stopped: false,
+            statistics,
+            ssa,
+        };
+        builder.validate("Initial SSA:");
+        builder.clear_dumps();
+        builder.dump(0, "initial");
+        Ok(builder.print("Initial SSA:"))
     }
 
     fn finish(self) -> Ssa {
+        if let Some(statistics) = &self.statistics {
+            print_pass_statistics(statistics);
+        }
         self.ssa
     }
 
-    /// Runs the given SSA pass and prints the SSA afterward if `print_ssa_passes` is true.
-    fn run_pass(mut self, pass: fn(Ssa) -> Ssa, msg: &str) -> Self {
-        self.ssa = time(msg, self.print_codegen_timings, || pass(self.ssa));
-        self.print(msg)
-    }
+    /// Runs the given SSA pass, unless it is an optional pass which has been skipped,
+    /// and prints the SSA afterward if `print_ssa_passes` is true.
+    /// `index` is the position of the pass in the pipeline, which is used to name its dump.
+    fn run_pass(mut self, index: usize, pass: SsaPass) -> Result<Self, RuntimeError> {
+        if pass.optional && (self.stopped || self.skip_passes.iter().any(|name| name == pass.name))
+        {
+            return Ok(self);
+        }
 
-    /// The same as `run_pass` but for passes that may fail
-    fn try_run_pass(
-        mut self,
-        pass: fn(Ssa) -> Result<Ssa, RuntimeError>,
-        msg: &str,
-    ) -> Result<Self, RuntimeError> {
-        self.ssa = time(msg, self.print_codegen_timings, || pass(self.ssa))?;
+        let msg = pass.msg;
+        let observations = self.observe();
+        let start = Instant::now();
+        self.ssa = time(msg, self.print_codegen_timings, || match pass.run {
+            PassFunction::Infallible(run) => Ok(run(self.ssa)),
+            PassFunction::Fallible(run) => run(self.ssa),
+        })?;
+        let duration = start.elapsed();
+
+        if pass.flattens_acir {
+            self.acir_flattened = true;
+        }
+        self.validate(msg);
+        self.check_pass(observations, msg);
+        self.dump(index, pass.name);
+        if let Some(statistics) = &mut self.statistics {
+            let instructions = count_instructions(&self.ssa);
+            statistics.push(PassStatistics { name: pass.name, duration, instructions });
+        }
+        if self.stop_after_pass.as_deref() == Some(pass.name) {
+            self.stopped = true;
+        }
         Ok(self.print(msg))
     }
 
+    /// Removes the dumps of any previous compilation, so that they aren't mistaken for this one's.
+    /// Only files named like the dumps are removed since the directory is chosen by the user.
+    fn clear_dumps(&self) {
+        let Some(directory) = &self.dump_directory else { return };
+        let Ok(entries) = std::fs::read_dir(directory) else { return };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_dump = path.is_file()
+                && path.file_name().and_then(|name| name.to_str()).map_or(false, is_ssa_dump_name);
+
+            if is_dump {
+                if let Err(error) = std::fs::remove_file(&path) {
+                    eprintln!("Failed to remove SSA dump {}: {error}", path.display());
+                }
+            }
+        }
+    }
+
+    /// Writes the SSA to a numbered file in `dump_directory`, if it is set
+    fn dump(&self, index: usize, name: &str) {
+        let Some(directory) = &self.dump_directory else { return };
+        let path = directory.join(format!("{index:02}_{name}.ssa"));
+        let result = std::fs::create_dir_all(directory)
+            .and_then(|_| std::fs::write(&path, self.ssa.to_string()));
+        if let Err(error) = result {
+            eprintln!("Failed to write SSA to {}: {error}", path.display());
+        }
+    }
+
+    /// Panics if `validate_ssa` is true and the SSA is malformed
+    fn validate(&self, msg: &str) {
+        if !self.validate_ssa {
+            return;
+        }
+        if let Err(errors) = self.ssa.validate(self.acir_flattened) {
+            let errors =
+                errors.iter().map(|error| format!("  {error}")).collect::<Vec<_>>().join("\n");
+            panic!("ICE: SSA is malformed. {msg}\n{errors}");
+        }
+    }
+
+    /// Interprets the program on sample inputs if `check_ssa_passes` is true
+    fn observe(&self) -> Option<interpreter::Observations> {
+        if self.check_ssa_passes {
+            self.ssa.observe()
+        } else {
+            None
+        }
+    }
+
+    /// Panics if the program now behaves differently on the inputs observed before the pass
+    fn check_pass(&self, before: Option<interpreter::Observations>, msg: &str) {
+        let Some(before) = before else { return };
+        let Some(after) = self.ssa.observe() else { return };
+        if let Some(divergence) = before.find_divergence(&after) {
+            panic!("ICE: SSA miscompilation detected. {msg} {divergence}");
+        }
+    }
+
     fn print(self, msg: &str) -> Self {
         if self.print_ssa_passes {
             println!("{msg}\n{}", self.ssa);
@@ -366,3 +631,68 @@ impl SsaBuilder {
         self
     }
 }
+
+/// Whether `file_name` is of the form `NN_<pass>.ssa`, as written by `SsaBuilder::dump`
+fn is_ssa_dump_name(file_name: &str) -> bool {
+    let Some(pass_name) = file_name.strip_suffix(".ssa") else { return false };
+    let bytes = pass_name.as_bytes();
+    bytes.len() > 3 && bytes[0].is_ascii_digit() && bytes[1].is_ascii_digit() && bytes[2] == b'_'
+}
+
+/// The number of instructions in the reachable blocks of every function
+fn count_instructions(ssa: &Ssa) -> usize {
+    ssa.functions
+        .values()
+        .map(|function| {
+            let blocks = function.reachable_blocks();
+            blocks.iter().map(|block| function.dfg[*block].instructions().len()).sum::<usize>()
+        })
+        .sum()
+}
+
+fn print_pass_statistics(statistics: &[PassStatistics]) {
+    println!("{:<30} {:>10} {:>13} {:>8}", "SSA Pass", "Time (ms)", "Instructions", "Change");
+    let mut previous = None;
+    for pass in statistics {
+        let change = previous.map_or(String::new(), |previous: usize| {
+            format!("{:+}", pass.instructions as i64 - previous as i64)
+        });
+        let time = pass.duration.as_secs_f64() * 1000.0;
+        println!("{:<30} {time:>10.2} {:>13} {change:>8}", pass.name, pass.instructions);
+        previous = Some(pass.instructions);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{is_ssa_dump_name, optional_ssa_pass_names, ssa_pass_names};
+
+    #[test]
+    fn ssa_pass_names_are_unique() {
+        let names = ssa_pass_names();
+        let unique_names: HashSet<_> = names.iter().collect();
+        assert_eq!(names.len(), unique_names.len());
+    }
+
+    #[test]
+    fn passes_needed_for_acir_are_not_optional() {
+        let optional_names = optional_ssa_pass_names();
+        for name in ["defunctionalize", "inline", "unroll", "flatten_cfg", "mem2reg_flattened"] {
+            assert!(ssa_pass_names().contains(&name));
+            assert!(!optional_names.contains(&name));
+        }
+    }
+
+    #[test]
+    fn only_ssa_dumps_are_cleared() {
+        assert!(is_ssa_dump_name("00_initial.ssa"));
+        assert!(is_ssa_dump_name("12_mem2reg_flattened.ssa"));
+
+        for name in ["main.nr", "Nargo.toml", "notes.ssa", "1_initial.ssa", "00_.ssa", "00_initial"]
+        {
+            assert!(!is_ssa_dump_name(name), "{name}");
+        }
+    }
+}
@@ -946,7 +946,7 @@ impl<'a> Context<'a> {
             entry_point.link_with(artifact);
         }
         // Generate the final bytecode
-        Ok(entry_point.finish())
+        Ok(entry_point.finish().optimize())
     }
 
     /// Handles an ArrayGet or ArraySet instruction.
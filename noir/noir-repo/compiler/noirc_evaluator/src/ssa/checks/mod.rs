@@ -1 +1,2 @@
 mod check_for_underconstrained_values;
+mod validation;
@@ -0,0 +1,523 @@
+//! This module defines a check of the invariants every SSA program must uphold between passes.
+//! It is run after each pass in debug builds (or with `--validate-ssa`) so that a pass which
+//! breaks the SSA is reported right away, rather than causing a confusing panic much later on.
+//!
+//! The checks are:
+//! - every value used by a reachable block is defined, and its definition dominates the use
+//! - every reachable block is terminated, and instructions aren't shared between blocks
+//! - jumps pass as many arguments as their destination has parameters, with matching types
+//! - instructions' operands and results have consistent types
+//! - once flattened, ACIR functions contain no loops and no references
+use std::collections::HashMap;
+
+use acvm::acir::AcirField;
+use thiserror::Error;
+
+use crate::ssa::{
+    ir::{
+        basic_block::BasicBlockId,
+        dfg::DataFlowGraph,
+        dom::DominatorTree,
+        function::{Function, FunctionId, RuntimeType},
+        instruction::{Binary, BinaryOp, Instruction, InstructionId, TerminatorInstruction},
+        post_order::PostOrder,
+        types::Type,
+        value::{Value, ValueId},
+    },
+    ssa_gen::Ssa,
+};
+
+#[derive(Debug, Error)]
+pub(crate) enum ValidationError {
+    #[error("{function}: {value} is used in {block} but is never defined")]
+    UndefinedValue { function: FunctionId, block: BasicBlockId, value: ValueId },
+    #[error("{function}: {value} is used in {block} but its definition does not dominate the use")]
+    UseNotDominated { function: FunctionId, block: BasicBlockId, value: ValueId },
+    #[error("{function}: {block} has no terminator")]
+    MissingTerminator { function: FunctionId, block: BasicBlockId },
+    #[error("{function}: {instruction} is in both {first_block} and {second_block}")]
+    DuplicateInstruction {
+        function: FunctionId,
+        instruction: InstructionId,
+        first_block: BasicBlockId,
+        second_block: BasicBlockId,
+    },
+    #[error("{function}: the jump from {block} to {destination} passes {arguments} arguments but {destination} has {parameters} parameters")]
+    JumpArgumentCount {
+        function: FunctionId,
+        block: BasicBlockId,
+        destination: BasicBlockId,
+        arguments: usize,
+        parameters: usize,
+    },
+    #[error("{function}: the jump from {block} to {destination} passes a {argument} argument for a {parameter} parameter")]
+    JumpArgumentType {
+        function: FunctionId,
+        block: BasicBlockId,
+        destination: BasicBlockId,
+        argument: Type,
+        parameter: Type,
+    },
+    #[error("{function}: {instruction} in {block} expects {expected} but found {found}")]
+    TypeMismatch {
+        function: FunctionId,
+        block: BasicBlockId,
+        instruction: InstructionId,
+        expected: Type,
+        found: Type,
+    },
+    #[error("{function}: flattened ACIR function loops back to {block}")]
+    LoopInAcir { function: FunctionId, block: BasicBlockId },
+    #[error("{function}: flattened ACIR function uses the reference {value} in {block}")]
+    ReferenceInAcir { function: FunctionId, block: BasicBlockId, value: ValueId },
+}
+
+impl Ssa {
+    /// Checks that this SSA is well formed, returning every broken invariant found.
+    ///
+    /// `acir_flattened` should be set once ACIR functions have been flattened and had their
+    /// references removed, after which they are also checked to contain no loops or references.
+    pub(crate) fn validate(&self, acir_flattened: bool) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        for function in self.functions.values() {
+            let mut validator = Validator::new(function);
+            validator.validate();
+            if acir_flattened && matches!(function.runtime(), RuntimeType::Acir(_)) {
+                validator.validate_flattened_acir();
+            }
+            errors.extend(validator.errors);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+struct Validator<'f> {
+    function: &'f Function,
+    dom_tree: DominatorTree,
+    post_order: PostOrder,
+
+    /// Maps each instruction in a reachable block to that block and its position in it
+    instruction_positions: HashMap<InstructionId, (BasicBlockId, usize)>,
+
+    errors: Vec<ValidationError>,
+}
+
+impl<'f> Validator<'f> {
+    fn new(function: &'f Function) -> Self {
+        Self {
+            function,
+            dom_tree: DominatorTree::with_function(function),
+            post_order: PostOrder::with_function(function),
+            instruction_positions: HashMap::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    fn dfg(&self) -> &'f DataFlowGraph {
+        &self.function.dfg
+    }
+
+    fn validate(&mut self) {
+        let function = self.function.id();
+        let blocks: Vec<_> = self.post_order.as_slice().iter().rev().copied().collect();
+
+        for block in &blocks {
+            for (position, instruction) in self.dfg()[*block].instructions().iter().enumerate() {
+                if let Some((first_block, _)) =
+                    self.instruction_positions.insert(*instruction, (*block, position))
+                {
+                    self.errors.push(ValidationError::DuplicateInstruction {
+                        function,
+                        instruction: *instruction,
+                        first_block,
+                        second_block: *block,
+                    });
+                }
+            }
+        }
+
+        for block in blocks {
+            self.validate_block(block);
+        }
+    }
+
+    fn validate_block(&mut self, block: BasicBlockId) {
+        let dfg = self.dfg();
+        let instructions = dfg[block].instructions();
+
+        for (position, instruction) in instructions.iter().enumerate() {
+            dfg[*instruction].for_each_value(|value| self.validate_use(value, block, position));
+            self.validate_instruction_types(block, *instruction);
+        }
+
+        let Some(terminator) = dfg[block].terminator() else {
+            let function = self.function.id();
+            self.errors.push(ValidationError::MissingTerminator { function, block });
+            return;
+        };
+
+        // Terminators come after every instruction in the block
+        terminator.for_each_value(|value| self.validate_use(value, block, instructions.len()));
+
+        match terminator {
+            TerminatorInstruction::Jmp { destination, arguments, .. } => {
+                self.validate_jump(block, *destination, arguments);
+            }
+            TerminatorInstruction::JmpIf { condition, then_destination, else_destination } => {
+                let condition_type = dfg.type_of_value(*condition);
+                if condition_type != Type::bool() {
+                    let function = self.function.id();
+                    self.errors.push(ValidationError::JumpArgumentType {
+                        function,
+                        block,
+                        destination: *then_destination,
+                        argument: condition_type,
+                        parameter: Type::bool(),
+                    });
+                }
+                self.validate_jump(block, *then_destination, &[]);
+                self.validate_jump(block, *else_destination, &[]);
+            }
+            TerminatorInstruction::Return { .. } => (),
+        }
+    }
+
+    /// Checks that `value`, used at `position` in `block`, is defined at a point dominating its use
+    fn validate_use(&mut self, value: ValueId, block: BasicBlockId, position: usize) {
+        let dfg = self.dfg();
+        let value = dfg.resolve(value);
+        let function = self.function.id();
+
+        let definition = match &dfg[value] {
+            Value::Instruction { instruction, position: result_position, .. } => {
+                match self.instruction_positions.get(instruction) {
+                    Some((definition_block, definition_position))
+                        if dfg.instruction_results(*instruction).get(*result_position)
+                            == Some(&value) =>
+                    {
+                        // A result is only available to the instructions after its own
+                        Some((*definition_block, *definition_position + 1))
+                    }
+                    _ => None,
+                }
+            }
+            Value::Param { block: definition_block, position: parameter_position, .. } => {
+                let parameters = dfg.block_parameters(*definition_block);
+                if self.dom_tree.is_reachable(*definition_block)
+                    && parameters.get(*parameter_position) == Some(&value)
+                {
+                    Some((*definition_block, 0))
+                } else {
+                    None
+                }
+            }
+            Value::Array { array, .. } => {
+                for element in array {
+                    self.validate_use(*element, block, position);
+                }
+                return;
+            }
+            Value::NumericConstant { .. }
+            | Value::Function(_)
+            | Value::Intrinsic(_)
+            | Value::ForeignFunction(_) => return,
+        };
+
+        let Some((definition_block, definition_position)) = definition else {
+            self.errors.push(ValidationError::UndefinedValue { function, block, value });
+            return;
+        };
+
+        let dominates = if definition_block == block {
+            definition_position <= position
+        } else {
+            self.dom_tree.dominates(definition_block, block)
+        };
+        if !dominates {
+            self.errors.push(ValidationError::UseNotDominated { function, block, value });
+        }
+    }
+
+    fn validate_jump(
+        &mut self,
+        block: BasicBlockId,
+        destination: BasicBlockId,
+        arguments: &[ValueId],
+    ) {
+        let dfg = self.dfg();
+        let function = self.function.id();
+        let parameters = dfg.block_parameters(destination);
+
+        if arguments.len() != parameters.len() {
+            self.errors.push(ValidationError::JumpArgumentCount {
+                function,
+                block,
+                destination,
+                arguments: arguments.len(),
+                parameters: parameters.len(),
+            });
+            return;
+        }
+
+        for (argument, parameter) in arguments.iter().zip(parameters) {
+            let argument = dfg.type_of_value(*argument);
+            let parameter = dfg.type_of_value(*parameter);
+            if argument != parameter {
+                self.errors.push(ValidationError::JumpArgumentType {
+                    function,
+                    block,
+                    destination,
+                    argument,
+                    parameter,
+                });
+            }
+        }
+    }
+
+    fn validate_instruction_types(&mut self, block: BasicBlockId, instruction: InstructionId) {
+        let dfg = self.dfg();
+        let type_of = |value: ValueId| dfg.type_of_value(value);
+        let result_type =
+            || dfg.instruction_results(instruction).first().map(|result| type_of(*result));
+
+        // Pairs of (expected, found) types
+        let mut expectations = Vec::new();
+        match &dfg[instruction] {
+            Instruction::Binary(Binary { lhs, rhs, operator }) => {
+                // The right hand side of a shift may have a different type than the left
+                if !matches!(operator, BinaryOp::Shl | BinaryOp::Shr) {
+                    expectations.push((type_of(*lhs), type_of(*rhs)));
+                }
+                let expected_result = match operator {
+                    BinaryOp::Eq | BinaryOp::Lt => Type::bool(),
+                    _ => type_of(*lhs),
+                };
+                expectations.extend(result_type().map(|result| (expected_result, result)));
+            }
+            Instruction::Cast(_, typ) => {
+                expectations.extend(result_type().map(|result| (typ.clone(), result)));
+            }
+            Instruction::Not(value) | Instruction::Truncate { value, .. } => {
+                expectations.extend(result_type().map(|result| (type_of(*value), result)));
+            }
+            Instruction::Load { address } => {
+                if let (Type::Reference(element), Some(result)) = (type_of(*address), result_type())
+                {
+                    expectations.push((element.as_ref().clone(), result));
+                }
+            }
+            Instruction::Store { address, value } => {
+                if let Type::Reference(element) = type_of(*address) {
+                    expectations.push((element.as_ref().clone(), type_of(*value)));
+                }
+            }
+            Instruction::ArrayGet { array, index } => {
+                if let (Some(element), Some(result)) =
+                    (self.element_type(*array, *index), result_type())
+                {
+                    expectations.push((element, result));
+                }
+            }
+            Instruction::ArraySet { array, index, value, .. } => {
+                if let Some(element) = self.element_type(*array, *index) {
+                    expectations.push((element, type_of(*value)));
+                }
+                expectations.extend(result_type().map(|result| (type_of(*array), result)));
+            }
+            Instruction::IfElse { then_value, else_value, .. } => {
+                expectations.push((type_of(*then_value), type_of(*else_value)));
+                expectations.extend(result_type().map(|result| (type_of(*then_value), result)));
+            }
+            Instruction::Constrain(..)
+            | Instruction::RangeCheck { .. }
+            | Instruction::Call { .. }
+            | Instruction::Allocate
+            | Instruction::EnableSideEffects { .. }
+            | Instruction::IncrementRc { .. }
+            | Instruction::DecrementRc { .. } => (),
+        }
+
+        let function = self.function.id();
+        for (expected, found) in expectations {
+            if expected != found {
+                self.errors.push(ValidationError::TypeMismatch {
+                    function,
+                    block,
+                    instruction,
+                    expected,
+                    found,
+                });
+            }
+        }
+    }
+
+    /// Returns the type of the element at `index` in `array`, if it can be determined.
+    /// Arrays of composite types are flattened, so their element type depends on the index.
+    fn element_type(&self, array: ValueId, index: ValueId) -> Option<Type> {
+        let dfg = self.dfg();
+        let (Type::Array(element_types, _) | Type::Slice(element_types)) = dfg.type_of_value(array)
+        else {
+            return None;
+        };
+        if element_types.is_empty() {
+            return None;
+        }
+        match dfg.get_numeric_constant(index) {
+            Some(index) => {
+                let index = index.try_to_u64()? as usize;
+                element_types.get(index % element_types.len()).cloned()
+            }
+            None if element_types.len() == 1 => Some(element_types[0].clone()),
+            None => None,
+        }
+    }
+
+    /// Checks that a flattened ACIR function has no loops and uses no references
+    fn validate_flattened_acir(&mut self) {
+        let dfg = self.dfg();
+        let function = self.function.id();
+
+        // A reverse post-order visits each block before its successors,
+        // except for the targets of back edges which have been visited already
+        let reverse_post_order: HashMap<_, _> =
+            self.post_order.as_slice().iter().rev().enumerate().map(|(i, b)| (*b, i)).collect();
+        for (block, index) in &reverse_post_order {
+            for successor in dfg[*block].successors() {
+                if reverse_post_order[&successor] <= *index {
+                    self.errors.push(ValidationError::LoopInAcir { function, block: successor });
+                }
+            }
+        }
+
+        // Allocations which mem2reg couldn't remove are reported to the user when generating
+        // ACIR, so they aren't broken invariants of the SSA.
+        let has_allocations = reverse_post_order.keys().any(|block| {
+            dfg[*block]
+                .instructions()
+                .iter()
+                .any(|instruction| matches!(dfg[*instruction], Instruction::Allocate))
+        });
+        if has_allocations {
+            return;
+        }
+
+        for block in reverse_post_order.keys() {
+            let mut values = dfg.block_parameters(*block).to_vec();
+            for instruction in dfg[*block].instructions() {
+                values.extend_from_slice(dfg.instruction_results(*instruction));
+            }
+            for value in values {
+                if matches!(dfg.type_of_value(value), Type::Reference(_)) {
+                    let block = *block;
+                    self.errors.push(ValidationError::ReferenceInAcir { function, block, value });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ssa::ssa_gen::Ssa;
+
+    use super::ValidationError;
+
+    fn validate(src: &str, acir_flattened: bool) -> Vec<ValidationError> {
+        let ssa = Ssa::from_str(src).unwrap();
+        ssa.validate(acir_flattened).err().unwrap_or_default()
+    }
+
+    #[test]
+    fn accepts_valid_ssa() {
+        let src = "
+brillig fn main f0 {
+  b0(v0: u32):
+    jmp b1(u32 0)
+  b1(v1: u32):
+    v3 = lt v1, v0
+    jmpif v3 then: b2, else: b3
+  b2():
+    v5 = add v1, u32 1
+    jmp b1(v5)
+  b3():
+    return v1
+}
+";
+        assert!(validate(src, true).is_empty());
+    }
+
+    #[test]
+    fn rejects_uses_which_are_not_dominated() {
+        let src = "
+acir(inline) fn main f0 {
+  b0(v0: u1):
+    jmpif v0 then: b1, else: b2
+  b1():
+    v1 = add Field 1, Field 2
+    jmp b3()
+  b2():
+    jmp b3()
+  b3():
+    return v1
+}
+";
+        let errors = validate(src, false);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], ValidationError::UseNotDominated { .. }));
+    }
+
+    #[test]
+    fn rejects_jumps_with_mismatched_arguments() {
+        let src = "
+acir(inline) fn main f0 {
+  b0(v0: u32):
+    jmp b1(v0)
+  b1(v1: Field):
+    jmp b2(v1)
+  b2():
+    return
+}
+";
+        let errors = validate(src, false);
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[0], ValidationError::JumpArgumentType { .. }));
+        assert!(matches!(errors[1], ValidationError::JumpArgumentCount { .. }));
+    }
+
+    #[test]
+    fn rejects_mismatched_operand_types() {
+        let src = "
+acir(inline) fn main f0 {
+  b0(v0: [Field; 2]):
+    v2 = array_get v0, index u32 0 -> u8
+    return v2
+}
+";
+        let errors = validate(src, false);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], ValidationError::TypeMismatch { .. }));
+    }
+
+    #[test]
+    fn rejects_loops_and_references_in_flattened_acir() {
+        let src = "
+acir(inline) fn main f0 {
+  b0(v0: &mut Field):
+    jmp b1()
+  b1():
+    jmp b1()
+}
+";
+        assert!(validate(src, false).is_empty());
+
+        let errors = validate(src, true);
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[0], ValidationError::LoopInAcir { .. }));
+        assert!(matches!(errors[1], ValidationError::ReferenceInAcir { .. }));
+    }
+}
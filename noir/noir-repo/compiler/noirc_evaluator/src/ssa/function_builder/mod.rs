@@ -37,6 +37,9 @@ pub(crate) struct FunctionBuilder {
     finished_functions: Vec<Function>,
     call_stack: CallStack,
     error_types: BTreeMap<ErrorSelector, ErrorType>,
+    /// Whether instructions are simplified as they are inserted. This is only
+    /// disabled when the SSA being built should match its source exactly.
+    simplify: bool,
 }
 
 impl FunctionBuilder {
@@ -53,9 +56,15 @@ impl FunctionBuilder {
             finished_functions: Vec::new(),
             call_stack: CallStack::new(),
             error_types: BTreeMap::default(),
+            simplify: true,
         }
     }
 
+    /// Sets whether instructions should be simplified as they are inserted.
+    pub(crate) fn set_simplify(&mut self, simplify: bool) {
+        self.simplify = simplify;
+    }
+
     /// Set the runtime of the initial function that is created internally after constructing
     /// the FunctionBuilder. A function's default runtime type is `RuntimeType::Acir(InlineType::Inline)`.
     /// This should only be used immediately following construction of a FunctionBuilder
@@ -165,12 +174,22 @@ impl FunctionBuilder {
         ctrl_typevars: Option<Vec<Type>>,
     ) -> InsertInstructionResult {
         let block = self.current_block();
-        self.current_function.dfg.insert_instruction_and_results(
-            instruction,
-            block,
-            ctrl_typevars,
-            self.call_stack.clone(),
-        )
+        let call_stack = self.call_stack.clone();
+        if self.simplify {
+            self.current_function.dfg.insert_instruction_and_results(
+                instruction,
+                block,
+                ctrl_typevars,
+                call_stack,
+            )
+        } else {
+            self.current_function.dfg.insert_instruction_and_results_without_simplification(
+                instruction,
+                block,
+                ctrl_typevars,
+                call_stack,
+            )
+        }
     }
 
     /// Switch to inserting instructions in the given block.
@@ -0,0 +1,152 @@
+use acvm::{acir::AcirField, FieldElement};
+
+use crate::ssa::ir::{instruction::BinaryOp, types::NumericType};
+
+use super::{value::Value, InterpreterError};
+
+/// Evaluates a binary operation on two numeric values of type `typ`.
+///
+/// Unsigned arithmetic which overflows and division by zero are failures while side effects
+/// are enabled, which is when ACIR and Brillig generation check them. Otherwise these wrap and
+/// evaluate to zero respectively, matching the code generated for a disabled predicate.
+pub(super) fn evaluate_binary(
+    operator: BinaryOp,
+    lhs: FieldElement,
+    rhs: FieldElement,
+    typ: NumericType,
+    side_effects_enabled: bool,
+) -> Result<Value, InterpreterError> {
+    match operator {
+        BinaryOp::Eq => return Ok(Value::bool(lhs == rhs)),
+        BinaryOp::Div | BinaryOp::Mod if rhs.is_zero() => {
+            return if side_effects_enabled {
+                Err(InterpreterError::DivisionByZero)
+            } else {
+                Ok(Value::Numeric { value: FieldElement::zero(), typ })
+            };
+        }
+        _ => (),
+    }
+
+    match typ {
+        NumericType::NativeField => evaluate_field_binary(operator, lhs, rhs),
+        NumericType::Unsigned { bit_size } => {
+            evaluate_unsigned_binary(operator, lhs, rhs, bit_size, side_effects_enabled)
+        }
+        NumericType::Signed { bit_size } => evaluate_signed_binary(operator, lhs, rhs, bit_size),
+    }
+}
+
+fn evaluate_field_binary(
+    operator: BinaryOp,
+    lhs: FieldElement,
+    rhs: FieldElement,
+) -> Result<Value, InterpreterError> {
+    let value = match operator {
+        BinaryOp::Add => lhs + rhs,
+        BinaryOp::Sub => lhs - rhs,
+        BinaryOp::Mul => lhs * rhs,
+        BinaryOp::Div => lhs / rhs,
+        BinaryOp::Lt => return Ok(Value::bool(lhs < rhs)),
+        _ => {
+            return Err(InterpreterError::Internal(format!(
+                "Operator `{operator}` is not supported on fields"
+            )))
+        }
+    };
+    Ok(Value::field(value))
+}
+
+fn evaluate_unsigned_binary(
+    operator: BinaryOp,
+    lhs: FieldElement,
+    rhs: FieldElement,
+    bit_size: u32,
+    side_effects_enabled: bool,
+) -> Result<Value, InterpreterError> {
+    if bit_size > 128 {
+        return Err(InterpreterError::UnsupportedType(format!("u{bit_size}")));
+    }
+    let typ = NumericType::Unsigned { bit_size };
+    let max = u128::MAX >> (128 - bit_size);
+    let (lhs, rhs) = (lhs.to_u128(), rhs.to_u128());
+
+    let checked = |result: Option<u128>, wrapped: u128| match result.filter(|value| *value <= max) {
+        Some(value) => Ok(Value::integer(value, typ)),
+        None if side_effects_enabled => Err(InterpreterError::Overflow { operator, typ }),
+        None => Ok(Value::integer(wrapped, typ)),
+    };
+
+    match operator {
+        BinaryOp::Add => checked(lhs.checked_add(rhs), lhs.wrapping_add(rhs)),
+        BinaryOp::Sub => checked(lhs.checked_sub(rhs), lhs.wrapping_sub(rhs)),
+        BinaryOp::Mul => checked(lhs.checked_mul(rhs), lhs.wrapping_mul(rhs)),
+        BinaryOp::Div => Ok(Value::integer(lhs / rhs, typ)),
+        BinaryOp::Mod => Ok(Value::integer(lhs % rhs, typ)),
+        BinaryOp::Lt => Ok(Value::bool(lhs < rhs)),
+        BinaryOp::And => Ok(Value::integer(lhs & rhs, typ)),
+        BinaryOp::Or => Ok(Value::integer(lhs | rhs, typ)),
+        BinaryOp::Xor => Ok(Value::integer(lhs ^ rhs, typ)),
+        BinaryOp::Shl => Ok(Value::integer(shift_left(lhs, rhs, bit_size), typ)),
+        BinaryOp::Shr => Ok(Value::integer(shift_right(lhs, rhs, bit_size), typ)),
+        BinaryOp::Eq => unreachable!("Eq is handled for all types"),
+    }
+}
+
+/// Signed arithmetic wraps as the SSA generated for it truncates each result
+/// and checks for overflow explicitly.
+fn evaluate_signed_binary(
+    operator: BinaryOp,
+    lhs: FieldElement,
+    rhs: FieldElement,
+    bit_size: u32,
+) -> Result<Value, InterpreterError> {
+    if bit_size > 64 {
+        return Err(InterpreterError::UnsupportedType(format!("i{bit_size}")));
+    }
+    let typ = NumericType::Signed { bit_size };
+    let (lhs_bits, rhs_bits) = (lhs.to_u128(), rhs.to_u128());
+    let (lhs, rhs) = (to_signed(lhs_bits, bit_size), to_signed(rhs_bits, bit_size));
+
+    let value = match operator {
+        BinaryOp::Add => (lhs + rhs) as u128,
+        BinaryOp::Sub => (lhs - rhs) as u128,
+        BinaryOp::Mul => (lhs * rhs) as u128,
+        BinaryOp::Div => (lhs / rhs) as u128,
+        BinaryOp::Mod => (lhs % rhs) as u128,
+        BinaryOp::Lt => return Ok(Value::bool(lhs < rhs)),
+        BinaryOp::And => lhs_bits & rhs_bits,
+        BinaryOp::Or => lhs_bits | rhs_bits,
+        BinaryOp::Xor => lhs_bits ^ rhs_bits,
+        BinaryOp::Shl => shift_left(lhs_bits, rhs_bits, bit_size),
+        BinaryOp::Shr => shift_right(lhs_bits, rhs_bits, bit_size),
+        BinaryOp::Eq => unreachable!("Eq is handled for all types"),
+    };
+    Ok(Value::integer(value, typ))
+}
+
+/// Interprets the lowest `bit_size` bits of `value` as a two's complement integer
+fn to_signed(value: u128, bit_size: u32) -> i128 {
+    let value = value as i128;
+    if value >= 1 << (bit_size - 1) {
+        value - (1 << bit_size)
+    } else {
+        value
+    }
+}
+
+fn shift_left(lhs: u128, rhs: u128, bit_size: u32) -> u128 {
+    if rhs >= bit_size as u128 {
+        0
+    } else {
+        lhs << rhs
+    }
+}
+
+fn shift_right(lhs: u128, rhs: u128, bit_size: u32) -> u128 {
+    if rhs >= bit_size as u128 {
+        0
+    } else {
+        lhs >> rhs
+    }
+}
@@ -0,0 +1,226 @@
+use std::rc::Rc;
+
+use acvm::{acir::AcirField, FieldElement};
+use iter_extended::vecmap;
+use num_bigint::BigUint;
+
+use crate::ssa::ir::{
+    instruction::{Endian, Intrinsic},
+    types::{CompositeType, Type},
+};
+
+use super::{
+    value::{self, Value},
+    Frame, InterpreterError,
+};
+
+/// Calls an intrinsic function. Slices are passed to and returned from intrinsics
+/// as a length followed by the slice contents, as in the SSA.
+pub(super) fn call_intrinsic(
+    intrinsic: Intrinsic,
+    arguments: Vec<Value>,
+    result_types: &[Type],
+    frame: &Frame,
+) -> Result<Vec<Value>, InterpreterError> {
+    match intrinsic {
+        Intrinsic::ArrayLen => {
+            let length = match &arguments[0] {
+                // The length of a slice is the length passed along with it
+                Value::Numeric { value, .. } => *value,
+                array => {
+                    let (elements, element_types, _) = array.as_array()?;
+                    FieldElement::from(elements.len() / element_types.len())
+                }
+            };
+            Ok(vec![Value::numeric(length, &result_types[0])?])
+        }
+        Intrinsic::AsSlice => {
+            let (elements, element_types, _) = arguments[0].as_array()?;
+            let length = FieldElement::from(elements.len() / element_types.len());
+            Ok(vec![
+                Value::numeric(length, &result_types[0])?,
+                Value::array(elements.clone(), &result_types[1])?,
+            ])
+        }
+        Intrinsic::AssertConstant | Intrinsic::AsWitness => Ok(Vec::new()),
+        Intrinsic::StaticAssert => {
+            if arguments[0].as_bool()? {
+                Ok(Vec::new())
+            } else {
+                Err(InterpreterError::StaticAssertFailed)
+            }
+        }
+        Intrinsic::SlicePushBack => {
+            let (length, mut elements, element_types) = slice_arguments(&arguments)?;
+            let new_elements = &arguments[2..];
+            elements.extend(new_elements.iter().cloned());
+
+            // The slice may contain padding past its length, in which case ACIR
+            // writes the new elements at the length rather than at the end
+            let start = length * element_types.len();
+            for (index, element) in new_elements.iter().enumerate() {
+                elements[start + index] = element.clone();
+            }
+            Ok(vec![slice_length(length + 1, result_types)?, slice(elements, result_types)?])
+        }
+        Intrinsic::SlicePushFront => {
+            let (length, mut elements, _) = slice_arguments(&arguments)?;
+            for element in arguments[2..].iter().rev() {
+                elements.push_front(element.clone());
+            }
+            Ok(vec![slice_length(length + 1, result_types)?, slice(elements, result_types)?])
+        }
+        Intrinsic::SlicePopBack => {
+            let (length, mut elements, element_types) = slice_arguments(&arguments)?;
+            if length == 0 {
+                return empty_slice_pop(frame, result_types);
+            }
+            let start = (length - 1) * element_types.len();
+            check_index(start + element_types.len() - 1, &elements)?;
+            let popped = elements.slice(start..start + element_types.len());
+
+            let mut results =
+                vec![slice_length(length - 1, result_types)?, slice(elements, result_types)?];
+            results.extend(popped);
+            Ok(results)
+        }
+        Intrinsic::SlicePopFront => {
+            let (length, mut elements, element_types) = slice_arguments(&arguments)?;
+            if length == 0 {
+                return empty_slice_pop(frame, result_types);
+            }
+            check_index(element_types.len() - 1, &elements)?;
+            let remaining = elements.split_off(element_types.len());
+
+            // The popped elements come first in the results, followed by the new slice
+            let mut results: Vec<_> = elements.into_iter().collect();
+            let result_types = &result_types[element_types.len()..];
+            results.push(slice_length(length - 1, result_types)?);
+            results.push(slice(remaining, result_types)?);
+            Ok(results)
+        }
+        Intrinsic::SliceInsert => {
+            let (length, mut elements, element_types) = slice_arguments(&arguments)?;
+            let index = arguments[2].as_usize()? * element_types.len();
+            if index > elements.len() {
+                return Err(InterpreterError::IndexOutOfBounds { index, length: elements.len() });
+            }
+            for (offset, element) in arguments[3..].iter().enumerate() {
+                elements.insert(index + offset, element.clone());
+            }
+            Ok(vec![slice_length(length + 1, result_types)?, slice(elements, result_types)?])
+        }
+        Intrinsic::SliceRemove => {
+            let (length, mut elements, element_types) = slice_arguments(&arguments)?;
+            let index = arguments[2].as_usize()? * element_types.len();
+            check_index(index + element_types.len() - 1, &elements)?;
+            let removed = vecmap(0..element_types.len(), |_| elements.remove(index));
+
+            let mut results =
+                vec![slice_length(length - 1, result_types)?, slice(elements, result_types)?];
+            results.extend(removed);
+            Ok(results)
+        }
+        Intrinsic::ApplyRangeConstraint => {
+            let max_bit_size = arguments[1].as_usize()? as u32;
+            if arguments[0].as_field()?.num_bits() > max_bit_size {
+                return Err(InterpreterError::RangeCheckFailed {
+                    value: arguments[0].clone(),
+                    max_bit_size,
+                    message: Some("call to assert_max_bit_size".to_owned()),
+                });
+            }
+            Ok(Vec::new())
+        }
+        Intrinsic::StrAsBytes => {
+            let (elements, _, _) = arguments[0].as_array()?;
+            Ok(vec![Value::array(elements.clone(), &result_types[0])?])
+        }
+        Intrinsic::ToBits(endian) => {
+            let limb_count = arguments[1].as_usize()?;
+            to_radix(endian, &arguments[0], 2, limb_count, result_types, frame)
+        }
+        Intrinsic::ToRadix(endian) => {
+            let radix = arguments[1].as_usize()? as u32;
+            let limb_count = arguments[2].as_usize()?;
+            to_radix(endian, &arguments[0], radix, limb_count, result_types, frame)
+        }
+        Intrinsic::FromField => {
+            let value = value::truncate(arguments[0].as_field()?, result_types[0].bit_size());
+            Ok(vec![Value::numeric(value, &result_types[0])?])
+        }
+        Intrinsic::AsField => Ok(vec![Value::field(arguments[0].as_field()?)]),
+        Intrinsic::IsUnconstrained => Ok(vec![Value::bool(frame.unconstrained)]),
+        Intrinsic::BlackBox(_) | Intrinsic::DerivePedersenGenerators => {
+            Err(InterpreterError::UnsupportedIntrinsic(intrinsic.to_string()))
+        }
+    }
+}
+
+/// Splits the `(length, contents)` arguments slice intrinsics start with
+fn slice_arguments(
+    arguments: &[Value],
+) -> Result<(usize, im::Vector<Value>, Rc<CompositeType>), InterpreterError> {
+    let length = arguments[0].as_usize()?;
+    let (elements, element_types, _) = arguments[1].as_array()?;
+    Ok((length, elements.clone(), element_types.clone()))
+}
+
+fn slice_length(length: usize, result_types: &[Type]) -> Result<Value, InterpreterError> {
+    Value::numeric(FieldElement::from(length), &result_types[0])
+}
+
+fn slice(elements: im::Vector<Value>, result_types: &[Type]) -> Result<Value, InterpreterError> {
+    Value::array(elements, &result_types[1])
+}
+
+fn check_index(index: usize, elements: &im::Vector<Value>) -> Result<(), InterpreterError> {
+    if index < elements.len() {
+        Ok(())
+    } else {
+        Err(InterpreterError::IndexOutOfBounds { index, length: elements.len() })
+    }
+}
+
+/// Popping from an empty slice is only an error while side effects are enabled,
+/// otherwise the results are zeroed as they are for other disabled operations.
+fn empty_slice_pop(frame: &Frame, result_types: &[Type]) -> Result<Vec<Value>, InterpreterError> {
+    if frame.side_effects_enabled {
+        Err(InterpreterError::PopFromEmptySlice)
+    } else {
+        Ok(vecmap(result_types, Value::zero))
+    }
+}
+
+fn to_radix(
+    endian: Endian,
+    value: &Value,
+    radix: u32,
+    limb_count: usize,
+    result_types: &[Type],
+    frame: &Frame,
+) -> Result<Vec<Value>, InterpreterError> {
+    let field = value.as_field()?;
+    let mut digits = BigUint::from_bytes_be(&field.to_be_bytes()).to_radix_le(radix);
+    if digits.len() > limb_count && frame.side_effects_enabled {
+        return Err(InterpreterError::RadixOverflow { value: value.clone(), radix, limb_count });
+    }
+    digits.resize(limb_count, 0);
+    if endian == Endian::Big {
+        digits.reverse();
+    }
+
+    let element_type = match &result_types[1] {
+        Type::Array(element_types, _) | Type::Slice(element_types) => &element_types[0],
+        other => {
+            return Err(InterpreterError::Internal(format!("Expected an array, found {other}")))
+        }
+    };
+    let limbs = digits
+        .into_iter()
+        .map(|digit| Value::numeric(FieldElement::from(digit as u128), element_type));
+    Ok(vec![
+        slice_length(limb_count, result_types)?,
+        slice(limbs.collect::<Result<_, _>>()?, result_types)?,
+    ])
+}
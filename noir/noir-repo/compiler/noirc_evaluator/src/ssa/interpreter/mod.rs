@@ -0,0 +1,499 @@
+//! An interpreter which executes SSA functions on concrete inputs.
+//!
+//! The interpreter follows the semantics the SSA has once it is lowered to ACIR or Brillig:
+//! - Instructions which may fail, such as unsigned arithmetic, division and array accesses,
+//!   only fail while side effects are enabled. When they are disabled the instruction produces
+//!   the value the predicated ACIR would, e.g. wrapping arithmetic or zero.
+//! - Calls made while side effects are disabled are skipped, returning zeroed values.
+//! - Arrays are values, so `array_set` always produces a new array regardless of `mut`.
+//!   Reference counting instructions are therefore no-ops.
+//!
+//! Its main use is checking that optimization passes don't change the behavior of a program,
+//! by running the program before and after each pass and comparing the outcomes.
+//! See [`Ssa::observe`] and [`Observations::find_divergence`].
+use std::collections::HashMap;
+
+use acvm::acir::AcirField;
+use iter_extended::{try_vecmap, vecmap};
+use thiserror::Error;
+
+use super::{
+    ir::{
+        function::{Function, FunctionId, RuntimeType},
+        instruction::{
+            Binary, BinaryOp, ConstrainError, Instruction, InstructionId, TerminatorInstruction,
+        },
+        types::{NumericType, Type},
+        value::{self as ssa_value, ValueId},
+    },
+    ssa_gen::Ssa,
+};
+
+mod binary;
+mod intrinsics;
+mod value;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use value::Value;
+
+/// The maximum number of instructions a single run may execute, so that programs
+/// which loop for a long time (or forever) don't stall compilation.
+const STEP_LIMIT: usize = 1_000_000;
+
+/// The maximum depth of nested calls, to avoid overflowing the stack on deep recursion.
+const CALL_DEPTH_LIMIT: usize = 256;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub(crate) enum InterpreterError {
+    #[error("Constraint failed: {lhs} != {rhs}{}", display_message(.message))]
+    ConstrainEqFailed { lhs: Value, rhs: Value, message: Option<String> },
+    #[error("Value {value} does not fit in {max_bit_size} bits{}", display_message(.message))]
+    RangeCheckFailed { value: Value, max_bit_size: u32, message: Option<String> },
+    #[error("Static assertion failed")]
+    StaticAssertFailed,
+    #[error("Attempt to {operator} with overflow on {typ}")]
+    Overflow { operator: BinaryOp, typ: NumericType },
+    #[error("Attempt to divide by zero")]
+    DivisionByZero,
+    #[error("Index {index} is out of bounds for an array of length {length}")]
+    IndexOutOfBounds { index: usize, length: usize },
+    #[error("Value {value} does not fit in {limb_count} limbs of radix {radix}")]
+    RadixOverflow { value: Value, radix: u32, limb_count: usize },
+    #[error("Attempt to pop from an empty slice")]
+    PopFromEmptySlice,
+    #[error("Attempt to load from a reference which was never stored to")]
+    UninitializedReference,
+
+    #[error("Unsupported intrinsic `{0}`")]
+    UnsupportedIntrinsic(String),
+    #[error("Unsupported foreign call `{0}`")]
+    UnsupportedForeignCall(String),
+    #[error("Unsupported type `{0}`")]
+    UnsupportedType(String),
+    #[error("Execution exceeded the limit of {} instructions", STEP_LIMIT)]
+    StepLimitExceeded,
+    #[error("Execution exceeded the limit of {} nested calls", CALL_DEPTH_LIMIT)]
+    CallDepthExceeded,
+    #[error("Malformed SSA: {0}")]
+    Internal(String),
+}
+
+fn display_message(message: &Option<String>) -> String {
+    message.as_ref().map(|message| format!(": {message}")).unwrap_or_default()
+}
+
+impl InterpreterError {
+    /// Returns true if this error is a failure of the program being interpreted,
+    /// as opposed to the interpreter being unable to tell what the program would do.
+    pub(crate) fn is_program_failure(&self) -> bool {
+        match self {
+            InterpreterError::ConstrainEqFailed { .. }
+            | InterpreterError::RangeCheckFailed { .. }
+            | InterpreterError::StaticAssertFailed
+            | InterpreterError::Overflow { .. }
+            | InterpreterError::DivisionByZero
+            | InterpreterError::IndexOutOfBounds { .. }
+            | InterpreterError::RadixOverflow { .. }
+            | InterpreterError::PopFromEmptySlice
+            | InterpreterError::UninitializedReference => true,
+            InterpreterError::UnsupportedIntrinsic(_)
+            | InterpreterError::UnsupportedForeignCall(_)
+            | InterpreterError::UnsupportedType(_)
+            | InterpreterError::StepLimitExceeded
+            | InterpreterError::CallDepthExceeded
+            | InterpreterError::Internal(_) => false,
+        }
+    }
+}
+
+impl Ssa {
+    /// Executes the main function of this program on the given arguments, returning its results.
+    pub(crate) fn interpret(&self, arguments: Vec<Value>) -> Result<Vec<Value>, InterpreterError> {
+        let mut interpreter = Interpreter { ssa: self, steps: 0, call_depth: 0 };
+        let main = self.main();
+        let unconstrained = main.runtime() == RuntimeType::Brillig;
+        interpreter.call_function(main, arguments, unconstrained)
+    }
+
+    /// Runs main on a few inputs derived from its parameter types and records the outcomes,
+    /// so that they can be compared with those of the program after it is transformed.
+    /// Returns `None` if main takes parameters no inputs can be made for.
+    pub(crate) fn observe(&self) -> Option<Observations> {
+        let parameter_types =
+            vecmap(self.main().parameters(), |parameter| self.main().dfg.type_of_value(*parameter));
+
+        let mut observations = Vec::new();
+        for input in sample_inputs(&parameter_types)? {
+            let outcome = match self.interpret(input.clone()) {
+                Ok(results) => Outcome::Returned(results),
+                Err(error) if error.is_program_failure() => Outcome::Failed(error),
+                Err(_) => Outcome::Inconclusive,
+            };
+            observations.push((input, outcome));
+        }
+        Some(Observations(observations))
+    }
+}
+
+/// The outcomes of running a program on each of a set of inputs
+pub(crate) struct Observations(Vec<(Vec<Value>, Outcome)>);
+
+enum Outcome {
+    Returned(Vec<Value>),
+    Failed(InterpreterError),
+    /// The interpreter couldn't tell what the program does on this input
+    Inconclusive,
+}
+
+impl Observations {
+    /// Compares these outcomes with those of the same inputs on a transformed program,
+    /// returning a description of the first input the two programs disagree on.
+    ///
+    /// Only whether the programs fail is compared rather than how, since passes may
+    /// legitimately change which check catches an invalid input. The transformed program
+    /// may also fail on fewer inputs: removing an unused value removes any check on it
+    /// too, e.g. when dead instruction elimination removes an overflowing addition.
+    pub(crate) fn find_divergence(&self, after: &Observations) -> Option<String> {
+        for ((input, before), (_, after)) in self.0.iter().zip(&after.0) {
+            let divergence = match (before, after) {
+                (Outcome::Returned(before), Outcome::Returned(after)) if before != after => {
+                    format!("returned {} before and {} after", display(before), display(after))
+                }
+                (Outcome::Returned(before), Outcome::Failed(error)) => {
+                    format!("returned {} before but failed after with: {error}", display(before))
+                }
+                _ => continue,
+            };
+            return Some(format!("On input ({}) the program {divergence}", display(input)));
+        }
+        None
+    }
+}
+
+fn display(values: &[Value]) -> String {
+    vecmap(values, ToString::to_string).join(", ")
+}
+
+/// Deterministically generates inputs of the given types: all zeroes, small
+/// distinct values and pseudo-random values, the last of which are likely to
+/// exercise overflow and out of bounds checks.
+fn sample_inputs(types: &[Type]) -> Option<Vec<Vec<Value>>> {
+    let sample = |next: &mut dyn FnMut() -> u128| -> Option<Vec<Value>> {
+        types.iter().map(|typ| Value::sample(typ, next)).collect()
+    };
+
+    let mut counter = 0;
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    Some(vec![
+        sample(&mut || 0)?,
+        sample(&mut || {
+            counter += 1;
+            counter
+        })?,
+        sample(&mut || {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u128
+        })?,
+    ])
+}
+
+struct Interpreter<'ssa> {
+    ssa: &'ssa Ssa,
+    steps: usize,
+    call_depth: usize,
+}
+
+/// The state of a single function call
+struct Frame<'function> {
+    function: &'function Function,
+    values: HashMap<ValueId, Value>,
+    side_effects_enabled: bool,
+    /// Whether this call is executed as Brillig, either because the function
+    /// itself is unconstrained or because it was called from unconstrained code
+    unconstrained: bool,
+}
+
+impl<'ssa> Interpreter<'ssa> {
+    fn call_function(
+        &mut self,
+        function: &'ssa Function,
+        arguments: Vec<Value>,
+        unconstrained: bool,
+    ) -> Result<Vec<Value>, InterpreterError> {
+        if self.call_depth >= CALL_DEPTH_LIMIT {
+            return Err(InterpreterError::CallDepthExceeded);
+        }
+        self.call_depth += 1;
+
+        let mut frame = Frame {
+            function,
+            values: HashMap::new(),
+            side_effects_enabled: true,
+            unconstrained: unconstrained || function.runtime() == RuntimeType::Brillig,
+        };
+        let result = self.run(&mut frame, arguments);
+
+        self.call_depth -= 1;
+        result
+    }
+
+    fn run(
+        &mut self,
+        frame: &mut Frame,
+        arguments: Vec<Value>,
+    ) -> Result<Vec<Value>, InterpreterError> {
+        let function = frame.function;
+        let dfg = &function.dfg;
+        let mut block = function.entry_block();
+        let mut arguments = arguments;
+
+        loop {
+            let parameters = dfg.block_parameters(block);
+            if parameters.len() != arguments.len() {
+                return Err(InterpreterError::Internal(format!(
+                    "{block} expects {} arguments but was given {}",
+                    parameters.len(),
+                    arguments.len()
+                )));
+            }
+            frame.values.extend(parameters.iter().copied().zip(arguments));
+
+            for instruction in dfg[block].instructions() {
+                self.steps += 1;
+                if self.steps > STEP_LIMIT {
+                    return Err(InterpreterError::StepLimitExceeded);
+                }
+                self.execute_instruction(frame, *instruction)?;
+            }
+
+            match dfg[block].terminator() {
+                Some(TerminatorInstruction::Jmp {
+                    destination, arguments: jmp_arguments, ..
+                }) => {
+                    arguments = try_vecmap(jmp_arguments, |argument| frame.lookup(*argument))?;
+                    block = *destination;
+                }
+                Some(TerminatorInstruction::JmpIf {
+                    condition,
+                    then_destination,
+                    else_destination,
+                }) => {
+                    arguments = Vec::new();
+                    block = if frame.lookup(*condition)?.as_bool()? {
+                        *then_destination
+                    } else {
+                        *else_destination
+                    };
+                }
+                Some(TerminatorInstruction::Return { return_values, .. }) => {
+                    return try_vecmap(return_values, |value| frame.lookup(*value));
+                }
+                None => {
+                    return Err(InterpreterError::Internal(format!("{block} has no terminator")))
+                }
+            }
+        }
+    }
+
+    fn execute_instruction(
+        &mut self,
+        frame: &mut Frame,
+        instruction_id: InstructionId,
+    ) -> Result<(), InterpreterError> {
+        let function = frame.function;
+        let dfg = &function.dfg;
+        let results = dfg.instruction_results(instruction_id);
+        let result_type = |index: usize| {
+            results.get(index).map(|result| dfg.type_of_value(*result)).ok_or_else(|| {
+                InterpreterError::Internal(format!("{instruction_id} is missing a result"))
+            })
+        };
+
+        let values = match &dfg[instruction_id] {
+            Instruction::Binary(Binary { lhs, rhs, operator }) => {
+                let (lhs, typ) = frame.lookup(*lhs)?.as_numeric()?;
+                let rhs = frame.lookup(*rhs)?.as_field()?;
+                vec![binary::evaluate_binary(*operator, lhs, rhs, typ, frame.side_effects_enabled)?]
+            }
+            Instruction::Cast(value, typ) => {
+                let value = frame.lookup(*value)?.as_field()?;
+                vec![Value::numeric(value::truncate(value, typ.bit_size()), typ)?]
+            }
+            Instruction::Not(value) => {
+                let (value, typ) = frame.lookup(*value)?.as_numeric()?;
+                if typ == NumericType::NativeField {
+                    return Err(InterpreterError::Internal("Cannot apply `not` to a field".into()));
+                }
+                vec![Value::integer(!value.to_u128(), typ)]
+            }
+            Instruction::Truncate { value, bit_size, .. } => {
+                let (value, typ) = frame.lookup(*value)?.as_numeric()?;
+                vec![Value::Numeric { value: value::truncate(value, *bit_size), typ }]
+            }
+            Instruction::Constrain(lhs, rhs, error) => {
+                let (lhs, rhs) = (frame.lookup(*lhs)?, frame.lookup(*rhs)?);
+                if lhs != rhs {
+                    let message = match error {
+                        Some(ConstrainError::Intrinsic(message)) => Some(message.clone()),
+                        Some(ConstrainError::UserDefined(_, values)) => {
+                            let values = try_vecmap(values, |value| frame.lookup(*value))?;
+                            Some(display(&values))
+                        }
+                        None => None,
+                    };
+                    return Err(InterpreterError::ConstrainEqFailed { lhs, rhs, message });
+                }
+                Vec::new()
+            }
+            Instruction::RangeCheck { value, max_bit_size, assert_message } => {
+                let value = frame.lookup(*value)?;
+                if value.as_field()?.num_bits() > *max_bit_size {
+                    return Err(InterpreterError::RangeCheckFailed {
+                        value,
+                        max_bit_size: *max_bit_size,
+                        message: assert_message.clone(),
+                    });
+                }
+                Vec::new()
+            }
+            Instruction::Call { func, arguments } => {
+                let arguments = try_vecmap(arguments, |argument| frame.lookup(*argument))?;
+                let result_types = try_vecmap(0..results.len(), |index| result_type(index))?;
+                self.call(frame, *func, arguments, &result_types)?
+            }
+            Instruction::Allocate => vec![Value::uninitialized_reference()],
+            Instruction::Load { address } => {
+                let reference = frame.lookup(*address)?;
+                let value = reference.as_reference()?.borrow().clone();
+                vec![value.ok_or(InterpreterError::UninitializedReference)?]
+            }
+            Instruction::Store { address, value } => {
+                let value = frame.lookup(*value)?;
+                *frame.lookup(*address)?.as_reference()?.borrow_mut() = Some(value);
+                Vec::new()
+            }
+            Instruction::EnableSideEffects { condition } => {
+                frame.side_effects_enabled = frame.lookup(*condition)?.as_bool()?;
+                Vec::new()
+            }
+            Instruction::ArrayGet { array, index } => {
+                let array = frame.lookup(*array)?;
+                let (elements, _, _) = array.as_array()?;
+                let index = frame.lookup(*index)?.as_usize()?;
+                let element = match elements.get(index) {
+                    Some(element) => element.clone(),
+                    None if frame.side_effects_enabled => {
+                        return Err(InterpreterError::IndexOutOfBounds {
+                            index,
+                            length: elements.len(),
+                        })
+                    }
+                    // A disabled access reads the first element instead, as ACIR does
+                    None => match elements.get(0) {
+                        Some(element) => element.clone(),
+                        None => Value::zero(&result_type(0)?),
+                    },
+                };
+                vec![element]
+            }
+            Instruction::ArraySet { array, index, value, .. } => {
+                let mut array = frame.lookup(*array)?;
+                let index = frame.lookup(*index)?.as_usize()?;
+                let value = frame.lookup(*value)?;
+                if let Value::Array { elements, .. } = &mut array {
+                    if index < elements.len() {
+                        elements[index] = value;
+                    } else if frame.side_effects_enabled {
+                        return Err(InterpreterError::IndexOutOfBounds {
+                            index,
+                            length: elements.len(),
+                        });
+                    }
+                } else {
+                    return Err(InterpreterError::Internal(format!(
+                        "Expected an array, found {array}"
+                    )));
+                }
+                vec![array]
+            }
+            Instruction::IncrementRc { .. } | Instruction::DecrementRc { .. } => Vec::new(),
+            Instruction::IfElse { then_condition, then_value, else_value, .. } => {
+                if frame.lookup(*then_condition)?.as_bool()? {
+                    vec![frame.lookup(*then_value)?]
+                } else {
+                    vec![frame.lookup(*else_value)?]
+                }
+            }
+        };
+
+        if values.len() != results.len() {
+            return Err(InterpreterError::Internal(format!(
+                "{instruction_id} has {} results but produced {} values",
+                results.len(),
+                values.len()
+            )));
+        }
+        frame.values.extend(results.iter().copied().zip(values));
+        Ok(())
+    }
+
+    fn call(
+        &mut self,
+        frame: &Frame,
+        function: ValueId,
+        arguments: Vec<Value>,
+        result_types: &[Type],
+    ) -> Result<Vec<Value>, InterpreterError> {
+        match frame.lookup(function)? {
+            Value::Function(id) => {
+                // A call whose predicate is disabled is skipped entirely, as it is in ACIR
+                if !frame.side_effects_enabled {
+                    return Ok(vecmap(result_types, Value::zero));
+                }
+                let function = self.function(id)?;
+                self.call_function(function, arguments, frame.unconstrained)
+            }
+            Value::Intrinsic(intrinsic) => {
+                intrinsics::call_intrinsic(intrinsic, arguments, result_types, frame)
+            }
+            Value::ForeignFunction(name) if name == "print" => Ok(Vec::new()),
+            Value::ForeignFunction(name) => Err(InterpreterError::UnsupportedForeignCall(name)),
+            other => Err(InterpreterError::Internal(format!("Cannot call {other}"))),
+        }
+    }
+
+    fn function(&self, id: FunctionId) -> Result<&'ssa Function, InterpreterError> {
+        self.ssa
+            .functions
+            .get(&id)
+            .ok_or_else(|| InterpreterError::Internal(format!("Unknown function {id}")))
+    }
+}
+
+impl Frame<'_> {
+    /// Returns the current value of the given SSA value
+    fn lookup(&self, value: ValueId) -> Result<Value, InterpreterError> {
+        let dfg = &self.function.dfg;
+        let value = dfg.resolve(value);
+        match &dfg[value] {
+            ssa_value::Value::Instruction { .. } | ssa_value::Value::Param { .. } => {
+                self.values.get(&value).cloned().ok_or_else(|| {
+                    InterpreterError::Internal(format!("{value} is used before it is defined"))
+                })
+            }
+            ssa_value::Value::NumericConstant { constant, typ } => Value::numeric(*constant, typ),
+            ssa_value::Value::Array { array, typ } => {
+                let elements = array.iter().map(|element| self.lookup(*element));
+                Value::array(elements.collect::<Result<_, _>>()?, typ)
+            }
+            ssa_value::Value::Function(id) => Ok(Value::Function(*id)),
+            ssa_value::Value::Intrinsic(intrinsic) => Ok(Value::Intrinsic(*intrinsic)),
+            ssa_value::Value::ForeignFunction(name) => Ok(Value::ForeignFunction(name.clone())),
+        }
+    }
+}
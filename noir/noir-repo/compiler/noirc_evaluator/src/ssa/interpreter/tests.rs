@@ -0,0 +1,334 @@
+use acvm::FieldElement;
+
+use crate::ssa::{ir::types::NumericType, ssa_gen::Ssa};
+
+use super::{InterpreterError, Value};
+
+const U32: NumericType = NumericType::Unsigned { bit_size: 32 };
+const U8: NumericType = NumericType::Unsigned { bit_size: 8 };
+const I8: NumericType = NumericType::Signed { bit_size: 8 };
+
+fn interpret(src: &str, arguments: Vec<Value>) -> Result<Vec<Value>, InterpreterError> {
+    Ssa::from_str(src).unwrap().interpret(arguments)
+}
+
+fn field(value: u128) -> Value {
+    Value::field(FieldElement::from(value))
+}
+
+#[test]
+fn adds_fields() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: Field, v1: Field):
+    v2 = add v0, v1
+    return v2
+}
+";
+    let results = interpret(src, vec![field(2), field(3)]).unwrap();
+    assert_eq!(results, vec![field(5)]);
+}
+
+#[test]
+fn unsigned_overflow_fails_only_with_side_effects_enabled() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: u8, v1: u1):
+    enable_side_effects v1
+    v2 = add v0, u8 1
+    return v2
+}
+";
+    let error = interpret(src, vec![Value::integer(255, U8), Value::bool(true)]).unwrap_err();
+    assert!(matches!(error, InterpreterError::Overflow { .. }));
+
+    let results = interpret(src, vec![Value::integer(255, U8), Value::bool(false)]).unwrap();
+    assert_eq!(results, vec![Value::integer(0, U8)]);
+}
+
+#[test]
+fn division_by_zero() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: u32, v1: u1):
+    enable_side_effects v1
+    v2 = div u32 10, v0
+    return v2
+}
+";
+    let error = interpret(src, vec![Value::integer(0, U32), Value::bool(true)]).unwrap_err();
+    assert_eq!(error, InterpreterError::DivisionByZero);
+
+    let results = interpret(src, vec![Value::integer(0, U32), Value::bool(false)]).unwrap();
+    assert_eq!(results, vec![Value::integer(0, U32)]);
+}
+
+#[test]
+fn signed_arithmetic_wraps() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: i8, v1: i8):
+    v2 = sub v0, v1
+    v3 = lt v2, v0
+    return v2, v3
+}
+";
+    // -128 - 1 wraps around to 127, which is greater than -128
+    let results = interpret(src, vec![Value::integer(128, I8), Value::integer(1, I8)]).unwrap();
+    assert_eq!(results, vec![Value::integer(127, I8), Value::bool(false)]);
+}
+
+#[test]
+fn loops() {
+    let src = "
+brillig fn main f0 {
+  b0(v0: u32):
+    jmp b1(u32 0, u32 0)
+  b1(v1: u32, v2: u32):
+    v4 = lt v1, v0
+    jmpif v4 then: b2, else: b3
+  b2():
+    v5 = add v2, v1
+    v7 = add v1, u32 1
+    jmp b1(v7, v5)
+  b3():
+    return v2
+}
+";
+    let results = interpret(src, vec![Value::integer(5, U32)]).unwrap();
+    assert_eq!(results, vec![Value::integer(10, U32)]);
+}
+
+#[test]
+fn infinite_loops_exceed_the_step_limit() {
+    let src = "
+brillig fn main f0 {
+  b0():
+    jmp b1()
+  b1():
+    v0 = add Field 1, Field 2
+    jmp b1()
+}
+";
+    let error = interpret(src, Vec::new()).unwrap_err();
+    assert_eq!(error, InterpreterError::StepLimitExceeded);
+    assert!(!error.is_program_failure());
+}
+
+#[test]
+fn references() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: Field):
+    v1 = allocate -> &mut Field
+    store v0 at v1
+    v2 = load v1 -> Field
+    v3 = add v2, Field 1
+    store v3 at v1
+    v5 = load v1 -> Field
+    return v5
+}
+";
+    let results = interpret(src, vec![field(1)]).unwrap();
+    assert_eq!(results, vec![field(2)]);
+}
+
+#[test]
+fn loading_an_uninitialized_reference_fails() {
+    let src = "
+acir(inline) fn main f0 {
+  b0():
+    v0 = allocate -> &mut Field
+    v1 = load v0 -> Field
+    return v1
+}
+";
+    let error = interpret(src, Vec::new()).unwrap_err();
+    assert_eq!(error, InterpreterError::UninitializedReference);
+}
+
+#[test]
+fn array_get_and_set() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: [Field; 2], v1: u32):
+    v2 = array_set v0, index v1, value Field 5
+    v3 = array_get v2, index v1 -> Field
+    return v0, v3
+}
+";
+    let array = Value::Array {
+        elements: im::vector![field(1), field(2)],
+        element_types: vec![crate::ssa::ir::types::Type::field()].into(),
+        is_slice: false,
+    };
+    let results = interpret(src, vec![array.clone(), Value::integer(1, U32)]).unwrap();
+    assert_eq!(results, vec![array.clone(), field(5)]);
+
+    let error = interpret(src, vec![array, Value::integer(2, U32)]).unwrap_err();
+    assert_eq!(error, InterpreterError::IndexOutOfBounds { index: 2, length: 2 });
+}
+
+#[test]
+fn constrain_failure() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: Field):
+    constrain v0 == Field 1 '\"value must be one\"'
+    return
+}
+";
+    assert!(interpret(src, vec![field(1)]).unwrap().is_empty());
+
+    let error = interpret(src, vec![field(2)]).unwrap_err();
+    assert!(error.is_program_failure());
+    assert_eq!(error.to_string(), "Constraint failed: Field 2 != Field 1: value must be one");
+}
+
+#[test]
+fn calls_and_is_unconstrained() {
+    let src = "
+acir(inline) fn main f0 {
+  b0():
+    v1 = call f1() -> u1
+    v3 = call f2() -> u1
+    return v1, v3
+}
+brillig fn foo f1 {
+  b0():
+    v1 = call f2() -> u1
+    return v1
+}
+acir(inline) fn bar f2 {
+  b0():
+    v1 = call is_unconstrained() -> u1
+    return v1
+}
+";
+    let results = interpret(src, Vec::new()).unwrap();
+    assert_eq!(results, vec![Value::bool(true), Value::bool(false)]);
+}
+
+#[test]
+fn calls_are_skipped_with_side_effects_disabled() {
+    let src = "
+acir(inline) fn main f0 {
+  b0():
+    enable_side_effects u1 0
+    v2 = call f1() -> Field
+    return v2
+}
+brillig fn foo f1 {
+  b0():
+    constrain Field 1 == Field 2
+    return Field 3
+}
+";
+    let results = interpret(src, Vec::new()).unwrap();
+    assert_eq!(results, vec![field(0)]);
+}
+
+#[test]
+fn slice_intrinsics() {
+    let src = "
+brillig fn main f0 {
+  b0(v0: Field):
+    v1, v2 = call as_slice([Field 1, Field 2] of [Field; 2]) -> u32, [Field]
+    v3, v4 = call slice_push_back(v1, v2, v0) -> u32, [Field]
+    v5, v6, v7 = call slice_pop_front(v3, v4) -> Field, u32, [Field]
+    v8, v9, v10 = call slice_pop_back(v6, v7) -> u32, [Field], Field
+    return v5, v8, v10
+}
+";
+    let results = interpret(src, vec![field(3)]).unwrap();
+    assert_eq!(results, vec![field(1), Value::integer(1, U32), field(3)]);
+}
+
+#[test]
+fn popping_from_an_empty_slice_fails() {
+    let src = "
+brillig fn main f0 {
+  b0():
+    v2, v3 = call as_slice([] of [Field; 0]) -> u32, [Field]
+    v4, v5, v6 = call slice_pop_back(v2, v3) -> u32, [Field], Field
+    return v6
+}
+";
+    let error = interpret(src, Vec::new()).unwrap_err();
+    assert_eq!(error, InterpreterError::PopFromEmptySlice);
+}
+
+#[test]
+fn to_radix() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: Field):
+    v3, v4 = call to_le_bits(v0, u32 4) -> u32, [u1]
+    return v4
+}
+";
+    let results = interpret(src, vec![field(6)]).unwrap();
+    let bits = [0, 1, 1, 0].map(|bit| Value::integer(bit, NumericType::Unsigned { bit_size: 1 }));
+    let Value::Array { elements, .. } = &results[0] else { panic!("Expected an array") };
+    assert_eq!(elements.iter().cloned().collect::<Vec<_>>(), bits);
+
+    let error = interpret(src, vec![field(16)]).unwrap_err();
+    assert!(matches!(error, InterpreterError::RadixOverflow { .. }));
+}
+
+#[test]
+fn finds_divergences() {
+    let before = "
+acir(inline) fn main f0 {
+  b0(v0: u32):
+    v2 = mul v0, u32 2
+    return v2
+}
+";
+    let correct = "
+acir(inline) fn main f0 {
+  b0(v0: u32):
+    v1 = add v0, v0
+    return v1
+}
+";
+    let incorrect = "
+acir(inline) fn main f0 {
+  b0(v0: u32):
+    v2 = add v0, u32 2
+    return v2
+}
+";
+    let before = Ssa::from_str(before).unwrap().observe().unwrap();
+    let correct = Ssa::from_str(correct).unwrap().observe().unwrap();
+    let incorrect = Ssa::from_str(incorrect).unwrap().observe().unwrap();
+
+    assert_eq!(before.find_divergence(&correct), None);
+
+    let divergence = before.find_divergence(&incorrect).unwrap();
+    assert!(divergence.contains("returned 0 before and 2 after"), "{divergence}");
+}
+
+#[test]
+fn removing_a_failure_is_not_a_divergence() {
+    // `let _y = x + 200;` where the addition is unused, so DIE removes it along with its
+    // overflow check. The program then succeeds on inputs it used to fail on.
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: u8):
+    v2 = add v0, u8 200
+    return
+}
+";
+    let ssa = Ssa::from_str(src).unwrap();
+    let before = ssa.observe().unwrap();
+
+    let ssa = ssa.dead_instruction_elimination();
+    assert_eq!(ssa.main().dfg[ssa.main().entry_block()].instructions().len(), 0);
+
+    let after = ssa.observe().unwrap();
+    assert_eq!(before.find_divergence(&after), None);
+
+    // Failing where the original program returned is still a divergence
+    assert!(after.find_divergence(&before).is_some());
+}
@@ -0,0 +1,223 @@
+use std::{cell::RefCell, fmt::Display, rc::Rc};
+
+use acvm::{acir::AcirField, FieldElement};
+use iter_extended::vecmap;
+
+use crate::ssa::ir::{
+    function::FunctionId,
+    instruction::Intrinsic,
+    types::{CompositeType, NumericType, Type},
+};
+
+use super::InterpreterError;
+
+/// A concrete value produced while interpreting SSA.
+#[derive(Debug, Clone)]
+pub(crate) enum Value {
+    /// A numeric value. Integers are always kept within the range of their type,
+    /// with signed integers stored in two's complement form.
+    Numeric {
+        value: FieldElement,
+        typ: NumericType,
+    },
+
+    /// A reference created by an `allocate` instruction, which holds nothing until it is stored to
+    Reference(Rc<RefCell<Option<Value>>>),
+
+    /// An array or slice. As in the SSA, arrays of composite types are flattened
+    /// so each element is spread over `element_types.len()` consecutive values.
+    Array {
+        elements: im::Vector<Value>,
+        element_types: Rc<CompositeType>,
+        is_slice: bool,
+    },
+
+    Function(FunctionId),
+    Intrinsic(Intrinsic),
+    ForeignFunction(String),
+}
+
+impl Value {
+    pub(crate) fn field(value: FieldElement) -> Self {
+        Value::Numeric { value, typ: NumericType::NativeField }
+    }
+
+    pub(crate) fn bool(value: bool) -> Self {
+        Value::Numeric { value: value.into(), typ: NumericType::Unsigned { bit_size: 1 } }
+    }
+
+    /// Creates an integer of the given type, truncating `value` to the type's bit size
+    pub(crate) fn integer(value: u128, typ: NumericType) -> Self {
+        Value::Numeric { value: truncate(value.into(), typ.bit_size()), typ }
+    }
+
+    pub(crate) fn uninitialized_reference() -> Self {
+        Value::Reference(Rc::new(RefCell::new(None)))
+    }
+
+    pub(crate) fn numeric(value: FieldElement, typ: &Type) -> Result<Self, InterpreterError> {
+        match typ {
+            Type::Numeric(typ) => Ok(Value::Numeric { value, typ: *typ }),
+            other => {
+                Err(InterpreterError::Internal(format!("Expected a numeric type, found {other}")))
+            }
+        }
+    }
+
+    pub(crate) fn array(elements: im::Vector<Value>, typ: &Type) -> Result<Self, InterpreterError> {
+        match typ {
+            Type::Array(element_types, _) => {
+                Ok(Value::Array { elements, element_types: element_types.clone(), is_slice: false })
+            }
+            Type::Slice(element_types) => {
+                Ok(Value::Array { elements, element_types: element_types.clone(), is_slice: true })
+            }
+            other => {
+                Err(InterpreterError::Internal(format!("Expected an array type, found {other}")))
+            }
+        }
+    }
+
+    pub(crate) fn as_numeric(&self) -> Result<(FieldElement, NumericType), InterpreterError> {
+        match self {
+            Value::Numeric { value, typ } => Ok((*value, *typ)),
+            other => {
+                Err(InterpreterError::Internal(format!("Expected a numeric value, found {other}")))
+            }
+        }
+    }
+
+    pub(crate) fn as_field(&self) -> Result<FieldElement, InterpreterError> {
+        self.as_numeric().map(|(value, _)| value)
+    }
+
+    pub(crate) fn as_bool(&self) -> Result<bool, InterpreterError> {
+        self.as_field().map(|value| !value.is_zero())
+    }
+
+    pub(crate) fn as_usize(&self) -> Result<usize, InterpreterError> {
+        let value = self.as_field()?;
+        value.try_to_u64().map(|value| value as usize).ok_or_else(|| {
+            InterpreterError::Internal(format!("{value} is too large to be an index"))
+        })
+    }
+
+    pub(crate) fn as_array(
+        &self,
+    ) -> Result<(&im::Vector<Value>, &Rc<CompositeType>, bool), InterpreterError> {
+        match self {
+            Value::Array { elements, element_types, is_slice } => {
+                Ok((elements, element_types, *is_slice))
+            }
+            other => Err(InterpreterError::Internal(format!("Expected an array, found {other}"))),
+        }
+    }
+
+    pub(crate) fn as_reference(&self) -> Result<&Rc<RefCell<Option<Value>>>, InterpreterError> {
+        match self {
+            Value::Reference(reference) => Ok(reference),
+            other => {
+                Err(InterpreterError::Internal(format!("Expected a reference, found {other}")))
+            }
+        }
+    }
+
+    /// The value produced in place of the results of a call which was skipped
+    /// because side effects were disabled, mirroring a predicated call in ACIR.
+    pub(crate) fn zero(typ: &Type) -> Self {
+        match typ {
+            Type::Numeric(typ) => Value::Numeric { value: FieldElement::zero(), typ: *typ },
+            Type::Reference(_) => Value::uninitialized_reference(),
+            Type::Array(element_types, length) => {
+                let elements = (0..*length).flat_map(|_| element_types.iter().map(Value::zero));
+                Value::Array {
+                    elements: elements.collect(),
+                    element_types: element_types.clone(),
+                    is_slice: false,
+                }
+            }
+            Type::Slice(element_types) => Value::Array {
+                elements: im::Vector::new(),
+                element_types: element_types.clone(),
+                is_slice: true,
+            },
+            // There is no meaningful default function, any call through this value will fail
+            Type::Function => Value::ForeignFunction(String::new()),
+        }
+    }
+
+    /// Builds a value of the given type from `next`, which is called once for each numeric
+    /// value needed. Returns `None` for types which can't be passed to `main`.
+    pub(crate) fn sample(typ: &Type, next: &mut dyn FnMut() -> u128) -> Option<Self> {
+        match typ {
+            Type::Numeric(NumericType::NativeField) => Some(Value::field(next().into())),
+            Type::Numeric(typ) => Some(Value::integer(next(), *typ)),
+            Type::Array(element_types, length) => {
+                let mut elements = im::Vector::new();
+                for _ in 0..*length {
+                    for element_type in element_types.iter() {
+                        elements.push_back(Value::sample(element_type, next)?);
+                    }
+                }
+                Some(Value::Array {
+                    elements,
+                    element_types: element_types.clone(),
+                    is_slice: false,
+                })
+            }
+            Type::Reference(_) | Type::Slice(_) | Type::Function => None,
+        }
+    }
+}
+
+/// Truncates `value` to its lowest `bit_size` bits
+pub(crate) fn truncate(value: FieldElement, bit_size: u32) -> FieldElement {
+    if bit_size >= FieldElement::max_num_bits() || value.num_bits() <= bit_size {
+        return value;
+    }
+    let value = num_bigint::BigUint::from_bytes_be(&value.to_be_bytes());
+    let modulus = num_bigint::BigUint::from(1u32) << bit_size;
+    FieldElement::from_be_bytes_reduce(&(value % modulus).to_bytes_be())
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Value::Numeric { value: lhs, typ: lhs_type },
+                Value::Numeric { value: rhs, typ: rhs_type },
+            ) => lhs == rhs && lhs_type == rhs_type,
+            // References are compared by what they point to since the
+            // same program run twice allocates different references
+            (Value::Reference(lhs), Value::Reference(rhs)) => *lhs.borrow() == *rhs.borrow(),
+            (
+                Value::Array { elements: lhs, element_types: lhs_types, is_slice: lhs_is_slice },
+                Value::Array { elements: rhs, element_types: rhs_types, is_slice: rhs_is_slice },
+            ) => lhs == rhs && lhs_types == rhs_types && lhs_is_slice == rhs_is_slice,
+            (Value::Function(lhs), Value::Function(rhs)) => lhs == rhs,
+            (Value::Intrinsic(lhs), Value::Intrinsic(rhs)) => lhs == rhs,
+            (Value::ForeignFunction(lhs), Value::ForeignFunction(rhs)) => lhs == rhs,
+            _ => false,
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Numeric { value, typ } => write!(f, "{typ} {value}"),
+            Value::Reference(reference) => match &*reference.borrow() {
+                Some(value) => write!(f, "&mut {value}"),
+                None => write!(f, "&mut <uninitialized>"),
+            },
+            Value::Array { elements, is_slice, .. } => {
+                let elements = vecmap(elements, ToString::to_string).join(", ");
+                let prefix = if *is_slice { "&" } else { "" };
+                write!(f, "{prefix}[{elements}]")
+            }
+            Value::Function(id) => write!(f, "{id}"),
+            Value::Intrinsic(intrinsic) => write!(f, "{intrinsic}"),
+            Value::ForeignFunction(name) => write!(f, "{name}"),
+        }
+    }
+}
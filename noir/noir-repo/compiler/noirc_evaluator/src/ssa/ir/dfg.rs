@@ -196,6 +196,21 @@ impl DataFlowGraph {
         }
     }
 
+    /// Inserts a new instruction at the end of the given block and returns its results,
+    /// without trying to simplify the instruction first.
+    pub(crate) fn insert_instruction_and_results_without_simplification(
+        &mut self,
+        instruction: Instruction,
+        block: BasicBlockId,
+        ctrl_typevars: Option<Vec<Type>>,
+        call_stack: CallStack,
+    ) -> InsertInstructionResult {
+        let id = self.make_instruction(instruction, ctrl_typevars);
+        self.blocks[block].insert_instruction(id);
+        self.locations.insert(id, call_stack);
+        InsertInstructionResult::Results(id, self.instruction_results(id))
+    }
+
     /// Insert a value into the dfg's storage and return an id to reference it.
     /// Until the value is used in an instruction it is unreachable.
     pub(crate) fn make_value(&mut self, value: Value) -> ValueId {
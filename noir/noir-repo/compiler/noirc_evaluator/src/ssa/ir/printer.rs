@@ -6,13 +6,17 @@ use std::{
 
 use acvm::acir::circuit::{ErrorSelector, STRING_ERROR_SELECTOR};
 use acvm::acir::AcirField;
+use acvm::FieldElement;
 use iter_extended::vecmap;
+use num_bigint::BigUint;
 
 use super::{
     basic_block::BasicBlockId,
     dfg::DataFlowGraph,
     function::Function,
-    instruction::{ConstrainError, Instruction, InstructionId, TerminatorInstruction},
+    instruction::{
+        ConstrainError, Instruction, InstructionId, InstructionResultType, TerminatorInstruction,
+    },
     value::{Value, ValueId},
 };
 
@@ -66,17 +70,28 @@ fn value(function: &Function, id: ValueId) -> String {
     let id = function.dfg.resolve(id);
     match &function.dfg[id] {
         Value::NumericConstant { constant, typ } => {
-            format!("{typ} {constant}")
+            format!("{typ} {}", display_constant(*constant))
         }
         Value::Function(id) => id.to_string(),
         Value::Intrinsic(intrinsic) => intrinsic.to_string(),
-        Value::Array { array, .. } => {
+        Value::ForeignFunction(name) => name.clone(),
+        Value::Array { array, typ } => {
             let elements = vecmap(array, |element| value(function, *element));
-            format!("[{}]", elements.join(", "))
-        }
-        Value::Param { .. } | Value::Instruction { .. } | Value::ForeignFunction(_) => {
-            id.to_string()
+            format!("[{}] of {typ}", elements.join(", "))
         }
+        Value::Param { .. } | Value::Instruction { .. } => id.to_string(),
+    }
+}
+
+/// Display a numeric constant as a plain decimal number so that it can be parsed back.
+/// Values closer to the field modulus than to zero are shown as negative numbers.
+fn display_constant(constant: FieldElement) -> String {
+    let number = BigUint::from_bytes_be(&constant.to_be_bytes()).to_string();
+    let minus_number = BigUint::from_bytes_be(&(-constant).to_be_bytes()).to_string();
+    if minus_number.len() < number.len() {
+        format!("-{minus_number}")
+    } else {
+        number
     }
 }
 
@@ -115,7 +130,11 @@ pub(crate) fn display_terminator(
             )
         }
         Some(TerminatorInstruction::Return { return_values, .. }) => {
-            writeln!(f, "    return {}", value_list(function, return_values))
+            if return_values.is_empty() {
+                writeln!(f, "    return")
+            } else {
+                writeln!(f, "    return {}", value_list(function, return_values))
+            }
         }
         None => writeln!(f, "    (no terminator instruction)"),
     }
@@ -135,7 +154,17 @@ pub(crate) fn display_instruction(
         write!(f, "{} = ", value_list(function, results))?;
     }
 
-    display_instruction_inner(function, &function.dfg[instruction], f)
+    display_instruction_inner(function, &function.dfg[instruction], f)?;
+
+    // The types of these results can't be inferred from the instruction's operands,
+    // so they are shown explicitly for the SSA parser.
+    if matches!(function.dfg[instruction].result_type(), InstructionResultType::Unknown)
+        && !results.is_empty()
+    {
+        let types = vecmap(results, |result| function.dfg.type_of_value(*result).to_string());
+        write!(f, " -> {}", types.join(", "))?;
+    }
+    writeln!(f)
 }
 
 fn display_instruction_inner(
@@ -147,58 +176,62 @@ fn display_instruction_inner(
 
     match instruction {
         Instruction::Binary(binary) => {
-            writeln!(f, "{} {}, {}", binary.operator, show(binary.lhs), show(binary.rhs))
+            write!(f, "{} {}, {}", binary.operator, show(binary.lhs), show(binary.rhs))
         }
-        Instruction::Cast(lhs, typ) => writeln!(f, "cast {} as {typ}", show(*lhs)),
-        Instruction::Not(rhs) => writeln!(f, "not {}", show(*rhs)),
+        Instruction::Cast(lhs, typ) => write!(f, "cast {} as {typ}", show(*lhs)),
+        Instruction::Not(rhs) => write!(f, "not {}", show(*rhs)),
         Instruction::Truncate { value, bit_size, max_bit_size } => {
             let value = show(*value);
-            writeln!(f, "truncate {value} to {bit_size} bits, max_bit_size: {max_bit_size}",)
+            write!(f, "truncate {value} to {bit_size} bits, max_bit_size: {max_bit_size}",)
         }
         Instruction::Constrain(lhs, rhs, error) => {
             write!(f, "constrain {} == {}", show(*lhs), show(*rhs))?;
             if let Some(error) = error {
                 display_constrain_error(function, error, f)
             } else {
-                writeln!(f)
+                Ok(())
             }
         }
         Instruction::Call { func, arguments } => {
-            writeln!(f, "call {}({})", show(*func), value_list(function, arguments))
+            write!(f, "call {}({})", show(*func), value_list(function, arguments))
         }
-        Instruction::Allocate => writeln!(f, "allocate"),
-        Instruction::Load { address } => writeln!(f, "load {}", show(*address)),
+        Instruction::Allocate => write!(f, "allocate"),
+        Instruction::Load { address } => write!(f, "load {}", show(*address)),
         Instruction::Store { address, value } => {
-            writeln!(f, "store {} at {}", show(*value), show(*address))
+            write!(f, "store {} at {}", show(*value), show(*address))
         }
         Instruction::EnableSideEffects { condition } => {
-            writeln!(f, "enable_side_effects {}", show(*condition))
+            write!(f, "enable_side_effects {}", show(*condition))
         }
         Instruction::ArrayGet { array, index } => {
-            writeln!(f, "array_get {}, index {}", show(*array), show(*index))
+            write!(f, "array_get {}, index {}", show(*array), show(*index))
         }
         Instruction::ArraySet { array, index, value, mutable } => {
             let array = show(*array);
             let index = show(*index);
             let value = show(*value);
             let mutable = if *mutable { " mut" } else { "" };
-            writeln!(f, "array_set{mutable} {array}, index {index}, value {value}")
+            write!(f, "array_set{mutable} {array}, index {index}, value {value}")
         }
         Instruction::IncrementRc { value } => {
-            writeln!(f, "inc_rc {}", show(*value))
+            write!(f, "inc_rc {}", show(*value))
         }
         Instruction::DecrementRc { value } => {
-            writeln!(f, "dec_rc {}", show(*value))
+            write!(f, "dec_rc {}", show(*value))
         }
-        Instruction::RangeCheck { value, max_bit_size, .. } => {
-            writeln!(f, "range_check {} to {} bits", show(*value), *max_bit_size,)
+        Instruction::RangeCheck { value, max_bit_size, assert_message } => {
+            write!(f, "range_check {} to {} bits", show(*value), *max_bit_size)?;
+            if let Some(assert_message) = assert_message {
+                write!(f, " '{assert_message:?}'")?;
+            }
+            Ok(())
         }
         Instruction::IfElse { then_condition, then_value, else_condition, else_value } => {
             let then_condition = show(*then_condition);
             let then_value = show(*then_value);
             let else_condition = show(*else_condition);
             let else_value = show(*else_value);
-            writeln!(
+            write!(
                 f,
                 "if {then_condition} then {then_value} else if {else_condition} then {else_value}"
             )
@@ -241,15 +274,15 @@ fn display_constrain_error(
 ) -> Result {
     match error {
         ConstrainError::Intrinsic(assert_message_string) => {
-            writeln!(f, " '{assert_message_string:?}'")
+            write!(f, " '{assert_message_string:?}'")
         }
         ConstrainError::UserDefined(selector, values) => {
             if let Some(constant_string) =
                 try_to_extract_string_from_error_payload(*selector, values, &function.dfg)
             {
-                writeln!(f, " '{}'", constant_string)
+                write!(f, " '{}'", constant_string)
             } else {
-                writeln!(f, ", data {}", value_list(function, values))
+                write!(f, ", data {}: {}", selector.as_u64(), value_list(function, values))
             }
         }
     }
@@ -18,7 +18,8 @@
 //! now be simplified.
 //!
 //! This is the only pass which removes duplicated pure [`Instruction`]s however and so is needed when
-//! different blocks are merged, i.e. after the [`flatten_cfg`][super::flatten_cfg] pass.
+//! different blocks are merged, i.e. after the [`flatten_cfg`][super::flatten_cfg] pass. Duplicates in
+//! different blocks are removed beforehand by the [`global_value_numbering`][super::global_value_numbering] pass.
 use std::collections::HashSet;
 
 use acvm::{acir::AcirField, FieldElement};
@@ -287,6 +288,7 @@ mod test {
             types::Type,
             value::{Value, ValueId},
         },
+        ssa_gen::Ssa,
     };
     use acvm::{acir::AcirField, FieldElement};
 
@@ -346,6 +348,27 @@ mod test {
         }
     }
 
+    #[test]
+    fn constant_fold_from_text() {
+        let src = "
+acir(inline) fn main f0 {
+  b0():
+    v2 = add Field 1, Field 2
+    v4 = mul v2, Field 3
+    return v4
+}
+";
+        let expected = "
+acir(inline) fn main f0 {
+  b0():
+    return Field 9
+}
+";
+        let ssa = Ssa::from_str(src).unwrap();
+        let ssa = ssa.fold_constants();
+        assert_eq!(ssa.to_string().trim(), expected.trim());
+    }
+
     #[test]
     fn redundant_truncation() {
         // fn main f0 {
@@ -0,0 +1,228 @@
+//! This file contains the global value numbering pass, which removes instructions that
+//! recompute a value already computed in a dominating block.
+//!
+//! The [`constant_folding`][super::constant_folding] pass only reuses the results of identical
+//! instructions earlier in the same block. This is enough for ACIR functions once their control
+//! flow is flattened into a single block, but Brillig functions keep their CFG, so expressions
+//! which are recomputed in each branch of an `if` or on each iteration of a loop are never shared.
+//!
+//! The blocks of each function are visited in a pre-order walk of its dominator tree, keeping
+//! a table of the pure instructions available in the dominators of the current block. If an
+//! instruction is already in the table, its results are replaced with the earlier results and
+//! the instruction is removed. A block's instructions are taken out of the table once all of
+//! the blocks it dominates have been visited.
+//!
+//! Only instructions which compute their results purely from their arguments are numbered.
+//! Instructions returning arrays are also left alone, since Brillig mutates an array in place
+//! when it holds the only reference to it, so two arrays must not be merged into one.
+use crate::ssa::{
+    ir::{
+        basic_block::BasicBlockId,
+        cfg::ControlFlowGraph,
+        dfg::DataFlowGraph,
+        dom::DominatorTree,
+        function::Function,
+        instruction::{Instruction, InstructionId},
+        post_order::PostOrder,
+        value::{Value, ValueId},
+    },
+    ssa_gen::Ssa,
+};
+use fxhash::FxHashMap as HashMap;
+
+impl Ssa {
+    /// Removes pure instructions which recompute a value already computed in a dominating block.
+    ///
+    /// See [`global_value_numbering`][self] module for more information.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) fn global_value_numbering(mut self) -> Ssa {
+        for function in self.functions.values_mut() {
+            global_value_numbering(function);
+        }
+        self
+    }
+}
+
+enum Visit {
+    /// Number the instructions of a block, then visit the blocks it immediately dominates
+    Enter(BasicBlockId),
+    /// Remove the instructions of a block from the table, after its dominated blocks were visited
+    Exit(Vec<Instruction>),
+}
+
+fn global_value_numbering(function: &mut Function) {
+    let cfg = ControlFlowGraph::with_function(function);
+    let post_order = PostOrder::with_function(function);
+    let dom_tree = DominatorTree::with_cfg_and_post_order(&cfg, &post_order);
+
+    let mut dominated_blocks: HashMap<BasicBlockId, Vec<BasicBlockId>> = HashMap::default();
+    for block in post_order.as_slice() {
+        if let Some(dominator) = dom_tree.immediate_dominator(*block) {
+            dominated_blocks.entry(dominator).or_default().push(*block);
+        }
+    }
+
+    let mut available = HashMap::default();
+    let mut stack = vec![Visit::Enter(function.entry_block())];
+
+    while let Some(visit) = stack.pop() {
+        match visit {
+            Visit::Enter(block) => {
+                let numbered = number_block(&mut function.dfg, block, &mut available);
+                stack.push(Visit::Exit(numbered));
+                if let Some(blocks) = dominated_blocks.get(&block) {
+                    stack.extend(blocks.iter().map(|block| Visit::Enter(*block)));
+                }
+            }
+            Visit::Exit(numbered) => {
+                for instruction in numbered {
+                    available.remove(&instruction);
+                }
+            }
+        }
+    }
+}
+
+/// Removes the instructions of `block` whose results are already available, adding the others
+/// to `available`. Returns the instructions which were added.
+fn number_block(
+    dfg: &mut DataFlowGraph,
+    block: BasicBlockId,
+    available: &mut HashMap<Instruction, Vec<ValueId>>,
+) -> Vec<Instruction> {
+    let mut numbered = Vec::new();
+    let instructions = dfg[block].take_instructions();
+    let mut remaining = Vec::with_capacity(instructions.len());
+
+    for id in instructions {
+        if !can_be_numbered(dfg, id) {
+            remaining.push(id);
+            continue;
+        }
+
+        // Resolve the arguments so that identical instructions compare as equal
+        let instruction = dfg[id].map_values(|value| dfg.resolve(value));
+        let results = dfg.instruction_results(id).to_vec();
+
+        if let Some(available_results) = available.get(&instruction) {
+            for (result, available_result) in results.iter().zip(available_results) {
+                dfg.set_value_from_id(*result, *available_result);
+            }
+        } else {
+            available.insert(instruction.clone(), results);
+            numbered.push(instruction);
+            remaining.push(id);
+        }
+    }
+
+    *dfg[block].instructions_mut() = remaining;
+    numbered
+}
+
+/// True if the instruction's results depend only on its arguments, and aren't arrays
+fn can_be_numbered(dfg: &DataFlowGraph, instruction: InstructionId) -> bool {
+    let is_pure = match &dfg[instruction] {
+        Instruction::Binary(_)
+        | Instruction::Cast(..)
+        | Instruction::Not(_)
+        | Instruction::Truncate { .. }
+        | Instruction::IfElse { .. }
+        | Instruction::ArrayGet { .. } => true,
+
+        Instruction::Call { func, .. } => match dfg[*func] {
+            Value::Intrinsic(intrinsic) => !intrinsic.has_side_effects(),
+            _ => false,
+        },
+
+        Instruction::ArraySet { .. }
+        | Instruction::Constrain(..)
+        | Instruction::RangeCheck { .. }
+        | Instruction::Allocate
+        | Instruction::Load { .. }
+        | Instruction::Store { .. }
+        | Instruction::EnableSideEffects { .. }
+        | Instruction::IncrementRc { .. }
+        | Instruction::DecrementRc { .. } => false,
+    };
+
+    is_pure
+        && dfg
+            .instruction_results(instruction)
+            .iter()
+            .all(|result| !dfg.type_of_value(*result).contains_an_array())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ssa::{
+        ir::{basic_block::BasicBlockId, map::Id},
+        ssa_gen::Ssa,
+    };
+
+    /// Returns the number of instructions in each of the first blocks of main
+    fn instruction_counts(ssa: &Ssa, block_count: usize) -> Vec<usize> {
+        let main = ssa.main();
+        (0..block_count)
+            .map(|block| {
+                let block: BasicBlockId = Id::test_new(block);
+                main.dfg[block].instructions().len()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reuses_values_from_dominating_blocks() {
+        let src = "
+brillig fn main f0 {
+  b0(v0: u32, v1: u32, v2: u1):
+    v3 = add v0, v1
+    jmpif v2 then: b1, else: b2
+  b1():
+    v4 = add v0, v1
+    v5 = mul v0, v1
+    jmp b3(v4)
+  b2():
+    v6 = mul v0, v1
+    v7 = add v0, v1
+    jmp b3(v6)
+  b3(v8: u32):
+    v9 = mul v0, v1
+    v10 = add v8, v3
+    return v10
+}
+";
+        let ssa = Ssa::from_str(src).unwrap().global_value_numbering();
+
+        // The additions in both branches reuse `v3`, but the multiplications in each branch
+        // don't dominate each other or `b3`
+        assert_eq!(instruction_counts(&ssa, 4), vec![1, 1, 1, 2]);
+
+        let main = ssa.main();
+        let b1: BasicBlockId = Id::test_new(1);
+        let terminator = main.dfg[b1].unwrap_terminator();
+        let mut arguments = Vec::new();
+        terminator.for_each_value(|value| arguments.push(main.dfg.resolve(value)));
+        let v3 = main.dfg.instruction_results(main.dfg[main.entry_block()].instructions()[0])[0];
+        assert_eq!(arguments, vec![v3]);
+    }
+
+    #[test]
+    fn does_not_reuse_memory_accesses_or_arrays() {
+        let src = "
+brillig fn main f0 {
+  b0(v0: &mut Field, v1: [Field; 2], v2: Field):
+    v3 = load v0 -> Field
+    v4 = array_set v1, index u32 0, value v2
+    jmp b1()
+  b1():
+    v5 = load v0 -> Field
+    v6 = array_set v1, index u32 0, value v2
+    v7 = array_get v1, index u32 1 -> Field
+    v8 = array_get v1, index u32 1 -> Field
+    return v3, v4, v5, v6, v7, v8
+}
+";
+        let ssa = Ssa::from_str(src).unwrap().global_value_numbering();
+        assert_eq!(instruction_counts(&ssa, 2), vec![2, 3]);
+    }
+}
@@ -0,0 +1,292 @@
+//! This file contains the loop-invariant code motion pass for Brillig functions.
+//!
+//! ACIR functions have all their loops unrolled, but the loops of Brillig functions are kept
+//! as loops. Any instruction in such a loop which computes the same value on each iteration
+//! is hoisted into the loop's pre-header, so that it is only computed once.
+//!
+//! An instruction is hoisted if:
+//! 1. It only computes its results from its arguments. Instructions which read or write memory,
+//!    such as loads, stores and reference counting, or which have side effects, are never hoisted.
+//! 2. Each of its arguments is defined outside of the loop, or by an instruction already hoisted.
+//! 3. It cannot fail, since the loop may not run at all, or it is at the start of the loop header.
+//!    The header runs whenever the loop is entered, so hoisting from it can't introduce failures.
+//!
+//! Loops are visited from the innermost outwards, so that instructions hoisted out of an inner
+//! loop may then be hoisted out of the loops containing it.
+use std::collections::HashSet;
+
+use acvm::{acir::AcirField, FieldElement};
+
+use crate::ssa::{
+    ir::{
+        basic_block::BasicBlockId,
+        cfg::ControlFlowGraph,
+        dfg::DataFlowGraph,
+        function::{Function, RuntimeType},
+        instruction::{Binary, BinaryOp, Instruction, InstructionId, Intrinsic},
+        post_order::PostOrder,
+        types::{NumericType, Type},
+        value::{Value, ValueId},
+    },
+    ssa_gen::Ssa,
+};
+
+use super::unrolling::{find_all_loops, Loop};
+
+impl Ssa {
+    /// Hoists loop-invariant instructions out of the loops of Brillig functions.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) fn loop_invariant_code_motion(mut self) -> Ssa {
+        for function in self.functions.values_mut() {
+            if function.runtime() == RuntimeType::Brillig {
+                hoist_loop_invariants(function);
+            }
+        }
+        self
+    }
+}
+
+fn hoist_loop_invariants(function: &mut Function) {
+    let loops = find_all_loops(function);
+
+    // Hoisting instructions doesn't change the CFG, so this order stays valid throughout
+    let mut reverse_post_order = PostOrder::with_function(function).into_vec();
+    reverse_post_order.reverse();
+
+    // Loops are sorted by their size, so inner loops come before the loops containing them
+    for loop_ in &loops.yet_to_unroll {
+        if let Some(pre_header) = get_pre_header(&loops.cfg, loop_) {
+            let blocks = reverse_post_order.iter().filter(|block| loop_.blocks.contains(*block));
+            hoist_from_loop(&mut function.dfg, loop_, pre_header, blocks.copied().collect());
+        }
+    }
+}
+
+/// Returns the block which is the only way into the loop, if there is one.
+/// Instructions are only hoisted into such a block, since it runs exactly once each
+/// time the loop is entered.
+fn get_pre_header(cfg: &ControlFlowGraph, loop_: &Loop) -> Option<BasicBlockId> {
+    let mut outside_predecessors =
+        cfg.predecessors(loop_.header).filter(|predecessor| !loop_.blocks.contains(predecessor));
+    let pre_header = outside_predecessors.next()?;
+    let only_jumps_to_header = cfg.successors(pre_header).len() == 1;
+    (outside_predecessors.next().is_none() && only_jumps_to_header).then_some(pre_header)
+}
+
+/// Moves each loop-invariant instruction in `blocks` to the end of `pre_header`.
+/// `blocks` are the blocks of the loop, ordered so that definitions come before their uses.
+fn hoist_from_loop(
+    dfg: &mut DataFlowGraph,
+    loop_: &Loop,
+    pre_header: BasicBlockId,
+    blocks: Vec<BasicBlockId>,
+) {
+    let mut defined_in_loop = HashSet::new();
+    for block in &blocks {
+        defined_in_loop.extend(dfg.block_parameters(*block));
+        for instruction in dfg[*block].instructions() {
+            defined_in_loop.extend(dfg.instruction_results(*instruction));
+        }
+    }
+
+    for block in blocks {
+        // Instructions which may fail can be hoisted until an instruction in the header stays in the loop
+        let mut can_hoist_failures = block == loop_.header;
+
+        let instructions = dfg[block].take_instructions();
+        let mut remaining = Vec::with_capacity(instructions.len());
+
+        for instruction in instructions {
+            let hoist = is_pure(dfg, instruction)
+                && (can_hoist_failures || !can_fail(dfg, instruction))
+                && is_loop_invariant(dfg, instruction, &defined_in_loop);
+
+            if hoist {
+                for result in dfg.instruction_results(instruction) {
+                    defined_in_loop.remove(result);
+                }
+                dfg[pre_header].insert_instruction(instruction);
+            } else {
+                can_hoist_failures = false;
+                remaining.push(instruction);
+            }
+        }
+
+        *dfg[block].instructions_mut() = remaining;
+    }
+}
+
+/// True if the instruction computes its results only from its arguments, without accessing memory
+fn is_pure(dfg: &DataFlowGraph, instruction: InstructionId) -> bool {
+    match &dfg[instruction] {
+        Instruction::Binary(_)
+        | Instruction::Cast(..)
+        | Instruction::Not(_)
+        | Instruction::Truncate { .. }
+        | Instruction::IfElse { .. }
+        | Instruction::ArrayGet { .. } => true,
+
+        Instruction::Call { func, .. } => matches!(
+            dfg[*func],
+            Value::Intrinsic(Intrinsic::ArrayLen | Intrinsic::AsField | Intrinsic::FromField)
+        ),
+
+        // Arrays may be mutated in place by an `ArraySet` if nothing else refers to them,
+        // so it must stay where it is relative to the reference counting instructions.
+        Instruction::ArraySet { .. }
+        | Instruction::Constrain(..)
+        | Instruction::RangeCheck { .. }
+        | Instruction::Allocate
+        | Instruction::Load { .. }
+        | Instruction::Store { .. }
+        | Instruction::EnableSideEffects { .. }
+        | Instruction::IncrementRc { .. }
+        | Instruction::DecrementRc { .. } => false,
+    }
+}
+
+/// True if the instruction may fail at runtime, given Brillig's checks
+fn can_fail(dfg: &DataFlowGraph, instruction: InstructionId) -> bool {
+    match &dfg[instruction] {
+        Instruction::Binary(Binary { lhs, rhs, operator }) => match operator {
+            BinaryOp::Div | BinaryOp::Mod => {
+                let is_signed =
+                    matches!(dfg.type_of_value(*lhs), Type::Numeric(NumericType::Signed { .. }));
+                is_signed || dfg.get_numeric_constant(*rhs).map_or(true, |rhs| rhs.is_zero())
+            }
+            // Brillig checks unsigned arithmetic for overflows
+            BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul => {
+                matches!(dfg.type_of_value(*lhs), Type::Numeric(NumericType::Unsigned { .. }))
+            }
+            _ => false,
+        },
+        // Reading from a constant index within an array's length can't go out of bounds
+        Instruction::ArrayGet { array, index } => {
+            match (dfg.type_of_value(*array), dfg.get_numeric_constant(*index)) {
+                (Type::Array(_, length), Some(index)) => {
+                    index >= FieldElement::from(length as u128)
+                }
+                _ => true,
+            }
+        }
+        _ => false,
+    }
+}
+
+fn is_loop_invariant(
+    dfg: &DataFlowGraph,
+    instruction: InstructionId,
+    defined_in_loop: &HashSet<ValueId>,
+) -> bool {
+    let mut invariant = true;
+    dfg[instruction].for_each_value(|value| {
+        invariant &= !uses_loop_value(dfg, value, defined_in_loop);
+    });
+    invariant
+}
+
+fn uses_loop_value(
+    dfg: &DataFlowGraph,
+    value: ValueId,
+    defined_in_loop: &HashSet<ValueId>,
+) -> bool {
+    let value = dfg.resolve(value);
+    match &dfg[value] {
+        Value::Array { array, .. } => {
+            array.iter().any(|element| uses_loop_value(dfg, *element, defined_in_loop))
+        }
+        _ => defined_in_loop.contains(&value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ssa::{
+        ir::{basic_block::BasicBlockId, map::Id},
+        ssa_gen::Ssa,
+    };
+
+    /// Returns the number of instructions in each of the first blocks of main
+    fn instruction_counts(ssa: &Ssa, block_count: usize) -> Vec<usize> {
+        let main = ssa.main();
+        (0..block_count)
+            .map(|block| {
+                let block: BasicBlockId = Id::test_new(block);
+                main.dfg[block].instructions().len()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn hoists_invariant_instructions() {
+        let src = "
+brillig fn main f0 {
+  b0(v0: Field, v1: [u32; 3], v2: u32):
+    jmp b1(u32 0)
+  b1(v3: u32):
+    v5 = lt v3, v2
+    jmpif v5 then: b2, else: b3
+  b2():
+    v6 = mul v0, v0
+    v7 = array_get v1, index u32 1 -> u32
+    v8 = call array_len(v1) -> u32
+    v9 = add v7, v8
+    v10 = add v3, u32 1
+    jmp b1(v10)
+  b3():
+    return
+}
+";
+        let ssa = Ssa::from_str(src).unwrap().loop_invariant_code_motion();
+
+        // The addition of invariant values may overflow, so it isn't hoisted from the loop body
+        // where it wouldn't run if the loop is skipped
+        assert_eq!(instruction_counts(&ssa, 4), vec![3, 1, 2, 0]);
+    }
+
+    #[test]
+    fn does_not_hoist_memory_accesses_or_failures() {
+        let src = "
+brillig fn main f0 {
+  b0(v0: &mut Field, v1: [Field; 3], v2: u32, v3: u32):
+    jmp b1(u32 0)
+  b1(v4: u32):
+    v6 = lt v4, v2
+    jmpif v6 then: b2, else: b3
+  b2():
+    v7 = load v0 -> Field
+    v8 = add v7, Field 1
+    store v8 at v0
+    v9 = array_get v1, index v3 -> Field
+    v10 = div v2, v3
+    v11 = add v4, u32 1
+    jmp b1(v11)
+  b3():
+    return
+}
+";
+        let ssa = Ssa::from_str(src).unwrap().loop_invariant_code_motion();
+        assert_eq!(instruction_counts(&ssa, 4), vec![0, 1, 6, 0]);
+    }
+
+    #[test]
+    fn does_not_hoist_from_acir_functions() {
+        let src = "
+acir(inline) fn main f0 {
+  b0(v0: Field, v1: u32):
+    jmp b1(u32 0)
+  b1(v2: u32):
+    v4 = lt v2, v1
+    jmpif v4 then: b2, else: b3
+  b2():
+    v5 = mul v0, v0
+    v7 = add v2, u32 1
+    jmp b1(v7)
+  b3():
+    return
+}
+";
+        let ssa = Ssa::from_str(src).unwrap().loop_invariant_code_motion();
+        assert_eq!(instruction_counts(&ssa, 4), vec![0, 1, 2, 0]);
+    }
+}
@@ -11,8 +11,11 @@ mod constant_folding;
 mod defunctionalize;
 mod die;
 pub(crate) mod flatten_cfg;
+mod global_value_numbering;
 mod inlining;
+mod loop_invariant;
 mod mem2reg;
+mod range_analysis;
 mod rc;
 mod remove_bit_shifts;
 mod remove_enable_side_effects;
@@ -0,0 +1,592 @@
+//! This file contains a value range analysis pass, which uses the known bounds of values to remove
+//! range checks and truncations which can never fail or have any effect.
+//!
+//! Each numeric value is assigned an inclusive range `[min, max]` that it is known to lie within.
+//! These ranges start from those of the values' types and constants, and are then narrowed by the
+//! instructions defining them. For example, adding two `u8` values cast to `u32` gives a value
+//! within `[0, 510]` rather than `[0, 2^32 - 1]`. Using these ranges:
+//! - `range_check`s of values which always fit in the checked bit size are removed
+//! - `truncate`s of values which always fit in the truncated bit size are replaced by the value
+//! - `truncate`s which can't be removed have their `max_bit_size` narrowed to the value's range
+//! - `lt` and `eq` comparisons with a known result are replaced by constants. This in particular
+//!   removes array index checks which are known to be in bounds.
+//!
+//! The induction variables of loops with constant bounds, such as `i` in `for i in 0..4`, are
+//! given the range of their loop: `[0, 4]` in the loop header, and `[0, 3]` within the loop body
+//! where the loop condition is known to hold. These loops are only left in Brillig functions,
+//! since those of ACIR functions have been unrolled.
+//!
+//! Note that the results of signed addition, subtraction, multiplication and left shifts may exceed
+//! the range of their type until they are truncated, so they do not assume their type's range.
+use acvm::{acir::AcirField, FieldElement};
+use fxhash::FxHashMap as HashMap;
+
+use crate::ssa::{
+    ir::{
+        basic_block::BasicBlockId,
+        cfg::ControlFlowGraph,
+        dfg::DataFlowGraph,
+        function::Function,
+        instruction::{Binary, BinaryOp, Instruction, InstructionId, TerminatorInstruction},
+        post_order::PostOrder,
+        types::{NumericType, Type},
+        value::{Value, ValueId},
+    },
+    ssa_gen::Ssa,
+};
+
+use super::unrolling::{find_all_loops, Loop};
+
+impl Ssa {
+    /// Removes the `range_check` and `truncate` instructions which are redundant given
+    /// the known ranges of their arguments.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) fn remove_redundant_range_checks(mut self) -> Ssa {
+        for function in self.functions.values_mut() {
+            RangeAnalysis::default().analyze_function(function);
+        }
+        self
+    }
+}
+
+/// An inclusive range of values
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Range {
+    min: u128,
+    max: u128,
+}
+
+impl Range {
+    fn new(min: u128, max: u128) -> Self {
+        Self { min, max }
+    }
+
+    fn constant(value: u128) -> Self {
+        Self::new(value, value)
+    }
+
+    fn of_bit_size(bit_size: u32) -> Self {
+        let max = if bit_size >= 128 { u128::MAX } else { (1 << bit_size) - 1 };
+        Self::new(0, max)
+    }
+
+    /// Returns the range of values of an integer type.
+    /// Fields are not given a range since their values may not fit in a `u128`.
+    fn of_type(typ: &Type) -> Option<Self> {
+        match typ {
+            Type::Numeric(
+                NumericType::Unsigned { bit_size } | NumericType::Signed { bit_size },
+            ) => Some(Self::of_bit_size(*bit_size)),
+            _ => None,
+        }
+    }
+
+    /// The number of bits needed to represent every value in the range
+    fn bit_size(self) -> u32 {
+        u128::BITS - self.max.leading_zeros()
+    }
+
+    fn fits_in(self, bit_size: u32) -> bool {
+        self.bit_size() <= bit_size
+    }
+
+    fn union(self, other: Self) -> Self {
+        Self::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    /// Returns the intersection of both ranges, or `other` if they don't overlap
+    fn intersect(self, other: Self) -> Self {
+        let intersection = Self::new(self.min.max(other.min), self.max.min(other.max));
+        if intersection.min <= intersection.max {
+            intersection
+        } else {
+            other
+        }
+    }
+
+    fn is_disjoint(self, other: Self) -> bool {
+        self.max < other.min || other.max < self.min
+    }
+}
+
+#[derive(Default)]
+struct RangeAnalysis {
+    /// The ranges of values which have been narrowed beyond those of their types
+    ranges: HashMap<ValueId, Range>,
+
+    /// The ranges of values which have been range checked in the current block. These only
+    /// hold after the check, so unlike `ranges` they can't be used in other blocks.
+    checked_ranges: HashMap<ValueId, Range>,
+
+    /// The ranges of loop induction variables within each block of their loop's body
+    loop_ranges: HashMap<BasicBlockId, Vec<(ValueId, Range)>>,
+}
+
+impl RangeAnalysis {
+    fn analyze_function(&mut self, function: &mut Function) {
+        self.find_induction_variable_ranges(function);
+
+        // Visiting blocks in reverse post-order means values are defined before they are used,
+        // except for block parameters, which are given the range of their type unless they are
+        // a loop's induction variable.
+        let mut blocks = PostOrder::with_function(function).into_vec();
+        blocks.reverse();
+        for block in blocks {
+            self.analyze_block(&mut function.dfg, block);
+        }
+    }
+
+    fn analyze_block(&mut self, dfg: &mut DataFlowGraph, block: BasicBlockId) {
+        self.checked_ranges.clear();
+        if let Some(loop_ranges) = self.loop_ranges.get(&block) {
+            self.checked_ranges.extend(loop_ranges.iter().copied());
+        }
+
+        let instructions = dfg[block].take_instructions();
+        let mut remaining = Vec::with_capacity(instructions.len());
+
+        for instruction in instructions {
+            if self.analyze_instruction(dfg, instruction) {
+                remaining.push(instruction);
+            }
+        }
+
+        *dfg[block].instructions_mut() = remaining;
+    }
+
+    fn find_induction_variable_ranges(&mut self, function: &Function) {
+        let loops = find_all_loops(function);
+        let mut loops_per_header = HashMap::default();
+        for loop_ in &loops.yet_to_unroll {
+            *loops_per_header.entry(loop_.header).or_insert(0) += 1;
+        }
+
+        for loop_ in &loops.yet_to_unroll {
+            // With several back edges the induction variable may be updated in several ways
+            if loops_per_header[&loop_.header] != 1 {
+                continue;
+            }
+            let Some((variable, range, body_range)) =
+                induction_variable(&function.dfg, &loops.cfg, loop_)
+            else {
+                continue;
+            };
+            self.ranges.insert(variable, range);
+            if let Some(body_range) = body_range {
+                for block in loop_.blocks.iter().filter(|block| **block != loop_.header) {
+                    self.loop_ranges.entry(*block).or_default().push((variable, body_range));
+                }
+            }
+        }
+    }
+
+    /// Records the ranges of the instruction's results, simplifying it if they make it redundant.
+    /// Returns false if the instruction should be removed.
+    fn analyze_instruction(&mut self, dfg: &mut DataFlowGraph, instruction: InstructionId) -> bool {
+        match dfg[instruction].clone() {
+            Instruction::RangeCheck { value, max_bit_size, .. } => {
+                let checked = Range::of_bit_size(max_bit_size);
+                match self.range(dfg, value) {
+                    Some(range) if range.fits_in(max_bit_size) => false,
+                    range => {
+                        // Once the check has passed, the value is known to be within its range
+                        let range = range.map_or(checked, |range| range.intersect(checked));
+                        self.checked_ranges.insert(dfg.resolve(value), range);
+                        true
+                    }
+                }
+            }
+            Instruction::Truncate { value, bit_size, max_bit_size } => {
+                let result = dfg.instruction_results(instruction)[0];
+                match self.range(dfg, value) {
+                    Some(range) if range.fits_in(bit_size) => {
+                        dfg.set_value_from_id(result, value);
+                        return false;
+                    }
+                    Some(range) if range.bit_size() < max_bit_size => {
+                        let max_bit_size = range.bit_size();
+                        dfg[instruction] = Instruction::Truncate { value, bit_size, max_bit_size };
+                    }
+                    _ => (),
+                }
+                self.ranges.insert(result, Range::of_bit_size(bit_size));
+                true
+            }
+            Instruction::Binary(binary) => {
+                let result = dfg.instruction_results(instruction)[0];
+                if let Some(constant) = self.compare(dfg, &binary) {
+                    let constant = dfg.make_constant(FieldElement::from(constant), Type::bool());
+                    dfg.set_value_from_id(result, constant);
+                    return false;
+                }
+                if let Some(range) = self.binary_range(dfg, &binary) {
+                    self.ranges.insert(result, range);
+                }
+                true
+            }
+            Instruction::Cast(value, typ) => {
+                // Casts don't change the value, but it's assumed to fit in the new type
+                let result = dfg.instruction_results(instruction)[0];
+                let range = match (self.range(dfg, value), Range::of_type(&typ)) {
+                    (Some(range), Some(type_range)) => Some(range.intersect(type_range)),
+                    (range, type_range) => range.or(type_range),
+                };
+                if let Some(range) = range {
+                    self.ranges.insert(result, range);
+                }
+                true
+            }
+            Instruction::Not(value) => {
+                let result = dfg.instruction_results(instruction)[0];
+                let type_range = Range::of_type(&dfg.type_of_value(value));
+                if let (Some(range), Some(type_range)) = (self.range(dfg, value), type_range) {
+                    let max = type_range.max;
+                    self.ranges.insert(result, Range::new(max - range.max, max - range.min));
+                }
+                true
+            }
+            Instruction::IfElse { then_value, else_value, .. } => {
+                let result = dfg.instruction_results(instruction)[0];
+                let then_range = self.range(dfg, then_value);
+                let else_range = self.range(dfg, else_value);
+                if let (Some(then_range), Some(else_range)) = (then_range, else_range) {
+                    self.ranges.insert(result, then_range.union(else_range));
+                }
+                true
+            }
+            _ => true,
+        }
+    }
+
+    /// Returns the known range of a value, if there is one
+    fn range(&self, dfg: &DataFlowGraph, value: ValueId) -> Option<Range> {
+        let value = dfg.resolve(value);
+        if let Some(range) = self.checked_ranges.get(&value).or_else(|| self.ranges.get(&value)) {
+            return Some(*range);
+        }
+        match dfg.get_numeric_constant(value) {
+            Some(constant) => constant.try_into_u128().map(Range::constant),
+            None => Range::of_type(&dfg.type_of_value(value)),
+        }
+    }
+
+    /// Returns the result of an `eq` or `lt` comparison, if the ranges of its operands decide it
+    fn compare(&self, dfg: &DataFlowGraph, binary: &Binary) -> Option<bool> {
+        let lhs = self.range(dfg, binary.lhs)?;
+        let rhs = self.range(dfg, binary.rhs)?;
+        match binary.operator {
+            BinaryOp::Eq if lhs.is_disjoint(rhs) => Some(false),
+            // Signed values are compared as two's complement, so their ranges can't decide `lt`
+            BinaryOp::Lt if !is_signed(&dfg.type_of_value(binary.lhs)) => {
+                if lhs.max < rhs.min {
+                    Some(true)
+                } else if lhs.min >= rhs.max {
+                    Some(false)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn binary_range(&self, dfg: &DataFlowGraph, binary: &Binary) -> Option<Range> {
+        if matches!(binary.operator, BinaryOp::Eq | BinaryOp::Lt) {
+            return Some(Range::of_bit_size(1));
+        }
+
+        let typ = dfg.type_of_value(binary.lhs);
+        let lhs = self.range(dfg, binary.lhs);
+        let rhs = self.range(dfg, binary.rhs);
+        let derived = lhs.zip(rhs).and_then(|(lhs, rhs)| derive_range(binary.operator, lhs, rhs));
+
+        match typ {
+            Type::Numeric(NumericType::Unsigned { .. }) => {
+                // Unsigned arithmetic is checked for overflow, so results stay within their type
+                let type_range = Range::of_type(&typ)?;
+                Some(derived.map_or(type_range, |range| range.intersect(type_range)))
+            }
+            Type::Numeric(NumericType::Signed { .. }) => match binary.operator {
+                // Brillig wraps these, giving a result which may be below the derived minimum
+                BinaryOp::Add | BinaryOp::Mul => derived.map(|range| Range::new(0, range.max)),
+                BinaryOp::Sub | BinaryOp::Shl => None,
+                _ => Range::of_type(&typ),
+            },
+            // Division isn't numeric division for fields
+            _ if matches!(binary.operator, BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul) => {
+                derived
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Finds the induction variable of a loop of the form `for i in start..end` with constant bounds.
+/// Returns the variable, its range in the loop header, and its range in the rest of the loop if
+/// the loop runs at all.
+///
+/// The loop header must take the variable as a parameter, continue into the loop's body if
+/// `i < end` and exit it otherwise. It must be entered with `start` and jumped back to with
+/// `i + 1`, which can then be at most `end` since it is computed in the body.
+fn induction_variable(
+    dfg: &DataFlowGraph,
+    cfg: &ControlFlowGraph,
+    loop_: &Loop,
+) -> Option<(ValueId, Range, Option<Range>)> {
+    let header = loop_.header;
+    let TerminatorInstruction::JmpIf { condition, then_destination, else_destination } =
+        dfg[header].terminator()?
+    else {
+        return None;
+    };
+
+    // The body must only be entered once the loop condition has passed, so that the condition
+    // holds throughout the rest of the loop
+    let body = *then_destination;
+    if !loop_.blocks.contains(&body)
+        || loop_.blocks.contains(else_destination)
+        || cfg.predecessors(body).len() != 1
+    {
+        return None;
+    }
+
+    let Value::Instruction { instruction, .. } = &dfg[dfg.resolve(*condition)] else {
+        return None;
+    };
+    let Instruction::Binary(Binary { lhs, rhs, operator: BinaryOp::Lt }) = &dfg[*instruction]
+    else {
+        return None;
+    };
+    let end = dfg.get_numeric_constant(*rhs)?.try_into_u128()?;
+
+    let variable = dfg.resolve(*lhs);
+    let index = dfg.block_parameters(header).iter().position(|parameter| *parameter == variable)?;
+    if !matches!(
+        dfg.type_of_value(variable),
+        Type::Numeric(NumericType::Unsigned { .. } | NumericType::NativeField)
+    ) {
+        return None;
+    }
+
+    let mut start = None;
+    for predecessor in cfg.predecessors(header) {
+        let TerminatorInstruction::Jmp { arguments, .. } = dfg[predecessor].terminator()? else {
+            return None;
+        };
+        let argument = dfg.resolve(*arguments.get(index)?);
+        if let Some(constant) = dfg.get_numeric_constant(argument) {
+            if start.replace(constant.try_into_u128()?).is_some() {
+                return None;
+            }
+        } else if !is_increment_in_body(dfg, header, argument, variable) {
+            return None;
+        }
+    }
+
+    let start = start?;
+    let body_range = (start < end).then(|| Range::new(start, end - 1));
+    Some((variable, Range::new(start, start.max(end)), body_range))
+}
+
+/// True if `value` is `variable + 1`, computed outside of the loop header
+fn is_increment_in_body(
+    dfg: &DataFlowGraph,
+    header: BasicBlockId,
+    value: ValueId,
+    variable: ValueId,
+) -> bool {
+    let Value::Instruction { instruction, .. } = &dfg[value] else {
+        return false;
+    };
+    let is_one = |value| dfg.get_numeric_constant(value).map_or(false, |value| value.is_one());
+    let is_increment = match &dfg[*instruction] {
+        Instruction::Binary(Binary { lhs, rhs, operator: BinaryOp::Add }) => {
+            (dfg.resolve(*lhs) == variable && is_one(*rhs))
+                || (dfg.resolve(*rhs) == variable && is_one(*lhs))
+        }
+        _ => false,
+    };
+    is_increment && !dfg[header].instructions().contains(instruction)
+}
+
+fn is_signed(typ: &Type) -> bool {
+    matches!(typ, Type::Numeric(NumericType::Signed { .. }))
+}
+
+/// Returns the range of an integer operation on values in the given ranges,
+/// if it doesn't overflow.
+fn derive_range(operator: BinaryOp, lhs: Range, rhs: Range) -> Option<Range> {
+    match operator {
+        BinaryOp::Add => {
+            Some(Range::new(lhs.min.checked_add(rhs.min)?, lhs.max.checked_add(rhs.max)?))
+        }
+        BinaryOp::Sub => {
+            (lhs.min >= rhs.max).then(|| Range::new(lhs.min - rhs.max, lhs.max - rhs.min))
+        }
+        BinaryOp::Mul => {
+            Some(Range::new(lhs.min.checked_mul(rhs.min)?, lhs.max.checked_mul(rhs.max)?))
+        }
+        BinaryOp::Div => (rhs.min > 0).then(|| Range::new(lhs.min / rhs.max, lhs.max / rhs.min)),
+        BinaryOp::Mod => (rhs.max > 0).then(|| Range::new(0, lhs.max.min(rhs.max - 1))),
+        BinaryOp::And => Some(Range::new(0, lhs.max.min(rhs.max))),
+        BinaryOp::Or | BinaryOp::Xor => {
+            Some(Range::of_bit_size(lhs.bit_size().max(rhs.bit_size())))
+        }
+        BinaryOp::Shr => {
+            let shift = u32::try_from(rhs.min).ok()?;
+            Some(Range::new(0, lhs.max.checked_shr(shift).unwrap_or(0)))
+        }
+        BinaryOp::Shl | BinaryOp::Eq | BinaryOp::Lt => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::{acir::AcirField, FieldElement};
+
+    use crate::ssa::{
+        ir::instruction::{Instruction, TerminatorInstruction},
+        ssa_gen::Ssa,
+    };
+
+    fn main_instructions(ssa: &Ssa) -> Vec<Instruction> {
+        let main = ssa.main();
+        let instructions = main.dfg[main.entry_block()].instructions();
+        instructions.iter().map(|instruction| main.dfg[*instruction].clone()).collect()
+    }
+
+    #[test]
+    fn removes_checks_of_values_within_range() {
+        let src = "
+acir(inline) fn main f0 {
+  b0(v0: u8, v1: u8):
+    v2 = cast v0 as u32
+    v3 = cast v1 as u32
+    v4 = add v2, v3
+    range_check v4 to 9 bits
+    v5 = truncate v4 to 16 bits, max_bit_size: 32
+    return v5
+}
+";
+        let ssa = Ssa::from_str(src).unwrap().remove_redundant_range_checks();
+        let instructions = main_instructions(&ssa);
+        assert_eq!(instructions.len(), 3);
+        assert!(matches!(instructions[2], Instruction::Binary(_)));
+
+        // The truncation is replaced by the sum
+        let main = ssa.main();
+        let TerminatorInstruction::Return { return_values, .. } =
+            main.dfg[main.entry_block()].unwrap_terminator()
+        else {
+            panic!("Expected a return");
+        };
+        let sum = main.dfg.instruction_results(main.dfg[main.entry_block()].instructions()[2])[0];
+        assert_eq!(main.dfg.resolve(return_values[0]), sum);
+    }
+
+    #[test]
+    fn narrows_truncations() {
+        let src = "
+acir(inline) fn main f0 {
+  b0(v0: u16):
+    v1 = cast v0 as u32
+    v3 = mul v1, u32 2
+    v4 = truncate v3 to 8 bits, max_bit_size: 32
+    return v4
+}
+";
+        let ssa = Ssa::from_str(src).unwrap().remove_redundant_range_checks();
+        let instructions = main_instructions(&ssa);
+        assert_eq!(instructions.len(), 3);
+        assert!(matches!(
+            instructions[2],
+            Instruction::Truncate { bit_size: 8, max_bit_size: 17, .. }
+        ));
+    }
+
+    #[test]
+    fn folds_index_checks_known_to_be_in_bounds() {
+        let src = "
+acir(inline) fn main f0 {
+  b0(v0: u8):
+    v1 = cast v0 as u32
+    v3 = lt v1, u32 256
+    constrain v3 == u1 1
+    return
+}
+";
+        let ssa = Ssa::from_str(src).unwrap().remove_redundant_range_checks();
+        let instructions = main_instructions(&ssa);
+        assert_eq!(instructions.len(), 2);
+
+        let Instruction::Constrain(lhs, _, _) = instructions[1] else {
+            panic!("Expected a constrain");
+        };
+        let lhs = ssa.main().dfg.get_numeric_constant(lhs);
+        assert_eq!(lhs, Some(FieldElement::one()));
+    }
+
+    #[test]
+    fn derives_ranges_of_loop_induction_variables() {
+        // for i in 0..4 { assert(i < 4); range_check(i, 2) }
+        let src = "
+brillig fn main f0 {
+  b0():
+    jmp b1(u32 0)
+  b1(v0: u32):
+    v3 = lt v0, u32 4
+    jmpif v3 then: b2, else: b3
+  b2():
+    v4 = lt v0, u32 4
+    constrain v4 == u1 1
+    range_check v0 to 2 bits
+    v6 = add v0, u32 1
+    jmp b1(v6)
+  b3():
+    v7 = lt v0, u32 5
+    constrain v7 == u1 1
+    return
+}
+";
+        let ssa = Ssa::from_str(src).unwrap().remove_redundant_range_checks();
+        let main = ssa.main();
+        let TerminatorInstruction::Jmp { destination: header, .. } =
+            main.dfg[main.entry_block()].unwrap_terminator()
+        else {
+            panic!("Expected a jump");
+        };
+        let TerminatorInstruction::JmpIf { then_destination, else_destination, .. } =
+            main.dfg[*header].unwrap_terminator()
+        else {
+            panic!("Expected a jmpif");
+        };
+
+        // The loop condition itself can't be folded, but it holds within the body, where the
+        // range check is removed. After the loop the variable is at most 4.
+        assert_eq!(main.dfg[*header].instructions().len(), 1);
+        for block in [then_destination, else_destination] {
+            let instructions = main.dfg[*block].instructions();
+            let Instruction::Constrain(lhs, _, _) = main.dfg[instructions[0]] else {
+                panic!("Expected a constrain");
+            };
+            assert_eq!(main.dfg.get_numeric_constant(lhs), Some(FieldElement::one()));
+        }
+        assert_eq!(main.dfg[*then_destination].instructions().len(), 2);
+    }
+
+    #[test]
+    fn keeps_truncations_of_signed_arithmetic() {
+        let src = "
+acir(inline) fn main f0 {
+  b0(v0: i8, v1: i8):
+    v2 = add v0, v1
+    v3 = truncate v2 to 8 bits, max_bit_size: 9
+    return v3
+}
+";
+        let ssa = Ssa::from_str(src).unwrap().remove_redundant_range_checks();
+        let instructions = main_instructions(&ssa);
+        assert_eq!(instructions.len(), 2);
+        assert!(matches!(instructions[1], Instruction::Truncate { max_bit_size: 9, .. }));
+    }
+}
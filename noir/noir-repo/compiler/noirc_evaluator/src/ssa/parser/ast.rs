@@ -0,0 +1,164 @@
+use std::fmt::Display;
+
+use acvm::FieldElement;
+use noirc_errors::Span;
+
+use crate::ssa::ir::{function::RuntimeType, instruction::BinaryOp, types::Type};
+
+#[derive(Debug)]
+pub(crate) struct ParsedSsa {
+    pub(crate) functions: Vec<ParsedFunction>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ParsedFunction {
+    pub(crate) runtime_type: RuntimeType,
+    pub(crate) external_name: String,
+    /// The id the function was printed with, e.g. `f1`, which is how calls refer to it
+    pub(crate) internal_name: Identifier,
+    pub(crate) blocks: Vec<ParsedBlock>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ParsedBlock {
+    pub(crate) name: Identifier,
+    pub(crate) parameters: Vec<ParsedParameter>,
+    pub(crate) instructions: Vec<ParsedInstruction>,
+    pub(crate) terminator: ParsedTerminator,
+}
+
+#[derive(Debug)]
+pub(crate) struct ParsedParameter {
+    pub(crate) identifier: Identifier,
+    pub(crate) typ: Type,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Identifier {
+    pub(crate) name: String,
+    pub(crate) span: Span,
+}
+
+impl Identifier {
+    pub(crate) fn new(name: String, span: Span) -> Self {
+        Self { name, span }
+    }
+}
+
+impl Display for Identifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ParsedInstruction {
+    /// The values the results of this instruction are assigned to
+    pub(crate) targets: Vec<Identifier>,
+    pub(crate) kind: ParsedInstructionKind,
+    /// The result types written after `->`, only given for instructions
+    /// whose result types can't be inferred from their operands
+    pub(crate) result_types: Option<Vec<Type>>,
+    pub(crate) span: Span,
+}
+
+#[derive(Debug)]
+pub(crate) enum ParsedInstructionKind {
+    Binary {
+        lhs: ParsedValue,
+        operator: BinaryOp,
+        rhs: ParsedValue,
+    },
+    Cast {
+        value: ParsedValue,
+        typ: Type,
+    },
+    Not {
+        value: ParsedValue,
+    },
+    Truncate {
+        value: ParsedValue,
+        bit_size: u32,
+        max_bit_size: u32,
+    },
+    Constrain {
+        lhs: ParsedValue,
+        rhs: ParsedValue,
+        assert_message: Option<AssertMessage>,
+    },
+    RangeCheck {
+        value: ParsedValue,
+        max_bit_size: u32,
+        assert_message: Option<String>,
+    },
+    Call {
+        function: ParsedValue,
+        arguments: Vec<ParsedValue>,
+    },
+    Allocate,
+    Load {
+        address: ParsedValue,
+    },
+    Store {
+        address: ParsedValue,
+        value: ParsedValue,
+    },
+    EnableSideEffects {
+        condition: ParsedValue,
+    },
+    ArrayGet {
+        array: ParsedValue,
+        index: ParsedValue,
+    },
+    ArraySet {
+        array: ParsedValue,
+        index: ParsedValue,
+        value: ParsedValue,
+        mutable: bool,
+    },
+    IncrementRc {
+        value: ParsedValue,
+    },
+    DecrementRc {
+        value: ParsedValue,
+    },
+    IfElse {
+        then_condition: ParsedValue,
+        then_value: ParsedValue,
+        else_condition: ParsedValue,
+        else_value: ParsedValue,
+    },
+}
+
+#[derive(Debug)]
+pub(crate) enum AssertMessage {
+    /// A message hardcoded by the compiler, printed as `'"message"'`
+    Intrinsic(String),
+    /// A constant string given by the user, printed as `'message'`
+    Static(String),
+    /// Any other error payload, printed as `, data <selector>: <values>`
+    Dynamic(u64, Vec<ParsedValue>),
+}
+
+#[derive(Debug)]
+pub(crate) enum ParsedTerminator {
+    Jmp { destination: Identifier, arguments: Vec<ParsedValue> },
+    Jmpif { condition: ParsedValue, then_block: Identifier, else_block: Identifier },
+    Return(Vec<ParsedValue>),
+}
+
+#[derive(Debug)]
+pub(crate) enum ParsedValue {
+    NumericConstant {
+        constant: FieldElement,
+        typ: Type,
+    },
+    Array {
+        values: Vec<ParsedValue>,
+        typ: Type,
+    },
+    Variable(Identifier),
+    Function(Identifier),
+    /// An intrinsic or a foreign function, referred to by name
+    Builtin(Identifier),
+}
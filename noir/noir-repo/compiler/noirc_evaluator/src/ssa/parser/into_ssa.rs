@@ -0,0 +1,325 @@
+use std::{collections::HashMap, rc::Rc};
+
+use acvm::acir::circuit::{ErrorSelector, STRING_ERROR_SELECTOR};
+
+use crate::ssa::{
+    function_builder::FunctionBuilder,
+    ir::{
+        basic_block::BasicBlockId,
+        function::{Function, FunctionId, RuntimeType},
+        instruction::{ConstrainError, Instruction, Intrinsic},
+        map::AtomicCounter,
+        types::Type,
+        value::ValueId,
+    },
+    ssa_gen::Ssa,
+};
+
+use super::{
+    ast::{
+        AssertMessage, Identifier, ParsedBlock, ParsedFunction, ParsedInstruction,
+        ParsedInstructionKind, ParsedSsa, ParsedTerminator, ParsedValue,
+    },
+    SsaError,
+};
+
+impl ParsedSsa {
+    pub(crate) fn into_ssa(self) -> Result<Ssa, SsaError> {
+        Translator::translate(self)
+    }
+}
+
+struct Translator {
+    builder: FunctionBuilder,
+
+    /// Maps the internal names of functions (e.g. `f1`) to their ids
+    functions: HashMap<String, FunctionId>,
+
+    /// Maps block names to their ids, for the function being translated
+    blocks: HashMap<String, BasicBlockId>,
+
+    /// Maps variable names to their ids, for the function being translated
+    variables: HashMap<String, ValueId>,
+}
+
+impl Translator {
+    fn translate(parsed_ssa: ParsedSsa) -> Result<Ssa, SsaError> {
+        let mut functions = parsed_ssa.functions.into_iter();
+        let main_function = functions.next().expect("Expected at least one SSA function");
+
+        // Functions are given ids in the order they are defined, with main first.
+        // All ids are assigned upfront so that calls can refer to functions defined later on.
+        let id_counter = AtomicCounter::<Function>::default();
+        let mut function_ids = HashMap::new();
+        for function in std::iter::once(&main_function).chain(functions.as_slice()) {
+            let name = &function.internal_name;
+            if function_ids.insert(name.name.clone(), id_counter.next()).is_some() {
+                return Err(SsaError::FunctionAlreadyDefined(name.clone()));
+            }
+        }
+
+        let main_id = function_ids[&main_function.internal_name.name];
+        let mut builder = FunctionBuilder::new(main_function.external_name.clone(), main_id);
+        builder.set_runtime(main_function.runtime_type);
+        builder.set_simplify(false);
+
+        let mut translator = Self {
+            builder,
+            functions: function_ids,
+            blocks: HashMap::new(),
+            variables: HashMap::new(),
+        };
+
+        translator.translate_function_body(main_function)?;
+        for function in functions {
+            translator.translate_function(function)?;
+        }
+
+        Ok(translator.builder.finish())
+    }
+
+    fn translate_function(&mut self, function: ParsedFunction) -> Result<(), SsaError> {
+        let function_id = self.functions[&function.internal_name.name];
+        let external_name = function.external_name.clone();
+
+        match function.runtime_type {
+            RuntimeType::Acir(inline_type) => {
+                self.builder.new_function(external_name, function_id, inline_type);
+            }
+            RuntimeType::Brillig => self.builder.new_brillig_function(external_name, function_id),
+        }
+
+        self.translate_function_body(function)
+    }
+
+    fn translate_function_body(&mut self, function: ParsedFunction) -> Result<(), SsaError> {
+        self.blocks.clear();
+        self.variables.clear();
+
+        // The first block is the entry block, which the builder has already created
+        let entry_block = self.builder.current_block();
+        for (index, block) in function.blocks.iter().enumerate() {
+            let block_id = if index == 0 { entry_block } else { self.builder.insert_block() };
+            if self.blocks.insert(block.name.name.clone(), block_id).is_some() {
+                return Err(SsaError::BlockAlreadyDefined(block.name.clone()));
+            }
+        }
+
+        for block in function.blocks {
+            self.translate_block(block)?;
+        }
+        Ok(())
+    }
+
+    fn translate_block(&mut self, block: ParsedBlock) -> Result<(), SsaError> {
+        let block_id = self.blocks[&block.name.name];
+        self.builder.switch_to_block(block_id);
+
+        for parameter in block.parameters {
+            let value_id = self.builder.add_block_parameter(block_id, parameter.typ);
+            self.define_variable(parameter.identifier, value_id)?;
+        }
+
+        for instruction in block.instructions {
+            self.translate_instruction(instruction)?;
+        }
+
+        match block.terminator {
+            ParsedTerminator::Jmp { destination, arguments } => {
+                let destination = self.lookup_block(&destination)?;
+                let arguments = self.translate_values(arguments)?;
+                self.builder.terminate_with_jmp(destination, arguments);
+            }
+            ParsedTerminator::Jmpif { condition, then_block, else_block } => {
+                let condition = self.translate_value(condition)?;
+                let then_destination = self.lookup_block(&then_block)?;
+                let else_destination = self.lookup_block(&else_block)?;
+                self.builder.terminate_with_jmpif(condition, then_destination, else_destination);
+            }
+            ParsedTerminator::Return(values) => {
+                let return_values = self.translate_values(values)?;
+                self.builder.terminate_with_return(return_values);
+            }
+        }
+        Ok(())
+    }
+
+    fn translate_instruction(&mut self, parsed: ParsedInstruction) -> Result<(), SsaError> {
+        let instruction = match parsed.kind {
+            ParsedInstructionKind::Binary { lhs, operator, rhs } => {
+                let lhs = self.translate_value(lhs)?;
+                let rhs = self.translate_value(rhs)?;
+                Instruction::binary(operator, lhs, rhs)
+            }
+            ParsedInstructionKind::Cast { value, typ } => {
+                Instruction::Cast(self.translate_value(value)?, typ)
+            }
+            ParsedInstructionKind::Not { value } => Instruction::Not(self.translate_value(value)?),
+            ParsedInstructionKind::Truncate { value, bit_size, max_bit_size } => {
+                let value = self.translate_value(value)?;
+                Instruction::Truncate { value, bit_size, max_bit_size }
+            }
+            ParsedInstructionKind::Constrain { lhs, rhs, assert_message } => {
+                let lhs = self.translate_value(lhs)?;
+                let rhs = self.translate_value(rhs)?;
+                let error = match assert_message {
+                    Some(message) => Some(self.translate_assert_message(message)?),
+                    None => None,
+                };
+                Instruction::Constrain(lhs, rhs, error)
+            }
+            ParsedInstructionKind::RangeCheck { value, max_bit_size, assert_message } => {
+                let value = self.translate_value(value)?;
+                Instruction::RangeCheck { value, max_bit_size, assert_message }
+            }
+            ParsedInstructionKind::Call { function, arguments } => {
+                let func = self.translate_value(function)?;
+                let arguments = self.translate_values(arguments)?;
+                Instruction::Call { func, arguments }
+            }
+            ParsedInstructionKind::Allocate => Instruction::Allocate,
+            ParsedInstructionKind::Load { address } => {
+                Instruction::Load { address: self.translate_value(address)? }
+            }
+            ParsedInstructionKind::Store { address, value } => {
+                let value = self.translate_value(value)?;
+                let address = self.translate_value(address)?;
+                Instruction::Store { address, value }
+            }
+            ParsedInstructionKind::EnableSideEffects { condition } => {
+                Instruction::EnableSideEffects { condition: self.translate_value(condition)? }
+            }
+            ParsedInstructionKind::ArrayGet { array, index } => {
+                let array = self.translate_value(array)?;
+                let index = self.translate_value(index)?;
+                Instruction::ArrayGet { array, index }
+            }
+            ParsedInstructionKind::ArraySet { array, index, value, mutable } => {
+                let array = self.translate_value(array)?;
+                let index = self.translate_value(index)?;
+                let value = self.translate_value(value)?;
+                Instruction::ArraySet { array, index, value, mutable }
+            }
+            ParsedInstructionKind::IncrementRc { value } => {
+                Instruction::IncrementRc { value: self.translate_value(value)? }
+            }
+            ParsedInstructionKind::DecrementRc { value } => {
+                Instruction::DecrementRc { value: self.translate_value(value)? }
+            }
+            ParsedInstructionKind::IfElse {
+                then_condition,
+                then_value,
+                else_condition,
+                else_value,
+            } => Instruction::IfElse {
+                then_condition: self.translate_value(then_condition)?,
+                then_value: self.translate_value(then_value)?,
+                else_condition: self.translate_value(else_condition)?,
+                else_value: self.translate_value(else_value)?,
+            },
+        };
+
+        let ctrl_typevars = if instruction.requires_ctrl_typevars() {
+            match parsed.result_types {
+                Some(types) => Some(types),
+                // A call without results doesn't need to list any result types
+                None if parsed.targets.is_empty()
+                    && matches!(instruction, Instruction::Call { .. }) =>
+                {
+                    Some(Vec::new())
+                }
+                None => return Err(SsaError::MissingResultTypes(parsed.span)),
+            }
+        } else {
+            None
+        };
+
+        let results =
+            self.builder.insert_instruction(instruction, ctrl_typevars).results().to_vec();
+        if results.len() != parsed.targets.len() {
+            return Err(SsaError::MismatchedResults {
+                expected: results.len(),
+                found: parsed.targets.len(),
+                span: parsed.span,
+            });
+        }
+
+        for (target, result) in parsed.targets.into_iter().zip(results) {
+            self.define_variable(target, result)?;
+        }
+        Ok(())
+    }
+
+    fn translate_assert_message(
+        &mut self,
+        message: AssertMessage,
+    ) -> Result<ConstrainError, SsaError> {
+        match message {
+            AssertMessage::Intrinsic(message) => Ok(ConstrainError::Intrinsic(message)),
+            AssertMessage::Static(message) => {
+                // Constant string messages are passed as an array of bytes, as in SSA generation
+                let bytes = message
+                    .bytes()
+                    .map(|byte| self.builder.numeric_constant(byte as u128, Type::unsigned(8)))
+                    .collect();
+                let typ = Type::Array(Rc::new(vec![Type::unsigned(8)]), message.len());
+                let payload = self.builder.array_constant(bytes, typ);
+                Ok(ConstrainError::UserDefined(STRING_ERROR_SELECTOR, vec![payload]))
+            }
+            AssertMessage::Dynamic(selector, values) => {
+                let values = self.translate_values(values)?;
+                Ok(ConstrainError::UserDefined(ErrorSelector::new(selector), values))
+            }
+        }
+    }
+
+    fn translate_values(&mut self, values: Vec<ParsedValue>) -> Result<Vec<ValueId>, SsaError> {
+        values.into_iter().map(|value| self.translate_value(value)).collect()
+    }
+
+    fn translate_value(&mut self, value: ParsedValue) -> Result<ValueId, SsaError> {
+        match value {
+            ParsedValue::NumericConstant { constant, typ } => {
+                Ok(self.builder.numeric_constant(constant, typ))
+            }
+            ParsedValue::Array { values, typ } => {
+                let values = self.translate_values(values)?;
+                Ok(self.builder.array_constant(values.into(), typ))
+            }
+            ParsedValue::Variable(identifier) => self
+                .variables
+                .get(&identifier.name)
+                .copied()
+                .ok_or(SsaError::UnknownVariable(identifier)),
+            ParsedValue::Function(identifier) => {
+                let Some(function_id) = self.functions.get(&identifier.name) else {
+                    return Err(SsaError::UnknownFunction(identifier));
+                };
+                Ok(self.builder.import_function(*function_id))
+            }
+            ParsedValue::Builtin(identifier) => match Intrinsic::lookup(&identifier.name) {
+                Some(intrinsic) => Ok(self.builder.import_intrinsic_id(intrinsic)),
+                None => Ok(self.builder.import_foreign_function(&identifier.name)),
+            },
+        }
+    }
+
+    fn define_variable(
+        &mut self,
+        identifier: Identifier,
+        value_id: ValueId,
+    ) -> Result<(), SsaError> {
+        if self.variables.contains_key(&identifier.name) {
+            return Err(SsaError::VariableAlreadyDefined(identifier));
+        }
+        self.variables.insert(identifier.name, value_id);
+        Ok(())
+    }
+
+    fn lookup_block(&self, identifier: &Identifier) -> Result<BasicBlockId, SsaError> {
+        self.blocks
+            .get(&identifier.name)
+            .copied()
+            .ok_or_else(|| SsaError::UnknownBlock(identifier.clone()))
+    }
+}
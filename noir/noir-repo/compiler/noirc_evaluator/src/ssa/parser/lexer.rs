@@ -0,0 +1,161 @@
+use std::str::CharIndices;
+
+use acvm::FieldElement;
+use noirc_errors::{Position, Span};
+use thiserror::Error;
+
+use super::token::{SpannedToken, Token};
+
+pub(crate) struct Lexer<'a> {
+    source: &'a str,
+    chars: CharIndices<'a>,
+    position: Position,
+}
+
+pub(crate) type SpannedTokenResult = Result<SpannedToken, LexerError>;
+
+impl<'a> Lexer<'a> {
+    pub(crate) fn new(source: &'a str) -> Self {
+        Lexer { source, chars: source.char_indices(), position: 0 }
+    }
+
+    pub(crate) fn next_token(&mut self) -> SpannedTokenResult {
+        match self.next_char() {
+            Some(char) if char.is_ascii_whitespace() => {
+                while let Some(char) = self.peek_char() {
+                    if char.is_ascii_whitespace() {
+                        self.next_char();
+                    } else {
+                        break;
+                    }
+                }
+                self.next_token()
+            }
+            Some('/') if self.peek_char() == Some('/') => {
+                while let Some(char) = self.next_char() {
+                    if char == '\n' {
+                        break;
+                    }
+                }
+                self.next_token()
+            }
+            Some('(') => self.single_char_token(Token::LeftParen),
+            Some(')') => self.single_char_token(Token::RightParen),
+            Some('[') => self.single_char_token(Token::LeftBracket),
+            Some(']') => self.single_char_token(Token::RightBracket),
+            Some('{') => self.single_char_token(Token::LeftBrace),
+            Some('}') => self.single_char_token(Token::RightBrace),
+            Some(',') => self.single_char_token(Token::Comma),
+            Some(':') => self.single_char_token(Token::Colon),
+            Some(';') => self.single_char_token(Token::Semicolon),
+            Some('&') => self.single_char_token(Token::Ampersand),
+            Some('=') if self.peek_char() == Some('=') => self.double_char_token(Token::Equal),
+            Some('=') => self.single_char_token(Token::Assign),
+            Some('-') if self.peek_char() == Some('>') => self.double_char_token(Token::Arrow),
+            Some('-') if self.peek_char().is_some_and(|char| char.is_ascii_digit()) => {
+                let start = self.position;
+                self.next_char();
+                self.eat_digits(start, true)
+            }
+            Some('\'') => self.eat_string(),
+            Some(char) if char.is_ascii_alphabetic() || char == '_' => self.eat_ident(char),
+            Some(char) if char.is_ascii_digit() => self.eat_digits(self.position, false),
+            Some(char) => Err(LexerError::UnexpectedCharacter {
+                char,
+                span: Span::single_char(self.position),
+            }),
+            None => Ok(Token::Eof.into_single_span(self.position)),
+        }
+    }
+
+    fn eat_ident(&mut self, initial_char: char) -> SpannedTokenResult {
+        let start = self.position;
+        let mut word = String::from(initial_char);
+        while let Some(char) = self.peek_char() {
+            if char.is_ascii_alphanumeric() || char == '_' {
+                word.push(char);
+                self.next_char();
+            } else {
+                break;
+            }
+        }
+        Ok(Token::Ident(word).into_span(start, self.position))
+    }
+
+    /// Lexes an integer literal, which is either decimal or hexadecimal and may be negative.
+    fn eat_digits(&mut self, start: Position, negative: bool) -> SpannedTokenResult {
+        let digits_start = self.position as usize;
+        while let Some(char) = self.peek_char() {
+            if char.is_ascii_alphanumeric() {
+                self.next_char();
+            } else {
+                break;
+            }
+        }
+        let digits = &self.source[digits_start..=self.position as usize];
+        let span = Span::inclusive(start, self.position);
+        let Some(value) = FieldElement::try_from_str(digits) else {
+            return Err(LexerError::InvalidIntegerLiteral { span, found: digits.to_string() });
+        };
+        let value = if negative { -value } else { value };
+        Ok(SpannedToken::new(Token::Int(value), span))
+    }
+
+    /// Lexes a single-quoted string. Strings may contain single quotes themselves
+    /// (the printer doesn't escape them) so the string ends at the last quote on the line.
+    fn eat_string(&mut self) -> SpannedTokenResult {
+        let start = self.position;
+        let contents_start = start as usize + 1;
+        let line_end = self.source[contents_start..]
+            .find('\n')
+            .map_or(self.source.len(), |index| contents_start + index);
+        let Some(length) = self.source[contents_start..line_end].rfind('\'') else {
+            return Err(LexerError::UnterminatedString { span: Span::single_char(start) });
+        };
+        let contents = self.source[contents_start..contents_start + length].to_string();
+        while self.position < (contents_start + length) as Position {
+            self.next_char();
+        }
+        Ok(Token::Str(contents).into_span(start, self.position))
+    }
+
+    fn single_char_token(&self, token: Token) -> SpannedTokenResult {
+        Ok(token.into_single_span(self.position))
+    }
+
+    fn double_char_token(&mut self, token: Token) -> SpannedTokenResult {
+        let start_position = self.position;
+        self.next_char();
+        Ok(token.into_span(start_position, self.position))
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let (position, char) = self.chars.next()?;
+        self.position = position as Position;
+        Some(char)
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        self.chars.clone().next().map(|(_, char)| char)
+    }
+}
+
+#[derive(Debug, Error)]
+pub(crate) enum LexerError {
+    #[error("Unexpected character: {char:?}")]
+    UnexpectedCharacter { char: char, span: Span },
+    #[error("Invalid integer literal: {found:?}")]
+    InvalidIntegerLiteral { span: Span, found: String },
+    #[error("Unterminated string")]
+    UnterminatedString { span: Span },
+}
+
+impl LexerError {
+    pub(crate) fn span(&self) -> Span {
+        match self {
+            LexerError::UnexpectedCharacter { span, .. }
+            | LexerError::InvalidIntegerLiteral { span, .. }
+            | LexerError::UnterminatedString { span } => *span,
+        }
+    }
+}
@@ -0,0 +1,669 @@
+//! A parser for the textual SSA format printed by [`crate::ssa::ir::printer`],
+//! so that SSA can be written by hand (for example to test optimization passes)
+//! or reproduced from the output of `--show-ssa`.
+//!
+//! Parsing is done in two steps: the source is first parsed into a [`ParsedSsa`],
+//! which is then translated into an [`Ssa`] with a [`FunctionBuilder`][crate::ssa::function_builder::FunctionBuilder].
+//! Value ids are assigned in order of definition, so the printed ids of a parsed program
+//! may differ from the ones in the source, as numeric constants are given ids too.
+use std::{
+    fmt::{self, Debug, Formatter},
+    rc::Rc,
+};
+
+use acvm::{AcirField, FieldElement};
+use noirc_errors::Span;
+use noirc_frontend::monomorphization::ast::InlineType;
+use thiserror::Error;
+
+use self::{
+    ast::{
+        AssertMessage, Identifier, ParsedBlock, ParsedFunction, ParsedInstruction,
+        ParsedInstructionKind, ParsedParameter, ParsedSsa, ParsedTerminator, ParsedValue,
+    },
+    lexer::{Lexer, LexerError},
+    token::{SpannedToken, Token},
+};
+
+use super::{
+    ir::{
+        function::RuntimeType,
+        instruction::BinaryOp,
+        types::{NumericType, Type},
+    },
+    ssa_gen::Ssa,
+};
+
+mod ast;
+mod into_ssa;
+mod lexer;
+mod token;
+
+#[cfg(test)]
+mod tests;
+
+impl Ssa {
+    /// Creates an Ssa object from its textual representation, as printed by `--show-ssa`.
+    pub(crate) fn from_str(src: &str) -> Result<Ssa, SsaErrorWithSource> {
+        let mut parser =
+            Parser::new(src).map_err(|err| SsaErrorWithSource::new(src, err.into()))?;
+        let parsed_ssa =
+            parser.parse_ssa().map_err(|err| SsaErrorWithSource::new(src, err.into()))?;
+        parsed_ssa.into_ssa().map_err(|error| SsaErrorWithSource::new(src, error))
+    }
+}
+
+/// An [`SsaError`] together with the source it was found in, which is
+/// shown alongside the error when debug-printed.
+pub(crate) struct SsaErrorWithSource {
+    src: String,
+    error: SsaError,
+}
+
+impl SsaErrorWithSource {
+    fn new(src: &str, error: SsaError) -> Self {
+        Self { src: src.to_string(), error }
+    }
+}
+
+impl Debug for SsaErrorWithSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let start = (self.error.span().start() as usize).min(self.src.len());
+        let line_start = self.src[..start].rfind('\n').map_or(0, |index| index + 1);
+        let line_end = self.src[start..].find('\n').map_or(self.src.len(), |index| start + index);
+        let line_number = self.src[..start].matches('\n').count() + 1;
+
+        writeln!(f, "{}", self.error)?;
+        writeln!(f, "{line_number}: {}", &self.src[line_start..line_end])?;
+        let column = start - line_start + line_number.to_string().len() + 2;
+        write!(f, "{}^", " ".repeat(column))
+    }
+}
+
+#[derive(Debug, Error)]
+pub(crate) enum SsaError {
+    #[error("{0}")]
+    ParserError(#[from] ParserError),
+    #[error("Unknown variable '{0}'")]
+    UnknownVariable(Identifier),
+    #[error("Unknown block '{0}'")]
+    UnknownBlock(Identifier),
+    #[error("Unknown function '{0}'")]
+    UnknownFunction(Identifier),
+    #[error("Variable '{0}' is already defined")]
+    VariableAlreadyDefined(Identifier),
+    #[error("Block '{0}' is already defined")]
+    BlockAlreadyDefined(Identifier),
+    #[error("Function '{0}' is already defined")]
+    FunctionAlreadyDefined(Identifier),
+    #[error("Expected {expected} result(s) but found {found}")]
+    MismatchedResults { expected: usize, found: usize, span: Span },
+    #[error("The result types of this instruction must be given with `->`")]
+    MissingResultTypes(Span),
+}
+
+impl SsaError {
+    pub(crate) fn span(&self) -> Span {
+        match self {
+            SsaError::ParserError(parser_error) => parser_error.span(),
+            SsaError::UnknownVariable(identifier)
+            | SsaError::UnknownBlock(identifier)
+            | SsaError::UnknownFunction(identifier)
+            | SsaError::VariableAlreadyDefined(identifier)
+            | SsaError::BlockAlreadyDefined(identifier)
+            | SsaError::FunctionAlreadyDefined(identifier) => identifier.span,
+            SsaError::MismatchedResults { span, .. } | SsaError::MissingResultTypes(span) => *span,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub(crate) enum ParserError {
+    #[error("{0}")]
+    LexerError(#[from] LexerError),
+    #[error("Expected '{token}', found '{found}'")]
+    ExpectedToken { token: Token, found: Token, span: Span },
+    #[error("Expected {expected}, found '{found}'")]
+    Expected { expected: &'static str, found: Token, span: Span },
+    #[error("Integer {value} is too large")]
+    IntegerTooLarge { value: FieldElement, span: Span },
+}
+
+impl ParserError {
+    fn span(&self) -> Span {
+        match self {
+            ParserError::LexerError(lexer_error) => lexer_error.span(),
+            ParserError::ExpectedToken { span, .. }
+            | ParserError::Expected { span, .. }
+            | ParserError::IntegerTooLarge { span, .. } => *span,
+        }
+    }
+}
+
+type ParseResult<T> = Result<T, ParserError>;
+
+/// Instructions which never have results, and so don't start with `vN = `
+const INSTRUCTIONS_WITHOUT_RESULTS: [&str; 6] =
+    ["constrain", "store", "enable_side_effects", "inc_rc", "dec_rc", "range_check"];
+
+pub(crate) struct Parser<'a> {
+    lexer: Lexer<'a>,
+    token: SpannedToken,
+}
+
+impl<'a> Parser<'a> {
+    pub(crate) fn new(source: &'a str) -> ParseResult<Self> {
+        let mut lexer = Lexer::new(source);
+        let token = lexer.next_token()?;
+        Ok(Self { lexer, token })
+    }
+
+    pub(crate) fn parse_ssa(&mut self) -> ParseResult<ParsedSsa> {
+        let mut functions = Vec::new();
+        while !self.at(Token::Eof) {
+            functions.push(self.parse_function()?);
+        }
+        if functions.is_empty() {
+            return self.expected("a function");
+        }
+        Ok(ParsedSsa { functions })
+    }
+
+    fn parse_function(&mut self) -> ParseResult<ParsedFunction> {
+        let runtime_type = self.parse_runtime_type()?;
+        self.eat_keyword_or_error("fn")?;
+        let external_name = self.eat_ident_or_error()?.name;
+        let internal_name = self.eat_ident_or_error()?;
+        self.eat_or_error(Token::LeftBrace)?;
+
+        let mut blocks = vec![self.parse_block()?];
+        while !self.eat(Token::RightBrace)? {
+            blocks.push(self.parse_block()?);
+        }
+
+        Ok(ParsedFunction { runtime_type, external_name, internal_name, blocks })
+    }
+
+    fn parse_runtime_type(&mut self) -> ParseResult<RuntimeType> {
+        if self.eat_keyword("acir")? {
+            self.eat_or_error(Token::LeftParen)?;
+            let inline_type = if self.eat_keyword("inline")? {
+                InlineType::Inline
+            } else if self.eat_keyword("fold")? {
+                InlineType::Fold
+            } else if self.eat_keyword("no_predicates")? {
+                InlineType::NoPredicates
+            } else {
+                return self.expected("an inline type");
+            };
+            self.eat_or_error(Token::RightParen)?;
+            Ok(RuntimeType::Acir(inline_type))
+        } else if self.eat_keyword("brillig")? {
+            Ok(RuntimeType::Brillig)
+        } else {
+            self.expected("a runtime type")
+        }
+    }
+
+    fn parse_block(&mut self) -> ParseResult<ParsedBlock> {
+        let name = self.eat_ident_or_error()?;
+        self.eat_or_error(Token::LeftParen)?;
+
+        let mut parameters = Vec::new();
+        if !self.eat(Token::RightParen)? {
+            loop {
+                let identifier = self.eat_ident_or_error()?;
+                self.eat_or_error(Token::Colon)?;
+                let typ = self.parse_type()?;
+                parameters.push(ParsedParameter { identifier, typ });
+                if !self.eat(Token::Comma)? {
+                    break;
+                }
+            }
+            self.eat_or_error(Token::RightParen)?;
+        }
+        self.eat_or_error(Token::Colon)?;
+
+        let mut instructions = Vec::new();
+        while let Some(instruction) = self.parse_instruction()? {
+            instructions.push(instruction);
+        }
+        let terminator = self.parse_terminator()?;
+
+        Ok(ParsedBlock { name, parameters, instructions, terminator })
+    }
+
+    fn parse_instruction(&mut self) -> ParseResult<Option<ParsedInstruction>> {
+        if self.at_keyword("jmp") || self.at_keyword("jmpif") || self.at_keyword("return") {
+            return Ok(None);
+        }
+
+        let span = self.token.to_span();
+        let has_results = !self.at_keyword("call")
+            && !INSTRUCTIONS_WITHOUT_RESULTS.iter().any(|keyword| self.at_keyword(keyword));
+        let mut targets = Vec::new();
+        if has_results {
+            targets.push(self.eat_ident_or_error()?);
+            while self.eat(Token::Comma)? {
+                targets.push(self.eat_ident_or_error()?);
+            }
+            self.eat_or_error(Token::Assign)?;
+        }
+
+        let kind = self.parse_instruction_kind()?;
+
+        let result_types = if self.eat(Token::Arrow)? {
+            let mut types = vec![self.parse_type()?];
+            while self.eat(Token::Comma)? {
+                types.push(self.parse_type()?);
+            }
+            Some(types)
+        } else {
+            None
+        };
+
+        Ok(Some(ParsedInstruction { targets, kind, result_types, span }))
+    }
+
+    fn parse_instruction_kind(&mut self) -> ParseResult<ParsedInstructionKind> {
+        let Some(keyword) = self.eat_ident()? else {
+            return self.expected("an instruction");
+        };
+
+        let kind = match keyword.name.as_str() {
+            "cast" => {
+                let value = self.parse_value()?;
+                self.eat_keyword_or_error("as")?;
+                let typ = self.parse_type()?;
+                ParsedInstructionKind::Cast { value, typ }
+            }
+            "not" => ParsedInstructionKind::Not { value: self.parse_value()? },
+            "truncate" => {
+                let value = self.parse_value()?;
+                self.eat_keyword_or_error("to")?;
+                let bit_size = self.eat_u32_or_error()?;
+                self.eat_keyword_or_error("bits")?;
+                self.eat_or_error(Token::Comma)?;
+                self.eat_keyword_or_error("max_bit_size")?;
+                self.eat_or_error(Token::Colon)?;
+                let max_bit_size = self.eat_u32_or_error()?;
+                ParsedInstructionKind::Truncate { value, bit_size, max_bit_size }
+            }
+            "constrain" => {
+                let lhs = self.parse_value()?;
+                self.eat_or_error(Token::Equal)?;
+                let rhs = self.parse_value()?;
+                let assert_message = if let Some(message) = self.eat_str()? {
+                    match unquote(&message) {
+                        Some(message) => Some(AssertMessage::Intrinsic(message)),
+                        None => Some(AssertMessage::Static(message)),
+                    }
+                } else if self.eat(Token::Comma)? {
+                    self.eat_keyword_or_error("data")?;
+                    let span = self.token.to_span();
+                    let selector = self.eat_int_or_error()?;
+                    let Some(selector) = selector.try_to_u64() else {
+                        return Err(ParserError::IntegerTooLarge { value: selector, span });
+                    };
+                    self.eat_or_error(Token::Colon)?;
+                    Some(AssertMessage::Dynamic(selector, self.parse_values()?))
+                } else {
+                    None
+                };
+                ParsedInstructionKind::Constrain { lhs, rhs, assert_message }
+            }
+            "range_check" => {
+                let value = self.parse_value()?;
+                self.eat_keyword_or_error("to")?;
+                let max_bit_size = self.eat_u32_or_error()?;
+                self.eat_keyword_or_error("bits")?;
+                let assert_message =
+                    self.eat_str()?.map(|message| unquote(&message).unwrap_or(message));
+                ParsedInstructionKind::RangeCheck { value, max_bit_size, assert_message }
+            }
+            "call" => {
+                let function = self.parse_value()?;
+                self.eat_or_error(Token::LeftParen)?;
+                let arguments =
+                    if self.at(Token::RightParen) { Vec::new() } else { self.parse_values()? };
+                self.eat_or_error(Token::RightParen)?;
+                ParsedInstructionKind::Call { function, arguments }
+            }
+            "allocate" => ParsedInstructionKind::Allocate,
+            "load" => ParsedInstructionKind::Load { address: self.parse_value()? },
+            "store" => {
+                let value = self.parse_value()?;
+                self.eat_keyword_or_error("at")?;
+                let address = self.parse_value()?;
+                ParsedInstructionKind::Store { address, value }
+            }
+            "enable_side_effects" => {
+                ParsedInstructionKind::EnableSideEffects { condition: self.parse_value()? }
+            }
+            "array_get" => {
+                let array = self.parse_value()?;
+                self.eat_or_error(Token::Comma)?;
+                self.eat_keyword_or_error("index")?;
+                let index = self.parse_value()?;
+                ParsedInstructionKind::ArrayGet { array, index }
+            }
+            "array_set" => {
+                let mutable = self.eat_keyword("mut")?;
+                let array = self.parse_value()?;
+                self.eat_or_error(Token::Comma)?;
+                self.eat_keyword_or_error("index")?;
+                let index = self.parse_value()?;
+                self.eat_or_error(Token::Comma)?;
+                self.eat_keyword_or_error("value")?;
+                let value = self.parse_value()?;
+                ParsedInstructionKind::ArraySet { array, index, value, mutable }
+            }
+            "inc_rc" => ParsedInstructionKind::IncrementRc { value: self.parse_value()? },
+            "dec_rc" => ParsedInstructionKind::DecrementRc { value: self.parse_value()? },
+            "if" => {
+                let then_condition = self.parse_value()?;
+                self.eat_keyword_or_error("then")?;
+                let then_value = self.parse_value()?;
+                self.eat_keyword_or_error("else")?;
+                self.eat_keyword_or_error("if")?;
+                let else_condition = self.parse_value()?;
+                self.eat_keyword_or_error("then")?;
+                let else_value = self.parse_value()?;
+                ParsedInstructionKind::IfElse {
+                    then_condition,
+                    then_value,
+                    else_condition,
+                    else_value,
+                }
+            }
+            other => {
+                let Some(operator) = binary_operator(other) else {
+                    return Err(ParserError::Expected {
+                        expected: "an instruction",
+                        found: Token::Ident(keyword.name),
+                        span: keyword.span,
+                    });
+                };
+                let lhs = self.parse_value()?;
+                self.eat_or_error(Token::Comma)?;
+                let rhs = self.parse_value()?;
+                ParsedInstructionKind::Binary { lhs, operator, rhs }
+            }
+        };
+        Ok(kind)
+    }
+
+    fn parse_terminator(&mut self) -> ParseResult<ParsedTerminator> {
+        if self.eat_keyword("jmp")? {
+            let destination = self.eat_ident_or_error()?;
+            self.eat_or_error(Token::LeftParen)?;
+            let arguments =
+                if self.at(Token::RightParen) { Vec::new() } else { self.parse_values()? };
+            self.eat_or_error(Token::RightParen)?;
+            Ok(ParsedTerminator::Jmp { destination, arguments })
+        } else if self.eat_keyword("jmpif")? {
+            let condition = self.parse_value()?;
+            self.eat_keyword_or_error("then")?;
+            self.eat_or_error(Token::Colon)?;
+            let then_block = self.eat_ident_or_error()?;
+            self.eat_or_error(Token::Comma)?;
+            self.eat_keyword_or_error("else")?;
+            self.eat_or_error(Token::Colon)?;
+            let else_block = self.eat_ident_or_error()?;
+            Ok(ParsedTerminator::Jmpif { condition, then_block, else_block })
+        } else if self.eat_keyword("return")? {
+            // A return without values is followed by the end of the function or the next block
+            let at_block_end = self.at(Token::RightBrace)
+                || matches!(self.token.token(), Token::Ident(name) if is_block_name(name));
+            let values = if at_block_end { Vec::new() } else { self.parse_values()? };
+            Ok(ParsedTerminator::Return(values))
+        } else {
+            self.expected("an instruction or terminator")
+        }
+    }
+
+    /// Parses a non-empty, comma-separated list of values
+    fn parse_values(&mut self) -> ParseResult<Vec<ParsedValue>> {
+        let mut values = vec![self.parse_value()?];
+        while self.eat(Token::Comma)? {
+            values.push(self.parse_value()?);
+        }
+        Ok(values)
+    }
+
+    fn parse_value(&mut self) -> ParseResult<ParsedValue> {
+        if let Some(typ) = self.eat_numeric_type()? {
+            let constant = self.eat_int_or_error()?;
+            return Ok(ParsedValue::NumericConstant { constant, typ });
+        }
+
+        if self.eat(Token::LeftBracket)? {
+            let values =
+                if self.at(Token::RightBracket) { Vec::new() } else { self.parse_values()? };
+            self.eat_or_error(Token::RightBracket)?;
+            self.eat_keyword_or_error("of")?;
+            let typ = self.parse_type()?;
+            return Ok(ParsedValue::Array { values, typ });
+        }
+
+        let Some(identifier) = self.eat_ident()? else {
+            return self.expected("a value");
+        };
+        if is_numbered_name(&identifier.name, 'v') {
+            Ok(ParsedValue::Variable(identifier))
+        } else if is_numbered_name(&identifier.name, 'f') {
+            Ok(ParsedValue::Function(identifier))
+        } else {
+            Ok(ParsedValue::Builtin(identifier))
+        }
+    }
+
+    fn parse_type(&mut self) -> ParseResult<Type> {
+        if let Some(typ) = self.eat_numeric_type()? {
+            return Ok(typ);
+        }
+
+        if self.eat_keyword("function")? {
+            return Ok(Type::Function);
+        }
+
+        if self.eat(Token::Ampersand)? {
+            self.eat_keyword_or_error("mut")?;
+            let typ = self.parse_type()?;
+            return Ok(Type::Reference(Rc::new(typ)));
+        }
+
+        if self.eat(Token::LeftBracket)? {
+            let mut element_types = vec![self.parse_type()?];
+            while self.eat(Token::Comma)? {
+                element_types.push(self.parse_type()?);
+            }
+            if self.eat(Token::Semicolon)? {
+                let length = self.eat_u32_or_error()?;
+                self.eat_or_error(Token::RightBracket)?;
+                return Ok(Type::Array(Rc::new(element_types), length as usize));
+            }
+            self.eat_or_error(Token::RightBracket)?;
+            return Ok(Type::Slice(Rc::new(element_types)));
+        }
+
+        self.expected("a type")
+    }
+
+    fn eat_numeric_type(&mut self) -> ParseResult<Option<Type>> {
+        let Token::Ident(name) = self.token.token() else {
+            return Ok(None);
+        };
+        let typ = if name == "Field" {
+            Type::Numeric(NumericType::NativeField)
+        } else if let Some(bit_size) = name.strip_prefix('u').and_then(|size| size.parse().ok()) {
+            Type::unsigned(bit_size)
+        } else if let Some(bit_size) = name.strip_prefix('i').and_then(|size| size.parse().ok()) {
+            Type::signed(bit_size)
+        } else {
+            return Ok(None);
+        };
+        self.bump()?;
+        Ok(Some(typ))
+    }
+
+    fn eat_ident(&mut self) -> ParseResult<Option<Identifier>> {
+        if !matches!(self.token.token(), Token::Ident(_)) {
+            return Ok(None);
+        }
+        let token = self.bump()?;
+        let span = token.to_span();
+        let Token::Ident(name) = token.into_token() else { unreachable!() };
+        Ok(Some(Identifier::new(name, span)))
+    }
+
+    fn eat_ident_or_error(&mut self) -> ParseResult<Identifier> {
+        match self.eat_ident()? {
+            Some(identifier) => Ok(identifier),
+            None => self.expected("an identifier"),
+        }
+    }
+
+    fn eat_int_or_error(&mut self) -> ParseResult<FieldElement> {
+        if let Token::Int(int) = self.token.token() {
+            let int = *int;
+            self.bump()?;
+            Ok(int)
+        } else {
+            self.expected("an integer")
+        }
+    }
+
+    fn eat_u32_or_error(&mut self) -> ParseResult<u32> {
+        let span = self.token.to_span();
+        let int = self.eat_int_or_error()?;
+        int.try_to_u32().ok_or(ParserError::IntegerTooLarge { value: int, span })
+    }
+
+    fn eat_str(&mut self) -> ParseResult<Option<String>> {
+        if !matches!(self.token.token(), Token::Str(_)) {
+            return Ok(None);
+        }
+        let Token::Str(string) = self.bump()?.into_token() else { unreachable!() };
+        Ok(Some(string))
+    }
+
+    fn at_keyword(&self, keyword: &str) -> bool {
+        matches!(self.token.token(), Token::Ident(name) if name == keyword)
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> ParseResult<bool> {
+        if self.at_keyword(keyword) {
+            self.bump()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn eat_keyword_or_error(&mut self, keyword: &str) -> ParseResult<()> {
+        if self.eat_keyword(keyword)? {
+            Ok(())
+        } else {
+            self.expected_token(Token::Ident(keyword.to_string()))
+        }
+    }
+
+    fn at(&self, token: Token) -> bool {
+        self.token.token() == &token
+    }
+
+    fn eat(&mut self, token: Token) -> ParseResult<bool> {
+        if self.at(token) {
+            self.bump()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn eat_or_error(&mut self, token: Token) -> ParseResult<()> {
+        if self.eat(token.clone())? {
+            Ok(())
+        } else {
+            self.expected_token(token)
+        }
+    }
+
+    fn bump(&mut self) -> ParseResult<SpannedToken> {
+        let token = self.lexer.next_token()?;
+        Ok(std::mem::replace(&mut self.token, token))
+    }
+
+    fn expected<T>(&self, expected: &'static str) -> ParseResult<T> {
+        Err(ParserError::Expected {
+            expected,
+            found: self.token.token().clone(),
+            span: self.token.to_span(),
+        })
+    }
+
+    fn expected_token<T>(&self, token: Token) -> ParseResult<T> {
+        Err(ParserError::ExpectedToken {
+            token,
+            found: self.token.token().clone(),
+            span: self.token.to_span(),
+        })
+    }
+}
+
+fn binary_operator(name: &str) -> Option<BinaryOp> {
+    let operator = match name {
+        "add" => BinaryOp::Add,
+        "sub" => BinaryOp::Sub,
+        "mul" => BinaryOp::Mul,
+        "div" => BinaryOp::Div,
+        "eq" => BinaryOp::Eq,
+        "mod" => BinaryOp::Mod,
+        "lt" => BinaryOp::Lt,
+        "and" => BinaryOp::And,
+        "or" => BinaryOp::Or,
+        "xor" => BinaryOp::Xor,
+        "shl" => BinaryOp::Shl,
+        "shr" => BinaryOp::Shr,
+        _ => return None,
+    };
+    Some(operator)
+}
+
+/// Whether `name` is a prefix followed by a number, like `v12` or `f0`
+fn is_numbered_name(name: &str, prefix: char) -> bool {
+    name.strip_prefix(prefix).is_some_and(|number| {
+        !number.is_empty() && number.chars().all(|char| char.is_ascii_digit())
+    })
+}
+
+fn is_block_name(name: &str) -> bool {
+    is_numbered_name(name, 'b')
+}
+
+/// Messages of intrinsic errors are printed as a quoted, escaped string inside the
+/// single quotes. Returns the unescaped message if `message` is such a string.
+fn unquote(message: &str) -> Option<String> {
+    let inner = message.strip_prefix('"')?.strip_suffix('"')?;
+    let mut result = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            result.push(char);
+            continue;
+        }
+        match chars.next()? {
+            'n' => result.push('\n'),
+            'r' => result.push('\r'),
+            't' => result.push('\t'),
+            '0' => result.push('\0'),
+            'u' => {
+                let code: String = chars.by_ref().skip(1).take_while(|char| *char != '}').collect();
+                result.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+            }
+            other => result.push(other),
+        }
+    }
+    Some(result)
+}
@@ -0,0 +1,374 @@
+use crate::ssa::ssa_gen::Ssa;
+
+/// Parses the given SSA and checks that printing it gives back the source.
+/// The source must use the value ids the parser assigns, which means
+/// numeric constants take up an id when they are first used.
+fn assert_ssa_roundtrip(src: &str) {
+    let ssa = Ssa::from_str(src).unwrap();
+    assert_eq!(ssa.to_string().trim(), src.trim());
+}
+
+#[test]
+fn test_empty_acir_function() {
+    let src = "
+acir(inline) fn main f0 {
+  b0():
+    return
+}
+";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn test_empty_brillig_function() {
+    let src = "
+brillig fn main f0 {
+  b0():
+    return
+}
+";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn test_inline_types() {
+    for inline_type in ["inline", "fold", "no_predicates"] {
+        let src = format!(
+            "
+acir({inline_type}) fn main f0 {{
+  b0():
+    return
+}}
+"
+        );
+        assert_ssa_roundtrip(&src);
+    }
+}
+
+#[test]
+fn test_return_integer() {
+    for typ in ["u1", "u8", "u16", "u32", "u64", "i1", "i8", "i16", "i32", "i64", "Field"] {
+        let src = format!(
+            "
+acir(inline) fn main f0 {{
+  b0():
+    return {typ} 1
+}}
+"
+        );
+        assert_ssa_roundtrip(&src);
+    }
+}
+
+#[test]
+fn test_return_negative_and_large_integers() {
+    let src = "
+acir(inline) fn main f0 {
+  b0():
+    return Field -1, Field 340282366920938463463374607431768211456
+}
+";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn test_return_array() {
+    let src = "
+acir(inline) fn main f0 {
+  b0():
+    return [Field 1, u32 2] of [Field, u32; 1]
+}
+";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn test_block_parameters() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: Field, v1: [Field; 3], v2: [u8], v3: &mut u32, v4: function):
+    return v0, v1, v2, v3, v4
+}
+";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn test_jmp() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: Field):
+    jmp b1(v0)
+  b1(v1: Field):
+    return v1
+}
+";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn test_jmpif() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: u1):
+    jmpif v0 then: b1, else: b2
+  b1():
+    return
+  b2():
+    return
+}
+";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn test_call() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: Field):
+    v2 = call f1(v0) -> Field
+    return v2
+}
+acir(inline) fn foo f1 {
+  b0(v0: Field):
+    return v0
+}
+";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn test_call_multiple_return_values() {
+    let src = "
+acir(inline) fn main f0 {
+  b0():
+    v1, v2 = call f1() -> [Field; 3], [Field; 3]
+    return v1
+}
+acir(inline) fn foo f1 {
+  b0():
+    return [Field 1, Field 2, Field 3] of [Field; 3], [Field 1, Field 2, Field 3] of [Field; 3]
+}
+";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn test_call_intrinsic() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: [Field; 3]):
+    v2 = call array_len(v0) -> u32
+    return v2
+}
+";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn test_call_foreign_function_without_results() {
+    let src = "
+brillig fn main f0 {
+  b0(v0: Field):
+    call print(v0)
+    return
+}
+";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn test_binary_operators() {
+    for operator in
+        ["add", "sub", "mul", "div", "eq", "mod", "lt", "and", "or", "xor", "shl", "shr"]
+    {
+        let src = format!(
+            "
+acir(inline) fn main f0 {{
+  b0(v0: u32, v1: u32):
+    v2 = {operator} v0, v1
+    return v2
+}}
+"
+        );
+        assert_ssa_roundtrip(&src);
+    }
+}
+
+#[test]
+fn test_cast_not_and_truncate() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: Field):
+    v1 = cast v0 as u32
+    v2 = not v1
+    v3 = truncate v2 to 8 bits, max_bit_size: 32
+    return v3
+}
+";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn test_constrain() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: Field, v1: Field):
+    constrain v0 == v1
+    constrain v0 == Field 1 '\"attempt to add with overflow\"'
+    constrain v0 == Field 1 'Oh no!'
+    constrain v0 == v1, data 1234: v0, v1
+    return
+}
+";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn test_range_check() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: Field):
+    range_check v0 to 8 bits
+    range_check v0 to 16 bits '\"value does not fit\"'
+    return
+}
+";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn test_allocate_load_and_store() {
+    let src = "
+acir(inline) fn main f0 {
+  b0():
+    v0 = allocate -> &mut Field
+    store Field 1 at v0
+    v2 = load v0 -> Field
+    return v2
+}
+";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn test_enable_side_effects() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: u1):
+    enable_side_effects v0
+    return
+}
+";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn test_array_get_and_set() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: [Field; 3]):
+    v2 = array_get v0, index u32 0 -> Field
+    v5 = array_set v0, index u32 1, value Field 2
+    v6 = array_set mut v5, index u32 1, value Field 2
+    return v2, v6
+}
+";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn test_reference_counting() {
+    let src = "
+brillig fn main f0 {
+  b0(v0: [Field; 3]):
+    inc_rc v0
+    dec_rc v0
+    return
+}
+";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn test_if_else() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: u1, v1: Field, v2: u1, v3: Field):
+    v4 = if v0 then v1 else if v2 then v3
+    return v4
+}
+";
+    assert_ssa_roundtrip(src);
+}
+
+#[test]
+fn test_renumbers_values() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v3: Field):
+    v7 = add v3, Field 1
+    return v7
+}
+";
+    let expected = "
+acir(inline) fn main f0 {
+  b0(v0: Field):
+    v2 = add v0, Field 1
+    return v2
+}
+";
+    let ssa = Ssa::from_str(src).unwrap();
+    assert_eq!(ssa.to_string().trim(), expected.trim());
+}
+
+#[test]
+fn test_parses_comments() {
+    let src = "
+// The entry point
+acir(inline) fn main f0 {
+  b0():
+    return // Nothing to return
+}
+";
+    let ssa = Ssa::from_str(src).unwrap();
+    assert_eq!(ssa.main().name(), "main");
+}
+
+#[test]
+fn test_unknown_variable() {
+    let src = "
+acir(inline) fn main f0 {
+  b0():
+    return v0
+}
+";
+    let Err(error) = Ssa::from_str(src) else {
+        panic!("Expected an error for an unknown variable");
+    };
+    assert!(format!("{error:?}").starts_with("Unknown variable 'v0'"));
+}
+
+#[test]
+fn test_missing_result_types() {
+    let src = "
+acir(inline) fn main f0 {
+  b0():
+    v0 = allocate
+    return
+}
+";
+    assert!(Ssa::from_str(src).is_err());
+}
+
+#[test]
+fn test_mismatched_results() {
+    let src = "
+acir(inline) fn main f0 {
+  b0(v0: Field):
+    v1, v2 = add v0, v0
+    return
+}
+";
+    assert!(Ssa::from_str(src).is_err());
+}
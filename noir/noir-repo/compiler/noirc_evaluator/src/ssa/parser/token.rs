@@ -0,0 +1,98 @@
+use std::fmt::Display;
+
+use acvm::FieldElement;
+use noirc_errors::{Span, Spanned};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SpannedToken(Spanned<Token>);
+
+impl SpannedToken {
+    pub(crate) fn new(token: Token, span: Span) -> SpannedToken {
+        SpannedToken(Spanned::from(span, token))
+    }
+
+    pub(crate) fn to_span(&self) -> Span {
+        self.0.span()
+    }
+
+    pub(crate) fn token(&self) -> &Token {
+        &self.0.contents
+    }
+
+    pub(crate) fn into_token(self) -> Token {
+        self.0.contents
+    }
+}
+
+/// The tokens of the textual SSA format, as printed by [`crate::ssa::ir::printer`].
+///
+/// Keywords such as `fn`, `call` or `jmpif` are lexed as identifiers and only
+/// recognized by the parser, since most of them are also valid function names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Token {
+    Ident(String),
+    Int(FieldElement),
+    /// The contents of a single-quoted string, e.g. `'attempt to add with overflow'`
+    Str(String),
+    /// (
+    LeftParen,
+    /// )
+    RightParen,
+    /// [
+    LeftBracket,
+    /// ]
+    RightBracket,
+    /// {
+    LeftBrace,
+    /// }
+    RightBrace,
+    /// ,
+    Comma,
+    /// :
+    Colon,
+    /// ;
+    Semicolon,
+    /// =
+    Assign,
+    /// ==
+    Equal,
+    /// ->
+    Arrow,
+    /// &
+    Ampersand,
+    Eof,
+}
+
+impl Token {
+    pub(crate) fn into_single_span(self, position: u32) -> SpannedToken {
+        self.into_span(position, position)
+    }
+
+    pub(crate) fn into_span(self, start: u32, end: u32) -> SpannedToken {
+        SpannedToken(Spanned::from_position(start, end, self))
+    }
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Ident(ident) => write!(f, "{ident}"),
+            Token::Int(int) => write!(f, "{int}"),
+            Token::Str(string) => write!(f, "'{string}'"),
+            Token::LeftParen => write!(f, "("),
+            Token::RightParen => write!(f, ")"),
+            Token::LeftBracket => write!(f, "["),
+            Token::RightBracket => write!(f, "]"),
+            Token::LeftBrace => write!(f, "{{"),
+            Token::RightBrace => write!(f, "}}"),
+            Token::Comma => write!(f, ","),
+            Token::Colon => write!(f, ":"),
+            Token::Semicolon => write!(f, ";"),
+            Token::Assign => write!(f, "="),
+            Token::Equal => write!(f, "=="),
+            Token::Arrow => write!(f, "->"),
+            Token::Ampersand => write!(f, "&"),
+            Token::Eof => write!(f, "(end of input)"),
+        }
+    }
+}
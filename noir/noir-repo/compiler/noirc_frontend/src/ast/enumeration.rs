@@ -0,0 +1,65 @@
+use std::fmt::Display;
+
+use crate::ast::{Ident, UnresolvedGenerics, UnresolvedType};
+use crate::token::SecondaryAttribute;
+
+use iter_extended::vecmap;
+use noirc_errors::Span;
+
+/// Ast node for an enum
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NoirEnumeration {
+    pub name: Ident,
+    pub attributes: Vec<SecondaryAttribute>,
+    pub generics: UnresolvedGenerics,
+    pub variants: Vec<EnumVariant>,
+    pub span: Span,
+}
+
+/// A single variant of an enum. Unit variants such as `None` have no parameters
+/// while tuple-like variants such as `Some(T)` have one parameter per element.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnumVariant {
+    pub name: Ident,
+    pub parameters: Vec<UnresolvedType>,
+}
+
+impl NoirEnumeration {
+    pub fn new(
+        name: Ident,
+        attributes: Vec<SecondaryAttribute>,
+        generics: UnresolvedGenerics,
+        variants: Vec<EnumVariant>,
+        span: Span,
+    ) -> NoirEnumeration {
+        NoirEnumeration { name, attributes, generics, variants, span }
+    }
+}
+
+impl Display for NoirEnumeration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let generics = vecmap(&self.generics, |generic| generic.to_string());
+        let generics = if generics.is_empty() { "".into() } else { generics.join(", ") };
+
+        writeln!(f, "enum {}{} {{", self.name, generics)?;
+
+        for variant in self.variants.iter() {
+            writeln!(f, "    {variant},")?;
+        }
+
+        write!(f, "}}")
+    }
+}
+
+impl Display for EnumVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+
+        if !self.parameters.is_empty() {
+            let parameters = vecmap(&self.parameters, ToString::to_string);
+            write!(f, "({})", parameters.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
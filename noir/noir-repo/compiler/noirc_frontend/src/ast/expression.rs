@@ -29,6 +29,7 @@ pub enum ExpressionKind {
     Cast(Box<CastExpression>),
     Infix(Box<InfixExpression>),
     If(Box<IfExpression>),
+    Match(Box<MatchExpression>),
     // The optional vec here is the optional list of generics
     // provided by the turbofish operator, if used
     Variable(Path, Option<Vec<UnresolvedType>>),
@@ -268,7 +269,8 @@ impl Expression {
         // Need to check if lhs is an if expression since users can sequence if expressions
         // with tuples without calling them. E.g. `if c { t } else { e }(a, b)` is interpreted
         // as a sequence of { if, tuple } rather than a function call. This behavior matches rust.
-        let kind = if matches!(&lhs.kind, ExpressionKind::If(..)) {
+        // The same applies to match expressions.
+        let kind = if matches!(&lhs.kind, ExpressionKind::If(..) | ExpressionKind::Match(..)) {
             ExpressionKind::Block(BlockExpression {
                 statements: vec![
                     Statement { kind: StatementKind::Expression(lhs), span },
@@ -450,6 +452,14 @@ pub struct IfExpression {
     pub alternative: Option<Expression>,
 }
 
+/// A `match` expression. Each rule pairs a (possibly refutable) pattern with the
+/// expression evaluated when the scrutinee matches it. Rules are tried in order.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MatchExpression {
+    pub expression: Expression,
+    pub rules: Vec<(Pattern, Expression)>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Lambda {
     pub parameters: Vec<(Pattern, UnresolvedType)>,
@@ -583,6 +593,7 @@ impl Display for ExpressionKind {
             Cast(cast) => cast.fmt(f),
             Infix(infix) => infix.fmt(f),
             If(if_expr) => if_expr.fmt(f),
+            Match(match_expr) => match_expr.fmt(f),
             Variable(path, generics) => {
                 if let Some(generics) = generics {
                     let generics = vecmap(generics, ToString::to_string);
@@ -758,6 +769,16 @@ impl Display for IfExpression {
     }
 }
 
+impl Display for MatchExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "match {} {{", self.expression)?;
+        for (pattern, branch) in &self.rules {
+            writeln!(f, "    {pattern} => {branch},")?;
+        }
+        write!(f, "}}")
+    }
+}
+
 impl Display for Lambda {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let parameters = vecmap(&self.parameters, |(name, r#type)| format!("{name}: {type}"));
@@ -4,6 +4,7 @@
 //!
 //! Noir's Ast is produced by the parser and taken as input to name resolution,
 //! where it is converted into the Hir (defined in the hir_def module).
+mod enumeration;
 mod expression;
 mod function;
 mod statement;
@@ -11,6 +12,7 @@ mod structure;
 mod traits;
 mod type_alias;
 
+pub use enumeration::*;
 pub use expression::*;
 pub use function::*;
 
@@ -100,7 +100,9 @@ impl StatementKind {
             StatementKind::Expression(expr) => {
                 match (&expr.kind, semi, last_statement_in_block) {
                     // Semicolons are optional for these expressions
-                    (ExpressionKind::Block(_), semi, _) | (ExpressionKind::If(_), semi, _) => {
+                    (ExpressionKind::Block(_), semi, _)
+                    | (ExpressionKind::If(_), semi, _)
+                    | (ExpressionKind::Match(_), semi, _) => {
                         if semi.is_some() {
                             StatementKind::Semi(expr)
                         } else {
@@ -475,6 +477,9 @@ pub enum Pattern {
     Mutable(Box<Pattern>, Span, /*is_synthesized*/ bool),
     Tuple(Vec<Pattern>, Span),
     Struct(Path, Vec<(Ident, Pattern)>, Span),
+    /// An enum variant pattern such as `Option::Some(x)` or `Option::None`.
+    /// Unit variants have no sub-patterns.
+    Variant(Path, Vec<Pattern>, Span),
 }
 
 impl Pattern {
@@ -487,7 +492,8 @@ impl Pattern {
             Pattern::Identifier(ident) => ident.span(),
             Pattern::Mutable(_, span, _)
             | Pattern::Tuple(_, span)
-            | Pattern::Struct(_, _, span) => *span,
+            | Pattern::Struct(_, _, span)
+            | Pattern::Variant(_, _, span) => *span,
         }
     }
     pub fn name_ident(&self) -> &Ident {
@@ -776,6 +782,14 @@ impl Display for Pattern {
                 let fields = vecmap(fields, |(name, pattern)| format!("{name}: {pattern}"));
                 write!(f, "{} {{ {} }}", typename, fields.join(", "))
             }
+            Pattern::Variant(path, patterns, _) => {
+                if patterns.is_empty() {
+                    path.fmt(f)
+                } else {
+                    let patterns = vecmap(patterns, ToString::to_string);
+                    write!(f, "{}({})", path, patterns.join(", "))
+                }
+            }
         }
     }
 }
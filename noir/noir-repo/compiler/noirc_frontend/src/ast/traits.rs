@@ -81,12 +81,16 @@ pub struct UnresolvedTraitConstraint {
     pub trait_bound: TraitBound,
 }
 
-/// Represents a single trait bound, such as `TraitX` or `TraitY<U, V>`
+/// Represents a single trait bound, such as `TraitX`, `TraitY<U, V>` or `Iterator<Item = Field>`
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TraitBound {
     pub trait_path: Path,
     pub trait_id: Option<TraitId>, // initially None, gets assigned during DC
     pub trait_generics: Vec<UnresolvedType>,
+
+    /// Any associated types or constants constrained by name, such as the `Item = Field`
+    /// in `Iterator<Item = Field>`.
+    pub named_generics: Vec<(Ident, UnresolvedType)>,
 }
 
 #[derive(Clone, Debug)]
@@ -177,7 +181,9 @@ impl Display for UnresolvedTraitConstraint {
 
 impl Display for TraitBound {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let generics = vecmap(&self.trait_generics, |generic| generic.to_string());
+        let mut generics = vecmap(&self.trait_generics, |generic| generic.to_string());
+        generics.extend(self.named_generics.iter().map(|(name, typ)| format!("{name} = {typ}")));
+
         if !generics.is_empty() {
             write!(f, "{}<{}>", self.trait_path, generics.join(", "))
         } else {
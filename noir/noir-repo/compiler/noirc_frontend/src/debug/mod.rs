@@ -404,6 +404,12 @@ impl DebugInstrumenter {
                     self.walk_expr(alt);
                 }
             }
+            ast::ExpressionKind::Match(match_expr) => {
+                self.walk_expr(&mut match_expr.expression);
+                match_expr.rules.iter_mut().for_each(|(_, ref mut branch)| {
+                    self.walk_expr(branch);
+                });
+            }
             ast::ExpressionKind::Tuple(exprs) => {
                 exprs.iter_mut().for_each(|ref mut expr| {
                     self.walk_expr(expr);
@@ -677,6 +683,9 @@ fn pattern_vars(pattern: &ast::Pattern) -> Vec<(ast::Ident, bool)> {
                 stack.extend(pids.iter().map(|(_, pattern)| (pattern, is_mut)));
                 vars.extend(pids.iter().map(|(id, _)| (id.clone(), false)));
             }
+            ast::Pattern::Variant(_, patterns, _) => {
+                stack.extend(patterns.iter().map(|pattern| (pattern, is_mut)));
+            }
         }
     }
     vars
@@ -703,6 +712,12 @@ fn pattern_to_string(pattern: &ast::Pattern) -> String {
                     .join(", "),
             )
         }
+        ast::Pattern::Variant(name, arguments, _) if arguments.is_empty() => name.to_string(),
+        ast::Pattern::Variant(name, arguments, _) => format!(
+            "{}({})",
+            name,
+            arguments.iter().map(pattern_to_string).collect::<Vec<String>>().join(", ")
+        ),
     }
 }
 
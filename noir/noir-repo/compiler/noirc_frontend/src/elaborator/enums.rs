@@ -0,0 +1,406 @@
+//! Elaboration of enum constructors and `match` expressions, including the
+//! exhaustiveness and reachability checks for match rules.
+//!
+//! Exhaustiveness checking follows the usefulness algorithm described in
+//! "Warnings for pattern matching" (Maranget, 2007): a match is exhaustive if a
+//! wildcard pattern would not be useful when added after every existing rule.
+use iter_extended::vecmap;
+use noirc_errors::Span;
+
+use crate::{
+    ast::{Expression, MatchExpression, UnresolvedType},
+    hir::{resolution::errors::ResolverError, type_check::TypeCheckError},
+    hir_def::{
+        expr::{HirEnumConstructorExpression, HirMatchExpression},
+        stmt::HirPattern,
+    },
+    macros_api::{HirExpression, Path},
+    Shared, StructType, Type,
+};
+
+use super::Elaborator;
+
+impl<'context> Elaborator<'context> {
+    /// Looks up the enum variant referred to by a path such as `Option::Some`,
+    /// returning the enum type along with the index of the variant.
+    pub(super) fn lookup_enum_variant(
+        &mut self,
+        path: &Path,
+    ) -> Option<(Shared<StructType>, usize)> {
+        let (enum_type, variant) = self.lookup_enum(path)?;
+        let index = enum_type.borrow().variant_index(&variant.0.contents)?;
+        Some((enum_type, index))
+    }
+
+    /// Equivalent to `lookup_enum_variant` but issues an error if the path does
+    /// not refer to an enum variant.
+    pub(super) fn lookup_enum_variant_or_error(
+        &mut self,
+        path: Path,
+    ) -> Option<(Shared<StructType>, usize)> {
+        let (enum_type, variant) = match self.lookup_enum(&path) {
+            Some(enum_and_variant) => enum_and_variant,
+            None => {
+                self.push_err(ResolverError::Expected {
+                    span: path.span,
+                    expected: "enum variant".into(),
+                    got: path.to_string(),
+                });
+                return None;
+            }
+        };
+
+        let index = enum_type.borrow().variant_index(&variant.0.contents);
+        if index.is_none() {
+            let enum_name = enum_type.borrow().name.to_string();
+            self.push_err(ResolverError::NoSuchVariant { variant, enum_name });
+        }
+        index.map(|index| (enum_type, index))
+    }
+
+    /// Elaborates a use of an enum variant such as `Option::Some(x)` or `Option::None`.
+    /// Unit variants are used without any arguments.
+    pub(super) fn elaborate_enum_constructor(
+        &mut self,
+        enum_type: Shared<StructType>,
+        variant_index: usize,
+        turbofish: Option<Vec<UnresolvedType>>,
+        arguments: Vec<Expression>,
+        span: Span,
+    ) -> (HirExpression, Type) {
+        let enum_generics = match turbofish {
+            Some(turbofish) => {
+                let mut generics = vecmap(turbofish, |typ| self.resolve_type(typ));
+                let expected_count = enum_type.borrow().generics.len();
+                if generics.len() != expected_count {
+                    self.push_err(ResolverError::IncorrectGenericCount {
+                        span,
+                        item_name: enum_type.borrow().name.to_string(),
+                        actual: generics.len(),
+                        expected: expected_count,
+                    });
+                    generics.resize_with(expected_count, || Type::Error);
+                }
+                generics
+            }
+            None => enum_type.borrow().instantiate(self.interner),
+        };
+
+        let (variant_name, parameters) =
+            enum_type.borrow().get_variants(&enum_generics).swap_remove(variant_index);
+
+        if arguments.len() != parameters.len() {
+            self.push_err(ResolverError::VariantArgumentCountMismatch {
+                variant: variant_name,
+                expected: parameters.len(),
+                actual: arguments.len(),
+                span,
+            });
+        }
+
+        let arguments = vecmap(arguments.into_iter().enumerate(), |(i, argument)| {
+            let expr_span = argument.span;
+            let (argument, argument_type) = self.elaborate_expression(argument);
+            let parameter_type = parameters.get(i).cloned().unwrap_or(Type::Error);
+
+            self.unify_with_coercions(&argument_type, &parameter_type, argument, || {
+                TypeCheckError::TypeMismatch {
+                    expected_typ: parameter_type.to_string(),
+                    expr_typ: argument_type.to_string(),
+                    expr_span,
+                }
+            });
+            argument
+        });
+
+        let typ = Type::Struct(enum_type.clone(), enum_generics.clone());
+        let expr = HirExpression::EnumConstructor(HirEnumConstructorExpression {
+            r#type: enum_type,
+            enum_generics,
+            variant_index,
+            arguments,
+        });
+        (expr, typ)
+    }
+
+    pub(super) fn elaborate_match(
+        &mut self,
+        match_expr: MatchExpression,
+        span: Span,
+    ) -> (HirExpression, Type) {
+        let (expression, expression_type) = self.elaborate_expression(match_expr.expression);
+        let result_type = self.interner.next_type_variable();
+
+        let rules = vecmap(match_expr.rules, |(pattern, branch)| {
+            self.push_scope();
+            let pattern = self.elaborate_match_pattern(pattern, expression_type.clone());
+
+            let expr_span = branch.span;
+            let (branch, branch_type) = self.elaborate_expression(branch);
+            self.unify(&branch_type, &result_type, || {
+                let error = TypeCheckError::TypeMismatch {
+                    expected_typ: result_type.to_string(),
+                    expr_typ: branch_type.to_string(),
+                    expr_span,
+                };
+                error.add_context("Expected the types of all match arms to be equal")
+            });
+
+            self.pop_scope();
+            (pattern, branch)
+        });
+
+        let patterns = vecmap(&rules, |(pattern, _)| pattern);
+        self.check_match_rules(&patterns, &expression_type, span);
+
+        let match_expr = HirMatchExpression { expression, rules };
+        (HirExpression::Match(match_expr), result_type)
+    }
+
+    /// Issues an error if the given patterns do not cover every value of `typ`
+    /// and a warning for each pattern which can never be matched.
+    fn check_match_rules(&mut self, patterns: &[&HirPattern], typ: &Type, span: Span) {
+        let mut rows: Vec<Vec<Pat>> = Vec::with_capacity(patterns.len());
+        let types = [typ.clone()];
+
+        for pattern in patterns {
+            let row = vec![Pat::from_hir(pattern, typ)];
+            if !is_useful(&rows, &row, &types) {
+                self.push_err(ResolverError::UnreachablePattern { span: pattern.span() });
+            }
+            rows.push(row);
+        }
+
+        if let Some(mut missing) = find_missing(&rows, &types) {
+            let missing = missing.swap_remove(0);
+            self.push_err(ResolverError::NonExhaustiveMatch { missing, span });
+        }
+    }
+
+    /// Patterns outside of `match` expressions must match every value of their type,
+    /// otherwise there would be no value to bind their variables to.
+    pub(super) fn check_pattern_is_irrefutable(&mut self, pattern: &HirPattern, typ: &Type) {
+        let row = vec![Pat::from_hir(pattern, typ)];
+        if matches!(row[0], Pat::Wildcard) {
+            return;
+        }
+
+        if let Some(mut missing) = find_missing(&[row], &[typ.clone()]) {
+            let span = pattern.span();
+            let missing = missing.swap_remove(0);
+            self.push_err(ResolverError::RefutablePattern { missing, span });
+        }
+    }
+}
+
+/// A simplified pattern used for exhaustiveness checking. Bindings are
+/// wildcards and every other pattern is a constructor of its type: a variant
+/// of an enum, or the single constructor of a struct or tuple.
+#[derive(Debug, Clone)]
+enum Pat {
+    Wildcard,
+    Constructor(usize, Vec<Pat>),
+}
+
+impl Pat {
+    fn from_hir(pattern: &HirPattern, typ: &Type) -> Pat {
+        let constructors = match pattern {
+            HirPattern::Identifier(_) => return Pat::Wildcard,
+            HirPattern::Mutable(pattern, _) => return Pat::from_hir(pattern, typ),
+            _ => constructors(typ),
+        };
+
+        match (pattern, constructors) {
+            (HirPattern::Tuple(patterns, _), Some(constructors)) if constructors.len() == 1 => {
+                let fields = &constructors[0].fields;
+                if fields.len() != patterns.len() {
+                    return Pat::Wildcard;
+                }
+                let patterns = patterns.iter().zip(fields);
+                Pat::Constructor(0, vecmap(patterns, |(pattern, typ)| Pat::from_hir(pattern, typ)))
+            }
+            (HirPattern::Struct(_, patterns, _), Some(constructors)) if constructors.len() == 1 => {
+                // Struct patterns may list fields in any order, so sort them by definition order.
+                // Any missing fields have already been reported as an error.
+                let constructor = &constructors[0];
+                let fields = constructor.field_names.iter().zip(&constructor.fields);
+                Pat::Constructor(
+                    0,
+                    vecmap(fields, |(name, typ)| {
+                        let pattern = patterns.iter().find(|(field, _)| field.0.contents == *name);
+                        pattern.map_or(Pat::Wildcard, |(_, pattern)| Pat::from_hir(pattern, typ))
+                    }),
+                )
+            }
+            (HirPattern::Variant(_, index, patterns, _), Some(constructors)) => {
+                match constructors.get(*index) {
+                    Some(constructor) if constructor.fields.len() == patterns.len() => {
+                        let patterns = patterns.iter().zip(&constructor.fields);
+                        let patterns =
+                            vecmap(patterns, |(pattern, typ)| Pat::from_hir(pattern, typ));
+                        Pat::Constructor(*index, patterns)
+                    }
+                    // Errors have already been issued for mismatched patterns
+                    _ => Pat::Wildcard,
+                }
+            }
+            (_, _) => Pat::Wildcard,
+        }
+    }
+}
+
+/// A way of constructing a value of a type, along with the types of its fields.
+struct Constructor {
+    /// The name used when displaying missing patterns, e.g. `Option::Some`.
+    /// Empty for tuples.
+    name: String,
+    /// Only non-empty for structs
+    field_names: Vec<String>,
+    fields: Vec<Type>,
+}
+
+impl Constructor {
+    fn display(&self, fields: Vec<String>) -> String {
+        if !self.field_names.is_empty() {
+            let fields = self.field_names.iter().zip(fields);
+            let fields = vecmap(fields, |(name, field)| format!("{name}: {field}"));
+            format!("{} {{ {} }}", self.name, fields.join(", "))
+        } else if fields.is_empty() && !self.name.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}({})", self.name, fields.join(", "))
+        }
+    }
+}
+
+/// Returns every constructor of the given type or None if the type's values
+/// can only be matched by a wildcard, e.g. integers.
+fn constructors(typ: &Type) -> Option<Vec<Constructor>> {
+    match typ.follow_bindings() {
+        Type::Struct(definition, generics) => {
+            let definition = definition.borrow();
+            if definition.is_enum() {
+                let variants = definition.get_variants(&generics);
+                Some(vecmap(variants, |(variant, fields)| Constructor {
+                    name: format!("{}::{variant}", definition.name),
+                    field_names: Vec::new(),
+                    fields,
+                }))
+            } else {
+                let (field_names, fields) = definition.get_fields(&generics).into_iter().unzip();
+                Some(vec![Constructor { name: definition.name.to_string(), field_names, fields }])
+            }
+        }
+        Type::Tuple(fields) => {
+            Some(vec![Constructor { name: String::new(), field_names: Vec::new(), fields }])
+        }
+        Type::Alias(alias, generics) => constructors(&alias.borrow().get_type(&generics)),
+        _ => None,
+    }
+}
+
+/// Specializes the rows of a pattern matrix by the constructor at the given index:
+/// rows starting with that constructor are expanded to its fields, rows starting
+/// with a wildcard are expanded to wildcards, and all other rows are removed.
+fn specialize(rows: &[Vec<Pat>], index: usize, arity: usize) -> Vec<Vec<Pat>> {
+    let mut specialized = Vec::new();
+    for row in rows {
+        match &row[0] {
+            Pat::Constructor(row_index, fields) if *row_index == index => {
+                let mut new_row = fields.clone();
+                new_row.extend_from_slice(&row[1..]);
+                specialized.push(new_row);
+            }
+            Pat::Constructor(..) => (),
+            Pat::Wildcard => {
+                let mut new_row = vec![Pat::Wildcard; arity];
+                new_row.extend_from_slice(&row[1..]);
+                specialized.push(new_row);
+            }
+        }
+    }
+    specialized
+}
+
+/// Returns the rows starting with a wildcard, with that wildcard removed.
+fn default_rows(rows: &[Vec<Pat>]) -> Vec<Vec<Pat>> {
+    let rows = rows.iter().filter(|row| matches!(row[0], Pat::Wildcard));
+    rows.map(|row| row[1..].to_vec()).collect()
+}
+
+fn uses_constructor(rows: &[Vec<Pat>]) -> bool {
+    rows.iter().any(|row| matches!(row[0], Pat::Constructor(..)))
+}
+
+/// True if some value matched by `row` is not matched by any of `rows`.
+fn is_useful(rows: &[Vec<Pat>], row: &[Pat], types: &[Type]) -> bool {
+    if types.is_empty() {
+        return rows.is_empty();
+    }
+
+    let rest_types = &types[1..];
+    match (&row[0], constructors(&types[0])) {
+        (Pat::Constructor(index, fields), Some(constructors)) => {
+            let mut field_types = constructors[*index].fields.clone();
+            field_types.extend_from_slice(rest_types);
+
+            let mut new_row = fields.clone();
+            new_row.extend_from_slice(&row[1..]);
+            is_useful(&specialize(rows, *index, fields.len()), &new_row, &field_types)
+        }
+        (Pat::Wildcard, Some(constructors)) if uses_constructor(rows) => {
+            constructors.iter().enumerate().any(|(index, constructor)| {
+                let arity = constructor.fields.len();
+                let mut field_types = constructor.fields.clone();
+                field_types.extend_from_slice(rest_types);
+
+                let mut new_row = vec![Pat::Wildcard; arity];
+                new_row.extend_from_slice(&row[1..]);
+                is_useful(&specialize(rows, index, arity), &new_row, &field_types)
+            })
+        }
+        // Constructors of types without known constructors can't be written, so
+        // only wildcards need to be considered here.
+        (_, _) => is_useful(&default_rows(rows), &row[1..], rest_types),
+    }
+}
+
+/// Returns an example of values, one per type, which are not matched by any of
+/// the given rows, or None if the rows are exhaustive.
+fn find_missing(rows: &[Vec<Pat>], types: &[Type]) -> Option<Vec<String>> {
+    if types.is_empty() {
+        return if rows.is_empty() { Some(Vec::new()) } else { None };
+    }
+
+    let rest_types = &types[1..];
+    match constructors(&types[0]) {
+        // Uninhabited types such as enums without variants need no patterns
+        Some(constructors) if constructors.is_empty() => None,
+        Some(constructors) if uses_constructor(rows) => {
+            constructors.iter().enumerate().find_map(|(index, constructor)| {
+                let arity = constructor.fields.len();
+                let mut field_types = constructor.fields.clone();
+                field_types.extend_from_slice(rest_types);
+
+                let mut missing = find_missing(&specialize(rows, index, arity), &field_types)?;
+                let rest = missing.split_off(arity);
+                let mut result = vec![constructor.display(missing)];
+                result.extend(rest);
+                Some(result)
+            })
+        }
+        constructors => {
+            let mut missing = find_missing(&default_rows(rows), rest_types)?;
+            // Any constructor is missing here so prefer showing one for a more helpful message
+            let first = match constructors {
+                Some(constructors) if constructors.len() > 1 => {
+                    let arity = constructors[0].fields.len();
+                    constructors[0].display(vec!["_".to_string(); arity])
+                }
+                _ => "_".to_string(),
+            };
+            missing.insert(0, first);
+            Some(missing)
+        }
+    }
+}
@@ -50,8 +50,18 @@ impl<'context> Elaborator<'context> {
             ExpressionKind::Cast(cast) => self.elaborate_cast(*cast, expr.span),
             ExpressionKind::Infix(infix) => return self.elaborate_infix(*infix, expr.span),
             ExpressionKind::If(if_) => self.elaborate_if(*if_),
+            ExpressionKind::Match(match_) => self.elaborate_match(*match_, expr.span),
             ExpressionKind::Variable(variable, generics) => {
-                return self.elaborate_variable(variable, generics)
+                match self.lookup_enum_variant(&variable) {
+                    Some((enum_type, index)) => self.elaborate_enum_constructor(
+                        enum_type,
+                        index,
+                        generics,
+                        Vec::new(),
+                        expr.span,
+                    ),
+                    None => return self.elaborate_variable(variable, generics),
+                }
             }
             ExpressionKind::Tuple(tuple) => self.elaborate_tuple(tuple),
             ExpressionKind::Lambda(lambda) => self.elaborate_lambda(*lambda),
@@ -272,6 +282,16 @@ impl<'context> Elaborator<'context> {
     }
 
     fn elaborate_call(&mut self, call: CallExpression, span: Span) -> (HirExpression, Type) {
+        // Calls to enum variants such as `Option::Some(x)` construct the variant
+        if let ExpressionKind::Variable(path, generics) = &call.func.kind {
+            if let Some((enum_type, index)) = self.lookup_enum_variant(path) {
+                let generics = generics.clone();
+                let arguments = call.arguments;
+                return self
+                    .elaborate_enum_constructor(enum_type, index, generics, arguments, span);
+            }
+        }
+
         let (func, func_type) = self.elaborate_expression(*call.func);
 
         let mut arguments = Vec::with_capacity(call.arguments.len());
@@ -327,6 +347,13 @@ impl<'context> Elaborator<'context> {
                 };
 
                 let generics = if func_id != FuncId::dummy_id() {
+                    if let Some(current_item) = self.current_item {
+                        self.interner.add_function_dependency(current_item, func_id);
+                    }
+                    let method_location = Location::new(method_call.method_name.span(), self.file);
+                    let reference = ReferenceId::Reference(method_location, false);
+                    self.interner.add_reference(ReferenceId::Function(func_id), reference);
+
                     let function_type = self.interner.function_meta(&func_id).typ.clone();
                     self.try_add_mutable_reference_to_object(
                         &function_type,
@@ -396,7 +423,9 @@ impl<'context> Elaborator<'context> {
             (typ, generics)
         } else {
             match self.lookup_type_or_error(constructor.type_name) {
-                Some(Type::Struct(r#type, struct_generics)) => (r#type, struct_generics),
+                Some(Type::Struct(r#type, struct_generics)) if !r#type.borrow().is_enum() => {
+                    (r#type, struct_generics)
+                }
                 Some(typ) => {
                     self.push_err(ResolverError::NonStructUsedInConstructor { typ, span });
                     return (HirExpression::Error, Type::Error);
@@ -4,13 +4,13 @@ use std::{
 };
 
 use crate::{
-    ast::{FunctionKind, UnresolvedTraitConstraint},
+    ast::{FunctionKind, NoirEnumeration, UnresolvedTraitConstraint},
     hir::{
         comptime::{self, Interpreter, InterpreterError, Value},
         def_collector::{
             dc_crate::{
-                filter_literal_globals, CompilationError, ImplMap, UnresolvedGlobal,
-                UnresolvedStruct, UnresolvedTypeAlias,
+                filter_literal_globals, CompilationError, ImplMap, UnresolvedEnum,
+                UnresolvedGlobal, UnresolvedStruct, UnresolvedTypeAlias,
             },
             dc_mod,
             errors::DuplicateType,
@@ -23,7 +23,7 @@ use crate::{
         expr::HirIdent,
         function::{FunctionBody, Parameters},
         traits::TraitConstraint,
-        types::{Generics, Kind, ResolvedGeneric},
+        types::{Generics, Kind, ResolvedGeneric, ResolvedVariant},
     },
     macros_api::{
         BlockExpression, Ident, NodeInterner, NoirFunction, NoirStruct, Pattern,
@@ -58,6 +58,7 @@ use crate::{
     macros_api::ItemVisibility,
 };
 
+mod enums;
 mod expressions;
 mod lints;
 mod patterns;
@@ -117,6 +118,11 @@ pub struct Elaborator<'context> {
     /// to the corresponding trait impl ID.
     current_trait_impl: Option<TraitImplId>,
 
+    /// If we're currently resolving a trait or trait impl, this is the constraint
+    /// `Self: Trait<..>` with every trait generic filled in, including the associated
+    /// types and constants. It is used to resolve paths such as `Self::Item`.
+    self_trait_constraint: Option<TraitConstraint>,
+
     trait_id: Option<TraitId>,
 
     /// In-resolution names
@@ -197,6 +203,7 @@ impl<'context> Elaborator<'context> {
             trait_bounds: Vec::new(),
             function_context: vec![FunctionContext::default()],
             current_trait_impl: None,
+            self_trait_constraint: None,
             comptime_scopes: vec![HashMap::default()],
             unresolved_globals: BTreeMap::new(),
         }
@@ -242,6 +249,8 @@ impl<'context> Elaborator<'context> {
         // Must resolve structs before we resolve globals.
         let generated_items = self.collect_struct_definitions(items.types);
 
+        self.collect_enum_definitions(items.enums);
+
         self.define_function_metas(&mut items.functions, &mut items.impls, &mut items.trait_impls);
 
         self.collect_traits(items.traits);
@@ -456,12 +465,20 @@ impl<'context> Elaborator<'context> {
 
         let name = format!("impl {trait_path}");
         let generic_type = Type::NamedGeneric(new_generic, Rc::new(name), Kind::Normal);
-        let trait_bound = TraitBound { trait_path, trait_id: None, trait_generics };
+        let trait_bound =
+            TraitBound { trait_path, trait_id: None, trait_generics, named_generics: Vec::new() };
+
+        // Resolving the bound may add implicit generics for the trait's associated items
+        let old_generic_count = self.generics.len();
 
         if let Some(new_constraint) = self.resolve_trait_bound(&trait_bound, generic_type.clone()) {
             trait_constraints.push(new_constraint);
         }
 
+        generics.extend(
+            self.generics[old_generic_count..].iter().map(|generic| generic.type_var.clone()),
+        );
+
         generic_type
     }
 
@@ -536,7 +553,12 @@ impl<'context> Elaborator<'context> {
         let path_resolver = StandardPathResolver::new(self.module_id());
 
         let error = match path_resolver.resolve(self.def_maps, path.clone(), &mut None) {
-            Ok(PathResolution { module_def_id: ModuleDefId::TraitId(trait_id), error }) => {
+            Ok(PathResolution {
+                module_def_id: ModuleDefId::TraitId(trait_id),
+                error,
+                used_names,
+            }) => {
+                self.interner.usage_tracker.mark_as_used(&used_names);
                 if let Some(error) = error {
                     self.push_err(error);
                 }
@@ -571,35 +593,87 @@ impl<'context> Elaborator<'context> {
 
     fn resolve_trait_bound(&mut self, bound: &TraitBound, typ: Type) -> Option<TraitConstraint> {
         let the_trait = self.lookup_trait_or_error(bound.trait_path.clone())?;
+        let trait_id = the_trait.id;
+        let trait_name = the_trait.name.clone();
+        let resolved_generics = the_trait.generics.clone();
+
+        let associated_types = the_trait.types.iter().map(|typ| (&typ.name, &typ.generic));
+        let associated_constants =
+            the_trait.constants.iter().map(|constant| (&constant.name, &constant.generic));
+        let associated_items =
+            vecmap(associated_types.chain(associated_constants), |(name, generic)| {
+                (name.clone(), generic.kind.clone())
+            });
 
-        let resolved_generics = &the_trait.generics.clone();
-        assert_eq!(resolved_generics.len(), bound.trait_generics.len());
         let generics_with_types = resolved_generics.iter().zip(&bound.trait_generics);
-        let trait_generics = vecmap(generics_with_types, |(generic, typ)| {
+        let mut trait_generics = vecmap(generics_with_types, |(generic, typ)| {
             self.resolve_type_inner(typ.clone(), &generic.kind)
         });
 
-        let the_trait = self.lookup_trait_or_error(bound.trait_path.clone())?;
-        let trait_id = the_trait.id;
-
         let span = bound.trait_path.span();
 
-        let expected_generics = the_trait.generics.len();
-        let actual_generics = trait_generics.len();
+        let expected_generics = resolved_generics.len();
+        let actual_generics = bound.trait_generics.len();
 
         if actual_generics != expected_generics {
-            let item_name = the_trait.name.to_string();
+            let item_name = trait_name.to_string();
             self.push_err(ResolverError::IncorrectGenericCount {
                 span,
                 item_name,
                 actual: actual_generics,
                 expected: expected_generics,
             });
+            trait_generics.resize_with(expected_generics, || Type::Error);
+        }
+
+        // Any associated types and constants not constrained by name, as in `T: Iterator`, are
+        // left as fresh generics for now. They are determined by the impl selected for `typ`.
+        for (name, kind) in &associated_items {
+            let named_generic = bound.named_generics.iter().find(|(item, _)| item == name);
+
+            let generic = match named_generic {
+                Some((_, arg)) => self.resolve_type_inner(arg.clone(), kind),
+                None => self.add_associated_item_generic(&typ, name, kind.clone(), span),
+            };
+            trait_generics.push(generic);
+        }
+
+        for (item_name, _) in &bound.named_generics {
+            if !associated_items.iter().any(|(name, _)| name == item_name) {
+                let trait_name = trait_name.clone();
+                let item_name = item_name.clone();
+                self.push_err(DefCollectorErrorKind::AssociatedItemNotInTrait {
+                    trait_name,
+                    item_name,
+                });
+            }
         }
 
         Some(TraitConstraint { typ, trait_id, trait_generics })
     }
 
+    /// Adds a fresh implicit generic to the current item standing in for the associated type
+    /// or constant `name` of `typ`, e.g. `T::Item`. When the item is used, this generic is
+    /// instantiated and later bound by the impl selected for `typ`.
+    fn add_associated_item_generic(
+        &mut self,
+        typ: &Type,
+        name: &Ident,
+        kind: Kind,
+        span: Span,
+    ) -> Type {
+        let type_var = TypeVariable::unbound(self.interner.next_type_variable_id());
+        let name = Rc::new(format!("{typ}::{name}"));
+        let generic = ResolvedGeneric {
+            name: name.clone(),
+            type_var: type_var.clone(),
+            kind: kind.clone(),
+            span,
+        };
+        self.generics.push(generic);
+        Type::NamedGeneric(type_var, name, kind)
+    }
+
     /// Extract metadata from a NoirFunction
     /// to be used in analysis and intern the function parameters
     /// Prerequisite: any implicit generics, including any generics from the impl,
@@ -648,10 +722,22 @@ impl<'context> Elaborator<'context> {
         let should_fold = func.attributes().is_foldable();
         let has_inline_attribute = has_no_predicates_attribute || should_fold;
         let is_pub_allowed = self.pub_allowed(func, in_contract);
+
+        let func_generics_start = self.generics.len();
         self.add_generics(&func.def.generics);
+        let func_generics_end = self.generics.len();
 
         let mut trait_constraints = self.resolve_trait_constraints(&func.def.where_clause);
 
+        // Resolving the where clause may add implicit generics for the associated types and
+        // constants of each trait. Implicit generics must precede the function's own generics
+        // so that any turbofish generics are matched up with the correct type variables.
+        let implicit_generics = self.generics.split_off(func_generics_end);
+        self.generics.splice(func_generics_start..func_generics_start, implicit_generics);
+
+        // The where clause is needed to resolve paths such as `T::Item` in the signature
+        let old_trait_bounds = std::mem::replace(&mut self.trait_bounds, trait_constraints.clone());
+
         let mut generics = vecmap(&self.generics, |generic| generic.type_var.clone());
         let mut parameters = Vec::new();
         let mut parameter_types = Vec::new();
@@ -691,6 +777,7 @@ impl<'context> Elaborator<'context> {
         }
 
         let return_type = Box::new(self.resolve_type(func.return_type()));
+        self.trait_bounds = old_trait_bounds;
 
         let mut typ = Type::Function(parameter_types, return_type, Box::new(Type::Unit));
 
@@ -849,6 +936,14 @@ impl<'context> Elaborator<'context> {
         self.generics = trait_impl.resolved_generics;
         self.current_trait_impl = trait_impl.impl_id;
 
+        if let (Some(trait_id), Some(self_type)) =
+            (trait_impl.trait_id, trait_impl.resolved_object_type)
+        {
+            let trait_generics = trait_impl.resolved_trait_generics;
+            self.self_trait_constraint =
+                Some(TraitConstraint::new(self_type, trait_id, trait_generics));
+        }
+
         for (module, function, _) in &trait_impl.methods.functions {
             self.local_module = *module;
             let errors = check_trait_impl_method_matches_declaration(self.interner, *function);
@@ -859,6 +954,7 @@ impl<'context> Elaborator<'context> {
 
         self.self_type = None;
         self.current_trait_impl = None;
+        self.self_trait_constraint = None;
         self.generics.clear();
     }
 
@@ -897,6 +993,11 @@ impl<'context> Elaborator<'context> {
 
         if let Some(trait_id) = trait_impl.trait_id {
             self.generics = trait_impl.resolved_generics.clone();
+
+            let trait_generics = trait_impl.resolved_trait_generics.clone();
+            self.self_trait_constraint =
+                Some(TraitConstraint::new(self_type.clone(), trait_id, trait_generics));
+
             self.collect_trait_impl_methods(trait_id, trait_impl);
 
             let span = trait_impl.object_type.span.expect("All trait self types should have spans");
@@ -951,6 +1052,7 @@ impl<'context> Elaborator<'context> {
         self.generics.clear();
 
         self.current_trait_impl = None;
+        self.self_trait_constraint = None;
         self.self_type = None;
     }
 
@@ -1243,6 +1345,30 @@ impl<'context> Elaborator<'context> {
         generated_items
     }
 
+    fn collect_enum_definitions(&mut self, enums: BTreeMap<StructId, UnresolvedEnum>) {
+        // Resolve the parameters of each variant in each enum.
+        // Each enum should already be present in the NodeInterner after def collection.
+        for (type_id, typ) in enums {
+            self.file = typ.file_id;
+            self.local_module = typ.module_id;
+
+            let variants = self.resolve_enum_variants(typ.enum_def, type_id);
+            self.interner.update_struct(type_id, |enum_def| enum_def.set_variants(variants));
+
+            let enum_type = self.interner.get_struct(type_id);
+            let enum_type = enum_type.borrow();
+
+            // As with structs, generic enums are checked for nested slices after monomorphization
+            if enum_type.generics.is_empty() {
+                let variants = enum_type.get_variants(&[]);
+                let mut parameters = variants.iter().flat_map(|(_, parameters)| parameters);
+                if parameters.any(Type::is_nested_slice) {
+                    self.push_err(ResolverError::NestedSlices { span: enum_type.location.span });
+                }
+            }
+        }
+    }
+
     fn run_comptime_attributes_on_struct(
         &mut self,
         attributes: Vec<SecondaryAttribute>,
@@ -1318,6 +1444,39 @@ impl<'context> Elaborator<'context> {
         })
     }
 
+    fn resolve_enum_variants(
+        &mut self,
+        unresolved: NoirEnumeration,
+        enum_id: StructId,
+    ) -> Vec<ResolvedVariant> {
+        self.recover_generics(|this| {
+            this.current_item = Some(DependencyId::Struct(enum_id));
+
+            this.resolving_ids.insert(enum_id);
+
+            let enum_def = this.interner.get_struct(enum_id);
+            this.add_existing_generics(&unresolved.generics, &enum_def.borrow().generics);
+
+            let mut seen_variants: HashMap<String, Span> = HashMap::default();
+            let variants = vecmap(unresolved.variants, |variant| {
+                let name = variant.name.0.contents.clone();
+                if let Some(first_span) = seen_variants.insert(name.clone(), variant.name.span()) {
+                    let second_span = variant.name.span();
+                    let error =
+                        ResolverError::DuplicateDefinition { name, first_span, second_span };
+                    this.push_err(error);
+                }
+
+                let parameters = vecmap(variant.parameters, |typ| this.resolve_type(typ));
+                ResolvedVariant { name: variant.name, parameters }
+            });
+
+            this.resolving_ids.remove(&enum_id);
+
+            variants
+        })
+    }
+
     fn elaborate_global(&mut self, global: UnresolvedGlobal) {
         let old_module = std::mem::replace(&mut self.local_module, global.module_id);
         let old_file = std::mem::replace(&mut self.file, global.file_id);
@@ -1438,7 +1597,17 @@ impl<'context> Elaborator<'context> {
 
             let self_type = self.resolve_type(unresolved_type.clone());
             self.self_type = Some(self_type.clone());
-            trait_impl.methods.self_type = Some(self_type);
+            trait_impl.methods.self_type = Some(self_type.clone());
+
+            if let Some(trait_id) = trait_id {
+                let associated_items =
+                    self.resolve_trait_impl_associated_items(trait_id, trait_impl);
+                trait_impl.resolved_trait_generics.extend(associated_items);
+
+                let trait_generics = trait_impl.resolved_trait_generics.clone();
+                self.self_trait_constraint =
+                    Some(TraitConstraint::new(self_type, trait_id, trait_generics));
+            }
 
             let impl_id = self.interner.next_trait_impl_id();
             self.current_trait_impl = Some(impl_id);
@@ -1447,6 +1616,7 @@ impl<'context> Elaborator<'context> {
 
             trait_impl.resolved_object_type = self.self_type.take();
             trait_impl.impl_id = self.current_trait_impl.take();
+            self.self_trait_constraint = None;
             self.generics.clear();
 
             if let Some(trait_id) = trait_id {
@@ -1534,6 +1704,7 @@ impl<'context> Elaborator<'context> {
         let comptime = CollectedItems {
             functions: comptime_function_sets,
             types: BTreeMap::new(),
+            enums: BTreeMap::new(),
             type_aliases: BTreeMap::new(),
             traits: BTreeMap::new(),
             trait_impls: Vec::new(),
@@ -1583,6 +1754,7 @@ impl<'context> Elaborator<'context> {
                         methods,
                         generics: trait_impl.impl_generics,
                         where_clause: trait_impl.where_clause,
+                        associated_items: trait_impl.items,
 
                         // These last fields are filled in later
                         trait_id: None,
@@ -1612,6 +1784,7 @@ impl<'context> Elaborator<'context> {
                 TopLevelStatement::Module(_)
                 | TopLevelStatement::Import(_)
                 | TopLevelStatement::Struct(_)
+                | TopLevelStatement::Enum(_)
                 | TopLevelStatement::Trait(_)
                 | TopLevelStatement::Impl(_)
                 | TopLevelStatement::TypeAlias(_)
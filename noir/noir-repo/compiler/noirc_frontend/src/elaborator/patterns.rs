@@ -13,12 +13,13 @@ use crate::{
     hir_def::{
         expr::{HirIdent, ImplKind},
         stmt::HirPattern,
+        traits::Trait,
     },
     macros_api::{HirExpression, Ident, Path, Pattern},
     node_interner::{
         DefinitionId, DefinitionKind, ExprId, FuncId, GlobalId, ReferenceId, TraitImplKind,
     },
-    Shared, StructType, Type, TypeBindings,
+    Kind, Shared, StructType, Type, TypeBindings, TypeVariable,
 };
 
 use super::{Elaborator, ResolverMeta};
@@ -30,14 +31,16 @@ impl<'context> Elaborator<'context> {
         expected_type: Type,
         definition_kind: DefinitionKind,
     ) -> HirPattern {
-        self.elaborate_pattern_mut(
+        let pattern = self.elaborate_pattern_mut(
             pattern,
-            expected_type,
+            expected_type.clone(),
             definition_kind,
             None,
             &mut Vec::new(),
             None,
-        )
+        );
+        self.check_pattern_is_irrefutable(&pattern, &expected_type);
+        pattern
     }
 
     /// Equivalent to `elaborate_pattern`, this version just also
@@ -50,14 +53,26 @@ impl<'context> Elaborator<'context> {
         created_ids: &mut Vec<HirIdent>,
         global_id: Option<GlobalId>,
     ) -> HirPattern {
-        self.elaborate_pattern_mut(
+        let pattern = self.elaborate_pattern_mut(
             pattern,
-            expected_type,
+            expected_type.clone(),
             definition_kind,
             None,
             created_ids,
             global_id,
-        )
+        );
+        self.check_pattern_is_irrefutable(&pattern, &expected_type);
+        pattern
+    }
+
+    /// Elaborates a pattern of a `match` rule. Unlike other patterns, these may be refutable.
+    pub(super) fn elaborate_match_pattern(
+        &mut self,
+        pattern: Pattern,
+        expected_type: Type,
+    ) -> HirPattern {
+        let definition = DefinitionKind::Local(None);
+        self.elaborate_pattern_mut(pattern, expected_type, definition, None, &mut Vec::new(), None)
     }
 
     fn elaborate_pattern_mut(
@@ -146,6 +161,15 @@ impl<'context> Elaborator<'context> {
                 mutable,
                 new_definitions,
             ),
+            Pattern::Variant(path, patterns, span) => self.elaborate_variant_pattern(
+                path,
+                patterns,
+                span,
+                expected_type,
+                definition,
+                mutable,
+                new_definitions,
+            ),
         }
     }
 
@@ -173,7 +197,9 @@ impl<'context> Elaborator<'context> {
         };
 
         let (struct_type, generics) = match self.lookup_type_or_error(name) {
-            Some(Type::Struct(struct_type, generics)) => (struct_type, generics),
+            Some(Type::Struct(struct_type, generics)) if !struct_type.borrow().is_enum() => {
+                (struct_type, generics)
+            }
             None => return error_identifier(self),
             Some(typ) => {
                 self.push_err(ResolverError::NonStructUsedInConstructor { typ, span });
@@ -209,6 +235,74 @@ impl<'context> Elaborator<'context> {
         HirPattern::Struct(expected_type, fields, location)
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn elaborate_variant_pattern(
+        &mut self,
+        path: Path,
+        patterns: Vec<Pattern>,
+        span: Span,
+        expected_type: Type,
+        definition: DefinitionKind,
+        mutable: Option<Span>,
+        new_definitions: &mut Vec<HirIdent>,
+    ) -> HirPattern {
+        let location = Location::new(span, self.file);
+
+        let (enum_type, variant_index) = match self.lookup_enum_variant_or_error(path) {
+            Some(variant) => variant,
+            None => {
+                // Still elaborate each sub-pattern so that the variables they define are in scope
+                let patterns = vecmap(patterns, |pattern| {
+                    self.elaborate_pattern_mut(
+                        pattern,
+                        Type::Error,
+                        definition.clone(),
+                        mutable,
+                        new_definitions,
+                        None,
+                    )
+                });
+                return HirPattern::Tuple(patterns, location);
+            }
+        };
+
+        let generics = enum_type.borrow().instantiate(self.interner);
+        let actual_type = Type::Struct(enum_type.clone(), generics.clone());
+
+        self.unify(&actual_type, &expected_type, || TypeCheckError::TypeMismatchWithSource {
+            expected: expected_type.clone(),
+            actual: actual_type.clone(),
+            span,
+            source: Source::Assignment,
+        });
+
+        let (variant_name, parameters) =
+            enum_type.borrow().get_variants(&generics).swap_remove(variant_index);
+
+        if patterns.len() != parameters.len() {
+            self.push_err(ResolverError::VariantArgumentCountMismatch {
+                variant: variant_name,
+                expected: parameters.len(),
+                actual: patterns.len(),
+                span,
+            });
+        }
+
+        let patterns = vecmap(patterns.into_iter().enumerate(), |(i, pattern)| {
+            let parameter_type = parameters.get(i).cloned().unwrap_or(Type::Error);
+            self.elaborate_pattern_mut(
+                pattern,
+                parameter_type,
+                definition.clone(),
+                mutable,
+                new_definitions,
+                None,
+            )
+        });
+
+        HirPattern::Variant(actual_type, variant_index, patterns, location)
+    }
+
     /// Resolve all the fields of a struct constructor expression.
     /// Ensures all fields are present, none are repeated, and all
     /// are part of the struct.
@@ -467,6 +561,10 @@ impl<'context> Elaborator<'context> {
     }
 
     fn resolve_variable(&mut self, path: Path) -> HirIdent {
+        if let Some(hir_ident) = self.resolve_associated_constant(&path) {
+            return hir_ident;
+        }
+
         if let Some((method, constraint, assumed)) = self.resolve_trait_generic_path(&path) {
             HirIdent {
                 location: Location::new(path.span, self.file),
@@ -533,6 +631,36 @@ impl<'context> Elaborator<'context> {
         }
     }
 
+    /// Resolves a path such as `Self::Size` or `T::Size` referring to an associated constant
+    /// being used as a value. Like numeric generics, these are represented as a definition
+    /// referring to the type variable holding the constant's value.
+    fn resolve_associated_constant(&mut self, path: &Path) -> Option<HirIdent> {
+        let (value, numeric_type) =
+            match self.lookup_associated_item(path, Trait::find_associated_constant)? {
+                (value, Kind::Numeric(numeric_type)) => (value, numeric_type),
+                (_, Kind::Normal) => return None,
+            };
+
+        let type_variable = match value {
+            Type::NamedGeneric(type_variable, ..) | Type::TypeVariable(type_variable, _) => {
+                type_variable
+            }
+            // The value is already known, e.g. within a trait impl
+            value => {
+                let type_variable = TypeVariable::unbound(self.interner.next_type_variable_id());
+                type_variable.bind(value);
+                type_variable
+            }
+        };
+
+        let location = Location::new(path.span(), self.file);
+        let definition = DefinitionKind::GenericType(type_variable);
+        let id =
+            self.interner.push_definition(path.to_string(), false, false, definition, location);
+        self.interner.push_definition_type(id, *numeric_type);
+        Some(HirIdent::non_trait_method(id, location))
+    }
+
     pub(super) fn type_check_variable(
         &mut self,
         ident: HirIdent,
@@ -547,9 +675,10 @@ impl<'context> Elaborator<'context> {
         // the type used in the trait constraint (if it exists). See #4088.
         if let ImplKind::TraitMethod(_, constraint, assumed) = &ident.impl_kind {
             let the_trait = self.interner.get_trait(constraint.trait_id);
-            assert_eq!(the_trait.generics.len(), constraint.trait_generics.len());
+            let trait_generics = the_trait.all_generics();
+            assert_eq!(trait_generics.len(), constraint.trait_generics.len());
 
-            for (param, arg) in the_trait.generics.iter().zip(&constraint.trait_generics) {
+            for (param, arg) in trait_generics.iter().zip(&constraint.trait_generics) {
                 // Avoid binding t = t
                 if !arg.occurs(param.type_var.id()) {
                     bindings.insert(param.type_var.id(), (param.type_var.clone(), arg.clone()));
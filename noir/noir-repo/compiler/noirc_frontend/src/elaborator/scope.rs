@@ -63,6 +63,8 @@ impl<'context> Elaborator<'context> {
             path_resolution = resolver.resolve(self.def_maps, path, &mut None)?;
         }
 
+        self.interner.usage_tracker.mark_as_used(&path_resolution.used_names);
+
         if let Some(error) = path_resolution.error {
             self.push_err(error);
         }
@@ -211,6 +213,47 @@ impl<'context> Elaborator<'context> {
         }
     }
 
+    /// Looks up the enum type referred to by all but the last segment of a path such as
+    /// `Option::Some` or `Self::Some`, returning it along with the last segment.
+    /// No errors are issued if the prefix doesn't refer to an enum since such paths may
+    /// still refer to other items, e.g. a method on a struct.
+    pub(super) fn lookup_enum(&mut self, path: &Path) -> Option<(Shared<StructType>, Ident)> {
+        if path.segments.len() < 2 {
+            return None;
+        }
+
+        let mut prefix = path.clone();
+        let variant = prefix.pop();
+        let prefix_span = prefix.segments.last().map_or(prefix.span, Ident::span);
+        let is_self_type = prefix.as_ident().map_or(false, |ident| ident == SELF_TYPE_NAME);
+
+        let enum_type = if is_self_type {
+            match &self.self_type {
+                Some(Type::Struct(enum_type, _)) => enum_type.clone(),
+                _ => return None,
+            }
+        } else {
+            let resolver = StandardPathResolver::new(self.module_id());
+            let path_resolution = resolver.resolve(self.def_maps, prefix, &mut None).ok()?;
+            self.interner.usage_tracker.mark_as_used(&path_resolution.used_names);
+
+            match path_resolution.module_def_id {
+                ModuleDefId::TypeId(struct_id) => self.get_struct(struct_id),
+                _ => return None,
+            }
+        };
+
+        if !enum_type.borrow().is_enum() {
+            return None;
+        }
+
+        let referenced = ReferenceId::Struct(enum_type.borrow().id);
+        let reference = ReferenceId::Reference(Location::new(prefix_span, self.file), is_self_type);
+        self.interner.add_reference(referenced, reference);
+
+        Some((enum_type, variant))
+    }
+
     pub fn lookup_type_alias(&mut self, path: Path) -> Option<Shared<TypeAlias>> {
         self.lookup(path).ok().map(|id| self.interner.get_type_alias(id))
     }
@@ -2,13 +2,21 @@ use std::{collections::BTreeMap, rc::Rc};
 
 use iter_extended::vecmap;
 use noirc_errors::Location;
+use rustc_hash::FxHashMap as HashMap;
 
 use crate::{
     ast::{
-        FunctionKind, TraitItem, UnresolvedGeneric, UnresolvedGenerics, UnresolvedTraitConstraint,
+        FunctionKind, TraitImplItem, TraitItem, UnresolvedGeneric, UnresolvedGenerics,
+        UnresolvedTraitConstraint, UnresolvedTypeExpression,
     },
-    hir::def_collector::dc_crate::UnresolvedTrait,
-    hir_def::traits::{TraitConstant, TraitFunction, TraitType},
+    hir::{
+        def_collector::{
+            dc_crate::{UnresolvedTrait, UnresolvedTraitImpl},
+            errors::{DefCollectorErrorKind, DuplicateType},
+        },
+        type_check::TypeCheckError,
+    },
+    hir_def::traits::{TraitConstraint, TraitFunction},
     macros_api::{
         BlockExpression, FunctionDefinition, FunctionReturnType, Ident, ItemVisibility,
         NoirFunction, Param, Pattern, UnresolvedType, Visibility,
@@ -24,19 +32,29 @@ impl<'context> Elaborator<'context> {
     pub fn collect_traits(&mut self, traits: BTreeMap<TraitId, UnresolvedTrait>) {
         for (trait_id, unresolved_trait) in traits {
             self.recover_generics(|this| {
-                let resolved_generics = this.interner.get_trait(trait_id).generics.clone();
+                let the_trait = this.interner.get_trait(trait_id);
+                let resolved_generics = the_trait.generics.clone();
+                let all_generics = the_trait.all_generics();
+                let self_typevar = the_trait.self_type_typevar.clone();
+
                 this.add_existing_generics(
                     &unresolved_trait.trait_def.generics,
                     &resolved_generics,
                 );
 
-                // Resolve order
-                // 1. Trait Types ( Trait constants can have a trait type, therefore types before constants)
-                let _ = this.resolve_trait_types(&unresolved_trait);
-                // 2. Trait Constants ( Trait's methods can use trait types & constants, therefore they should be after)
-                let _ = this.resolve_trait_constants(&unresolved_trait);
-                // 3. Trait Methods
+                // The associated types and constants are implicit generics within the trait.
+                // They are named `Self::Item` so they cannot be referred to as just `Item`.
+                this.generics.extend(all_generics[resolved_generics.len()..].iter().cloned());
+
+                let self_type = Type::TypeVariable(self_typevar, TypeVariableKind::Normal);
+                let trait_generics = vecmap(all_generics, |generic| {
+                    Type::NamedGeneric(generic.type_var, generic.name, generic.kind)
+                });
+                this.self_trait_constraint =
+                    Some(TraitConstraint::new(self_type, trait_id, trait_generics));
+
                 let methods = this.resolve_trait_methods(trait_id, &unresolved_trait);
+                this.self_trait_constraint = None;
 
                 this.interner.update_trait(trait_id, |trait_def| {
                     trait_def.set_methods(methods);
@@ -52,17 +70,120 @@ impl<'context> Elaborator<'context> {
         }
     }
 
-    fn resolve_trait_types(&mut self, _unresolved_trait: &UnresolvedTrait) -> Vec<TraitType> {
-        // TODO
-        vec![]
-    }
-
-    fn resolve_trait_constants(
+    /// Resolves the associated types and constants provided by a trait impl, returned in the
+    /// order the trait declares them. Constants which the impl does not provide are given the
+    /// trait's default value, if there is one.
+    pub(super) fn resolve_trait_impl_associated_items(
         &mut self,
-        _unresolved_trait: &UnresolvedTrait,
-    ) -> Vec<TraitConstant> {
-        // TODO
-        vec![]
+        trait_id: TraitId,
+        trait_impl: &UnresolvedTraitImpl,
+    ) -> Vec<Type> {
+        let the_trait = self.interner.get_trait(trait_id);
+        let trait_name = the_trait.name.clone();
+        let trait_types = the_trait.types.clone();
+        let trait_constants = the_trait.constants.clone();
+
+        let trait_impl_span =
+            trait_impl.object_type.span.expect("All trait self types should have spans");
+
+        let mut resolved = Vec::new();
+
+        for trait_type in &trait_types {
+            let provided = trait_impl.associated_items.iter().find_map(|item| match item {
+                TraitImplItem::Type { name, alias } if name == &trait_type.name => Some(alias),
+                _ => None,
+            });
+
+            let typ = match provided {
+                Some(alias) => self.resolve_type(alias.clone()),
+                None => {
+                    self.push_err(DefCollectorErrorKind::TraitMissingAssociatedItem {
+                        trait_name: trait_name.clone(),
+                        item_name: trait_type.name.clone(),
+                        trait_impl_span,
+                    });
+                    Type::Error
+                }
+            };
+            resolved.push(typ);
+        }
+
+        for trait_constant in &trait_constants {
+            let provided = trait_impl.associated_items.iter().find_map(|item| match item {
+                TraitImplItem::Constant(name, typ, value) if name == &trait_constant.name => {
+                    Some((name, typ, value))
+                }
+                _ => None,
+            });
+
+            let value = match provided {
+                Some((name, typ, value)) => {
+                    if let Kind::Numeric(expected) = &trait_constant.generic.kind {
+                        let actual = self.resolve_type(typ.clone());
+                        let expr_span = typ.span.unwrap_or_else(|| name.span());
+                        self.unify(&actual, expected, || TypeCheckError::TypeMismatch {
+                            expected_typ: expected.to_string(),
+                            expr_typ: actual.to_string(),
+                            expr_span,
+                        });
+                    }
+
+                    match UnresolvedTypeExpression::from_expr(value.clone(), value.span) {
+                        Ok(value) => self.convert_expression_type(value),
+                        Err(error) => {
+                            self.push_err(error);
+                            Type::Error
+                        }
+                    }
+                }
+                None => match &trait_constant.default_value {
+                    Some(default_value) => self.convert_expression_type(default_value.clone()),
+                    None => {
+                        self.push_err(DefCollectorErrorKind::TraitMissingAssociatedItem {
+                            trait_name: trait_name.clone(),
+                            item_name: trait_constant.name.clone(),
+                            trait_impl_span,
+                        });
+                        Type::Error
+                    }
+                },
+            };
+            resolved.push(value);
+        }
+
+        // Finally, check for any items the trait does not declare or which are defined twice.
+        // As in the trait itself, types and constants are in separate namespaces.
+        let mut defined_types: HashMap<String, Ident> = HashMap::default();
+        let mut defined_constants: HashMap<String, Ident> = HashMap::default();
+
+        for item in &trait_impl.associated_items {
+            let (name, declared_in_trait, defined_items, duplicate_type) = match item {
+                TraitImplItem::Type { name, .. } => {
+                    let declared = trait_types.iter().any(|typ| &typ.name == name);
+                    (name, declared, &mut defined_types, DuplicateType::TraitAssociatedType)
+                }
+                TraitImplItem::Constant(name, ..) => {
+                    let declared = trait_constants.iter().any(|constant| &constant.name == name);
+                    (name, declared, &mut defined_constants, DuplicateType::TraitAssociatedConst)
+                }
+                TraitImplItem::Function(_) => continue,
+            };
+
+            if !declared_in_trait {
+                self.push_err(DefCollectorErrorKind::AssociatedItemNotInTrait {
+                    trait_name: trait_name.clone(),
+                    item_name: name.clone(),
+                });
+            } else if let Some(first_def) = defined_items.insert(name.to_string(), name.clone()) {
+                self.push_err(DefCollectorErrorKind::Duplicate {
+                    typ: duplicate_type,
+                    first_def,
+                    second_def: name.clone(),
+                });
+            }
+        }
+
+        resolved
     }
 
     fn resolve_trait_methods(
@@ -24,7 +24,7 @@ use crate::{
             HirPrefixExpression,
         },
         function::{FuncMeta, Parameters},
-        traits::TraitConstraint,
+        traits::{Trait, TraitConstraint},
     },
     macros_api::{
         HirExpression, HirLiteral, HirStatement, Path, PathKind, SecondaryAttribute, Signedness,
@@ -362,6 +362,10 @@ impl<'context> Elaborator<'context> {
             }
         }
 
+        if let Some((typ, _kind)) = self.lookup_associated_item(path, Trait::find_associated_item) {
+            return Some(typ);
+        }
+
         // If we cannot find a local generic of the same name, try to look up a global
         match self.resolve_path(path.clone()) {
             Ok(ModuleDefId::GlobalId(id)) => {
@@ -380,6 +384,46 @@ impl<'context> Elaborator<'context> {
         }
     }
 
+    /// Looks up an associated type or constant referred to by a path such as `Self::Item`
+    /// within a trait or trait impl, or `T::Item` where `T: Iterator` is in the current
+    /// `where` clause. Returns the type (or numeric value) of the item along with its kind.
+    /// `find_item` determines which of the trait's associated items the path may refer to.
+    pub(super) fn lookup_associated_item(
+        &mut self,
+        path: &Path,
+        find_item: fn(&Trait, &str) -> Option<usize>,
+    ) -> Option<(Type, Kind)> {
+        if path.kind != PathKind::Plain || path.segments.len() != 2 {
+            return None;
+        }
+
+        let object_name = &path.segments[0].0.contents;
+        let item_name = &path.segments[1].0.contents;
+
+        let constraints = if object_name == SELF_TYPE_NAME {
+            self.self_trait_constraint.iter().cloned().collect()
+        } else {
+            vecmap(&self.trait_bounds, Clone::clone)
+        };
+
+        for constraint in constraints {
+            if object_name != SELF_TYPE_NAME {
+                match &constraint.typ {
+                    Type::NamedGeneric(_, name, _) if name.as_str() == object_name => (),
+                    _ => continue,
+                }
+            }
+
+            let the_trait = self.interner.get_trait(constraint.trait_id);
+            if let Some(index) = find_item(the_trait, item_name) {
+                let kind = the_trait.all_generics().swap_remove(index).kind;
+                let typ = constraint.trait_generics.get(index).cloned().unwrap_or(Type::Error);
+                return Some((typ, kind));
+            }
+        }
+        None
+    }
+
     pub(super) fn convert_expression_type(&mut self, length: UnresolvedTypeExpression) -> Type {
         match length {
             UnresolvedTypeExpression::Variable(path) => {
@@ -429,9 +473,10 @@ impl<'context> Elaborator<'context> {
 
                 let constraint = TraitConstraint {
                     typ: self.self_type.clone()?,
-                    trait_generics: Type::from_generics(&vecmap(&the_trait.generics, |generic| {
-                        generic.type_var.clone()
-                    })),
+                    trait_generics: Type::from_generics(&vecmap(
+                        the_trait.all_generics(),
+                        |generic| generic.type_var,
+                    )),
                     trait_id,
                 };
 
@@ -463,8 +508,8 @@ impl<'context> Elaborator<'context> {
                     the_trait.self_type_typevar.clone(),
                     TypeVariableKind::Normal,
                 ),
-                trait_generics: Type::from_generics(&vecmap(&the_trait.generics, |generic| {
-                    generic.type_var.clone()
+                trait_generics: Type::from_generics(&vecmap(the_trait.all_generics(), |generic| {
+                    generic.type_var
                 })),
                 trait_id,
             };
@@ -19,9 +19,9 @@ use crate::{
     hir_def::{
         expr::{
             HirArrayLiteral, HirBlockExpression, HirCallExpression, HirCastExpression,
-            HirConstructorExpression, HirIdent, HirIfExpression, HirIndexExpression,
-            HirInfixExpression, HirLambda, HirMemberAccess, HirMethodCallExpression,
-            HirPrefixExpression,
+            HirConstructorExpression, HirEnumConstructorExpression, HirIdent, HirIfExpression,
+            HirIndexExpression, HirInfixExpression, HirLambda, HirMatchExpression, HirMemberAccess,
+            HirMethodCallExpression, HirPrefixExpression,
         },
         stmt::{
             HirAssignStatement, HirConstrainStatement, HirForStatement, HirLValue, HirLetStatement,
@@ -277,6 +277,51 @@ impl<'a> Interpreter<'a> {
                 self.pop_scope();
                 res
             }
+            HirPattern::Variant(_, variant_index, patterns, _) => match argument {
+                Value::Enum(index, arguments, _)
+                    if index == *variant_index && arguments.len() == patterns.len() =>
+                {
+                    for (pattern, argument) in patterns.iter().zip(arguments) {
+                        let argument_type = argument.get_type().into_owned();
+                        self.define_pattern(pattern, &argument_type, argument, location)?;
+                    }
+                    Ok(())
+                }
+                value => {
+                    Err(InterpreterError::TypeMismatch { expected: typ.clone(), value, location })
+                }
+            },
+        }
+    }
+
+    /// Returns true if the given value matches the shape of `pattern`. Only enum
+    /// variants can fail to match, every other pattern is irrefutable.
+    fn pattern_matches(pattern: &HirPattern, value: &Value) -> bool {
+        match (pattern, value) {
+            (HirPattern::Identifier(_), _) => true,
+            (HirPattern::Mutable(pattern, _), value) => Self::pattern_matches(pattern, value),
+            (HirPattern::Tuple(patterns, _), Value::Tuple(fields)) => patterns
+                .iter()
+                .zip(fields)
+                .all(|(pattern, field)| Self::pattern_matches(pattern, field)),
+            (HirPattern::Struct(_, patterns, _), Value::Struct(fields, _)) => {
+                patterns.iter().all(|(name, pattern)| {
+                    fields
+                        .get(&name.0.contents)
+                        .map_or(false, |field| Self::pattern_matches(pattern, field))
+                })
+            }
+            (
+                HirPattern::Variant(_, variant_index, patterns, _),
+                Value::Enum(index, arguments, _),
+            ) => {
+                index == variant_index
+                    && patterns
+                        .iter()
+                        .zip(arguments)
+                        .all(|(pattern, argument)| Self::pattern_matches(pattern, argument))
+            }
+            _ => false,
         }
     }
 
@@ -330,11 +375,15 @@ impl<'a> Interpreter<'a> {
             HirExpression::Infix(infix) => self.evaluate_infix(infix, id),
             HirExpression::Index(index) => self.evaluate_index(index, id),
             HirExpression::Constructor(constructor) => self.evaluate_constructor(constructor, id),
+            HirExpression::EnumConstructor(constructor) => {
+                self.evaluate_enum_constructor(constructor, id)
+            }
             HirExpression::MemberAccess(access) => self.evaluate_access(access, id),
             HirExpression::Call(call) => self.evaluate_call(call, id),
             HirExpression::MethodCall(call) => self.evaluate_method_call(call, id),
             HirExpression::Cast(cast) => self.evaluate_cast(&cast, id),
             HirExpression::If(if_) => self.evaluate_if(if_, id),
+            HirExpression::Match(match_) => self.evaluate_match(match_, id),
             HirExpression::Tuple(tuple) => self.evaluate_tuple(tuple),
             HirExpression::Lambda(lambda) => self.evaluate_lambda(lambda, id),
             HirExpression::Quote(tokens) => self.evaluate_quote(tokens, id),
@@ -1011,6 +1060,16 @@ impl<'a> Interpreter<'a> {
         Ok(Value::Struct(fields, typ))
     }
 
+    fn evaluate_enum_constructor(
+        &mut self,
+        constructor: HirEnumConstructorExpression,
+        id: ExprId,
+    ) -> IResult<Value> {
+        let arguments = try_vecmap(constructor.arguments, |argument| self.evaluate(argument))?;
+        let typ = self.interner.id_type(id).follow_bindings();
+        Ok(Value::Enum(constructor.variant_index, arguments, typ))
+    }
+
     fn evaluate_access(&mut self, access: HirMemberAccess, id: ExprId) -> IResult<Value> {
         let (fields, struct_type) = match self.evaluate(access.lhs)? {
             Value::Struct(fields, typ) => (fields, typ),
@@ -1215,6 +1274,28 @@ impl<'a> Interpreter<'a> {
         result
     }
 
+    fn evaluate_match(&mut self, match_: HirMatchExpression, id: ExprId) -> IResult<Value> {
+        let value = self.evaluate(match_.expression)?;
+        let location = self.interner.expr_location(&id);
+
+        for (pattern, branch) in match_.rules {
+            if Self::pattern_matches(&pattern, &value) {
+                let typ = value.get_type().into_owned();
+                self.push_scope();
+                let result = self
+                    .define_pattern(&pattern, &typ, value, location)
+                    .and_then(|()| self.evaluate(branch));
+                self.pop_scope();
+                return result;
+            }
+        }
+
+        // Match expressions are checked for exhaustiveness during elaboration so this
+        // is only reachable if an error was already issued for this expression.
+        let expected = self.interner.id_type(match_.expression);
+        Err(InterpreterError::TypeMismatch { expected, value, location })
+    }
+
     fn evaluate_tuple(&mut self, tuple: Vec<ExprId>) -> IResult<Value> {
         let fields = try_vecmap(tuple, |field| self.evaluate(field))?;
         Ok(Value::Tuple(fields))
@@ -8,7 +8,10 @@ use noirc_errors::Location;
 
 use crate::{
     ast::{ArrayLiteral, ConstructorExpression, Ident, IntegerBitSize, Signedness},
-    hir_def::expr::{HirArrayLiteral, HirConstructorExpression, HirIdent, HirLambda, ImplKind},
+    hir_def::expr::{
+        HirArrayLiteral, HirConstructorExpression, HirEnumConstructorExpression, HirIdent,
+        HirLambda, ImplKind,
+    },
     macros_api::{
         Expression, ExpressionKind, HirExpression, HirLiteral, Literal, NodeInterner, Path,
         StructId,
@@ -40,6 +43,8 @@ pub enum Value {
     Closure(HirLambda, Vec<Value>, Type),
     Tuple(Vec<Value>),
     Struct(HashMap<Rc<String>, Value>, Type),
+    /// An enum value holding the index of its variant and that variant's arguments.
+    Enum(usize, Vec<Value>, Type),
     Pointer(Shared<Value>),
     Array(Vector<Value>, Type),
     Slice(Vector<Value>, Type),
@@ -71,6 +76,7 @@ impl Value {
                 Type::Tuple(vecmap(fields, |field| field.get_type().into_owned()))
             }
             Value::Struct(_, typ) => return Cow::Borrowed(typ),
+            Value::Enum(_, _, typ) => return Cow::Borrowed(typ),
             Value::Array(_, typ) => return Cow::Borrowed(typ),
             Value::Slice(_, typ) => return Cow::Borrowed(typ),
             Value::Code(_) => Type::Quoted(QuotedType::Quoted),
@@ -166,6 +172,12 @@ impl Value {
                     struct_type,
                 }))
             }
+            value @ Value::Enum(..) => {
+                // There is no surface syntax to construct an enum variant without a path,
+                // so we go through the already-resolved HIR form instead.
+                let expr_id = value.into_hir_expression(interner, location)?;
+                ExpressionKind::Resolved(expr_id)
+            }
             Value::Array(elements, _) => {
                 let elements =
                     try_vecmap(elements, |element| element.into_expression(interner, location))?;
@@ -285,6 +297,23 @@ impl Value {
                     fields,
                 })
             }
+            Value::Enum(variant_index, arguments, typ) => {
+                let arguments = try_vecmap(arguments, |argument| {
+                    argument.into_hir_expression(interner, location)
+                })?;
+
+                let (r#type, enum_generics) = match typ.follow_bindings() {
+                    Type::Struct(def, generics) => (def, generics),
+                    _ => return Err(InterpreterError::NonStructInConstructor { typ, location }),
+                };
+
+                HirExpression::EnumConstructor(HirEnumConstructorExpression {
+                    r#type,
+                    enum_generics,
+                    variant_index,
+                    arguments,
+                })
+            }
             Value::Array(elements, _) => {
                 let elements = try_vecmap(elements, |element| {
                     element.into_hir_expression(interner, location)
@@ -385,6 +414,24 @@ impl Display for Value {
                 let fields = vecmap(fields, |(name, value)| format!("{}: {}", name, value));
                 write!(f, "{typename} {{ {} }}", fields.join(", "))
             }
+            Value::Enum(variant_index, arguments, typ) => {
+                match typ.follow_bindings() {
+                    Type::Struct(def, _) => {
+                        let def = def.borrow();
+                        write!(f, "{}::", def.name)?;
+                        match def.variant_name(*variant_index) {
+                            Some(name) => write!(f, "{name}")?,
+                            None => write!(f, "{variant_index}")?,
+                        }
+                    }
+                    other => write!(f, "{other}::{variant_index}")?,
+                }
+                if !arguments.is_empty() {
+                    let arguments = vecmap(arguments, ToString::to_string);
+                    write!(f, "({})", arguments.join(", "))?;
+                }
+                Ok(())
+            }
             Value::Pointer(value) => write!(f, "&mut {}", value.borrow()),
             Value::Array(values, _) => {
                 let values = vecmap(values, ToString::to_string);
@@ -3,7 +3,7 @@ use super::errors::{DefCollectorErrorKind, DuplicateType};
 use crate::elaborator::Elaborator;
 use crate::graph::CrateId;
 use crate::hir::comptime::{Interpreter, InterpreterError};
-use crate::hir::def_map::{CrateDefMap, LocalModuleId, ModuleId};
+use crate::hir::def_map::{CrateDefMap, LocalModuleId, ModuleDefId, ModuleId};
 use crate::hir::resolution::errors::ResolverError;
 use crate::{ResolvedGeneric, Type};
 
@@ -24,8 +24,9 @@ use crate::node_interner::{
 };
 
 use crate::ast::{
-    ExpressionKind, Ident, LetStatement, Literal, NoirFunction, NoirStruct, NoirTrait,
-    NoirTypeAlias, Path, PathKind, UnresolvedGenerics, UnresolvedTraitConstraint, UnresolvedType,
+    ExpressionKind, Ident, LetStatement, Literal, NoirEnumeration, NoirFunction, NoirStruct,
+    NoirTrait, NoirTypeAlias, Path, PathKind, TraitImplItem, UnresolvedGenerics,
+    UnresolvedTraitConstraint, UnresolvedType,
 };
 
 use crate::parser::{ParserError, SortedModule};
@@ -102,6 +103,12 @@ pub struct UnresolvedStruct {
     pub struct_def: NoirStruct,
 }
 
+pub struct UnresolvedEnum {
+    pub file_id: FileId,
+    pub module_id: LocalModuleId,
+    pub enum_def: NoirEnumeration,
+}
+
 #[derive(Clone)]
 pub struct UnresolvedTrait {
     pub file_id: FileId,
@@ -122,6 +129,10 @@ pub struct UnresolvedTraitImpl {
     pub generics: UnresolvedGenerics,
     pub where_clause: Vec<UnresolvedTraitConstraint>,
 
+    /// The associated types and constants defined in this impl.
+    /// These are only ever `TraitImplItem::Type` or `TraitImplItem::Constant`.
+    pub associated_items: Vec<TraitImplItem>,
+
     // Every field after this line is filled in later in the elaborator
     pub trait_id: Option<TraitId>,
     pub impl_id: Option<TraitImplId>,
@@ -129,7 +140,8 @@ pub struct UnresolvedTraitImpl {
     pub resolved_generics: Vec<ResolvedGeneric>,
 
     // The resolved generic on the trait itself. E.g. it is the `<C, D>` in
-    // `impl<A, B> Foo<C, D> for Bar<E, F> { ... }`
+    // `impl<A, B> Foo<C, D> for Bar<E, F> { ... }`, followed by the resolved
+    // associated types and constants of the impl.
     pub resolved_trait_generics: Vec<Type>,
 }
 
@@ -159,6 +171,7 @@ pub struct DefCollector {
 pub struct CollectedItems {
     pub(crate) functions: Vec<UnresolvedFunctions>,
     pub(crate) types: BTreeMap<StructId, UnresolvedStruct>,
+    pub(crate) enums: BTreeMap<StructId, UnresolvedEnum>,
     pub(crate) type_aliases: BTreeMap<TypeAliasId, UnresolvedTypeAlias>,
     pub(crate) traits: BTreeMap<TraitId, UnresolvedTrait>,
     pub(crate) globals: Vec<UnresolvedGlobal>,
@@ -170,6 +183,7 @@ impl CollectedItems {
     pub fn is_empty(&self) -> bool {
         self.functions.is_empty()
             && self.types.is_empty()
+            && self.enums.is_empty()
             && self.type_aliases.is_empty()
             && self.traits.is_empty()
             && self.globals.is_empty()
@@ -253,6 +267,7 @@ impl DefCollector {
             items: CollectedItems {
                 functions: vec![],
                 types: BTreeMap::new(),
+                enums: BTreeMap::new(),
                 type_aliases: BTreeMap::new(),
                 traits: BTreeMap::new(),
                 impls: HashMap::default(),
@@ -361,10 +376,28 @@ impl DefCollector {
                         ));
                     }
 
+                    let usage_tracker = &mut context.def_interner.usage_tracker;
+                    usage_tracker.mark_as_used(&resolved_import.used_names);
+
+                    // Trait imports are not tracked as their methods can be used without being named
+                    let is_trait_import = matches!(
+                        resolved_import.resolved_namespace.types,
+                        Some((ModuleDefId::TraitId(_), _, _))
+                    );
+                    let name = resolved_import.name;
+                    // Imports injected by macros have no span
+                    if !resolved_import.is_prelude
+                        && !is_trait_import
+                        && name.span() != Span::empty(0)
+                    {
+                        let module_id =
+                            ModuleId { krate: crate_id, local_id: resolved_import.module_scope };
+                        usage_tracker.add_unused_import(module_id, name.clone());
+                    }
+
                     // Populate module namespaces according to the imports used
                     let current_def_map = context.def_maps.get_mut(&crate_id).unwrap();
 
-                    let name = resolved_import.name;
                     for ns in resolved_import.resolved_namespace.iter_defs() {
                         let result = current_def_map.modules[resolved_import.module_scope.0]
                             .import(name.clone(), ns, resolved_import.is_prelude);
@@ -399,11 +432,33 @@ impl DefCollector {
         if !use_legacy {
             let mut more_errors = Elaborator::elaborate(context, crate_id, def_collector.items);
             errors.append(&mut more_errors);
+
+            // Dependencies aren't checked as their imports may be used by the crates depending on them
+            if crate_id.is_root() {
+                errors.extend(unused_import_warnings(context, crate_id));
+            }
             return errors;
         }
 
         let mut resolved_module = ResolvedModule { errors, ..Default::default() };
 
+        // Enums are only supported by the elaborator
+        for unresolved in def_collector.items.enums.values() {
+            let span = unresolved.enum_def.span;
+            let error = ResolverError::EnumsRequireElaborator { span };
+            resolved_module.errors.push((error.into(), unresolved.file_id));
+        }
+
+        // As are associated types and constants. Traits may still declare them, in which
+        // case they are ignored, but impls may not provide them.
+        for unresolved in &def_collector.items.trait_impls {
+            if !unresolved.associated_items.is_empty() {
+                let span = unresolved.trait_path.span();
+                let error = ResolverError::AssociatedItemsRequireElaborator { span };
+                resolved_module.errors.push((error.into(), unresolved.file_id));
+            }
+        }
+
         // We must first resolve and intern the globals before we can resolve any stmts inside each function.
         // Each function uses its own resolver with a newly created ScopeForest, and must be resolved again to be within a function's scope
         //
@@ -500,6 +555,39 @@ impl DefCollector {
     }
 }
 
+/// Returns a warning for each import of the given crate that was never used
+fn unused_import_warnings(context: &Context, crate_id: CrateId) -> Vec<(CompilationError, FileId)> {
+    let def_map = &context.def_maps[&crate_id];
+
+    // Without `pub use`, imports are also how a library re-exports items, which may only be used
+    // by the crates depending on it. So only programs and contracts have their imports checked.
+    let is_library = def_map.main_function().is_none()
+        && def_map.modules().iter().all(|(_, module)| !module.is_contract);
+    if is_library {
+        return Vec::new();
+    }
+
+    let unused_imports = context.def_interner.usage_tracker.unused_imports();
+
+    let mut module_ids: Vec<_> =
+        unused_imports.keys().filter(|module_id| module_id.krate == crate_id).collect();
+    module_ids.sort();
+
+    let mut warnings = Vec::new();
+    for module_id in module_ids {
+        let file_id = def_map.file_id(module_id.local_id);
+
+        let mut names: Vec<_> = unused_imports[module_id].iter().collect();
+        names.sort_by_key(|name| name.span().start());
+
+        for name in names {
+            let error = ResolverError::UnusedImport { ident: name.clone() };
+            warnings.push((error.into(), file_id));
+        }
+    }
+    warnings
+}
+
 fn add_import_reference(
     def_id: crate::macros_api::ModuleDefId,
     name: &Ident,
@@ -543,7 +631,7 @@ fn inject_prelude(
             span: Span::default(),
         };
 
-        if let Ok(PathResolution { module_def_id, error }) = path_resolver::resolve_path(
+        if let Ok(PathResolution { module_def_id, error, .. }) = path_resolver::resolve_path(
             &context.def_maps,
             ModuleId { krate: crate_id, local_id: crate_root },
             path,
@@ -1,4 +1,5 @@
 use std::path::Path;
+use std::rc::Rc;
 use std::vec;
 
 use acvm::{AcirField, FieldElement};
@@ -9,19 +10,22 @@ use num_traits::Num;
 use rustc_hash::FxHashMap as HashMap;
 
 use crate::ast::{
-    FunctionDefinition, Ident, ItemVisibility, LetStatement, ModuleDeclaration, NoirFunction,
-    NoirStruct, NoirTrait, NoirTraitImpl, NoirTypeAlias, Pattern, TraitImplItem, TraitItem,
-    TypeImpl,
+    FunctionDefinition, Ident, ItemVisibility, LetStatement, ModuleDeclaration, NoirEnumeration,
+    NoirFunction, NoirStruct, NoirTrait, NoirTraitImpl, NoirTypeAlias, Pattern, TraitImplItem,
+    TraitItem, TypeImpl, UnresolvedGeneric, UnresolvedTypeExpression,
 };
+use crate::hir::resolution::resolver::SELF_TYPE_NAME;
+use crate::hir_def::traits::{TraitConstant, TraitType};
 use crate::macros_api::NodeInterner;
 use crate::node_interner::ReferenceId;
 use crate::{
     graph::CrateId,
-    hir::def_collector::dc_crate::{UnresolvedStruct, UnresolvedTrait},
+    hir::def_collector::dc_crate::{UnresolvedEnum, UnresolvedStruct, UnresolvedTrait},
     macros_api::MacroProcessor,
     node_interner::{FunctionModifiers, TraitId, TypeAliasId},
     parser::{SortedModule, SortedSubModule},
 };
+use crate::{Kind, ResolvedGeneric, Type, TypeVariable};
 
 use super::{
     dc_crate::{
@@ -90,6 +94,8 @@ pub fn collect_defs(
 
     errors.extend(collector.collect_structs(context, ast.types, crate_id));
 
+    errors.extend(collector.collect_enums(context, ast.enums, crate_id));
+
     errors.extend(collector.collect_type_aliases(context, ast.type_aliases));
 
     errors.extend(collector.collect_functions(context, ast.functions, crate_id));
@@ -185,6 +191,7 @@ impl<'a> ModCollector<'a> {
                 generics: trait_impl.impl_generics,
                 where_clause: trait_impl.where_clause,
                 trait_generics: trait_impl.trait_generics,
+                associated_items: trait_impl.items,
 
                 // These last fields are filled later on
                 trait_id: None,
@@ -323,6 +330,70 @@ impl<'a> ModCollector<'a> {
         definition_errors
     }
 
+    /// Collect any enum definitions declared within the ast.
+    /// Enums share the struct namespace so a duplicate of either is reported.
+    fn collect_enums(
+        &mut self,
+        context: &mut Context,
+        enums: Vec<NoirEnumeration>,
+        krate: CrateId,
+    ) -> Vec<(CompilationError, FileId)> {
+        let mut definition_errors = vec![];
+        for enum_definition in enums {
+            let name = enum_definition.name.clone();
+
+            let unresolved = UnresolvedEnum {
+                file_id: self.file_id,
+                module_id: self.module_id,
+                enum_def: enum_definition,
+            };
+
+            let resolved_generics = context.resolve_generics(
+                &unresolved.enum_def.generics,
+                &mut definition_errors,
+                self.file_id,
+            );
+
+            // Create the corresponding module for the enum namespace
+            let id = match self.push_child_module(
+                context,
+                &name,
+                Location::new(name.span(), self.file_id),
+                false,
+                false,
+            ) {
+                Ok(module_id) => context.def_interner.new_enum(
+                    &unresolved,
+                    resolved_generics,
+                    krate,
+                    module_id.local_id,
+                    self.file_id,
+                ),
+                Err(error) => {
+                    definition_errors.push((error.into(), self.file_id));
+                    continue;
+                }
+            };
+
+            let result =
+                self.def_collector.def_map.modules[self.module_id.0].declare_struct(name, id);
+
+            if let Err((first_def, second_def)) = result {
+                let error = DefCollectorErrorKind::Duplicate {
+                    typ: DuplicateType::TypeDefinition,
+                    first_def,
+                    second_def,
+                };
+                definition_errors.push((error.into(), self.file_id));
+            }
+
+            self.def_collector.items.enums.insert(id, unresolved);
+
+            context.def_interner.add_definition_location(ReferenceId::Struct(id));
+        }
+        definition_errors
+    }
+
     /// Collect any type aliases definitions declared within the ast.
     /// Returns a vector of errors if any type aliases were already defined.
     fn collect_type_aliases(
@@ -419,6 +490,9 @@ impl<'a> ModCollector<'a> {
             };
 
             let mut method_ids = HashMap::default();
+            let mut associated_types = Vec::new();
+            let mut associated_constants = Vec::new();
+
             for trait_item in &trait_definition.items {
                 match trait_item {
                     TraitItem::Function {
@@ -479,7 +553,7 @@ impl<'a> ModCollector<'a> {
                             }
                         }
                     }
-                    TraitItem::Constant { name, .. } => {
+                    TraitItem::Constant { name, typ, default_value } => {
                         let global_id = context.def_interner.push_empty_global(
                             name.clone(),
                             trait_id.0.local_id,
@@ -499,7 +573,28 @@ impl<'a> ModCollector<'a> {
                                 second_def,
                             };
                             errors.push((error.into(), self.file_id));
+                            continue;
                         }
+
+                        let generic =
+                            UnresolvedGeneric::Numeric { ident: name.clone(), typ: typ.clone() };
+                        let kind = generic.kind().unwrap_or_else(|error| {
+                            errors.push((error.into(), self.file_id));
+                            Kind::Numeric(Box::new(Type::Error))
+                        });
+
+                        let default_value = default_value.as_ref().and_then(|value| {
+                            UnresolvedTypeExpression::from_expr(value.clone(), value.span)
+                                .map_err(|error| errors.push((error.into(), self.file_id)))
+                                .ok()
+                        });
+
+                        let generic = associated_item_generic(context, name, kind);
+                        associated_constants.push(TraitConstant {
+                            name: name.clone(),
+                            generic,
+                            default_value,
+                        });
                     }
                     TraitItem::Type { name } => {
                         // TODO(nickysn or alexvitkov): implement context.def_interner.push_empty_type_alias and get an id, instead of using TypeAliasId::dummy_id()
@@ -513,7 +608,11 @@ impl<'a> ModCollector<'a> {
                                 second_def,
                             };
                             errors.push((error.into(), self.file_id));
+                            continue;
                         }
+
+                        let generic = associated_item_generic(context, name, Kind::Normal);
+                        associated_types.push(TraitType { name: name.clone(), generic });
                     }
                 }
             }
@@ -530,7 +629,13 @@ impl<'a> ModCollector<'a> {
                 method_ids,
                 fns_with_default_impl: unresolved_functions,
             };
-            context.def_interner.push_empty_trait(trait_id, &unresolved, resolved_generics);
+            context.def_interner.push_empty_trait(
+                trait_id,
+                &unresolved,
+                resolved_generics,
+                associated_types,
+                associated_constants,
+            );
 
             context.def_interner.add_definition_location(ReferenceId::Trait(trait_id));
 
@@ -815,6 +920,14 @@ fn is_native_field(str: &str) -> bool {
     }
 }
 
+/// Creates the generic standing in for an associated type or constant of a trait.
+/// These are named `Self::Name` since that is how they are referred to within the trait.
+fn associated_item_generic(context: &mut Context, name: &Ident, kind: Kind) -> ResolvedGeneric {
+    let type_var = TypeVariable::unbound(context.def_interner.next_type_variable_id());
+    let name_string = Rc::new(format!("{SELF_TYPE_NAME}::{name}"));
+    ResolvedGeneric { name: name_string, type_var, kind, span: name.span() }
+}
+
 pub(crate) fn collect_trait_impl_functions(
     interner: &mut NodeInterner,
     trait_impl: &mut NoirTraitImpl,
@@ -827,12 +940,15 @@ pub(crate) fn collect_trait_impl_functions(
 
     let module = ModuleId { krate, local_id };
 
+    // Any associated types and constants are left in `trait_impl.items`
     for item in std::mem::take(&mut trait_impl.items) {
         if let TraitImplItem::Function(impl_method) = item {
             let func_id = interner.push_empty_fn();
             let location = Location::new(impl_method.span(), file_id);
             interner.push_function(func_id, &impl_method.def, module, location);
             unresolved_functions.push_fn(local_id, func_id, impl_method);
+        } else {
+            trait_impl.items.push(item);
         }
     }
 
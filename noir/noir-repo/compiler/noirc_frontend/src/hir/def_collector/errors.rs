@@ -58,6 +58,10 @@ pub enum DefCollectorErrorKind {
     TraitNotFound { trait_path: Path },
     #[error("Missing Trait method implementation")]
     TraitMissingMethod { trait_name: Ident, method_name: Ident, trait_impl_span: Span },
+    #[error("Missing Trait associated type or constant")]
+    TraitMissingAssociatedItem { trait_name: Ident, item_name: Ident, trait_impl_span: Span },
+    #[error("Associated item not in trait")]
+    AssociatedItemNotInTrait { trait_name: Ident, item_name: Ident },
     #[error("Module is already part of the crate")]
     ModuleAlreadyPartOfCrate { mod_name: Ident, span: Span },
     #[error("Module was originally declared here")]
@@ -216,6 +220,26 @@ impl<'a> From<&'a DefCollectorErrorKind> for Diagnostic {
                     *trait_impl_span,
                 )
             }
+            DefCollectorErrorKind::TraitMissingAssociatedItem {
+                trait_name,
+                item_name,
+                trait_impl_span,
+            } => {
+                let primary_message = format!(
+                    "Associated type or constant `{item_name}` from trait `{trait_name}` is not implemented"
+                );
+                Diagnostic::simple_error(
+                    primary_message,
+                    format!("Please define {item_name} here"),
+                    *trait_impl_span,
+                )
+            }
+            DefCollectorErrorKind::AssociatedItemNotInTrait { trait_name, item_name } => {
+                let primary_message = format!(
+                    "`{item_name}` is not an associated type or constant of trait `{trait_name}`"
+                );
+                Diagnostic::simple_error(primary_message, String::new(), item_name.span())
+            }
             DefCollectorErrorKind::NotATrait { not_a_trait_name } => {
                 let span = not_a_trait_name.span();
                 Diagnostic::simple_error(
@@ -147,6 +147,11 @@ impl CrateDefMap {
         self.krate
     }
 
+    /// Returns the root modules of this crate's dependencies, keyed by the name they are imported as
+    pub fn extern_prelude(&self) -> &BTreeMap<String, ModuleId> {
+        &self.extern_prelude
+    }
+
     /// Find the main function for this crate
     pub fn main_function(&self) -> Option<FuncId> {
         let root_module = &self.modules()[self.root.0];
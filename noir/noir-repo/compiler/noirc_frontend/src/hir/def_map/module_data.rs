@@ -38,10 +38,14 @@ impl ModuleData {
         }
     }
 
-    pub(crate) fn scope(&self) -> &ItemScope {
+    pub fn scope(&self) -> &ItemScope {
         &self.scope
     }
 
+    pub fn definitions(&self) -> &ItemScope {
+        &self.definitions
+    }
+
     fn declare(
         &mut self,
         name: Ident,
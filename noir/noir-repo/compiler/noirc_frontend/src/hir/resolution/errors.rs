@@ -20,6 +20,8 @@ pub enum ResolverError {
     DuplicateDefinition { name: String, first_span: Span, second_span: Span },
     #[error("Unused variable")]
     UnusedVariable { ident: Ident },
+    #[error("Unused import")]
+    UnusedImport { ident: Ident },
     #[error("Could not find variable in this scope")]
     VariableNotDeclared { name: String, span: Span },
     #[error("path is not an identifier")]
@@ -116,6 +118,20 @@ pub enum ResolverError {
     NonFunctionInAnnotation { span: Span },
     #[error("Unknown annotation")]
     UnknownAnnotation { span: Span },
+    #[error("No such variant in enum")]
+    NoSuchVariant { variant: Ident, enum_name: String },
+    #[error("Incorrect number of arguments to enum variant")]
+    VariantArgumentCountMismatch { variant: String, expected: usize, actual: usize, span: Span },
+    #[error("Refutable pattern in binding")]
+    RefutablePattern { missing: String, span: Span },
+    #[error("Non-exhaustive match")]
+    NonExhaustiveMatch { missing: String, span: Span },
+    #[error("Unreachable match arm")]
+    UnreachablePattern { span: Span },
+    #[error("Enums and match expressions require the elaborator")]
+    EnumsRequireElaborator { span: Span },
+    #[error("Associated types and constants require the elaborator")]
+    AssociatedItemsRequireElaborator { span: Span },
 }
 
 impl ResolverError {
@@ -148,6 +164,15 @@ impl<'a> From<&'a ResolverError> for Diagnostic {
                     ident.span(),
                 )
             }
+            ResolverError::UnusedImport { ident } => {
+                let name = &ident.0.contents;
+
+                Diagnostic::simple_warning(
+                    format!("unused import {name}"),
+                    "unused import ".to_string(),
+                    ident.span(),
+                )
+            }
             ResolverError::VariableNotDeclared { name, span } => Diagnostic::simple_error(
                 format!("cannot find `{name}` in this scope "),
                 "not found in this scope".to_string(),
@@ -467,6 +492,56 @@ impl<'a> From<&'a ResolverError> for Diagnostic {
                     *span,
                 )
             },
+            ResolverError::NoSuchVariant { variant, enum_name } => {
+                Diagnostic::simple_error(
+                    format!("no such variant {variant} defined in enum {enum_name}"),
+                    String::new(),
+                    variant.span(),
+                )
+            },
+            ResolverError::VariantArgumentCountMismatch { variant, expected, actual, span } => {
+                let plural = if *expected == 1 { "" } else { "s" };
+                Diagnostic::simple_error(
+                    format!("Variant {variant} expects {expected} argument{plural} but {actual} were given"),
+                    String::new(),
+                    *span,
+                )
+            },
+            ResolverError::RefutablePattern { missing, span } => {
+                Diagnostic::simple_error(
+                    format!("Refutable pattern in binding: `{missing}` not covered"),
+                    "Bindings must match every possible value, use a `match` expression instead".into(),
+                    *span,
+                )
+            },
+            ResolverError::NonExhaustiveMatch { missing, span } => {
+                Diagnostic::simple_error(
+                    format!("Non-exhaustive match: pattern `{missing}` is not covered"),
+                    "Add a match arm for this pattern or a wildcard `_` arm".into(),
+                    *span,
+                )
+            },
+            ResolverError::UnreachablePattern { span } => {
+                Diagnostic::simple_warning(
+                    "Unreachable match arm".into(),
+                    "This pattern is already covered by a previous arm".into(),
+                    *span,
+                )
+            },
+            ResolverError::EnumsRequireElaborator { span } => {
+                Diagnostic::simple_error(
+                    "Enums and match expressions are not supported by the legacy resolver".into(),
+                    "Remove the `--use-legacy` flag to compile this program".into(),
+                    *span,
+                )
+            },
+            ResolverError::AssociatedItemsRequireElaborator { span } => {
+                Diagnostic::simple_error(
+                    "Associated types and constants are not supported by the legacy resolver".into(),
+                    "Remove the `--use-legacy` flag to compile this program".into(),
+                    *span,
+                )
+            },
         }
     }
 }
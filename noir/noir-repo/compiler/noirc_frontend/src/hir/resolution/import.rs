@@ -23,6 +23,7 @@ struct NamespaceResolution {
     module_id: ModuleId,
     namespace: PerNs,
     error: Option<PathResolutionError>,
+    used_names: Vec<(ModuleId, Ident)>,
 }
 
 type NamespaceResolutionResult = Result<NamespaceResolution, PathResolutionError>;
@@ -31,6 +32,10 @@ pub struct PathResolution {
     pub module_def_id: ModuleDefId,
 
     pub error: Option<PathResolutionError>,
+
+    /// The names looked up in each module while resolving the path.
+    /// This is used to track which imports are used.
+    pub used_names: Vec<(ModuleId, Ident)>,
 }
 
 pub(crate) type PathResolutionResult = Result<PathResolution, PathResolutionError>;
@@ -53,6 +58,8 @@ pub struct ResolvedImport {
     pub module_scope: LocalModuleId,
     pub is_prelude: bool,
     pub error: Option<PathResolutionError>,
+    // The names looked up in each module while resolving the import
+    pub used_names: Vec<(ModuleId, Ident)>,
 }
 
 impl From<PathResolutionError> for CompilationError {
@@ -88,6 +95,7 @@ pub fn resolve_import(
         module_id: resolved_module,
         namespace: resolved_namespace,
         mut error,
+        used_names,
     } = resolve_path_to_ns(import_directive, crate_id, crate_id, def_maps, path_references)?;
 
     let name = resolve_path_name(import_directive);
@@ -118,6 +126,7 @@ pub fn resolve_import(
         module_scope,
         is_prelude: import_directive.is_prelude,
         error,
+        used_names,
     })
 }
 
@@ -227,6 +236,7 @@ fn resolve_name_in_module(
             module_id: current_mod_id,
             namespace: PerNs::types(current_mod_id.into()),
             error: None,
+            used_names: Vec::new(),
         });
     }
 
@@ -236,6 +246,7 @@ fn resolve_name_in_module(
         return Err(PathResolutionError::Unresolved(first_segment.clone()));
     }
 
+    let mut used_names = vec![(current_mod_id, first_segment.clone())];
     let mut warning: Option<PathResolutionError> = None;
     for (last_segment, current_segment) in import_path.iter().zip(import_path.iter().skip(1)) {
         let (typ, visibility) = match current_ns.types {
@@ -292,10 +303,16 @@ fn resolve_name_in_module(
             return Err(PathResolutionError::Unresolved(current_segment.clone()));
         }
 
+        used_names.push((current_mod_id, current_segment.clone()));
         current_ns = found_ns;
     }
 
-    Ok(NamespaceResolution { module_id: current_mod_id, namespace: current_ns, error: warning })
+    Ok(NamespaceResolution {
+        module_id: current_mod_id,
+        namespace: current_ns,
+        error: warning,
+        used_names,
+    })
 }
 
 fn resolve_path_name(import_directive: &ImportDirective) -> Ident {
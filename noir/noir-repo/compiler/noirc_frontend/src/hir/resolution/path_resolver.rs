@@ -69,5 +69,9 @@ pub fn resolve_path(
     let id =
         namespace.values.or(namespace.types).map(|(id, _, _)| id).expect("Found empty namespace");
 
-    Ok(PathResolution { module_def_id: id, error: resolved_import.error })
+    Ok(PathResolution {
+        module_def_id: id,
+        error: resolved_import.error,
+        used_names: resolved_import.used_names,
+    })
 }
@@ -247,6 +247,7 @@ impl<'a> Resolver<'a> {
                     trait_path: path.clone(),
                     trait_id: None,
                     trait_generics: args.to_vec(),
+                    named_generics: Vec::new(),
                 };
                 let new_trait_constraint = UnresolvedTraitConstraint {
                     typ: new_generic_type,
@@ -528,6 +529,10 @@ impl<'a> Resolver<'a> {
             vecmap(constraint.trait_bound.trait_generics, |typ| self.resolve_type(typ));
 
         let span = constraint.trait_bound.trait_path.span();
+
+        if !constraint.trait_bound.named_generics.is_empty() {
+            self.push_err(ResolverError::AssociatedItemsRequireElaborator { span });
+        }
         let the_trait = self.lookup_trait_or_error(constraint.trait_bound.trait_path)?;
         let trait_id = the_trait.id;
 
@@ -766,7 +771,7 @@ impl<'a> Resolver<'a> {
 
         // If we cannot find a local generic of the same name, try to look up a global
         match self.path_resolver.resolve(self.def_maps, path.clone(), &mut None) {
-            Ok(PathResolution { module_def_id: ModuleDefId::GlobalId(id), error }) => {
+            Ok(PathResolution { module_def_id: ModuleDefId::GlobalId(id), error, .. }) => {
                 if let Some(current_item) = self.current_item {
                     self.interner.add_global_dependency(current_item, id);
                 }
@@ -1606,6 +1611,10 @@ impl<'a> Resolver<'a> {
                 consequence: self.resolve_expression(if_expr.consequence),
                 alternative: if_expr.alternative.map(|e| self.resolve_expression(e)),
             }),
+            ExpressionKind::Match(_) => {
+                self.push_err(ResolverError::EnumsRequireElaborator { span: expr.span });
+                HirExpression::Error
+            }
             ExpressionKind::Index(indexed_expr) => HirExpression::Index(HirIndexExpression {
                 collection: self.resolve_expression(indexed_expr.collection),
                 index: self.resolve_expression(indexed_expr.index),
@@ -1765,6 +1774,12 @@ impl<'a> Resolver<'a> {
                 let location = Location::new(span, self.file);
                 HirPattern::Struct(typ, fields, location)
             }
+            Pattern::Variant(_, _, span) => {
+                self.push_err(ResolverError::EnumsRequireElaborator { span });
+                let name = ERROR_IDENT.into();
+                let identifier = self.add_variable_decl(name, false, true, definition);
+                HirPattern::Identifier(identifier)
+            }
         }
     }
 
@@ -15,7 +15,7 @@ use crate::{
         def_map::{CrateDefMap, ModuleDefId, ModuleId},
         Context,
     },
-    hir_def::traits::{TraitConstant, TraitFunction, TraitImpl, TraitType},
+    hir_def::traits::{TraitFunction, TraitImpl},
     node_interner::{FuncId, NodeInterner, TraitId},
     GenericTypeVars, Shared, Type, TypeVariableKind,
 };
@@ -46,14 +46,17 @@ pub(crate) fn resolve_traits(
         );
         let generic_type_vars = generics.iter().map(|generic| generic.type_var.clone()).collect();
 
-        context.def_interner.push_empty_trait(trait_id, &unresolved_trait, generics);
+        // Associated types and constants are ignored here. An error is issued
+        // before resolution begins for any impl which provides them.
+        let (types, constants) = (Vec::new(), Vec::new());
+        context.def_interner.push_empty_trait(
+            trait_id,
+            &unresolved_trait,
+            generics,
+            types,
+            constants,
+        );
 
-        // Resolve order
-        // 1. Trait Types ( Trait constants can have a trait type, therefore types before constants)
-        let _ = resolve_trait_types(context, crate_id, &unresolved_trait);
-        // 2. Trait Constants ( Trait's methods can use trait types & constants, therefore they should be after)
-        let _ = resolve_trait_constants(context, crate_id, &unresolved_trait);
-        // 3. Trait Methods
         let (methods, errors) = resolve_trait_methods(
             context,
             trait_id,
@@ -78,23 +81,6 @@ pub(crate) fn resolve_traits(
     all_errors
 }
 
-fn resolve_trait_types(
-    _context: &mut Context,
-    _crate_id: CrateId,
-    _unresolved_trait: &UnresolvedTrait,
-) -> (Vec<TraitType>, Vec<(CompilationError, FileId)>) {
-    // TODO
-    (vec![], vec![])
-}
-fn resolve_trait_constants(
-    _context: &mut Context,
-    _crate_id: CrateId,
-    _unresolved_trait: &UnresolvedTrait,
-) -> (Vec<TraitConstant>, Vec<(CompilationError, FileId)>) {
-    // TODO
-    (vec![], vec![])
-}
-
 fn resolve_trait_methods(
     context: &mut Context,
     trait_id: TraitId,
@@ -389,7 +375,7 @@ pub(crate) fn resolve_trait_by_path(
     let path_resolver = StandardPathResolver::new(module);
 
     match path_resolver.resolve(def_maps, path.clone(), &mut None) {
-        Ok(PathResolution { module_def_id: ModuleDefId::TraitId(trait_id), error }) => {
+        Ok(PathResolution { module_def_id: ModuleDefId::TraitId(trait_id), error, .. }) => {
             Ok((trait_id, error))
         }
         Ok(_) => Err(DefCollectorErrorKind::NotATrait { not_a_trait_name: path }),
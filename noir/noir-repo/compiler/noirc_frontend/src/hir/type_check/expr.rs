@@ -316,6 +316,10 @@ impl<'interner> TypeChecker<'interner> {
             HirExpression::Unquote(block) => {
                 unreachable!("Unquote remaining during type checking {block:?}")
             }
+
+            // Enums are only supported by the elaborator. The legacy resolver issues an
+            // error and never creates these nodes.
+            HirExpression::EnumConstructor(_) | HirExpression::Match(_) => Type::Error,
         };
 
         self.interner.push_expr_type(*expr_id, typ.clone());
@@ -253,8 +253,11 @@ pub(crate) fn check_trait_impl_method_matches_declaration(
         (trait_info.self_type_typevar.clone(), impl_.typ.clone()),
     );
 
-    if trait_info.generics.len() != impl_.trait_generics.len() {
-        let expected = trait_info.generics.len();
+    // This includes any associated types and constants of the trait
+    let trait_generics = trait_info.all_generics();
+
+    if trait_generics.len() != impl_.trait_generics.len() {
+        let expected = trait_generics.len();
         let found = impl_.trait_generics.len();
         let span = impl_.ident.span();
         let item = trait_info.name.to_string();
@@ -262,7 +265,7 @@ pub(crate) fn check_trait_impl_method_matches_declaration(
     }
 
     // Substitute each generic on the trait with the corresponding generic on the impl
-    for (generic, arg) in trait_info.generics.iter().zip(&impl_.trait_generics) {
+    for (generic, arg) in trait_generics.iter().zip(&impl_.trait_generics) {
         bindings.insert(generic.type_var.id(), (generic.type_var.clone(), arg.clone()));
     }
 
@@ -703,7 +706,11 @@ pub mod test {
             self.0
                 .get(&name.0.contents)
                 .cloned()
-                .map(|module_def_id| PathResolution { module_def_id, error: None })
+                .map(|module_def_id| PathResolution {
+                    module_def_id,
+                    error: None,
+                    used_names: Vec::new(),
+                })
                 .ok_or_else(move || PathResolutionError::Unresolved(name.clone()))
         }
 
@@ -131,6 +131,8 @@ impl<'interner> TypeChecker<'interner> {
                     }
                 }
             }
+            // Variant patterns are only created by the elaborator
+            HirPattern::Variant(..) => (),
         }
     }
 
@@ -32,6 +32,8 @@ pub enum HirExpression {
     MethodCall(HirMethodCallExpression),
     Cast(HirCastExpression),
     If(HirIfExpression),
+    Match(HirMatchExpression),
+    EnumConstructor(HirEnumConstructorExpression),
     Tuple(Vec<ExprId>),
     Lambda(HirLambda),
     Quote(Tokens),
@@ -252,6 +254,23 @@ pub struct HirConstructorExpression {
     pub fields: Vec<(Ident, ExprId)>,
 }
 
+/// Constructs the variant at `variant_index` of an enum, as in `Option::Some(x)`.
+#[derive(Debug, Clone)]
+pub struct HirEnumConstructorExpression {
+    pub r#type: Shared<StructType>,
+    pub enum_generics: Vec<Type>,
+    pub variant_index: usize,
+    pub arguments: Vec<ExprId>,
+}
+
+/// Corresponds to `match expression { pattern => branch, ... }`. Rules are checked
+/// in order and are known to cover every possible value of `expression`.
+#[derive(Debug, Clone)]
+pub struct HirMatchExpression {
+    pub expression: ExprId,
+    pub rules: Vec<(HirPattern, ExprId)>,
+}
+
 /// Indexing, as in `array[index]`
 #[derive(Debug, Clone)]
 pub struct HirIndexExpression {
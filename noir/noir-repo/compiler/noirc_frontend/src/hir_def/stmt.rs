@@ -70,6 +70,9 @@ pub enum HirPattern {
     Mutable(Box<HirPattern>, Location),
     Tuple(Vec<HirPattern>, Location),
     Struct(Type, Vec<(Ident, HirPattern)>, Location),
+    /// A pattern matching the variant at the given index of the enum type,
+    /// along with patterns for each of the variant's parameters.
+    Variant(Type, usize, Vec<HirPattern>, Location),
 }
 
 impl HirPattern {
@@ -79,6 +82,7 @@ impl HirPattern {
             HirPattern::Mutable(pattern, _) => pattern.field_count(),
             HirPattern::Tuple(fields, _) => fields.len(),
             HirPattern::Struct(_, fields, _) => fields.len(),
+            HirPattern::Variant(_, _, parameters, _) => parameters.len(),
         }
     }
 
@@ -101,7 +105,8 @@ impl HirPattern {
             HirPattern::Identifier(ident) => ident.location.span,
             HirPattern::Mutable(_, location)
             | HirPattern::Tuple(_, location)
-            | HirPattern::Struct(_, _, location) => location.span,
+            | HirPattern::Struct(_, _, location)
+            | HirPattern::Variant(_, _, _, location) => location.span,
         }
     }
 
@@ -110,7 +115,8 @@ impl HirPattern {
             HirPattern::Identifier(ident) => ident.location,
             HirPattern::Mutable(_, location)
             | HirPattern::Tuple(_, location)
-            | HirPattern::Struct(_, _, location) => *location,
+            | HirPattern::Struct(_, _, location)
+            | HirPattern::Variant(_, _, _, location) => *location,
         }
     }
 }
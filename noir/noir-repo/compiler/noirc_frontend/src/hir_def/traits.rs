@@ -1,13 +1,13 @@
 use rustc_hash::FxHashMap as HashMap;
 
-use crate::ast::{Ident, NoirFunction};
+use crate::ast::{Ident, NoirFunction, UnresolvedTypeExpression};
 use crate::{
     graph::CrateId,
     node_interner::{FuncId, TraitId, TraitMethodId},
-    Generics, Type, TypeBindings, TypeVariable, TypeVariableId,
+    Generics, ResolvedGeneric, Type, TypeBindings, TypeVariable, TypeVariableId,
 };
 use fm::FileId;
-use noirc_errors::{Location, Span};
+use noirc_errors::Location;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TraitFunction {
@@ -18,18 +18,27 @@ pub struct TraitFunction {
     pub default_impl_module_id: crate::hir::def_map::LocalModuleId,
 }
 
+/// An associated constant declared in a trait, such as `let Size: u32;`
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TraitConstant {
     pub name: Ident,
-    pub ty: Type,
-    pub span: Span,
+
+    /// The numeric generic standing in for this constant within the trait.
+    /// Each impl of the trait binds it to the value the impl provides.
+    pub generic: ResolvedGeneric,
+
+    /// The value used by impls which do not provide their own, if any.
+    pub default_value: Option<UnresolvedTypeExpression>,
 }
 
+/// An associated type declared in a trait, such as `type Item;`
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TraitType {
     pub name: Ident,
-    pub ty: Type,
-    pub span: Span,
+
+    /// The generic standing in for this type within the trait.
+    /// Each impl of the trait binds it to the type the impl provides.
+    pub generic: ResolvedGeneric,
 }
 
 /// Represents a trait in the type system. Each instance of this struct
@@ -51,6 +60,8 @@ pub struct Trait {
     /// the information needed to create the full TraitFunction.
     pub method_ids: HashMap<String, FuncId>,
 
+    /// The associated constants and types of this trait. These are treated as
+    /// additional, implicit generics of the trait - see `Trait::all_generics`.
     pub constants: Vec<TraitConstant>,
     pub types: Vec<TraitType>,
 
@@ -71,6 +82,9 @@ pub struct TraitImpl {
     pub ident: Ident,
     pub typ: Type,
     pub trait_id: TraitId,
+
+    /// The trait's generics followed by the associated types and constants
+    /// provided by this impl, in the same order as `Trait::all_generics`.
     pub trait_generics: Vec<Type>,
     pub file: FileId,
     pub methods: Vec<FuncId>, // methods[i] is the implementation of trait.methods[i] for Type typ
@@ -86,6 +100,9 @@ pub struct TraitImpl {
 pub struct TraitConstraint {
     pub typ: Type,
     pub trait_id: TraitId,
+
+    /// The trait's generics followed by its associated types and constants,
+    /// in the same order as `Trait::all_generics`.
     pub trait_generics: Vec<Type>,
 }
 
@@ -128,6 +145,31 @@ impl Trait {
         }
         None
     }
+
+    /// Returns the trait's own generics followed by a generic for each associated type
+    /// and then each associated constant. Trait constraints and trait impls store their
+    /// trait generics in this order.
+    pub fn all_generics(&self) -> Generics {
+        let associated_types = self.types.iter().map(|typ| typ.generic.clone());
+        let associated_constants = self.constants.iter().map(|constant| constant.generic.clone());
+        self.generics.iter().cloned().chain(associated_types).chain(associated_constants).collect()
+    }
+
+    /// Returns the index within `all_generics` of the associated type or constant with the
+    /// given name, if this trait has one. Types take precedence over constants since a
+    /// trait may declare both under the same name.
+    pub fn find_associated_item(&self, name: &str) -> Option<usize> {
+        let type_index = self.types.iter().position(|typ| typ.name == *name);
+        type_index
+            .map(|index| self.generics.len() + index)
+            .or_else(|| self.find_associated_constant(name))
+    }
+
+    /// Returns the index within `all_generics` of the associated constant with the given name.
+    pub fn find_associated_constant(&self, name: &str) -> Option<usize> {
+        let index = self.constants.iter().position(|constant| constant.name == *name)?;
+        Some(self.generics.len() + self.types.len() + index)
+    }
 }
 
 impl std::fmt::Display for Trait {
@@ -166,13 +166,15 @@ impl Type {
         match self {
             Type::Slice(_) => true,
             Type::Struct(struct_typ, generics) => {
-                let fields = struct_typ.borrow().get_fields(generics);
+                let struct_typ = struct_typ.borrow();
+                let fields = struct_typ.get_fields(generics);
                 for field in fields.iter() {
                     if field.1.contains_slice() {
                         return true;
                     }
                 }
-                false
+                let variants = struct_typ.get_variants(generics);
+                variants.iter().flat_map(|(_, parameters)| parameters).any(Type::contains_slice)
             }
             Type::Tuple(types) => {
                 for typ in types.iter() {
@@ -238,10 +240,23 @@ pub struct StructType {
     /// since these will handle applying generic arguments to fields as well.
     fields: Vec<(Ident, Type)>,
 
+    /// Enums are represented as a struct type with no fields and a set of variants.
+    /// This is `None` for regular structs. Like fields, variants should only be accessed
+    /// through methods which apply the generic arguments to them.
+    variants: Option<Vec<ResolvedVariant>>,
+
     pub generics: Generics,
     pub location: Location,
 }
 
+/// A single variant of an enum along with the types of its parameters.
+/// Unit variants have no parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedVariant {
+    pub name: Ident,
+    pub parameters: Vec<Type>,
+}
+
 /// Corresponds to generic lists such as `<T, U>` in the source program.
 /// Used mainly for resolved types which no longer need information such
 /// as names or kinds.
@@ -281,7 +296,59 @@ impl StructType {
         fields: Vec<(Ident, Type)>,
         generics: Generics,
     ) -> StructType {
-        StructType { id, fields, name, location, generics }
+        StructType { id, fields, name, location, generics, variants: None }
+    }
+
+    /// Creates a new enum type. As with struct fields, the variants of an enum are
+    /// set once they are resolved via `set_variants`.
+    pub fn new_enum(id: StructId, name: Ident, location: Location, generics: Generics) -> Self {
+        StructType { id, fields: Vec::new(), name, location, generics, variants: Some(Vec::new()) }
+    }
+
+    pub fn is_enum(&self) -> bool {
+        self.variants.is_some()
+    }
+
+    /// Sets the variants of an enum once they have been resolved.
+    pub fn set_variants(&mut self, variants: Vec<ResolvedVariant>) {
+        let existing = self.variants.as_ref().expect("set_variants called on a non-enum type");
+        assert!(existing.is_empty());
+        self.variants = Some(variants);
+    }
+
+    pub fn num_variants(&self) -> usize {
+        self.variants.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Returns the name and parameter types of each variant of this enum, after being
+    /// applied to the given generic arguments. Returns an empty Vec for structs.
+    pub fn get_variants(&self, generic_args: &[Type]) -> Vec<(String, Vec<Type>)> {
+        let substitutions = self.generic_substitutions(generic_args);
+        let variants = self.variants.iter().flatten();
+        vecmap(variants, |variant| {
+            let parameters = vecmap(&variant.parameters, |typ| typ.substitute(&substitutions));
+            (variant.name.0.contents.clone(), parameters)
+        })
+    }
+
+    /// Returns the index of the variant with the given name, if this enum has one.
+    pub fn variant_index(&self, name: &str) -> Option<usize> {
+        self.variants.as_ref()?.iter().position(|variant| variant.name.0.contents == name)
+    }
+
+    /// Returns the name of the variant at the given index, if any.
+    pub fn variant_name(&self, index: usize) -> Option<&Ident> {
+        self.variants.as_ref()?.get(index).map(|variant| &variant.name)
+    }
+
+    fn generic_substitutions(&self, generic_args: &[Type]) -> TypeBindings {
+        assert_eq!(self.generics.len(), generic_args.len());
+
+        self.generics
+            .iter()
+            .zip(generic_args)
+            .map(|(old, new)| (old.type_var.id(), (old.type_var.clone(), new.clone())))
+            .collect()
     }
 
     /// To account for cyclic references between structs, a struct's
@@ -351,6 +418,11 @@ impl StructType {
         for (_, field) in self.fields.iter() {
             field.find_numeric_type_vars(found_names);
         }
+        for variant in self.variants.iter().flatten() {
+            for parameter in &variant.parameters {
+                parameter.find_numeric_type_vars(found_names);
+            }
+        }
     }
 
     /// True if the given index is the same index as a generic type of this struct
@@ -360,6 +432,12 @@ impl StructType {
     pub fn generic_is_numeric(&self, index_of_generic: usize) -> bool {
         let target_id = self.generics[index_of_generic].type_var.id();
         self.fields.iter().any(|(_, field)| field.contains_numeric_typevar(target_id))
+            || self.variants.iter().flatten().any(|variant| {
+                variant
+                    .parameters
+                    .iter()
+                    .any(|parameter| parameter.contains_numeric_typevar(target_id))
+            })
     }
 
     /// Instantiate this struct type, returning a Vec of the new generic args (in
@@ -870,6 +948,8 @@ impl Type {
             }
             Type::String(length) => length.is_valid_for_program_input(),
             Type::Tuple(elements) => elements.iter().all(|elem| elem.is_valid_for_program_input()),
+            // Enums have no representation in the ABI
+            Type::Struct(definition, _) if definition.borrow().is_enum() => false,
             Type::Struct(definition, generics) => definition
                 .borrow()
                 .get_fields(generics)
@@ -918,11 +998,17 @@ impl Type {
             }
             Type::String(length) => length.is_valid_non_inlined_function_input(),
             Type::Tuple(elements) => elements.iter().all(|elem| elem.is_valid_non_inlined_function_input()),
-            Type::Struct(definition, generics) => definition
-                .borrow()
-                .get_fields(generics)
-                .into_iter()
-                .all(|(_, field)| field.is_valid_non_inlined_function_input()),
+            Type::Struct(definition, generics) => {
+                let definition = definition.borrow();
+                let mut variants = definition.get_variants(generics).into_iter();
+                definition
+                    .get_fields(generics)
+                    .into_iter()
+                    .all(|(_, field)| field.is_valid_non_inlined_function_input())
+                    && variants.all(|(_, parameters)| {
+                        parameters.iter().all(Type::is_valid_non_inlined_function_input)
+                    })
+            }
         }
     }
 
@@ -961,11 +1047,17 @@ impl Type {
             Type::Tuple(elements) => {
                 elements.iter().all(|elem| elem.is_valid_for_unconstrained_boundary())
             }
-            Type::Struct(definition, generics) => definition
-                .borrow()
-                .get_fields(generics)
-                .into_iter()
-                .all(|(_, field)| field.is_valid_for_unconstrained_boundary()),
+            Type::Struct(definition, generics) => {
+                let definition = definition.borrow();
+                let mut variants = definition.get_variants(generics).into_iter();
+                definition
+                    .get_fields(generics)
+                    .into_iter()
+                    .all(|(_, field)| field.is_valid_for_unconstrained_boundary())
+                    && variants.all(|(_, parameters)| {
+                        parameters.iter().all(Type::is_valid_for_unconstrained_boundary)
+                    })
+            }
         }
     }
 
@@ -2110,6 +2202,16 @@ impl From<&Type> for PrintableType {
             Type::Error => unreachable!(),
             Type::Unit => PrintableType::Unit,
             Type::Constant(_) => unreachable!(),
+            Type::Struct(def, ref args) if def.borrow().is_enum() => {
+                // Enums are printed using their runtime representation: a tag followed
+                // by the parameters of every variant.
+                let variants = def.borrow().get_variants(args);
+                let mut types = vec![PrintableType::Field];
+                types.extend(variants.into_iter().map(|(_, parameters)| PrintableType::Tuple {
+                    types: vecmap(parameters, |typ| (&typ).into()),
+                }));
+                PrintableType::Tuple { types }
+            }
             Type::Struct(def, ref args) => {
                 let struct_type = def.borrow();
                 let fields = struct_type.get_fields(args);
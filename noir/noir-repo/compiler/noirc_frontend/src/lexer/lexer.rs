@@ -217,7 +217,15 @@ impl<'a> Lexer<'a> {
                 }
             }
             Token::Bang => self.single_double_peek_token('=', prev_token, Token::NotEqual),
-            Token::Assign => self.single_double_peek_token('=', prev_token, Token::Equal),
+            Token::Assign => {
+                if self.peek_char_is('>') {
+                    let start = self.position;
+                    self.next_char();
+                    Ok(Token::FatArrow.into_span(start, start + 1))
+                } else {
+                    self.single_double_peek_token('=', prev_token, Token::Equal)
+                }
+            }
             Token::Minus => self.single_double_peek_token('>', prev_token, Token::Arrow),
             Token::Colon => self.single_double_peek_token(':', prev_token, Token::DoubleColon),
             Token::Slash => {
@@ -686,7 +694,7 @@ mod tests {
 
     #[test]
     fn test_single_double_char() {
-        let input = "! != + ( ) { } [ ] | , ; : :: < <= > >= & - -> . .. % / * = == << >>";
+        let input = "! != + ( ) { } [ ] | , ; : :: < <= > >= & - -> . .. % / * = == => << >>";
 
         let expected = vec![
             Token::Bang,
@@ -717,6 +725,7 @@ mod tests {
             Token::Star,
             Token::Assign,
             Token::Equal,
+            Token::FatArrow,
             Token::ShiftLeft,
             Token::Greater,
             Token::Greater,
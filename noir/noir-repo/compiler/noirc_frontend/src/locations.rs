@@ -103,6 +103,17 @@ impl NodeInterner {
         Some(self.reference_graph[node_index])
     }
 
+    // Returns the `ReferenceId` of the node referenced at the given location, or of the node
+    // defined there if the location is that of a definition.
+    pub fn find_referenced(&self, location: Location) -> Option<ReferenceId> {
+        let node_index = self.location_indices.get_node_from_location(location)?;
+        let node_index = match self.reference_graph[node_index] {
+            ReferenceId::Reference(_, _) => self.referenced_index(node_index)?,
+            _ => node_index,
+        };
+        Some(self.reference_graph[node_index])
+    }
+
     // Starting at the given location, find the node referenced by it. Then, gather
     // all locations that reference that node, and return all of them
     // (the references and optionally the referenced node if `include_referencedd` is true).
@@ -160,6 +171,14 @@ impl NodeInterner {
         edit_locations
     }
 
+    /// Returns the locations where the given node is referenced
+    pub fn references_to(&self, referenced: ReferenceId) -> Vec<Location> {
+        match self.reference_graph_indices.get(&referenced) {
+            Some(index) => self.find_all_references_for_index(*index, false, true),
+            None => Vec::new(),
+        }
+    }
+
     // Given a reference index, returns the referenced index, if any.
     fn referenced_index(&self, reference_index: PetGraphIndex) -> Option<PetGraphIndex> {
         self.reference_graph
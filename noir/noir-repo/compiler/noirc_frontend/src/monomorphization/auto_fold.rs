@@ -0,0 +1,392 @@
+//! Chooses functions to compile as separate ACIR functions, as if they were marked `#[fold]`.
+//!
+//! Every constrained function is otherwise inlined into its callers, so a large function which
+//! is called many times is duplicated in the final circuit each time. Folding such a function
+//! instead compiles it once, with each call becoming an ACIR call opcode.
+//!
+//! The ACIR cost of each function is estimated from its monomorphized body, including the cost
+//! of any functions it inlines, along with the number of times it would be inlined. Functions
+//! whose estimated cost and call count are both large enough are folded, as long as:
+//! - they are never called under a predicate, i.e. from within an `if` branch, since a predicate
+//!   would have to be applied to the entire call
+//! - they aren't recursive, and their parameters and return values can be passed between circuits
+use std::collections::HashMap;
+
+use acvm::AcirField;
+use serde::{Deserialize, Serialize};
+
+use crate::ast::BinaryOpKind;
+
+use super::ast::{
+    Call, Definition, Expression, FuncId, Function, Ident, InlineType, LValue, Literal, Program,
+    Type,
+};
+
+/// The estimated cost a function must have for it to be worth folding
+const MIN_FOLD_COST: usize = 500;
+
+/// The number of times a function must be inlined for it to be worth folding
+const MIN_FOLD_CALLS: usize = 2;
+
+/// The estimated cost of a call to a black box function
+const BLACK_BOX_COST: usize = 100;
+
+/// A function which was automatically folded, and why
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutoFoldedFunction {
+    pub name: String,
+    /// The estimated number of ACIR opcodes in the function, including those it inlines
+    pub estimated_cost: usize,
+    /// The number of times the function would have been inlined
+    pub call_count: usize,
+}
+
+/// Marks the large, frequently called functions of the program as `InlineType::Fold`,
+/// returning the functions which were folded.
+pub fn auto_fold(program: &mut Program) -> Vec<AutoFoldedFunction> {
+    let summaries: Vec<_> = program.functions.iter().map(FunctionSummary::new).collect();
+    let mut analysis = FoldAnalysis::new(&program.functions, &summaries);
+
+    let mut folded = Vec::new();
+    for function in &program.functions {
+        if !analysis.is_candidate(function) {
+            continue;
+        }
+
+        let call_count = analysis.call_count(function.id);
+        let estimated_cost = analysis.cost(function.id);
+        if call_count >= MIN_FOLD_CALLS
+            && estimated_cost >= MIN_FOLD_COST
+            && analysis.is_predicate_free(function.id)
+        {
+            let name = function.name.clone();
+            folded.push((function.id, AutoFoldedFunction { name, estimated_cost, call_count }));
+        }
+    }
+
+    for (id, _) in &folded {
+        program.functions[id.0 as usize].inline_type = InlineType::Fold;
+    }
+
+    // Each entry point function needs a signature, in the order of the functions
+    program.function_signatures = program
+        .functions
+        .iter()
+        .filter(|function| {
+            function.inline_type.is_entry_point() || function.id == Program::main_id()
+        })
+        .map(|function| function.func_sig.clone())
+        .collect();
+
+    folded.into_iter().map(|(_, function)| function).collect()
+}
+
+/// A call to another function in the program
+struct CallSite {
+    callee: FuncId,
+    /// How many times the call is made, which is more than once for calls in loops
+    multiplicity: usize,
+    /// Whether the call is made from within an `if` branch
+    conditional: bool,
+}
+
+/// The cost of a function's own code, excluding the functions it calls,
+/// along with the calls it makes
+#[derive(Default)]
+struct FunctionSummary {
+    cost: usize,
+    calls: Vec<CallSite>,
+}
+
+impl FunctionSummary {
+    fn new(function: &Function) -> Self {
+        let mut summary = Self::default();
+        summary.expression(&function.body, 1, false);
+        summary
+    }
+
+    /// Adds the cost of an expression which runs `multiplicity` times
+    fn expression(&mut self, expression: &Expression, multiplicity: usize, conditional: bool) {
+        let visit = |summary: &mut Self, expression: &Expression| {
+            summary.expression(expression, multiplicity, conditional);
+        };
+
+        match expression {
+            Expression::Ident(_) | Expression::Break | Expression::Continue => (),
+            Expression::Literal(literal) => match literal {
+                Literal::Array(array) | Literal::Slice(array) => {
+                    array.contents.iter().for_each(|element| visit(self, element));
+                }
+                Literal::FmtStr(_, _, captures) => visit(self, captures),
+                Literal::Integer(..) | Literal::Bool(_) | Literal::Unit | Literal::Str(_) => (),
+            },
+            Expression::Block(expressions) | Expression::Tuple(expressions) => {
+                expressions.iter().for_each(|expression| visit(self, expression));
+            }
+            Expression::Unary(unary) => {
+                self.cost += multiplicity;
+                visit(self, &unary.rhs);
+            }
+            Expression::Binary(binary) => {
+                self.cost += multiplicity * binary_cost(binary.operator);
+                visit(self, &binary.lhs);
+                visit(self, &binary.rhs);
+            }
+            Expression::Index(index) => {
+                self.cost += multiplicity;
+                visit(self, &index.collection);
+                visit(self, &index.index);
+            }
+            Expression::Cast(cast) => {
+                self.cost += multiplicity;
+                visit(self, &cast.lhs);
+            }
+            Expression::For(for_loop) => {
+                visit(self, &for_loop.start_range);
+                visit(self, &for_loop.end_range);
+
+                // Loops are unrolled, so a loop with unknown bounds is counted as running once
+                let iterations = match (
+                    integer_literal(&for_loop.start_range),
+                    integer_literal(&for_loop.end_range),
+                ) {
+                    (Some(start), Some(end)) => end.saturating_sub(start) as usize,
+                    _ => 1,
+                };
+                self.expression(
+                    &for_loop.block,
+                    multiplicity.saturating_mul(iterations),
+                    conditional,
+                );
+            }
+            Expression::If(if_expression) => {
+                visit(self, &if_expression.condition);
+                // Both branches are executed once the control flow is flattened
+                self.expression(&if_expression.consequence, multiplicity, true);
+                if let Some(alternative) = &if_expression.alternative {
+                    self.expression(alternative, multiplicity, true);
+                }
+            }
+            Expression::ExtractTupleField(tuple, _) => visit(self, tuple),
+            Expression::Call(call) => {
+                call.arguments.iter().for_each(|argument| visit(self, argument));
+                self.call(call, multiplicity, conditional);
+            }
+            Expression::Let(let_statement) => visit(self, &let_statement.expression),
+            Expression::Constrain(condition, _, message) => {
+                self.cost += multiplicity;
+                visit(self, condition);
+                if let Some(message) = message {
+                    visit(self, &message.0);
+                }
+            }
+            Expression::Assign(assign) => {
+                self.lvalue(&assign.lvalue, multiplicity, conditional);
+                visit(self, &assign.expression);
+            }
+            Expression::Semi(expression) => visit(self, expression),
+        }
+    }
+
+    fn call(&mut self, call: &Call, multiplicity: usize, conditional: bool) {
+        match call.func.as_ref() {
+            Expression::Ident(Ident { definition: Definition::Function(callee), .. }) => {
+                self.calls.push(CallSite { callee: *callee, multiplicity, conditional });
+            }
+            Expression::Ident(Ident { definition: Definition::LowLevel(_), .. }) => {
+                self.cost += multiplicity * BLACK_BOX_COST;
+            }
+            Expression::Ident(Ident {
+                definition: Definition::Builtin(_) | Definition::Oracle(_),
+                ..
+            }) => {
+                self.cost += multiplicity;
+            }
+            // Calls to closures and function values are resolved during SSA generation,
+            // so only the cost of computing the function is counted.
+            function => self.expression(function, multiplicity, conditional),
+        }
+    }
+
+    fn lvalue(&mut self, lvalue: &LValue, multiplicity: usize, conditional: bool) {
+        match lvalue {
+            LValue::Ident(_) => (),
+            LValue::Index { array, index, .. } => {
+                self.cost += multiplicity;
+                self.lvalue(array, multiplicity, conditional);
+                self.expression(index, multiplicity, conditional);
+            }
+            LValue::MemberAccess { object, .. } => self.lvalue(object, multiplicity, conditional),
+            LValue::Dereference { reference, .. } => {
+                self.lvalue(reference, multiplicity, conditional);
+            }
+        }
+    }
+}
+
+/// The estimated number of opcodes needed for a binary operation. Operations other than field
+/// arithmetic generally require range checks, and bitwise operations decompose their operands.
+fn binary_cost(operator: BinaryOpKind) -> usize {
+    match operator {
+        BinaryOpKind::Add | BinaryOpKind::Subtract | BinaryOpKind::Multiply => 1,
+        BinaryOpKind::Equal | BinaryOpKind::NotEqual => 2,
+        BinaryOpKind::Less
+        | BinaryOpKind::LessEqual
+        | BinaryOpKind::Greater
+        | BinaryOpKind::GreaterEqual
+        | BinaryOpKind::ShiftLeft
+        | BinaryOpKind::ShiftRight => 8,
+        BinaryOpKind::Divide
+        | BinaryOpKind::Modulo
+        | BinaryOpKind::And
+        | BinaryOpKind::Or
+        | BinaryOpKind::Xor => 16,
+    }
+}
+
+fn integer_literal(expression: &Expression) -> Option<u128> {
+    match expression {
+        Expression::Literal(Literal::Integer(value, false, _, _)) => value.try_into_u128(),
+        _ => None,
+    }
+}
+
+/// Computes the costs and call counts of functions from their summaries.
+/// Recursive calls are ignored, which is fine as recursive functions aren't folded.
+struct FoldAnalysis<'a> {
+    functions: &'a [Function],
+    summaries: &'a [FunctionSummary],
+
+    /// The calls to each function from constrained functions, as (caller, call site) pairs
+    callers: HashMap<FuncId, Vec<(FuncId, &'a CallSite)>>,
+
+    costs: HashMap<FuncId, usize>,
+    call_counts: HashMap<FuncId, usize>,
+    predicate_free: HashMap<FuncId, bool>,
+}
+
+impl<'a> FoldAnalysis<'a> {
+    fn new(functions: &'a [Function], summaries: &'a [FunctionSummary]) -> Self {
+        let mut callers: HashMap<_, Vec<_>> = HashMap::new();
+        for (function, summary) in functions.iter().zip(summaries) {
+            if !function.unconstrained {
+                for call in &summary.calls {
+                    callers.entry(call.callee).or_default().push((function.id, call));
+                }
+            }
+        }
+
+        Self {
+            functions,
+            summaries,
+            callers,
+            costs: HashMap::new(),
+            call_counts: HashMap::new(),
+            predicate_free: HashMap::new(),
+        }
+    }
+
+    fn function(&self, id: FuncId) -> &'a Function {
+        &self.functions[id.0 as usize]
+    }
+
+    /// True for the functions which are compiled separately rather than inlined
+    fn is_entry_point(&self, id: FuncId) -> bool {
+        let function = self.function(id);
+        id == Program::main_id() || function.unconstrained || function.inline_type.is_entry_point()
+    }
+
+    fn is_candidate(&mut self, function: &Function) -> bool {
+        function.id != Program::main_id()
+            && !function.unconstrained
+            && function.inline_type == InlineType::Inline
+            && function.parameters.iter().all(|(_, _, _, typ)| can_cross_circuits(typ))
+            && can_cross_circuits(&function.return_type)
+            && !self.is_recursive(function.id)
+    }
+
+    /// The estimated cost of a function, including the costs of the functions inlined into it
+    fn cost(&mut self, id: FuncId) -> usize {
+        if let Some(cost) = self.costs.get(&id) {
+            return *cost;
+        }
+        // Guard against recursion while this function's cost is computed
+        self.costs.insert(id, 0);
+
+        let summaries = self.summaries;
+        let summary = &summaries[id.0 as usize];
+        let mut cost = summary.cost;
+        for call in &summary.calls {
+            if !self.is_entry_point(call.callee) {
+                cost =
+                    cost.saturating_add(call.multiplicity.saturating_mul(self.cost(call.callee)));
+            }
+        }
+
+        self.costs.insert(id, cost);
+        cost
+    }
+
+    /// The number of times a function is inlined into the program's entry points
+    fn call_count(&mut self, id: FuncId) -> usize {
+        if let Some(count) = self.call_counts.get(&id) {
+            return *count;
+        }
+        self.call_counts.insert(id, 0);
+
+        let mut count = 0usize;
+        for (caller, call) in self.callers.get(&id).cloned().unwrap_or_default() {
+            let caller_count =
+                if self.is_entry_point(caller) { 1 } else { self.call_count(caller) };
+            count = count.saturating_add(call.multiplicity.saturating_mul(caller_count));
+        }
+
+        self.call_counts.insert(id, count);
+        count
+    }
+
+    /// True if the function is never called under a predicate, once its callers are inlined
+    fn is_predicate_free(&mut self, id: FuncId) -> bool {
+        if let Some(predicate_free) = self.predicate_free.get(&id) {
+            return *predicate_free;
+        }
+        self.predicate_free.insert(id, false);
+
+        let callers = self.callers.get(&id).cloned().unwrap_or_default();
+        let predicate_free = callers.into_iter().all(|(caller, call)| {
+            !call.conditional && (self.is_entry_point(caller) || self.is_predicate_free(caller))
+        });
+
+        self.predicate_free.insert(id, predicate_free);
+        predicate_free
+    }
+
+    /// True if the function can reach a call to itself
+    fn is_recursive(&self, id: FuncId) -> bool {
+        let mut visited = vec![false; self.functions.len()];
+        let mut stack = vec![id];
+        while let Some(function) = stack.pop() {
+            for call in &self.summaries[function.0 as usize].calls {
+                if call.callee == id {
+                    return true;
+                }
+                if !std::mem::replace(&mut visited[call.callee.0 as usize], true) {
+                    stack.push(call.callee);
+                }
+            }
+        }
+        false
+    }
+}
+
+/// True if values of this type can be passed to or returned from a separate ACIR function
+fn can_cross_circuits(typ: &Type) -> bool {
+    match typ {
+        Type::Field | Type::Integer(..) | Type::Bool | Type::String(_) | Type::Unit => true,
+        Type::Array(_, element) => can_cross_circuits(element),
+        Type::Tuple(fields) => fields.iter().all(can_cross_circuits),
+        Type::FmtString(..) | Type::Slice(_) | Type::MutableReference(_) | Type::Function(..) => {
+            false
+        }
+    }
+}
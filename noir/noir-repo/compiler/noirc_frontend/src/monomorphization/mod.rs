@@ -8,7 +8,7 @@
 //!
 //! The entry point to this pass is the `monomorphize` function which, starting from a given
 //! function, will monomorphize the entire reachable program.
-use crate::ast::{FunctionKind, IntegerBitSize, Signedness, UnaryOp, Visibility};
+use crate::ast::{BinaryOpKind, FunctionKind, IntegerBitSize, Signedness, UnaryOp, Visibility};
 use crate::hir::comptime::InterpreterError;
 use crate::hir::type_check::NoMatchingImplFoundError;
 use crate::node_interner::ExprId;
@@ -40,6 +40,7 @@ use self::{
 };
 
 pub mod ast;
+pub mod auto_fold;
 mod debug;
 pub mod debug_types;
 pub mod errors;
@@ -400,6 +401,24 @@ impl<'interner> Monomorphizer<'interner> {
                     self.parameter(field, &field_type, new_params)?;
                 }
             }
+            HirPattern::Variant(_, variant_index, patterns, location) => {
+                // Like structs, enum parameters are flattened. Every part of the enum other
+                // than the matched variant's arguments is given a placeholder parameter.
+                new_params.push((self.next_local_id(), false, "_".into(), ast::Type::Field));
+
+                for (index, (_, parameter_types)) in unwrap_enum_type(typ).into_iter().enumerate() {
+                    if index == *variant_index {
+                        for (pattern, typ) in patterns.iter().zip(parameter_types) {
+                            self.parameter(pattern, &typ, new_params)?;
+                        }
+                    } else {
+                        let types =
+                            try_vecmap(parameter_types, |typ| Self::convert_type(&typ, *location))?;
+                        let new_id = self.next_local_id();
+                        new_params.push((new_id, false, "_".into(), ast::Type::Tuple(types)));
+                    }
+                }
+            }
         }
         Ok(())
     }
@@ -507,6 +526,10 @@ impl<'interner> Monomorphizer<'interner> {
                 ast::Expression::Tuple(fields)
             }
             HirExpression::Constructor(constructor) => self.constructor(constructor, expr)?,
+            HirExpression::EnumConstructor(constructor) => {
+                self.enum_constructor(constructor, expr)?
+            }
+            HirExpression::Match(match_expr) => self.match_expr(match_expr, expr)?,
 
             HirExpression::Lambda(lambda) => self.lambda(lambda, expr)?,
 
@@ -694,6 +717,171 @@ impl<'interner> Monomorphizer<'interner> {
         Ok(ast::Expression::Block(new_exprs))
     }
 
+    /// Enum values are represented as a tuple of the variant's tag followed by one tuple
+    /// per variant holding that variant's arguments. Every variant other than the one
+    /// being constructed has its arguments filled with zeroed values.
+    fn enum_constructor(
+        &mut self,
+        constructor: HirEnumConstructorExpression,
+        id: node_interner::ExprId,
+    ) -> Result<ast::Expression, MonomorphizationError> {
+        let location = self.interner.expr_location(&id);
+        let typ = Self::convert_type(&self.interner.id_type(id), location)?;
+
+        let variant_types = match typ {
+            ast::Type::Tuple(mut fields) => fields.split_off(1),
+            other => unreachable!("enum_constructor: expected tuple, found {other}"),
+        };
+
+        let tag = (constructor.variant_index as u128).into();
+        let tag = ast::Literal::Integer(tag, false, ast::Type::Field, location);
+        let mut fields = vec![ast::Expression::Literal(tag)];
+        let mut arguments = Some(try_vecmap(constructor.arguments, |arg| self.expr(arg))?);
+
+        for (index, variant_type) in variant_types.iter().enumerate() {
+            if index == constructor.variant_index {
+                let arguments = arguments.take().unwrap_or_default();
+                fields.push(ast::Expression::Tuple(arguments));
+            } else {
+                fields.push(self.zeroed_value_of_type(variant_type, location));
+            }
+        }
+
+        Ok(ast::Expression::Tuple(fields))
+    }
+
+    /// Lowers a match expression into a chain of if expressions, one per rule. Each rule's
+    /// condition checks the tags of any variant patterns it contains. Since matches are known
+    /// to be exhaustive, the final rule is always used as the last `else` branch.
+    fn match_expr(
+        &mut self,
+        match_expr: HirMatchExpression,
+        id: node_interner::ExprId,
+    ) -> Result<ast::Expression, MonomorphizationError> {
+        let location = self.interner.expr_location(&id);
+        let result_type = Self::convert_type(&self.interner.id_type(id), location)?;
+        let scrutinee_type = self.interner.id_type(match_expr.expression);
+
+        let scrutinee_id = self.next_local_id();
+        let name = "match".to_string();
+        let scrutinee = ast::Expression::Ident(ast::Ident {
+            location: Some(location),
+            mutable: false,
+            definition: Definition::Local(scrutinee_id),
+            name: name.clone(),
+            typ: Self::convert_type(&scrutinee_type, location)?,
+        });
+
+        let expression = Box::new(self.expr(match_expr.expression)?);
+        let let_scrutinee =
+            ast::Expression::Let(ast::Let { id: scrutinee_id, mutable: false, name, expression });
+
+        // Build the chain starting from the last rule so that each rule can
+        // use the rules after it as its `else` branch.
+        let mut rules = match_expr.rules.into_iter().rev();
+        let mut result = match rules.next() {
+            Some((pattern, branch)) => {
+                self.match_rule(pattern, branch, scrutinee.clone(), &scrutinee_type)?
+            }
+            // A match with no rules is only possible on an enum with no variants,
+            // which can never be constructed.
+            None => self.zeroed_value_of_type(&result_type, location),
+        };
+
+        for (pattern, branch) in rules {
+            let condition = Self::pattern_condition(&pattern, scrutinee.clone(), location);
+            let consequence =
+                self.match_rule(pattern, branch, scrutinee.clone(), &scrutinee_type)?;
+
+            result = match condition {
+                Some(condition) => ast::Expression::If(ast::If {
+                    condition: Box::new(condition),
+                    consequence: Box::new(consequence),
+                    alternative: Some(Box::new(result)),
+                    typ: result_type.clone(),
+                }),
+                // An irrefutable rule makes any rules after it unreachable
+                None => consequence,
+            };
+        }
+
+        Ok(ast::Expression::Block(vec![let_scrutinee, result]))
+    }
+
+    fn match_rule(
+        &mut self,
+        pattern: HirPattern,
+        branch: node_interner::ExprId,
+        scrutinee: ast::Expression,
+        scrutinee_type: &HirType,
+    ) -> Result<ast::Expression, MonomorphizationError> {
+        let definitions = self.unpack_pattern(pattern, scrutinee, scrutinee_type)?;
+        let branch = self.expr(branch)?;
+        Ok(ast::Expression::Block(vec![definitions, branch]))
+    }
+
+    /// Returns a boolean expression which is true if `value` matches the given pattern,
+    /// or None if the pattern is irrefutable. The payloads of variants which were not
+    /// constructed are zeroed, so checking nested patterns within them is always safe.
+    fn pattern_condition(
+        pattern: &HirPattern,
+        value: ast::Expression,
+        location: Location,
+    ) -> Option<ast::Expression> {
+        let extract = |value: &ast::Expression, index| {
+            ast::Expression::ExtractTupleField(Box::new(value.clone()), index)
+        };
+
+        let conditions = match pattern {
+            HirPattern::Identifier(_) => return None,
+            HirPattern::Mutable(pattern, _) => {
+                return Self::pattern_condition(pattern, value, location)
+            }
+            HirPattern::Tuple(patterns, _) => {
+                let conditions = patterns.iter().enumerate().filter_map(|(index, pattern)| {
+                    Self::pattern_condition(pattern, extract(&value, index), location)
+                });
+                conditions.collect::<Vec<_>>()
+            }
+            HirPattern::Struct(typ, patterns, _) => {
+                let fields = unwrap_struct_type(typ);
+                let conditions = patterns.iter().filter_map(|(name, pattern)| {
+                    let index = fields
+                        .iter()
+                        .position(|(field_name, _)| *field_name == name.0.contents)
+                        .expect("Expected struct pattern fields to be checked during elaboration");
+                    Self::pattern_condition(pattern, extract(&value, index), location)
+                });
+                conditions.collect::<Vec<_>>()
+            }
+            HirPattern::Variant(_, variant_index, patterns, _) => {
+                let tag = (*variant_index as u128).into();
+                let tag = ast::Literal::Integer(tag, false, ast::Type::Field, location);
+                let tag_check = ast::Expression::Binary(ast::Binary {
+                    lhs: Box::new(extract(&value, 0)),
+                    operator: BinaryOpKind::Equal,
+                    rhs: Box::new(ast::Expression::Literal(tag)),
+                    location,
+                });
+
+                let payload = extract(&value, variant_index + 1);
+                let nested = patterns.iter().enumerate().filter_map(|(index, pattern)| {
+                    Self::pattern_condition(pattern, extract(&payload, index), location)
+                });
+                std::iter::once(tag_check).chain(nested).collect::<Vec<_>>()
+            }
+        };
+
+        conditions.into_iter().reduce(|lhs, rhs| {
+            ast::Expression::Binary(ast::Binary {
+                lhs: Box::new(lhs),
+                operator: BinaryOpKind::And,
+                rhs: Box::new(rhs),
+                location,
+            })
+        })
+    }
+
     fn block(
         &mut self,
         statement_ids: Vec<StmtId>,
@@ -741,6 +929,12 @@ impl<'interner> Monomorphizer<'interner> {
 
                 self.unpack_tuple_pattern(value, patterns_iter)
             }
+            HirPattern::Variant(_, variant_index, patterns, _) => {
+                let (_, parameters) = unwrap_enum_type(typ).swap_remove(variant_index);
+                let payload =
+                    ast::Expression::ExtractTupleField(Box::new(value), variant_index + 1);
+                self.unpack_tuple_pattern(payload, patterns.into_iter().zip(parameters))
+            }
         }
     }
 
@@ -960,6 +1154,17 @@ impl<'interner> Monomorphizer<'interner> {
                 monomorphized_default
             }
 
+            HirType::Struct(def, args) if def.borrow().is_enum() => {
+                // An enum is a tag followed by a tuple of arguments for each variant
+                let mut fields = vec![ast::Type::Field];
+                for (_, parameters) in def.borrow().get_variants(args) {
+                    let parameters =
+                        try_vecmap(parameters, |typ| Self::convert_type(&typ, location))?;
+                    fields.push(ast::Type::Tuple(parameters));
+                }
+                ast::Type::Tuple(fields)
+            }
+
             HirType::Struct(def, args) => {
                 let fields = def.borrow().get_fields(args);
                 let fields = try_vecmap(fields, |(_, field)| Self::convert_type(&field, location))?;
@@ -1731,6 +1936,13 @@ fn unwrap_struct_type(typ: &HirType) -> Vec<(String, HirType)> {
     }
 }
 
+fn unwrap_enum_type(typ: &HirType) -> Vec<(String, Vec<HirType>)> {
+    match typ.follow_bindings() {
+        HirType::Struct(def, args) => def.borrow().get_variants(&args),
+        other => unreachable!("unwrap_enum_type: expected enum, found {:?}", other),
+    }
+}
+
 pub fn perform_instantiation_bindings(bindings: &TypeBindings) {
     for (var, binding) in bindings.values() {
         var.force_bind(binding.clone());
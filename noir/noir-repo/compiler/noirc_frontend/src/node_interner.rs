@@ -16,7 +16,9 @@ use crate::ast::Ident;
 use crate::graph::CrateId;
 use crate::hir::comptime;
 use crate::hir::def_collector::dc_crate::CompilationError;
-use crate::hir::def_collector::dc_crate::{UnresolvedStruct, UnresolvedTrait, UnresolvedTypeAlias};
+use crate::hir::def_collector::dc_crate::{
+    UnresolvedEnum, UnresolvedStruct, UnresolvedTrait, UnresolvedTypeAlias,
+};
 use crate::hir::def_map::{LocalModuleId, ModuleId};
 use crate::QuotedType;
 
@@ -25,7 +27,7 @@ use crate::hir::resolution::errors::ResolverError;
 use crate::hir_def::expr::HirIdent;
 use crate::hir_def::stmt::HirLetStatement;
 use crate::hir_def::traits::TraitImpl;
-use crate::hir_def::traits::{Trait, TraitConstraint};
+use crate::hir_def::traits::{Trait, TraitConstant, TraitConstraint, TraitType};
 use crate::hir_def::types::{StructType, Type};
 use crate::hir_def::{
     expr::HirExpression,
@@ -34,6 +36,7 @@ use crate::hir_def::{
 };
 use crate::locations::LocationIndices;
 use crate::token::{Attributes, SecondaryAttribute};
+use crate::usage_tracker::UsageTracker;
 use crate::GenericTypeVars;
 use crate::Generics;
 use crate::{Shared, TypeAlias, TypeBindings, TypeVariable, TypeVariableId, TypeVariableKind};
@@ -212,6 +215,9 @@ pub struct NodeInterner {
     /// Tracks the index of the references in the graph
     pub(crate) reference_graph_indices: HashMap<ReferenceId, PetGraphIndex>,
 
+    /// Tracks which imports have not been used, to warn about them
+    pub(crate) usage_tracker: UsageTracker,
+
     /// Store the location of the references in the graph
     pub(crate) location_indices: LocationIndices,
 }
@@ -579,6 +585,7 @@ impl Default for NodeInterner {
             location_indices: LocationIndices::default(),
             reference_graph: petgraph::graph::DiGraph::new(),
             reference_graph_indices: HashMap::new(),
+            usage_tracker: UsageTracker::default(),
         };
 
         // An empty block expression is used often, we add this into the `node` on startup
@@ -625,6 +632,8 @@ impl NodeInterner {
         type_id: TraitId,
         unresolved_trait: &UnresolvedTrait,
         generics: Generics,
+        types: Vec<TraitType>,
+        constants: Vec<TraitConstant>,
     ) {
         let self_type_typevar_id = self.next_type_variable_id();
 
@@ -638,8 +647,8 @@ impl NodeInterner {
             self_type_typevar: TypeVariable::unbound(self_type_typevar_id),
             methods: Vec::new(),
             method_ids: unresolved_trait.method_ids.clone(),
-            constants: Vec::new(),
-            types: Vec::new(),
+            constants,
+            types,
         };
 
         self.traits.insert(type_id, new_trait);
@@ -666,6 +675,25 @@ impl NodeInterner {
         struct_id
     }
 
+    pub fn new_enum(
+        &mut self,
+        typ: &UnresolvedEnum,
+        generics: Generics,
+        krate: CrateId,
+        local_id: LocalModuleId,
+        file_id: FileId,
+    ) -> StructId {
+        let enum_id = StructId(ModuleId { krate, local_id });
+        let name = typ.enum_def.name.clone();
+
+        // Variants will be filled in later
+        let location = Location::new(typ.enum_def.span, file_id);
+        let new_enum = StructType::new_enum(enum_id, name, location, generics);
+        self.structs.insert(enum_id, Shared::new(new_enum));
+        self.struct_attributes.insert(enum_id, typ.enum_def.attributes.clone());
+        enum_id
+    }
+
     pub fn push_type_alias(
         &mut self,
         typ: &UnresolvedTypeAlias,
@@ -960,6 +988,36 @@ impl NodeInterner {
         self.func_meta.get(func_id)
     }
 
+    /// Returns the location of the function's body, if it has been elaborated
+    pub fn function_body_location(&self, func_id: &FuncId) -> Option<Location> {
+        let Some(Node::Function(function)) = self.nodes.get(func_id.0) else {
+            return None;
+        };
+        self.id_to_location.get(&function.as_expr().into()).copied()
+    }
+
+    /// Returns the function whose body contains the given location, if any
+    pub fn find_function_containing(&self, location: Location) -> Option<FuncId> {
+        self.func_meta.keys().copied().find(|func_id| {
+            self.function_body_location(func_id)
+                .map_or(false, |body_location| body_location.contains(&location))
+        })
+    }
+
+    /// Returns the functions whose body is located in the given file, in source order
+    pub fn functions_in_file(&self, file: FileId) -> Vec<FuncId> {
+        let mut functions: Vec<_> = self
+            .func_meta
+            .keys()
+            .filter_map(|func_id| {
+                let body_location = self.function_body_location(func_id)?;
+                (body_location.file == file).then_some((body_location.span.start(), *func_id))
+            })
+            .collect();
+        functions.sort_by_key(|(start, _)| *start);
+        functions.into_iter().map(|(_, func_id)| func_id).collect()
+    }
+
     pub fn function_ident(&self, func_id: &FuncId) -> crate::ast::Ident {
         let name = self.function_name(func_id).to_owned();
         let span = self.function_meta(func_id).name.location.span;
@@ -1771,6 +1829,30 @@ impl NodeInterner {
         self.dependency_graph.update_edge(dependent_index, dependency_index, ());
     }
 
+    /// Returns the functions which call, or otherwise refer to, the given function
+    pub fn function_callers(&self, func_id: FuncId) -> Vec<FuncId> {
+        self.function_neighbors(func_id, petgraph::Direction::Incoming)
+    }
+
+    /// Returns the functions which the given function calls, or otherwise refers to
+    pub fn function_callees(&self, func_id: FuncId) -> Vec<FuncId> {
+        self.function_neighbors(func_id, petgraph::Direction::Outgoing)
+    }
+
+    fn function_neighbors(&self, func_id: FuncId, direction: petgraph::Direction) -> Vec<FuncId> {
+        let Some(index) = self.dependency_graph_indices.get(&DependencyId::Function(func_id))
+        else {
+            return Vec::new();
+        };
+        self.dependency_graph
+            .neighbors_directed(*index, direction)
+            .filter_map(|neighbor| match self.dependency_graph[neighbor] {
+                DependencyId::Function(id) => Some(id),
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn get_or_insert_dependency(&mut self, id: DependencyId) -> PetGraphIndex {
         if let Some(index) = self.dependency_graph_indices.get(&id) {
             return *index;
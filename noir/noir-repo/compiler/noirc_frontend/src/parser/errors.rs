@@ -18,6 +18,8 @@ pub enum ParserErrorReason {
     ExpectedPatternButFoundType(Token),
     #[error("Expected a ; separating these two statements")]
     MissingSeparatingSemi,
+    #[error("Expected a , separating these two match arms")]
+    MissingSeparatingComma,
     #[error("constrain keyword is deprecated")]
     ConstrainDeprecated,
     #[error("Expression is invalid in an array-length type: '{0}'. Only unsigned integer constants, globals, generics, +, -, *, /, and % may be used in this context.")]
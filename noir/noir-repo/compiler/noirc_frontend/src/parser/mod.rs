@@ -12,8 +12,9 @@ mod labels;
 mod parser;
 
 use crate::ast::{
-    Expression, Ident, ImportStatement, LetStatement, ModuleDeclaration, NoirFunction, NoirStruct,
-    NoirTrait, NoirTraitImpl, NoirTypeAlias, Recoverable, StatementKind, TypeImpl, UseTree,
+    Expression, Ident, ImportStatement, LetStatement, ModuleDeclaration, NoirEnumeration,
+    NoirFunction, NoirStruct, NoirTrait, NoirTraitImpl, NoirTypeAlias, Recoverable, StatementKind,
+    TypeImpl, UseTree,
 };
 use crate::token::{Keyword, Token};
 
@@ -22,7 +23,7 @@ use chumsky::primitive::Container;
 pub use errors::ParserError;
 pub use errors::ParserErrorReason;
 use noirc_errors::Span;
-pub use parser::{expression, parse_program, top_level_items};
+pub use parser::{expression, parse_expression, parse_program, top_level_items};
 
 #[derive(Debug, Clone)]
 pub enum TopLevelStatement {
@@ -30,6 +31,7 @@ pub enum TopLevelStatement {
     Module(ModuleDeclaration),
     Import(UseTree),
     Struct(NoirStruct),
+    Enum(NoirEnumeration),
     Trait(NoirTrait),
     TraitImpl(NoirTraitImpl),
     Impl(TypeImpl),
@@ -213,6 +215,7 @@ pub struct SortedModule {
     pub imports: Vec<ImportStatement>,
     pub functions: Vec<NoirFunction>,
     pub types: Vec<NoirStruct>,
+    pub enums: Vec<NoirEnumeration>,
     pub traits: Vec<NoirTrait>,
     pub trait_impls: Vec<NoirTraitImpl>,
     pub impls: Vec<TypeImpl>,
@@ -244,6 +247,10 @@ impl std::fmt::Display for SortedModule {
             write!(f, "{type_}")?;
         }
 
+        for enum_ in &self.enums {
+            write!(f, "{enum_}")?;
+        }
+
         for function in &self.functions {
             write!(f, "{function}")?;
         }
@@ -279,6 +286,7 @@ impl ParsedModule {
                 ItemKind::Import(import) => module.push_import(import),
                 ItemKind::Function(func) => module.push_function(func),
                 ItemKind::Struct(typ) => module.push_type(typ),
+                ItemKind::Enum(typ) => module.push_enum(typ),
                 ItemKind::Trait(noir_trait) => module.push_trait(noir_trait),
                 ItemKind::TraitImpl(trait_impl) => module.push_trait_impl(trait_impl),
                 ItemKind::Impl(r#impl) => module.push_impl(r#impl),
@@ -304,6 +312,7 @@ pub enum ItemKind {
     Import(UseTree),
     Function(NoirFunction),
     Struct(NoirStruct),
+    Enum(NoirEnumeration),
     Trait(NoirTrait),
     TraitImpl(NoirTraitImpl),
     Impl(TypeImpl),
@@ -360,6 +369,10 @@ impl SortedModule {
         self.types.push(typ);
     }
 
+    fn push_enum(&mut self, typ: NoirEnumeration) {
+        self.enums.push(typ);
+    }
+
     fn push_trait(&mut self, noir_trait: NoirTrait) {
         self.traits.push(noir_trait);
     }
@@ -479,6 +492,7 @@ impl std::fmt::Display for TopLevelStatement {
             TopLevelStatement::Trait(t) => t.fmt(f),
             TopLevelStatement::TraitImpl(i) => i.fmt(f),
             TopLevelStatement::Struct(s) => s.fmt(f),
+            TopLevelStatement::Enum(e) => e.fmt(f),
             TopLevelStatement::Impl(i) => i.fmt(f),
             TopLevelStatement::TypeAlias(t) => t.fmt(f),
             TopLevelStatement::SubModule(s) => s.fmt(f),
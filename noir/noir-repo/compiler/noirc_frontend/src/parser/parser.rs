@@ -24,7 +24,7 @@
 //! be limited to cases like the above `fn` example where it is clear we shouldn't back out of the
 //! current parser to try alternative parsers in a `choice` expression.
 use self::primitives::{keyword, macro_quote_marker, mutable_reference, variable};
-use self::types::{generic_type_args, maybe_comp_time, parse_type};
+use self::types::{generic_type_args, maybe_comp_time, parse_type, trait_bound_generic_args};
 
 use super::{
     foldl_with_span, labels::ParsingRuleLabel, parameter_name_recovery, parameter_recovery,
@@ -35,9 +35,9 @@ use super::{
 use super::{spanned, Item, ItemKind};
 use crate::ast::{
     BinaryOp, BinaryOpKind, BlockExpression, ForLoopStatement, ForRange, Ident, IfExpression,
-    InfixExpression, LValue, Literal, ModuleDeclaration, NoirTypeAlias, Param, Path, Pattern,
-    Recoverable, Statement, TraitBound, TypeImpl, UnaryRhsMemberAccess, UnaryRhsMethodCall,
-    UnresolvedTraitConstraint, UseTree, UseTreeKind, Visibility,
+    InfixExpression, LValue, Literal, MatchExpression, ModuleDeclaration, NoirTypeAlias, Param,
+    Path, PathKind, Pattern, Recoverable, Statement, TraitBound, TypeImpl, UnaryRhsMemberAccess,
+    UnaryRhsMethodCall, UnresolvedTraitConstraint, UseTree, UseTreeKind, Visibility,
 };
 use crate::ast::{
     Expression, ExpressionKind, LetStatement, StatementKind, UnresolvedType, UnresolvedTypeData,
@@ -53,6 +53,7 @@ use noirc_errors::{Span, Spanned};
 
 mod assertion;
 mod attributes;
+mod enums;
 mod function;
 mod lambdas;
 mod literals;
@@ -101,6 +102,18 @@ pub fn parse_program(source_program: &str) -> (ParsedModule, Vec<ParserError>) {
     (parsed_module, parsing_errors)
 }
 
+/// Parses a single standalone expression, eg. a watch expression entered in the debugger.
+///
+/// Unlike [parse_program], this does not attempt to recover from errors: either the whole
+/// input is a valid expression or the parsing errors are returned.
+pub fn parse_expression(source: &str) -> Result<Expression, Vec<ParserError>> {
+    let (tokens, lexing_errors) = Lexer::lex(source);
+    if !lexing_errors.is_empty() {
+        return Err(lexing_errors.into_iter().map(Into::into).collect());
+    }
+    expression().then_ignore(just(Token::EOF)).parse(tokens)
+}
+
 fn prototype_parse_use_tree(expected_use_tree_opt: Option<&UseTree>, input: &str) {
     // TODO(https://github.com/noir-lang/noir/issues/4777): currently skipping
     // recursive use trees, e.g. "use std::{foo, bar}"
@@ -178,6 +191,7 @@ fn module() -> impl NoirParser<ParsedModule> {
                     TopLevelStatement::Module(m) => push_item(ItemKind::ModuleDecl(m)),
                     TopLevelStatement::Import(i) => push_item(ItemKind::Import(i)),
                     TopLevelStatement::Struct(s) => push_item(ItemKind::Struct(s)),
+                    TopLevelStatement::Enum(e) => push_item(ItemKind::Enum(e)),
                     TopLevelStatement::Trait(t) => push_item(ItemKind::Trait(t)),
                     TopLevelStatement::TraitImpl(t) => push_item(ItemKind::TraitImpl(t)),
                     TopLevelStatement::Impl(i) => push_item(ItemKind::Impl(i)),
@@ -198,6 +212,7 @@ pub fn top_level_items() -> impl NoirParser<Vec<TopLevelStatement>> {
 
 /// top_level_statement: function_definition
 ///                    | struct_definition
+///                    | enum_definition
 ///                    | trait_definition
 ///                    | implementation
 ///                    | submodule
@@ -210,6 +225,7 @@ fn top_level_statement(
     choice((
         function::function_definition(false).map(TopLevelStatement::Function),
         structs::struct_definition(),
+        enums::enum_definition(),
         traits::trait_definition(),
         traits::trait_implementation(),
         implementation(),
@@ -409,10 +425,8 @@ fn trait_bounds() -> impl NoirParser<Vec<TraitBound>> {
 }
 
 fn trait_bound() -> impl NoirParser<TraitBound> {
-    path().then(generic_type_args(parse_type())).map(|(trait_path, trait_generics)| TraitBound {
-        trait_path,
-        trait_generics,
-        trait_id: None,
+    path().then(trait_bound_generic_args()).map(|(trait_path, (trait_generics, named_generics))| {
+        TraitBound { trait_path, trait_generics, named_generics, trait_id: None }
     })
 }
 
@@ -608,11 +622,38 @@ fn pattern() -> impl NoirParser<Pattern> {
             .map_with_span(|(typename, fields), span| Pattern::Struct(typename, fields, span));
 
         let tuple_pattern = pattern
+            .clone()
             .separated_by(just(Token::Comma))
             .delimited_by(just(Token::LeftParen), just(Token::RightParen))
             .map_with_span(Pattern::Tuple);
 
-        choice((mut_pattern, tuple_pattern, struct_pattern, ident_pattern))
+        let variant_pattern_arguments = pattern
+            .separated_by(just(Token::Comma))
+            .allow_trailing()
+            .delimited_by(just(Token::LeftParen), just(Token::RightParen));
+
+        let variant_pattern = path()
+            .then(variant_pattern_arguments)
+            .map_with_span(|(path, patterns), span| Pattern::Variant(path, patterns, span));
+
+        // A unit variant such as `Option::None` is only distinguishable from a variable
+        // binding by its path, so it must be qualified by at least one extra segment.
+        let unit_variant_pattern = path().try_map(|path, span| {
+            if path.segments.len() > 1 || path.kind != PathKind::Plain {
+                Ok(Pattern::Variant(path, Vec::new(), span))
+            } else {
+                Err(ParserError::empty(Token::DoubleColon, span))
+            }
+        });
+
+        choice((
+            mut_pattern,
+            tuple_pattern,
+            struct_pattern,
+            variant_pattern,
+            unit_variant_pattern,
+            ident_pattern,
+        ))
     })
     .labelled(ParsingRuleLabel::Pattern)
 }
@@ -969,6 +1010,50 @@ where
     })
 }
 
+/// match_expr: 'match' expression_no_constructors '{' match_rule ... '}'
+///
+/// match_rule: pattern '=>' expression ','
+///
+/// The trailing comma of a rule is optional when its expression is a block-like
+/// expression or when it is the last rule.
+fn match_expr<'a, P, P2>(
+    expr_parser: P,
+    expr_no_constructors: P2,
+) -> impl NoirParser<ExpressionKind> + 'a
+where
+    P: ExprParser + 'a,
+    P2: ExprParser + 'a,
+{
+    let rule = pattern()
+        .then_ignore(just(Token::FatArrow))
+        .then(expr_parser)
+        .then(just(Token::Comma).or_not().map(|comma| comma.is_some()));
+
+    let rules = rule.repeated().validate(|rules, _span, emit| {
+        let last_index = rules.len().saturating_sub(1);
+        vecmap(rules.into_iter().enumerate(), |(index, ((pattern, branch), has_comma))| {
+            let is_block_like = matches!(
+                branch.kind,
+                ExpressionKind::Block(_) | ExpressionKind::If(_) | ExpressionKind::Match(_)
+            );
+            if !has_comma && !is_block_like && index != last_index {
+                emit(ParserError::with_reason(
+                    ParserErrorReason::MissingSeparatingComma,
+                    branch.span,
+                ));
+            }
+            (pattern, branch)
+        })
+    });
+
+    keyword(Keyword::Match)
+        .ignore_then(expr_no_constructors)
+        .then(rules.delimited_by(just(Token::LeftBrace), just(Token::RightBrace)))
+        .map(|(expression, rules)| {
+            ExpressionKind::Match(Box::new(MatchExpression { expression, rules }))
+        })
+}
+
 fn for_loop<'a, P, S>(expr_no_constructors: P, statement: S) -> impl NoirParser<StatementKind> + 'a
 where
     P: ExprParser + 'a,
@@ -1078,7 +1163,8 @@ where
     S: NoirParser<StatementKind> + 'a,
 {
     choice((
-        if_expr(expr_no_constructors, statement.clone()),
+        if_expr(expr_no_constructors.clone(), statement.clone()),
+        match_expr(expr_parser.clone(), expr_no_constructors),
         slice_expr(expr_parser.clone()),
         array_expr(expr_parser.clone()),
         if allow_constructors {
@@ -1436,6 +1522,43 @@ mod test {
         );
     }
 
+    #[test]
+    fn parse_match_expr() {
+        parse_all(
+            match_expr(expression(), expression_no_constructors(expression())),
+            vec![
+                "match x { }",
+                "match x { _ => 1 }",
+                "match x { Option::Some(y) => y, Option::None => 0, }",
+                "match x + 1 { Foo::A => { 1 } Foo::B(a, (b, c)) => a + b + c }",
+                "match (x, y) { (Shape::Circle(r), _) => r, (_, mut z) => z }",
+                "match x { Foo::A => if y { 1 } else { 2 } _ => 3 }",
+            ],
+        );
+
+        parse_all_failing(
+            match_expr(expression(), expression_no_constructors(expression())),
+            vec![
+                "match x { A => 1 B => 2 }",
+                "match x { Foo::A -> 1 }",
+                "match { _ => 1 }",
+                "match x _ => 1",
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_variant_patterns() {
+        let patterns = parse_all(
+            pattern(),
+            vec!["Option::None", "Option::Some(x)", "Foo::Bar(Baz::Qux, (a, b), _)", "crate::A"],
+        );
+        assert!(patterns.iter().all(|pattern| matches!(pattern, Pattern::Variant(..))));
+
+        let identifier = parse_with(pattern(), "x").unwrap();
+        assert!(matches!(identifier, Pattern::Identifier(_)));
+    }
+
     #[test]
     fn parse_module_declaration() {
         parse_with(module_declaration(), "mod foo").unwrap();
@@ -0,0 +1,81 @@
+use chumsky::prelude::*;
+
+use crate::ast::{EnumVariant, NoirEnumeration};
+use crate::{
+    parser::{
+        parser::{
+            attributes::{attributes, validate_secondary_attributes},
+            function, parse_type,
+            primitives::{ident, keyword},
+        },
+        NoirParser, TopLevelStatement,
+    },
+    token::{Keyword, Token},
+};
+
+pub(super) fn enum_definition() -> impl NoirParser<TopLevelStatement> {
+    use self::Keyword::Enum;
+    use Token::*;
+
+    let variants = enum_variants().delimited_by(just(LeftBrace), just(RightBrace)).recover_with(
+        nested_delimiters(
+            LeftBrace,
+            RightBrace,
+            [(LeftParen, RightParen), (LeftBracket, RightBracket)],
+            |_| vec![],
+        ),
+    );
+
+    attributes()
+        .then_ignore(keyword(Enum))
+        .then(ident())
+        .then(function::generics())
+        .then(variants)
+        .validate(|(((raw_attributes, name), generics), variants), span, emit| {
+            let attributes = validate_secondary_attributes(raw_attributes, span, emit);
+            TopLevelStatement::Enum(NoirEnumeration { name, attributes, generics, variants, span })
+        })
+}
+
+/// enum_variant: ident
+///             | ident '(' type, ... ')'
+fn enum_variants() -> impl NoirParser<Vec<EnumVariant>> {
+    let parameters = parse_type()
+        .separated_by(just(Token::Comma))
+        .allow_trailing()
+        .delimited_by(just(Token::LeftParen), just(Token::RightParen));
+
+    ident()
+        .then(parameters.or_not())
+        .map(|(name, parameters)| EnumVariant { name, parameters: parameters.unwrap_or_default() })
+        .separated_by(just(Token::Comma))
+        .allow_trailing()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::parser::test_helpers::*;
+
+    #[test]
+    fn parse_enums() {
+        let cases = vec![
+            "enum Foo { }",
+            "enum Foo { A }",
+            "enum Foo { A, B, }",
+            "enum Option<T> { None, Some(T) }",
+            "enum Shape { Circle(Field), Rect(Field, Field,), Empty }",
+            "#[attribute] enum Foo { A(u8), B }",
+        ];
+        parse_all(enum_definition(), cases);
+
+        let failing = vec![
+            "enum {  }",
+            "enum Foo;",
+            "enum Foo { A: Field }",
+            "enum Foo { A(pub Field) }",
+            "#[oracle(some)] enum Foo { A }",
+        ];
+        parse_all_failing(enum_definition(), failing);
+    }
+}
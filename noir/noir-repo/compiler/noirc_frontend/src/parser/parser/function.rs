@@ -198,6 +198,8 @@ mod test {
                 "fn func_name<T>(f: Field, y : T) where T: SomeTrait<A, B> + SomeTrait2<C> {}",
                 "fn func_name<T>(f: Field, y : T) where T: SomeTrait + SomeTrait2<C> {}",
                 "fn func_name<T>(f: Field, y : T) where T: SomeTrait + SomeTrait2<C> + TraitY {}",
+                "fn func_name<T>(f: Field, y : T) where T: Iterator<Item = Field> {}",
+                "fn func_name<T>(f: Field, y : T) where T: SomeTrait<A, Item = B, Size = 3> {}",
                 "fn func_name<T>(f: Field, y : T, z : U) where SomeStruct<T>: SomeTrait<U> {}",
                 // 'where u32: SomeTrait' is allowed in Rust.
                 // It will result in compiler error in case SomeTrait isn't implemented for u32.
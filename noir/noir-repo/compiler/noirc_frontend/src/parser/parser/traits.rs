@@ -15,7 +15,7 @@ use crate::{
     token::{Keyword, Token},
 };
 
-use super::{generic_type_args, parse_type, path, primitives::ident};
+use super::{generic_type_args, parse_type, path, primitives::ident, trait_bound_generic_args};
 
 pub(super) fn trait_definition() -> impl NoirParser<TopLevelStatement> {
     keyword(Keyword::Trait)
@@ -41,6 +41,7 @@ fn optional_default_value() -> impl NoirParser<Option<Expression>> {
     ignore_then_commit(just(Token::Assign), expression()).or_not()
 }
 
+/// trait_constant_declaration: 'let' ident ':' type ('=' expression)? ';'
 fn trait_constant_declaration() -> impl NoirParser<TraitItem> {
     keyword(Keyword::Let)
         .ignore_then(ident())
@@ -48,13 +49,7 @@ fn trait_constant_declaration() -> impl NoirParser<TraitItem> {
         .then(parse_type())
         .then(optional_default_value())
         .then_ignore(just(Token::Semicolon))
-        .validate(|((name, typ), default_value), span, emit| {
-            emit(ParserError::with_reason(
-                ParserErrorReason::ExperimentalFeature("Associated constants"),
-                span,
-            ));
-            TraitItem::Constant { name, typ, default_value }
-        })
+        .map(|((name, typ), default_value)| TraitItem::Constant { name, typ, default_value })
 }
 
 /// trait_function_declaration: 'fn' ident generics '(' declaration_parameters ')' function_return_type
@@ -74,17 +69,12 @@ fn trait_function_declaration() -> impl NoirParser<TraitItem> {
         })
 }
 
-/// trait_type_declaration: 'type' ident generics
+/// trait_type_declaration: 'type' ident ';'
 fn trait_type_declaration() -> impl NoirParser<TraitItem> {
-    keyword(Keyword::Type).ignore_then(ident()).then_ignore(just(Token::Semicolon)).validate(
-        |name, span, emit| {
-            emit(ParserError::with_reason(
-                ParserErrorReason::ExperimentalFeature("Associated types"),
-                span,
-            ));
-            TraitItem::Type { name }
-        },
-    )
+    keyword(Keyword::Type)
+        .ignore_then(ident())
+        .then_ignore(just(Token::Semicolon))
+        .map(|name| TraitItem::Type { name })
 }
 
 /// Parses a trait implementation, implementing a particular trait for a type.
@@ -135,7 +125,16 @@ fn trait_implementation_body() -> impl NoirParser<Vec<TraitImplItem>> {
         .then_ignore(just(Token::Semicolon))
         .map(|(name, alias)| TraitImplItem::Type { name, alias });
 
-    function.or(alias).repeated()
+    let constant = keyword(Keyword::Let)
+        .ignore_then(ident())
+        .then_ignore(just(Token::Colon))
+        .then(parse_type())
+        .then_ignore(just(Token::Assign))
+        .then(expression())
+        .then_ignore(just(Token::Semicolon))
+        .map(|((name, typ), value)| TraitImplItem::Constant(name, typ, value));
+
+    function.or(alias).or(constant).repeated()
 }
 
 fn where_clause() -> impl NoirParser<Vec<UnresolvedTraitConstraint>> {
@@ -172,10 +171,8 @@ fn trait_bounds() -> impl NoirParser<Vec<TraitBound>> {
 }
 
 fn trait_bound() -> impl NoirParser<TraitBound> {
-    path().then(generic_type_args(parse_type())).map(|(trait_path, trait_generics)| TraitBound {
-        trait_path,
-        trait_generics,
-        trait_id: None,
+    path().then(trait_bound_generic_args()).map(|(trait_path, (trait_generics, named_generics))| {
+        TraitBound { trait_path, trait_generics, named_generics, trait_id: None }
     })
 }
 
@@ -210,4 +207,27 @@ mod test {
             vec!["trait MissingBody", "trait WrongDelimiter { fn foo() -> u8, fn bar() -> u8 }"],
         );
     }
+
+    #[test]
+    fn parse_trait_impl() {
+        parse_all(
+            trait_implementation(),
+            vec![
+                "impl Empty for Foo {}",
+                "impl Default for Foo { fn default() -> Self { Foo {} } }",
+                "impl<T> Container<T> for Foo<T> where T: Eq { fn get(self) -> T { self.x } }",
+                "impl Iterator for Foo { type Item = Field; fn next(self) -> Self::Item { 0 } }",
+                "impl Serialize for Foo { let Size: u32 = 2; }",
+                "impl<T> Serialize for [T; 2] where T: Serialize<Size = 1> { let Size: u32 = 2; }",
+            ],
+        );
+
+        parse_all_failing(
+            trait_implementation(),
+            vec![
+                "impl Serialize for Foo { let Size: u32; }",
+                "impl Iterator for Foo { type Item; }",
+            ],
+        );
+    }
 }
@@ -1,9 +1,11 @@
-use super::primitives::token_kind;
+use super::primitives::{ident, token_kind};
 use super::{
     expression_with_precedence, keyword, nothing, parenthesized, path, NoirParser, ParserError,
     ParserErrorReason, Precedence,
 };
-use crate::ast::{Recoverable, UnresolvedType, UnresolvedTypeData, UnresolvedTypeExpression};
+use crate::ast::{
+    Ident, Recoverable, UnresolvedType, UnresolvedTypeData, UnresolvedTypeExpression,
+};
 use crate::QuotedType;
 
 use crate::parser::labels::ParsingRuleLabel;
@@ -174,8 +176,55 @@ pub(super) fn named_trait<'a>(
 pub(super) fn generic_type_args<'a>(
     type_parser: impl NoirParser<UnresolvedType> + 'a,
 ) -> impl NoirParser<Vec<UnresolvedType>> + 'a {
+    generic_type_arg(type_parser)
+        .separated_by(just(Token::Comma))
+        .allow_trailing()
+        .at_least(1)
+        .delimited_by(just(Token::Less), just(Token::Greater))
+        .or_not()
+        .map(Option::unwrap_or_default)
+}
+
+/// Parses the generic arguments of a trait bound. In addition to the usual generic
+/// arguments, these may also name the associated types and constants of the trait:
+///
+/// trait_bound_generic_args: '<' (generic_type_arg | ident '=' generic_type_arg),* '>'
+///
+/// Returns the positional arguments followed by the named ones.
+pub(super) fn trait_bound_generic_args<'a>(
+) -> impl NoirParser<(Vec<UnresolvedType>, Vec<(Ident, UnresolvedType)>)> + 'a {
+    let named_arg = ident()
+        .then_ignore(just(Token::Assign))
+        .then(generic_type_arg(parse_type()))
+        .map(|(name, typ)| (Some(name), typ));
+
+    let arg = generic_type_arg(parse_type()).map(|typ| (None, typ));
+
+    named_arg
+        .or(arg)
+        .separated_by(just(Token::Comma))
+        .allow_trailing()
+        .at_least(1)
+        .delimited_by(just(Token::Less), just(Token::Greater))
+        .or_not()
+        .map(|args| {
+            let mut generics = Vec::new();
+            let mut named_generics = Vec::new();
+
+            for (name, typ) in args.unwrap_or_default() {
+                match name {
+                    Some(name) => named_generics.push((name, typ)),
+                    None => generics.push(typ),
+                }
+            }
+            (generics, named_generics)
+        })
+}
+
+fn generic_type_arg<'a>(
+    type_parser: impl NoirParser<UnresolvedType> + 'a,
+) -> impl NoirParser<UnresolvedType> + 'a {
     type_parser
-        .clone()
         // Without checking for a terminating ',' or '>' here we may incorrectly
         // parse a generic `N * 2` as just the type `N` then fail when there is no
         // separator afterward. Failing early here ensures we try the `type_expression`
@@ -183,12 +232,6 @@ pub(super) fn generic_type_args<'a>(
         .then_ignore(one_of([Token::Comma, Token::Greater]).rewind())
         .or(type_expression()
             .map_with_span(|expr, span| UnresolvedTypeData::Expression(expr).with_span(span)))
-        .separated_by(just(Token::Comma))
-        .allow_trailing()
-        .at_least(1)
-        .delimited_by(just(Token::Less), just(Token::Greater))
-        .or_not()
-        .map(Option::unwrap_or_default)
 }
 
 pub(super) fn array_type<'a>(
@@ -27,7 +27,7 @@ use crate::node_interner::{NodeInterner, StmtId};
 use crate::hir::def_collector::dc_crate::DefCollector;
 use crate::hir_def::expr::HirExpression;
 use crate::hir_def::stmt::HirStatement;
-use crate::monomorphization::monomorphize;
+use crate::monomorphization::{auto_fold::auto_fold, monomorphize};
 use crate::parser::ParserErrorReason;
 use crate::ParsedModule;
 use crate::{
@@ -929,6 +929,57 @@ fn resolve_unused_var() {
     }
 }
 
+#[test]
+fn resolve_unused_import() {
+    let src = r#"
+        mod foo {
+            pub fn bar() {}
+            pub fn baz() {}
+        }
+
+        use foo::bar;
+        use foo::baz as qux;
+
+        fn main() {
+            bar();
+        }
+    "#;
+
+    let errors = get_program_errors(src);
+    assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+    // It should be regarding the aliased import, which is never used
+    match &errors[0].0 {
+        CompilationError::ResolverError(ResolverError::UnusedImport { ident }) => {
+            assert_eq!(&ident.0.contents, "qux");
+        }
+        _ => unreachable!("we should only have an unused import error"),
+    }
+}
+
+#[test]
+fn does_not_warn_about_imports_used_in_types_or_other_imports() {
+    let src = r#"
+        mod foo {
+            pub struct Bar {}
+
+            pub mod baz {
+                pub fn qux() {}
+            }
+        }
+
+        use foo::Bar;
+        use foo::baz;
+        use baz::qux;
+
+        fn main() {
+            let _bar = Bar {};
+            qux();
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+}
+
 #[test]
 fn resolve_unresolved_var() {
     let src = r#"
@@ -2109,3 +2160,484 @@ fn turbofish_numeric_generic_nested_call() {
     let errors = get_program_errors(src);
     assert!(errors.is_empty());
 }
+
+fn get_auto_folded_functions(src: &str) -> Vec<(String, usize)> {
+    let (_program, mut context, errors) = get_program(src, false);
+    assert_eq!(errors.len(), 0);
+    let main_func_id = context.def_interner.find_function("main").unwrap();
+    let mut program = monomorphize(main_func_id, &mut context.def_interner).unwrap();
+    let auto_folded = auto_fold(&mut program);
+
+    // Each folded function must now have its own signature
+    assert_eq!(program.function_signatures.len(), auto_folded.len() + 1);
+    vecmap(auto_folded, |function| (function.name, function.call_count))
+}
+
+#[test]
+fn auto_folds_large_functions_called_many_times() {
+    let src = r#"
+    fn main(x: Field) -> pub Field {
+        expensive(x) + expensive(x + 1) + cheap(x) + cheap(x + 1)
+    }
+
+    fn expensive(x: Field) -> Field {
+        let mut sum = 0;
+        for i in 0..300 {
+            sum = sum + x * (i as Field);
+        }
+        sum
+    }
+
+    fn cheap(x: Field) -> Field {
+        x * x
+    }
+    "#;
+    assert_eq!(get_auto_folded_functions(src), vec![("expensive".to_string(), 2)]);
+}
+
+#[test]
+fn does_not_auto_fold_functions_called_under_a_predicate() {
+    let src = r#"
+    fn main(x: Field, y: bool) -> pub Field {
+        let mut result = expensive(x);
+        if y {
+            result = expensive(x + 1);
+        }
+        result
+    }
+
+    fn expensive(x: Field) -> Field {
+        let mut sum = 0;
+        for i in 0..300 {
+            sum = sum + x * (i as Field);
+        }
+        sum
+    }
+    "#;
+    assert!(get_auto_folded_functions(src).is_empty());
+}
+
+#[test]
+fn enums_and_match_expressions() {
+    let src = r#"
+    enum Shape {
+        Circle(Field),
+        Rectangle(Field, Field),
+        Empty,
+    }
+
+    enum Maybe<T> {
+        None,
+        Some(T),
+    }
+
+    fn area(shape: Shape) -> Field {
+        match shape {
+            Shape::Circle(radius) => 3 * radius * radius,
+            Shape::Rectangle(width, height) => width * height,
+            Shape::Empty => 0,
+        }
+    }
+
+    fn unwrap_or<T>(value: Maybe<T>, default: T) -> T {
+        match value {
+            Maybe::Some(x) => x,
+            Maybe::None => default,
+        }
+    }
+
+    fn main(x: Field) -> pub Field {
+        let shapes = [Shape::Circle(x), Shape::Rectangle(x, 2), Shape::Empty];
+        let mut total = 0;
+        for shape in shapes {
+            total = total + area(shape);
+        }
+
+        let nested = Maybe::Some(Maybe::Some(x));
+        let inner = match nested {
+            Maybe::Some(Maybe::Some(y)) => y,
+            Maybe::Some(Maybe::None) => 1,
+            Maybe::None => 2,
+        };
+        total + inner + unwrap_or(Maybe::None, 3)
+    }
+    "#;
+    let (_program, mut context, errors) = get_program(src, false);
+    assert_eq!(errors.len(), 0, "{errors:?}");
+
+    let main_func_id = context.def_interner.find_function("main").unwrap();
+    monomorphize(main_func_id, &mut context.def_interner).unwrap();
+}
+
+#[test]
+fn non_exhaustive_match() {
+    let src = r#"
+    enum Shape {
+        Circle(Field),
+        Rectangle(Field, Field),
+        Empty,
+    }
+
+    fn main(x: Field) -> pub Field {
+        match Shape::Circle(x) {
+            Shape::Circle(_) => 1,
+            Shape::Empty => 0,
+        }
+    }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    match &errors[0].0 {
+        CompilationError::ResolverError(ResolverError::NonExhaustiveMatch { missing, .. }) => {
+            assert_eq!(missing, "Shape::Rectangle(_, _)");
+        }
+        other => panic!("Expected a NonExhaustiveMatch error, got {other:?}"),
+    }
+}
+
+#[test]
+fn unreachable_match_arm() {
+    let src = r#"
+    enum Shape {
+        Circle(Field),
+        Rectangle(Field, Field),
+        Empty,
+    }
+
+    fn main(x: Field) -> pub Field {
+        match Shape::Circle(x) {
+            Shape::Circle(radius) => radius,
+            _ => 0,
+            Shape::Empty => 1,
+        }
+    }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::ResolverError(ResolverError::UnreachablePattern { .. })
+    ));
+}
+
+#[test]
+fn refutable_pattern_in_let() {
+    let src = r#"
+    enum Shape {
+        Circle(Field),
+        Rectangle(Field, Field),
+        Empty,
+    }
+
+    fn main(x: Field) -> pub Field {
+        let Shape::Circle(radius) = Shape::Circle(x);
+        radius
+    }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    match &errors[0].0 {
+        CompilationError::ResolverError(ResolverError::RefutablePattern { missing, .. }) => {
+            assert_eq!(missing, "Shape::Rectangle(_, _)");
+        }
+        other => panic!("Expected a RefutablePattern error, got {other:?}"),
+    }
+}
+
+#[test]
+fn unknown_variant_in_pattern() {
+    let src = r#"
+    enum Shape {
+        Circle(Field),
+        Rectangle(Field, Field),
+        Empty,
+    }
+
+    fn main(x: Field) -> pub Field {
+        match Shape::Circle(x) {
+            Shape::Triangle => 0,
+        }
+    }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    match &errors[0].0 {
+        CompilationError::ResolverError(ResolverError::NoSuchVariant { variant, enum_name }) => {
+            assert_eq!(variant.0.contents, "Triangle");
+            assert_eq!(enum_name, "Shape");
+        }
+        other => panic!("Expected a NoSuchVariant error, got {other:?}"),
+    }
+}
+
+#[test]
+fn enums_require_the_elaborator() {
+    let src = r#"
+    enum Shape {
+        Circle(Field),
+        Rectangle(Field, Field),
+        Empty,
+    }
+
+    fn main() {}
+    "#;
+    let errors = get_program(src, true).2;
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::ResolverError(ResolverError::EnumsRequireElaborator { .. })
+    ));
+}
+
+#[test]
+fn trait_associated_types_and_constants() {
+    let src = r#"
+    trait Container {
+        type Item;
+        let Size: u32;
+
+        fn get(self, index: u32) -> Self::Item;
+    }
+
+    struct Pair {
+        first: Field,
+        second: Field,
+    }
+
+    impl Container for Pair {
+        type Item = Field;
+        let Size: u32 = 2;
+
+        fn get(self, index: u32) -> Self::Item {
+            if index == 0 { self.first } else { self.second }
+        }
+    }
+
+    trait Describe {
+        let Id: Field = 7;
+    }
+
+    impl Describe for Pair {}
+
+    fn first<C>(container: C) -> C::Item where C: Container {
+        container.get(0)
+    }
+
+    fn sum<C>(container: C) -> Field where C: Container<Item = Field> {
+        let mut total = 0;
+        for i in 0..C::Size {
+            total = total + container.get(i);
+        }
+        total
+    }
+
+    fn id<T>(_value: T) -> Field where T: Describe {
+        T::Id
+    }
+
+    fn main(x: Field) -> pub Field {
+        let pair = Pair { first: x, second: 2 };
+        first(pair) + sum(pair) + id(pair)
+    }
+    "#;
+    let (_program, mut context, errors) = get_program(src, false);
+    assert_eq!(errors.len(), 0, "{errors:?}");
+
+    let main_func_id = context.def_interner.find_function("main").unwrap();
+    monomorphize(main_func_id, &mut context.def_interner).unwrap();
+}
+
+#[test]
+fn trait_bound_with_mismatched_associated_type() {
+    let src = r#"
+    trait Container {
+        type Item;
+        let Size: u32;
+
+        fn get(self, index: u32) -> Self::Item;
+    }
+
+    struct Pair {
+        first: Field,
+        second: Field,
+    }
+
+    impl Container for Pair {
+        type Item = Field;
+        let Size: u32 = 2;
+
+        fn get(self, index: u32) -> Self::Item {
+            if index == 0 { self.first } else { self.second }
+        }
+    }
+
+    fn sum<C>(container: C) -> u32 where C: Container<Item = u32> {
+        container.get(0) + container.get(1)
+    }
+
+    fn main(x: Field) {
+        let _ = sum(Pair { first: x, second: 2 });
+    }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::TypeError(TypeCheckError::NoMatchingImplFound(..))
+    ));
+}
+
+#[test]
+fn trait_impl_missing_associated_item() {
+    let src = r#"
+    trait Container {
+        type Item;
+        let Size: u32;
+    }
+
+    impl Container for Field {
+        type Item = Field;
+    }
+
+    fn main() {}
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1, "{errors:?}");
+
+    match &errors[0].0 {
+        CompilationError::DefinitionError(DefCollectorErrorKind::TraitMissingAssociatedItem {
+            item_name,
+            ..
+        }) => assert_eq!(item_name, "Size"),
+        other => panic!("Expected a TraitMissingAssociatedItem error, got {other:?}"),
+    }
+}
+
+#[test]
+fn trait_impl_unknown_associated_item() {
+    let src = r#"
+    trait Container {
+        type Item;
+    }
+
+    impl Container for Field {
+        type Item = Field;
+        let Size: u32 = 3;
+    }
+
+    fn main() {}
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1, "{errors:?}");
+
+    match &errors[0].0 {
+        CompilationError::DefinitionError(DefCollectorErrorKind::AssociatedItemNotInTrait {
+            trait_name,
+            item_name,
+        }) => {
+            assert_eq!(trait_name, "Container");
+            assert_eq!(item_name, "Size");
+        }
+        other => panic!("Expected an AssociatedItemNotInTrait error, got {other:?}"),
+    }
+}
+
+#[test]
+fn trait_bound_with_unknown_associated_item() {
+    let src = r#"
+    trait Container {
+        type Item;
+        let Size: u32;
+
+        fn get(self, index: u32) -> Self::Item;
+    }
+
+    struct Pair {
+        first: Field,
+        second: Field,
+    }
+
+    impl Container for Pair {
+        type Item = Field;
+        let Size: u32 = 2;
+
+        fn get(self, index: u32) -> Self::Item {
+            if index == 0 { self.first } else { self.second }
+        }
+    }
+
+    fn first<C>(container: C) -> Field where C: Container<Item = Field, Length = 2> {
+        container.get(0)
+    }
+
+    fn main() {}
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::DefinitionError(DefCollectorErrorKind::AssociatedItemNotInTrait { .. })
+    ));
+}
+
+#[test]
+fn duplicate_associated_type_in_trait_impl() {
+    let src = r#"
+    trait Container {
+        type Item;
+    }
+
+    impl Container for Field {
+        type Item = Field;
+        type Item = u32;
+    }
+
+    fn main() {}
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::DefinitionError(DefCollectorErrorKind::Duplicate {
+            typ: DuplicateType::TraitAssociatedType,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn associated_items_require_the_elaborator() {
+    // Traits which only declare associated items are accepted, as they were before
+    // associated items were supported, but impls may not provide them.
+    let src = r#"
+    trait Container {
+        type Item;
+        let Size: u32;
+    }
+
+    fn main() {}
+    "#;
+    let errors = get_program(src, true).2;
+    assert_eq!(errors.len(), 0, "{errors:?}");
+
+    let src = r#"
+    trait Container {
+        type Item;
+    }
+
+    impl Container for Field {
+        type Item = Field;
+    }
+
+    fn main() {}
+    "#;
+    let errors = get_program(src, true).2;
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::ResolverError(ResolverError::AssociatedItemsRequireElaborator { .. })
+    ));
+}
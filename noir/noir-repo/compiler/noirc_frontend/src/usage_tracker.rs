@@ -0,0 +1,28 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{ast::Ident, hir::def_map::ModuleId};
+
+/// Tracks which imports of each module have not been used yet.
+#[derive(Debug, Default)]
+pub struct UsageTracker {
+    unused_imports: HashMap<ModuleId, HashSet<Ident>>,
+}
+
+impl UsageTracker {
+    pub(crate) fn add_unused_import(&mut self, module_id: ModuleId, name: Ident) {
+        self.unused_imports.entry(module_id).or_default().insert(name);
+    }
+
+    /// Marks the names looked up in each module while resolving a path as used.
+    pub(crate) fn mark_as_used(&mut self, used_names: &[(ModuleId, Ident)]) {
+        for (module_id, name) in used_names {
+            if let Some(unused_imports) = self.unused_imports.get_mut(module_id) {
+                unused_imports.remove(name);
+            }
+        }
+    }
+
+    pub fn unused_imports(&self) -> &HashMap<ModuleId, HashSet<Ident>> {
+        &self.unused_imports
+    }
+}
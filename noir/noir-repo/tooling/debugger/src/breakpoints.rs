@@ -0,0 +1,247 @@
+use acvm::acir::native_types::Witness;
+use acvm::{AcirField, FieldElement};
+use noirc_artifacts::debug::StackFrame;
+use noirc_printable_type::{PrintableType, PrintableValue};
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ComparisonOp {
+    Equal,
+    NotEqual,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+}
+
+impl ComparisonOp {
+    // Two character operators need to be listed before their single character
+    // prefixes so that eg. `<=` is not parsed as `<`
+    const ALL: [(&'static str, ComparisonOp); 6] = [
+        ("==", ComparisonOp::Equal),
+        ("!=", ComparisonOp::NotEqual),
+        ("<=", ComparisonOp::LessThanOrEqual),
+        (">=", ComparisonOp::GreaterThanOrEqual),
+        ("<", ComparisonOp::LessThan),
+        (">", ComparisonOp::GreaterThan),
+    ];
+
+    fn as_str(&self) -> &'static str {
+        ComparisonOp::ALL.iter().find(|(_, op)| op == self).map(|(symbol, _)| *symbol).unwrap()
+    }
+
+    fn is_equality(&self) -> bool {
+        matches!(self, ComparisonOp::Equal | ComparisonOp::NotEqual)
+    }
+
+    fn compare<T: PartialOrd>(&self, lhs: T, rhs: T) -> bool {
+        match self {
+            ComparisonOp::Equal => lhs == rhs,
+            ComparisonOp::NotEqual => lhs != rhs,
+            ComparisonOp::LessThan => lhs < rhs,
+            ComparisonOp::LessThanOrEqual => lhs <= rhs,
+            ComparisonOp::GreaterThan => lhs > rhs,
+            ComparisonOp::GreaterThanOrEqual => lhs >= rhs,
+        }
+    }
+}
+
+/// A condition attached to a breakpoint, comparing the current value of a
+/// source-level variable against a constant, eg. `i == 42` or `done != true`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct BreakpointCondition {
+    variable: String,
+    op: ComparisonOp,
+    value: String,
+}
+
+impl BreakpointCondition {
+    /// Evaluates the condition against the variables visible in the given
+    /// stack frame. An error is returned if the variable is not in scope or if
+    /// its value cannot be compared against the constant in the condition.
+    pub(super) fn evaluate(&self, frame: &StackFrame<FieldElement>) -> Result<bool, String> {
+        let Some((_, value, typ)) =
+            frame.variables.iter().find(|(name, ..)| *name == self.variable)
+        else {
+            return Err(format!("variable `{}` is not in scope", self.variable));
+        };
+        let PrintableValue::Field(field_value) = value else {
+            return Err(format!("variable `{}` is not a scalar value", self.variable));
+        };
+
+        if let (Some(lhs), Some(rhs)) = (field_to_i128(*field_value, typ), parse_i128(&self.value))
+        {
+            return Ok(self.op.compare(lhs, rhs));
+        }
+        // Field values which do not fit in 128 bits can still be compared
+        // for (in)equality
+        match FieldElement::try_from_str(&self.value) {
+            Some(rhs) if self.op.is_equality() => Ok(self.op.compare(*field_value, rhs)),
+            _ => Err(format!(
+                "cannot compare `{}` of type {typ:?} with `{}` using `{}`",
+                self.variable,
+                self.value,
+                self.op.as_str()
+            )),
+        }
+    }
+}
+
+impl FromStr for BreakpointCondition {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let Some((index, symbol, op)) = ComparisonOp::ALL
+            .iter()
+            .filter_map(|(symbol, op)| input.find(symbol).map(|index| (index, *symbol, *op)))
+            .min_by_key(|(index, ..)| *index)
+        else {
+            return Err(format!("missing comparison operator in condition `{input}`"));
+        };
+
+        let variable = input[..index].trim();
+        let value = input[index + symbol.len()..].trim();
+        if variable.is_empty() || value.is_empty() {
+            return Err(format!("expected `<variable> {symbol} <value>`, found `{input}`"));
+        }
+        Ok(BreakpointCondition { variable: variable.to_string(), op, value: value.to_string() })
+    }
+}
+
+impl Display for BreakpointCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.variable, self.op.as_str(), self.value)
+    }
+}
+
+/// A value watched for changes during execution. Witnesses are written as
+/// `witness:_<index>`, using the notation the debugger displays the witness
+/// map with, anything else is considered to be the name of a source-level
+/// variable. The prefix keeps variables such as `_1` from being mistaken for
+/// witnesses.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) enum Watchpoint {
+    Witness(Witness),
+    Variable(String),
+}
+
+const WITNESS_PREFIX: &str = "witness:";
+
+impl FromStr for Watchpoint {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(String::from("empty watch expression"));
+        }
+        let Some(witness) = input.strip_prefix(WITNESS_PREFIX) else {
+            return Ok(Watchpoint::Variable(input.to_string()));
+        };
+        match witness.strip_prefix('_').map(|index| index.parse::<u32>()) {
+            Some(Ok(index)) => Ok(Watchpoint::Witness(Witness(index))),
+            _ => Err(format!("invalid witness `{witness}`, expected `{WITNESS_PREFIX}_<index>`")),
+        }
+    }
+}
+
+impl Display for Watchpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Watchpoint::Witness(witness) => {
+                write!(f, "{WITNESS_PREFIX}_{}", witness.witness_index())
+            }
+            Watchpoint::Variable(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+fn field_to_i128(value: FieldElement, typ: &PrintableType) -> Option<i128> {
+    match typ {
+        PrintableType::Boolean => Some(if value.is_zero() { 0 } else { 1 }),
+        PrintableType::UnsignedInteger { .. } => value.try_into_u128()?.try_into().ok(),
+        PrintableType::SignedInteger { width } => {
+            let unsigned = value.try_into_u128()?;
+            if *width == 0 || *width > 127 {
+                return None;
+            }
+            if unsigned >> (width - 1) == 1 {
+                Some(unsigned as i128 - (1i128 << width))
+            } else {
+                Some(unsigned as i128)
+            }
+        }
+        PrintableType::Field if value.num_bits() < 128 => Some(value.to_u128() as i128),
+        _ => None,
+    }
+}
+
+fn parse_i128(input: &str) -> Option<i128> {
+    match input {
+        "true" => Some(1),
+        "false" => Some(0),
+        _ => match input.strip_prefix("0x") {
+            Some(hex) => i128::from_str_radix(hex, 16).ok(),
+            None => input.parse::<i128>().ok(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame_with<'a>(
+        variables: Vec<(&'a str, &'a PrintableValue<FieldElement>, &'a PrintableType)>,
+    ) -> StackFrame<'a, FieldElement> {
+        StackFrame { function_name: "main", function_params: vec![], variables }
+    }
+
+    #[test]
+    fn parses_conditions() {
+        let condition: BreakpointCondition = "i<=10".parse().unwrap();
+        assert_eq!(condition.to_string(), "i <= 10");
+
+        let condition: BreakpointCondition = " done == true ".parse().unwrap();
+        assert_eq!(condition.to_string(), "done == true");
+
+        assert!("i".parse::<BreakpointCondition>().is_err());
+        assert!("== 3".parse::<BreakpointCondition>().is_err());
+    }
+
+    #[test]
+    fn evaluates_conditions_on_integer_variables() {
+        // -1 as an i8
+        let value = PrintableValue::Field(FieldElement::from(255_u128));
+        let signed = PrintableType::SignedInteger { width: 8 };
+        let unsigned = PrintableType::UnsignedInteger { width: 8 };
+        let frame = frame_with(vec![("x", &value, &signed)]);
+
+        let condition: BreakpointCondition = "x < 0".parse().unwrap();
+        assert_eq!(condition.evaluate(&frame), Ok(true));
+
+        let frame = frame_with(vec![("x", &value, &unsigned)]);
+        assert_eq!(condition.evaluate(&frame), Ok(false));
+
+        let condition: BreakpointCondition = "x == 0xff".parse().unwrap();
+        assert_eq!(condition.evaluate(&frame), Ok(true));
+
+        let condition: BreakpointCondition = "y == 0".parse().unwrap();
+        assert!(condition.evaluate(&frame).is_err());
+    }
+
+    #[test]
+    fn parses_watchpoints() {
+        assert_eq!("witness:_3".parse::<Watchpoint>(), Ok(Watchpoint::Witness(Witness(3))));
+        assert_eq!("_3".parse::<Watchpoint>(), Ok(Watchpoint::Variable(String::from("_3"))));
+        assert_eq!("_x".parse::<Watchpoint>(), Ok(Watchpoint::Variable(String::from("_x"))));
+        assert_eq!("sum".parse::<Watchpoint>(), Ok(Watchpoint::Variable(String::from("sum"))));
+        assert!("witness:3".parse::<Watchpoint>().is_err());
+        assert!("witness:_x".parse::<Watchpoint>().is_err());
+
+        let watchpoint = Watchpoint::Witness(Witness(3));
+        assert_eq!(watchpoint.to_string().parse::<Watchpoint>(), Ok(watchpoint));
+    }
+}
@@ -1,5 +1,11 @@
-use crate::foreign_calls::DebugForeignCallExecutor;
-use acvm::acir::circuit::brillig::BrilligBytecode;
+use crate::breakpoints::{BreakpointCondition, Watchpoint};
+use crate::evaluation::Evaluator;
+use crate::foreign_calls::{debug_var_id, DebugForeignCall, DebugForeignCallExecutor};
+use crate::history::{ExecutionHistory, StateEdit};
+use acvm::acir::brillig::{
+    ForeignCallParam, HeapArray, HeapValueType, Opcode as BrilligOpcode, ValueOrArray,
+};
+use acvm::acir::circuit::brillig::{BrilligBytecode, BrilligInputs};
 use acvm::acir::circuit::{Circuit, Opcode, OpcodeLocation};
 use acvm::acir::native_types::{Witness, WitnessMap};
 use acvm::brillig_vm::MemoryValue;
@@ -14,24 +20,91 @@ use nargo::errors::{ExecutionError, Location};
 use nargo::NargoError;
 use noirc_artifacts::debug::{DebugArtifact, StackFrame};
 use noirc_driver::DebugFile;
+use noirc_errors::debug_info::DebugVarId;
+use noirc_printable_type::{PrintableType, PrintableValue, PrintableValueDisplay};
 
-use std::collections::BTreeMap;
 use std::collections::{hash_set::Iter, HashSet};
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug)]
 pub(super) enum DebugCommandResult {
     Done,
     Ok,
     BreakpointReached(OpcodeLocation),
+    FunctionBreakpointReached(String),
+    WatchpointTriggered(Watchpoint),
     Error(NargoError<FieldElement>),
 }
 
+impl DebugCommandResult {
+    /// Returns a copy of the result if it represents stopping at a
+    /// breakpoint or watchpoint
+    fn as_stop(&self) -> Option<DebugCommandResult> {
+        match self {
+            DebugCommandResult::BreakpointReached(location) => {
+                Some(DebugCommandResult::BreakpointReached(*location))
+            }
+            DebugCommandResult::FunctionBreakpointReached(function_name) => {
+                Some(DebugCommandResult::FunctionBreakpointReached(function_name.clone()))
+            }
+            DebugCommandResult::WatchpointTriggered(watchpoint) => {
+                Some(DebugCommandResult::WatchpointTriggered(watchpoint.clone()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Where the flattened value of a source-level variable is held
+#[derive(Debug, Clone)]
+enum VariableLocation {
+    /// Witnesses of a variable assigned in constrained code
+    Witnesses(Vec<Witness>),
+    /// Memory cells of the Brillig VM being executed, for a variable assigned in
+    /// unconstrained code
+    BrilligMemory(Vec<usize>),
+}
+
+/// A watchpoint's last observed rendering of its value, or None if the value
+/// is not currently available (eg. variable out of scope)
+#[derive(Debug, Clone)]
+struct WatchedValue {
+    value: Option<String>,
+    /// The stack frame owning a watched variable, once it has been found
+    frame: Option<WatchedFrame>,
+}
+
+/// A source-level stack frame, identified by its depth in the call stack and
+/// by the step at which its function was entered. Watched variables are read
+/// from their own frame even while other functions are called, and their
+/// watchpoints are deleted once it returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct WatchedFrame {
+    depth: usize,
+    entered_at: usize,
+}
+
 pub(super) struct DebugContext<'a, B: BlackBoxFunctionSolver<FieldElement>> {
+    blackbox_solver: &'a B,
+    circuit: &'a Circuit<FieldElement>,
+    initial_witness: WitnessMap<FieldElement>,
     acvm: ACVM<'a, FieldElement, B>,
     brillig_solver: Option<BrilligSolver<'a, FieldElement, B>>,
     foreign_call_executor: Box<dyn DebugForeignCallExecutor + 'a>,
     debug_artifact: &'a DebugArtifact,
     breakpoints: HashSet<OpcodeLocation>,
+    breakpoint_conditions: HashMap<OpcodeLocation, BreakpointCondition>,
+    function_breakpoints: HashSet<String>,
+    watchpoints: HashMap<Watchpoint, WatchedValue>,
+    // Depth of the source-level call stack when stop conditions were last
+    // checked, used to detect entering a function
+    stack_depth: usize,
+    // Locations holding the values of the variables in each source-level stack
+    // frame, for the variables whose location is known
+    variable_locations: Vec<HashMap<DebugVarId, VariableLocation>>,
+    // Steps at which the function of each source-level stack frame was entered
+    frame_entry_steps: Vec<usize>,
+    history: ExecutionHistory,
     source_to_opcodes: BTreeMap<FileId, Vec<(usize, OpcodeLocation)>>,
     unconstrained_functions: &'a [BrilligBytecode<FieldElement>],
 
@@ -52,6 +125,9 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         let source_to_opcodes = build_source_to_opcode_debug_mappings(debug_artifact);
         let acir_opcode_addresses = build_acir_opcode_offsets(circuit, unconstrained_functions);
         Self {
+            blackbox_solver,
+            circuit,
+            initial_witness: initial_witness.clone(),
             // TODO: need to handle brillig pointer in the debugger
             acvm: ACVM::new(
                 blackbox_solver,
@@ -59,11 +135,19 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
                 initial_witness,
                 unconstrained_functions,
                 &circuit.assert_messages,
+                false,
             ),
             brillig_solver: None,
             foreign_call_executor,
             debug_artifact,
             breakpoints: HashSet::new(),
+            breakpoint_conditions: HashMap::new(),
+            function_breakpoints: HashSet::new(),
+            watchpoints: HashMap::new(),
+            stack_depth: 0,
+            variable_locations: vec![],
+            frame_entry_steps: vec![],
+            history: ExecutionHistory::default(),
             source_to_opcodes,
             unconstrained_functions,
             acir_opcode_addresses,
@@ -83,6 +167,7 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         witness: Witness,
         value: FieldElement,
     ) -> Option<FieldElement> {
+        self.history.record_edit(StateEdit::Witness(witness, value));
         self.acvm.overwrite_witness(witness, value)
     }
 
@@ -278,16 +363,10 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         match solver.step() {
             Ok(BrilligSolverStatus::InProgress) => {
                 self.brillig_solver = Some(solver);
-                if self.breakpoint_reached() {
-                    DebugCommandResult::BreakpointReached(
-                        self.get_current_opcode_location()
-                            .expect("Breakpoint reached but we have no location"),
-                    )
-                } else {
-                    DebugCommandResult::Ok
-                }
+                self.check_stop_conditions()
             }
             Ok(BrilligSolverStatus::Finished) => {
+                self.forget_brillig_memory_locations();
                 let status = self.acvm.finish_brillig_with_solver(solver);
                 self.handle_acvm_status(status)
             }
@@ -306,7 +385,21 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         &mut self,
         foreign_call: ForeignCallWaitInfo<FieldElement>,
     ) -> DebugCommandResult {
-        let foreign_call_result = self.foreign_call_executor.execute(&foreign_call);
+        let foreign_call_result =
+            if let Some(debug_call) = DebugForeignCall::lookup(&foreign_call.function) {
+                // Debug instrumentation calls only update the state tracked by the
+                // debugger itself, so they are executed again when replaying
+                self.track_variable_locations(&debug_call, &foreign_call);
+                self.foreign_call_executor.execute(&foreign_call)
+            } else if let Some(recorded_result) = self.history.next_recorded_foreign_call() {
+                Ok(recorded_result)
+            } else {
+                let result = self.foreign_call_executor.execute(&foreign_call);
+                if let Ok(ref result) = result {
+                    self.history.record_foreign_call(result.clone());
+                }
+                result
+            };
         match foreign_call_result {
             Ok(foreign_call_result) => {
                 if let Some(mut solver) = self.brillig_solver.take() {
@@ -316,12 +409,167 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
                     self.acvm.resolve_pending_foreign_call(foreign_call_result);
                 }
                 // TODO: should we retry executing the opcode somehow in this case?
-                DebugCommandResult::Ok
+                // Debug instrumentation calls update the source-level variables
+                // and call stack, so they may trigger watchpoints and function
+                // breakpoints
+                self.check_tracked_values().unwrap_or(DebugCommandResult::Ok)
             }
             Err(error) => DebugCommandResult::Error(error.into()),
         }
     }
 
+    /// Keeps track of the witnesses or Brillig memory cells holding the values
+    /// of the source-level variables, so that the user can modify them
+    fn track_variable_locations(
+        &mut self,
+        debug_call: &DebugForeignCall,
+        foreign_call: &ForeignCallWaitInfo<FieldElement>,
+    ) {
+        match debug_call {
+            DebugForeignCall::FnEnter => {
+                self.variable_locations.push(HashMap::new());
+                self.frame_entry_steps.push(self.history.steps());
+            }
+            DebugForeignCall::FnExit => {
+                self.variable_locations.pop();
+                self.frame_entry_steps.pop();
+            }
+            _ => {
+                let Some(ForeignCallParam::Single(var_id)) = foreign_call.inputs.first() else {
+                    return;
+                };
+                let var_id = debug_var_id(var_id);
+                // Partial assignments (eg. to a struct member) leave the
+                // variable split across different locations, so we stop
+                // tracking it
+                let location = match debug_call {
+                    DebugForeignCall::VarAssign => self
+                        .find_var_assign_witnesses(foreign_call)
+                        .map(VariableLocation::Witnesses)
+                        .or_else(|| {
+                            self.find_var_assign_memory(foreign_call)
+                                .map(VariableLocation::BrilligMemory)
+                        }),
+                    _ => None,
+                };
+                let Some(frame) = self.variable_locations.last_mut() else {
+                    return;
+                };
+                match location {
+                    Some(location) => frame.insert(var_id, location),
+                    None => frame.remove(&var_id),
+                };
+            }
+        }
+    }
+
+    /// Returns the witnesses holding the value assigned to a variable in
+    /// constrained code. The instrumentation is compiled to a Brillig call
+    /// receiving the variable id and its value, so if the inputs of the call
+    /// being executed are plain witnesses matching the foreign call arguments,
+    /// then those witnesses hold the value of the variable.
+    fn find_var_assign_witnesses(
+        &self,
+        foreign_call: &ForeignCallWaitInfo<FieldElement>,
+    ) -> Option<Vec<Witness>> {
+        let acir_index = self.get_current_acir_index()?;
+        let Opcode::BrilligCall { inputs, .. } = &self.get_opcodes()[acir_index] else {
+            return None;
+        };
+        let (BrilligInputs::Single(var_id_input), value_inputs) = inputs.split_first()? else {
+            return None;
+        };
+        let ForeignCallParam::Single(var_id) = foreign_call.inputs.first()? else {
+            return None;
+        };
+        if var_id_input.to_const() != Some(var_id) {
+            return None;
+        }
+
+        let mut expressions = vec![];
+        for input in value_inputs {
+            match input {
+                BrilligInputs::Single(expression) => expressions.push(expression),
+                BrilligInputs::Array(array) => expressions.extend(array.iter()),
+                BrilligInputs::MemoryArray(_) => return None,
+            }
+        }
+        let values: Vec<FieldElement> =
+            foreign_call.inputs[1..].iter().flat_map(|input| input.fields()).collect();
+        if expressions.len() != values.len() {
+            return None;
+        }
+        let witness_map = self.get_witness_map();
+        expressions
+            .into_iter()
+            .zip(values)
+            .map(|(expression, value)| {
+                let witness = expression.to_witness()?;
+                (witness_map.get(&witness) == Some(&value)).then_some(witness)
+            })
+            .collect()
+    }
+
+    /// Returns the Brillig memory cells holding the value assigned to a variable
+    /// in unconstrained code. These are the inputs of the foreign call opcode
+    /// being executed, as long as they are held directly in memory or in arrays
+    /// of scalars.
+    fn find_var_assign_memory(
+        &self,
+        foreign_call: &ForeignCallWaitInfo<FieldElement>,
+    ) -> Option<Vec<usize>> {
+        let memory = self.get_brillig_memory()?;
+        let Some(OpcodeLocation::Brillig { acir_index, brillig_index }) =
+            self.get_current_opcode_location()
+        else {
+            return None;
+        };
+        let Opcode::BrilligCall { id, .. } = &self.get_opcodes()[acir_index] else {
+            return None;
+        };
+        let bytecode = &self.unconstrained_functions[*id as usize].bytecode;
+        let BrilligOpcode::ForeignCall { inputs, input_value_types, .. } =
+            bytecode.get(brillig_index)?
+        else {
+            return None;
+        };
+
+        // The first input is the id of the variable
+        let mut cells = vec![];
+        for (input, value_type) in inputs.iter().zip(input_value_types).skip(1) {
+            match (input, value_type) {
+                (ValueOrArray::MemoryAddress(address), HeapValueType::Simple(_)) => {
+                    cells.push(address.to_usize());
+                }
+                (
+                    ValueOrArray::HeapArray(HeapArray { pointer, size }),
+                    HeapValueType::Array { value_types, .. },
+                ) if HeapValueType::all_simple(value_types) => {
+                    let start = memory.get(pointer.to_usize())?.to_usize();
+                    cells.extend(start..start + size);
+                }
+                _ => return None,
+            }
+        }
+
+        let values: Vec<FieldElement> =
+            foreign_call.inputs[1..].iter().flat_map(|input| input.fields()).collect();
+        let holds_values = cells.len() == values.len()
+            && cells
+                .iter()
+                .zip(&values)
+                .all(|(cell, value)| memory.get(*cell).map(MemoryValue::to_field) == Some(*value));
+        holds_values.then_some(cells)
+    }
+
+    /// Brillig memory is discarded once the Brillig call finishes, along with the
+    /// values of the variables held in it
+    fn forget_brillig_memory_locations(&mut self) {
+        for frame in &mut self.variable_locations {
+            frame.retain(|_, location| !matches!(location, VariableLocation::BrilligMemory(_)));
+        }
+    }
+
     fn handle_acvm_status(&mut self, status: ACVMStatus<FieldElement>) -> DebugCommandResult {
         if let ACVMStatus::RequiresForeignCall(foreign_call) = status {
             return self.handle_foreign_call(foreign_call);
@@ -329,16 +577,7 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
 
         match status {
             ACVMStatus::Solved => DebugCommandResult::Done,
-            ACVMStatus::InProgress => {
-                if self.breakpoint_reached() {
-                    DebugCommandResult::BreakpointReached(
-                        self.get_current_opcode_location()
-                            .expect("Breakpoint reached but we have no location"),
-                    )
-                } else {
-                    DebugCommandResult::Ok
-                }
-            }
+            ACVMStatus::InProgress => self.check_stop_conditions(),
             ACVMStatus::Failure(error) => DebugCommandResult::Error(NargoError::ExecutionError(
                 // TODO: debugger does not handle multiple acir calls
                 ExecutionError::SolvingError(error, None),
@@ -353,6 +592,7 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
     }
 
     pub(super) fn step_into_opcode(&mut self) -> DebugCommandResult {
+        self.history.record_step();
         if self.brillig_solver.is_some() {
             return self.step_brillig_opcode();
         }
@@ -407,8 +647,8 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         if self.is_executing_brillig() {
             self.step_out_of_brillig_opcode()
         } else {
-            let status = self.acvm.solve_opcode();
-            self.handle_acvm_status(status)
+            // not a Brillig call, so this solves a single ACIR opcode
+            self.step_into_opcode()
         }
     }
 
@@ -468,11 +708,136 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         }
     }
 
+    pub(super) fn can_step_back(&self) -> bool {
+        self.history.steps() > 0
+    }
+
+    /// Re-executes the program from the start, applying the recorded foreign
+    /// call results and user edits, until the given number of steps is
+    /// reached. The callback is invoked at the start and after every step with
+    /// the number of steps taken so far and the result of the last step.
+    fn replay(
+        &mut self,
+        steps: usize,
+        mut on_step: impl FnMut(&Self, usize, Option<&DebugCommandResult>),
+    ) {
+        let circuit = self.circuit;
+        self.acvm = ACVM::new(
+            self.blackbox_solver,
+            &circuit.opcodes,
+            self.initial_witness.clone(),
+            self.unconstrained_functions,
+            &circuit.assert_messages,
+            false,
+        );
+        self.brillig_solver = None;
+        self.foreign_call_executor.restart(self.debug_artifact);
+        self.stack_depth = 0;
+        self.variable_locations.clear();
+        self.frame_entry_steps.clear();
+        self.history.rewind();
+        self.apply_recorded_edits();
+        let watched: Vec<(Watchpoint, Option<WatchedFrame>)> = self
+            .watchpoints
+            .iter()
+            .map(|(watchpoint, watched)| (watchpoint.clone(), watched.frame))
+            .collect();
+        for (watchpoint, frame) in watched {
+            let value = self.read_watched_value(&watchpoint, frame);
+            self.watchpoints.insert(watchpoint, WatchedValue { value, frame });
+        }
+
+        on_step(&*self, 0, None);
+        while self.history.steps() < steps {
+            let result = self.step_into_opcode();
+            self.apply_recorded_edits();
+            on_step(&*self, self.history.steps(), Some(&result));
+            if matches!(result, DebugCommandResult::Done | DebugCommandResult::Error(_)) {
+                break;
+            }
+        }
+    }
+
+    fn apply_recorded_edits(&mut self) {
+        for edit in self.history.current_edits() {
+            match edit {
+                StateEdit::Witness(witness, value) => {
+                    self.acvm.overwrite_witness(witness, value);
+                }
+                StateEdit::BrilligMemory { ptr, value, bit_size } => {
+                    self.write_brillig_memory_at(ptr, value, bit_size);
+                }
+                StateEdit::Variable { var_id, indexes, value } => {
+                    self.assign_debug_var(var_id, &indexes, value);
+                }
+            }
+        }
+    }
+
+    /// Steps execution back by a single opcode
+    pub(super) fn step_back_opcode(&mut self) -> DebugCommandResult {
+        let steps = self.history.steps().saturating_sub(1);
+        self.replay(steps, |_, _, _| {});
+        DebugCommandResult::Ok
+    }
+
+    /// Steps execution back to the start of the previous source location
+    pub(super) fn next_back(&mut self) -> DebugCommandResult {
+        // steps at which execution arrived at a different source location
+        let mut location_starts: Vec<usize> = vec![];
+        let mut last_location = None;
+        self.replay(self.history.steps(), |context, step, _| {
+            let location = context.get_current_source_location();
+            if location.is_some() && location != last_location {
+                location_starts.push(step);
+                last_location = location;
+            }
+        });
+        if self.get_current_source_location().is_some() {
+            // we are somewhere in the run of opcodes of the current location
+            location_starts.pop();
+        }
+        let steps = location_starts.last().copied().unwrap_or(0);
+        self.replay(steps, |_, _, _| {});
+        DebugCommandResult::Ok
+    }
+
+    /// Runs execution backwards until the last breakpoint or watchpoint hit
+    /// before the current point, or the start of the program
+    pub(super) fn reverse_cont(&mut self) -> DebugCommandResult {
+        let current_steps = self.history.steps();
+        let mut last_stop: Option<(usize, DebugCommandResult)> = None;
+        self.replay(current_steps, |_, step, result| {
+            if let Some(stop) = result.and_then(DebugCommandResult::as_stop) {
+                if step < current_steps {
+                    last_stop = Some((step, stop));
+                }
+            }
+        });
+        match last_stop {
+            Some((steps, stop)) => {
+                self.replay(steps, |_, _, _| {});
+                stop
+            }
+            None => {
+                self.replay(0, |_, _, _| {});
+                DebugCommandResult::Ok
+            }
+        }
+    }
+
     pub(super) fn get_brillig_memory(&self) -> Option<&[MemoryValue<FieldElement>]> {
         self.brillig_solver.as_ref().map(|solver| solver.get_memory())
     }
 
     pub(super) fn write_brillig_memory(&mut self, ptr: usize, value: FieldElement, bit_size: u32) {
+        if self.brillig_solver.is_some() {
+            self.history.record_edit(StateEdit::BrilligMemory { ptr, value, bit_size });
+        }
+        self.write_brillig_memory_at(ptr, value, bit_size);
+    }
+
+    fn write_brillig_memory_at(&mut self, ptr: usize, value: FieldElement, bit_size: u32) {
         if let Some(solver) = self.brillig_solver.as_mut() {
             solver.write_memory_at(
                 ptr,
@@ -490,12 +855,225 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         return self.foreign_call_executor.current_stack_frame();
     }
 
+    /// Evaluates an expression over the variables of the given stack frame
+    /// (the innermost one by default) and the witness map
+    pub(super) fn evaluate(
+        &self,
+        expression: &str,
+        frame_index: Option<usize>,
+    ) -> Result<(PrintableValue<FieldElement>, PrintableType), String> {
+        let frames = self.get_variables();
+        let frame = match frame_index {
+            Some(index) => frames.get(index),
+            None => frames.last(),
+        };
+        Evaluator::new(frame, self.get_witness_map()).evaluate(expression)
+    }
+
+    /// Overwrites a witness with the result of evaluating the given expression
+    pub(super) fn set_witness(
+        &mut self,
+        witness: Witness,
+        expression: &str,
+    ) -> Result<FieldElement, String> {
+        let value = {
+            let frame = self.current_stack_frame();
+            Evaluator::new(frame.as_ref(), self.get_witness_map())
+                .evaluate_as(expression, &PrintableType::Field)?
+        };
+        self.overwrite_witness(witness, value);
+        Ok(value)
+    }
+
+    /// Overwrites a variable of the current stack frame, or a field or element
+    /// of one, with the result of evaluating the given expression. Only the
+    /// variables whose location is known can be modified: those last assigned
+    /// from witnesses in constrained code, or from Brillig memory in the
+    /// unconstrained code being executed.
+    pub(super) fn set_variable(&mut self, target: &str, expression: &str) -> Result<(), String> {
+        let (place, value) = {
+            let frame = self.current_stack_frame().ok_or("no variables in scope")?;
+            let evaluator = Evaluator::new(Some(&frame), self.get_witness_map());
+            let place = evaluator.place(target)?;
+            let value = evaluator.evaluate_as(expression, &place.typ)?;
+            (place, value)
+        };
+        let var_id = self
+            .foreign_call_executor
+            .current_frame_var_id(&place.variable)
+            .ok_or_else(|| format!("`{}` is not in scope", place.variable))?;
+
+        let location = self.variable_locations.last().and_then(|frame| frame.get(&var_id));
+        match location.cloned() {
+            Some(VariableLocation::Witnesses(witnesses)) if place.offset < witnesses.len() => {
+                self.overwrite_witness(witnesses[place.offset], value);
+            }
+            Some(VariableLocation::BrilligMemory(cells)) if place.offset < cells.len() => {
+                let ptr = cells[place.offset];
+                let bit_size = self
+                    .get_brillig_memory()
+                    .and_then(|memory| memory.get(ptr))
+                    .map(MemoryValue::bit_size)
+                    .ok_or_else(|| format!("the value of `{target}` is no longer in memory"))?;
+                self.write_brillig_memory(ptr, value, bit_size);
+            }
+            _ => {
+                return Err(format!(
+                    "the value of `{target}` is not held in a witness or in memory at this point"
+                ))
+            }
+        }
+        self.history.record_edit(StateEdit::Variable {
+            var_id,
+            indexes: place.indexes.clone(),
+            value,
+        });
+        self.assign_debug_var(var_id, &place.indexes, value);
+        Ok(())
+    }
+
+    /// Updates the value of a variable tracked by the debugger, or of the field
+    /// or element of it selected by the indexes, as if the program had assigned it
+    fn assign_debug_var(&mut self, var_id: DebugVarId, indexes: &[u32], value: FieldElement) {
+        let function = match indexes.len() {
+            0 => String::from("__debug_var_assign"),
+            arity => format!("__debug_member_assign_{arity}"),
+        };
+        let mut inputs = vec![FieldElement::from(var_id.0 as u128).into(), value.into()];
+        inputs.extend(indexes.iter().map(|index| FieldElement::from(*index as u128).into()));
+        // Assigning a scalar of the type of the variable or field cannot fail
+        let _ = self.foreign_call_executor.execute(&ForeignCallWaitInfo { function, inputs });
+    }
+
     fn breakpoint_reached(&self) -> bool {
-        if let Some(location) = self.get_current_opcode_location() {
-            self.breakpoints.contains(&location)
-        } else {
-            false
+        let Some(location) = self.get_current_opcode_location() else {
+            return false;
+        };
+        if !self.breakpoints.contains(&location) {
+            return false;
+        }
+        let Some(condition) = self.breakpoint_conditions.get(&location) else {
+            return true;
+        };
+        let Some(frame) = self.current_stack_frame() else {
+            return false;
+        };
+        // Conditions which cannot be evaluated stop execution so that the user
+        // gets a chance to fix them
+        condition.evaluate(&frame).unwrap_or(true)
+    }
+
+    /// Returns the name of the function just entered if it has a function
+    /// breakpoint set on it
+    fn function_breakpoint_reached(&mut self) -> Option<String> {
+        if self.function_breakpoints.is_empty() {
+            return None;
+        }
+        let (depth, current_function) = {
+            let frames = self.get_variables();
+            (frames.len(), frames.last().map(|frame| frame.function_name.to_string()))
+        };
+        let previous_depth = std::mem::replace(&mut self.stack_depth, depth);
+        if depth <= previous_depth {
+            return None;
+        }
+        current_function.filter(|function_name| self.function_breakpoints.contains(function_name))
+    }
+
+    fn read_watched_value(
+        &self,
+        watchpoint: &Watchpoint,
+        frame: Option<WatchedFrame>,
+    ) -> Option<String> {
+        match watchpoint {
+            Watchpoint::Witness(witness) => {
+                self.get_witness_map().get(witness).map(|value| value.to_string())
+            }
+            Watchpoint::Variable(name) => {
+                let frames = self.get_variables();
+                let stack_frame = match frame {
+                    Some(frame) if self.is_frame_active(frame) => frames.get(frame.depth - 1)?,
+                    Some(_) => return None,
+                    None => frames.last()?,
+                };
+                stack_frame.variables.iter().find(|(var_name, ..)| *var_name == name.as_str()).map(
+                    |(_, value, var_type)| {
+                        PrintableValueDisplay::Plain((*value).clone(), (*var_type).clone())
+                            .to_string()
+                    },
+                )
+            }
+        }
+    }
+
+    /// Returns the innermost stack frame if the variable is in it
+    fn find_watched_frame(&self, watchpoint: &Watchpoint) -> Option<WatchedFrame> {
+        let Watchpoint::Variable(name) = watchpoint else {
+            return None;
+        };
+        let stack_frame = self.current_stack_frame()?;
+        stack_frame.variables.iter().find(|(var_name, ..)| *var_name == name.as_str())?;
+        let entered_at = *self.frame_entry_steps.last()?;
+        Some(WatchedFrame { depth: self.frame_entry_steps.len(), entered_at })
+    }
+
+    fn is_frame_active(&self, frame: WatchedFrame) -> bool {
+        self.frame_entry_steps.get(frame.depth - 1) == Some(&frame.entered_at)
+    }
+
+    /// A frame which is not active has returned if execution went past the
+    /// point where it was entered, rather than going back to before it
+    fn has_frame_returned(&self, frame: WatchedFrame) -> bool {
+        !self.is_frame_active(frame) && self.history.steps() > frame.entered_at
+    }
+
+    /// Returns the first watchpoint whose value changed since the last check.
+    /// Watchpoints on variables whose frame has returned are triggered one
+    /// last time and deleted.
+    fn watchpoint_triggered(&mut self) -> Option<Watchpoint> {
+        let mut triggered = None;
+        let watched: Vec<(Watchpoint, WatchedValue)> = self
+            .watchpoints
+            .iter()
+            .map(|(watchpoint, watched)| (watchpoint.clone(), watched.clone()))
+            .collect();
+        for (watchpoint, previous) in watched {
+            let frame = previous.frame.or_else(|| self.find_watched_frame(&watchpoint));
+            if frame.is_some_and(|frame| self.has_frame_returned(frame)) {
+                self.watchpoints.remove(&watchpoint);
+                triggered.get_or_insert(watchpoint);
+                continue;
+            }
+            let value = self.read_watched_value(&watchpoint, frame);
+            let changed = previous.value != value;
+            self.watchpoints.insert(watchpoint.clone(), WatchedValue { value, frame });
+            if triggered.is_none() && changed {
+                triggered = Some(watchpoint);
+            }
         }
+        triggered
+    }
+
+    /// Checks watchpoints and function breakpoints, which depend on the
+    /// state of the execution rather than on the current opcode location
+    fn check_tracked_values(&mut self) -> Option<DebugCommandResult> {
+        if let Some(watchpoint) = self.watchpoint_triggered() {
+            return Some(DebugCommandResult::WatchpointTriggered(watchpoint));
+        }
+        self.function_breakpoint_reached().map(DebugCommandResult::FunctionBreakpointReached)
+    }
+
+    fn check_stop_conditions(&mut self) -> DebugCommandResult {
+        if let Some(result) = self.check_tracked_values() {
+            return result;
+        }
+        if self.breakpoint_reached() {
+            return DebugCommandResult::BreakpointReached(
+                self.get_current_opcode_location()
+                    .expect("Breakpoint reached but we have no location"),
+            );
+        }
+        DebugCommandResult::Ok
     }
 
     pub(super) fn is_valid_opcode_location(&self, location: &OpcodeLocation) -> bool {
@@ -523,10 +1101,31 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
     }
 
     pub(super) fn add_breakpoint(&mut self, location: OpcodeLocation) -> bool {
+        self.breakpoint_conditions.remove(&location);
         self.breakpoints.insert(location)
     }
 
+    /// Adds a breakpoint which only stops execution if the condition holds
+    /// when the opcode is reached. Replaces any condition previously set at
+    /// the same location.
+    pub(super) fn add_conditional_breakpoint(
+        &mut self,
+        location: OpcodeLocation,
+        condition: BreakpointCondition,
+    ) -> bool {
+        self.breakpoint_conditions.insert(location, condition);
+        self.breakpoints.insert(location)
+    }
+
+    pub(super) fn get_breakpoint_condition(
+        &self,
+        location: &OpcodeLocation,
+    ) -> Option<&BreakpointCondition> {
+        self.breakpoint_conditions.get(location)
+    }
+
     pub(super) fn delete_breakpoint(&mut self, location: &OpcodeLocation) -> bool {
+        self.breakpoint_conditions.remove(location);
         self.breakpoints.remove(location)
     }
 
@@ -536,6 +1135,69 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
 
     pub(super) fn clear_breakpoints(&mut self) {
         self.breakpoints.clear();
+        self.breakpoint_conditions.clear();
+    }
+
+    /// Returns true if the given name matches a function in the debug
+    /// artifact. Only functions in instrumented code can be tracked.
+    pub(super) fn is_valid_function_name(&self, function_name: &str) -> bool {
+        self.debug_artifact
+            .debug_symbols
+            .iter()
+            .flat_map(|debug_info| debug_info.functions.values())
+            .any(|function| function.name == function_name)
+    }
+
+    pub(super) fn add_function_breakpoint(&mut self, function_name: String) -> bool {
+        if self.function_breakpoints.is_empty() {
+            self.stack_depth = self.get_variables().len();
+        }
+        self.function_breakpoints.insert(function_name)
+    }
+
+    pub(super) fn delete_function_breakpoint(&mut self, function_name: &str) -> bool {
+        self.function_breakpoints.remove(function_name)
+    }
+
+    pub(super) fn iterate_function_breakpoints(&self) -> Iter<'_, String> {
+        self.function_breakpoints.iter()
+    }
+
+    pub(super) fn clear_function_breakpoints(&mut self) {
+        self.function_breakpoints.clear();
+    }
+
+    pub(super) fn add_watchpoint(&mut self, watchpoint: Watchpoint) -> bool {
+        if self.watchpoints.contains_key(&watchpoint) {
+            return false;
+        }
+        let frame = self.find_watched_frame(&watchpoint);
+        let value = self.read_watched_value(&watchpoint, frame);
+        self.watchpoints.insert(watchpoint, WatchedValue { value, frame });
+        true
+    }
+
+    pub(super) fn delete_watchpoint(&mut self, watchpoint: &Watchpoint) -> bool {
+        self.watchpoints.remove(watchpoint).is_some()
+    }
+
+    pub(super) fn iterate_watchpoints(&self) -> impl Iterator<Item = &Watchpoint> {
+        self.watchpoints.keys()
+    }
+
+    pub(super) fn clear_watchpoints(&mut self) {
+        self.watchpoints.clear();
+    }
+
+    /// Returns the current rendered value of a watched witness or variable,
+    /// or None if it is not available or no longer watched
+    pub(super) fn get_watched_value(&self, watchpoint: &Watchpoint) -> Option<String> {
+        let watched = self.watchpoints.get(watchpoint)?;
+        self.read_watched_value(watchpoint, watched.frame)
+    }
+
+    pub(super) fn is_watching(&self, watchpoint: &Watchpoint) -> bool {
+        self.watchpoints.contains_key(watchpoint)
     }
 
     pub(super) fn is_solved(&self) -> bool {
@@ -632,9 +1294,63 @@ mod tests {
         },
         blackbox_solver::StubbedBlackBoxSolver,
         brillig_vm::brillig::{
-            BinaryFieldOp, HeapValueType, MemoryAddress, Opcode as BrilligOpcode, ValueOrArray,
+            BinaryFieldOp, HeapArray, HeapValueType, MemoryAddress, Opcode as BrilligOpcode,
+            ValueOrArray,
         },
     };
+    use noirc_errors::debug_info::{
+        DebugFnId, DebugFunction, DebugInfo, DebugTypeId, DebugVariable,
+    };
+
+    /// Builds a debug artifact declaring the given functions and variables,
+    /// whose ids are their positions in the lists
+    fn debug_artifact(functions: &[&str], variables: &[(&str, PrintableType)]) -> DebugArtifact {
+        let functions = functions.iter().enumerate().map(|(id, name)| {
+            let function = DebugFunction { name: name.to_string(), arg_names: vec![] };
+            (DebugFnId(id as u32), function)
+        });
+        let types = variables
+            .iter()
+            .enumerate()
+            .map(|(id, (_, typ))| (DebugTypeId(id as u32), typ.clone()));
+        let variables = variables.iter().enumerate().map(|(id, (name, _))| {
+            let variable =
+                DebugVariable { name: name.to_string(), debug_type_id: DebugTypeId(id as u32) };
+            (DebugVarId(id as u32), variable)
+        });
+        let debug_info = DebugInfo {
+            locations: BTreeMap::new(),
+            variables: variables.collect(),
+            functions: functions.collect(),
+            types: types.collect(),
+        };
+        DebugArtifact { debug_symbols: vec![debug_info], file_map: BTreeMap::new() }
+    }
+
+    fn brillig_const(address: usize, value: u128, bit_size: u32) -> BrilligOpcode<FieldElement> {
+        BrilligOpcode::Const {
+            destination: MemoryAddress::from(address),
+            value: FieldElement::from(value),
+            bit_size,
+        }
+    }
+
+    /// Calls a debug instrumentation function with the given scalar inputs
+    fn brillig_debug_call(function: &str, inputs: &[(usize, u32)]) -> BrilligOpcode<FieldElement> {
+        BrilligOpcode::ForeignCall {
+            function: function.into(),
+            destinations: vec![],
+            destination_value_types: vec![],
+            inputs: inputs
+                .iter()
+                .map(|(address, _)| ValueOrArray::MemoryAddress(MemoryAddress::from(*address)))
+                .collect(),
+            input_value_types: inputs
+                .iter()
+                .map(|(_, bit_size)| HeapValueType::Simple(*bit_size))
+                .collect(),
+        }
+    }
 
     #[test]
     fn test_resolve_foreign_calls_stepping_into_brillig() {
@@ -824,6 +1540,329 @@ mod tests {
         assert_eq!(context.get_current_opcode_location(), None);
     }
 
+    #[test]
+    fn test_watchpoint_on_witness_stops_when_value_changes() {
+        let fe_0 = FieldElement::zero();
+        let fe_1 = FieldElement::one();
+        let w_x = Witness(1);
+        let w_y = Witness(2);
+        let w_z = Witness(3);
+
+        let opcodes = vec![
+            // x + y - z = 0, solves for z
+            Opcode::AssertZero(Expression {
+                mul_terms: vec![],
+                linear_combinations: vec![(fe_1, w_x), (fe_1, w_y), (-fe_1, w_z)],
+                q_c: fe_0,
+            }),
+            // x + y - z = 0, checks z
+            Opcode::AssertZero(Expression {
+                mul_terms: vec![],
+                linear_combinations: vec![(fe_1, w_x), (fe_1, w_y), (-fe_1, w_z)],
+                q_c: fe_0,
+            }),
+        ];
+        let current_witness_index = 3;
+        let circuit = &Circuit { current_witness_index, opcodes, ..Circuit::default() };
+
+        let debug_artifact = &DebugArtifact { debug_symbols: vec![], file_map: BTreeMap::new() };
+
+        let initial_witness = BTreeMap::from([(w_x, fe_1), (w_y, fe_1)]).into();
+
+        let foreign_call_executor =
+            Box::new(DefaultDebugForeignCallExecutor::from_artifact(true, debug_artifact));
+        let brillig_funcs: &[BrilligBytecode<FieldElement>] = &[];
+        let mut context = DebugContext::new(
+            &StubbedBlackBoxSolver,
+            circuit,
+            debug_artifact,
+            initial_witness,
+            foreign_call_executor,
+            brillig_funcs,
+        );
+
+        let watchpoint = Watchpoint::Witness(w_z);
+        assert!(context.add_watchpoint(watchpoint.clone()));
+        assert!(!context.add_watchpoint(watchpoint.clone()));
+
+        // solving the first opcode assigns z
+        let result = context.cont();
+        assert!(
+            matches!(result, DebugCommandResult::WatchpointTriggered(ref w) if *w == watchpoint)
+        );
+        assert_eq!(context.get_current_opcode_location(), Some(OpcodeLocation::Acir(1)));
+        assert_eq!(context.get_watched_value(&watchpoint), Some((fe_1 + fe_1).to_string()));
+
+        // z does not change anymore
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::Done));
+    }
+
+    #[test]
+    fn test_variable_watchpoint_stays_in_its_frame() {
+        let u8_type = PrintableType::UnsignedInteger { width: 8 };
+        let debug_artifact =
+            &debug_artifact(&["main", "foo"], &[("x", u8_type.clone()), ("x", u8_type)]);
+
+        // unconstrained fn main() { let mut x = 1; foo(); x = 2; }
+        // unconstrained fn foo() { let x = 9; }
+        let brillig_bytecode = BrilligBytecode {
+            bytecode: vec![
+                brillig_const(0, 0, 32),
+                brillig_debug_call("__debug_fn_enter", &[(0, 32)]),
+                brillig_const(1, 0, 32),
+                brillig_const(2, 1, 8),
+                brillig_debug_call("__debug_var_assign", &[(1, 32), (2, 8)]),
+                brillig_const(3, 1, 32),
+                brillig_debug_call("__debug_fn_enter", &[(3, 32)]),
+                brillig_const(4, 9, 8),
+                brillig_debug_call("__debug_var_assign", &[(3, 32), (4, 8)]),
+                brillig_debug_call("__debug_fn_exit", &[(3, 32)]),
+                brillig_const(2, 2, 8),
+                brillig_debug_call("__debug_var_assign", &[(1, 32), (2, 8)]),
+                brillig_debug_call("__debug_fn_exit", &[(0, 32)]),
+                BrilligOpcode::Stop { return_data_offset: 0, return_data_size: 0 },
+            ],
+        };
+        let opcodes =
+            vec![Opcode::BrilligCall { id: 0, inputs: vec![], outputs: vec![], predicate: None }];
+        let brillig_funcs = &vec![brillig_bytecode];
+        let circuit = &Circuit { current_witness_index: 0, opcodes, ..Circuit::default() };
+
+        let foreign_call_executor =
+            Box::new(DefaultDebugForeignCallExecutor::from_artifact(true, debug_artifact));
+        let mut context = DebugContext::new(
+            &StubbedBlackBoxSolver,
+            circuit,
+            debug_artifact,
+            WitnessMap::new(),
+            foreign_call_executor,
+            brillig_funcs,
+        );
+
+        let call_foo = Some(OpcodeLocation::Brillig { acir_index: 0, brillig_index: 5 });
+        while context.get_current_opcode_location() != call_foo {
+            let result = context.step_into_opcode();
+            assert!(matches!(result, DebugCommandResult::Ok));
+        }
+        let watchpoint = Watchpoint::Variable(String::from("x"));
+        assert!(context.add_watchpoint(watchpoint.clone()));
+        assert_eq!(context.get_watched_value(&watchpoint), Some(String::from("1")));
+
+        // stepping over foo, whose own `x` is not watched, up to the assignment in main
+        let result = context.cont();
+        assert!(
+            matches!(result, DebugCommandResult::WatchpointTriggered(ref w) if *w == watchpoint)
+        );
+        assert_eq!(
+            context.get_current_opcode_location(),
+            Some(OpcodeLocation::Brillig { acir_index: 0, brillig_index: 11 })
+        );
+        assert_eq!(context.get_watched_value(&watchpoint), Some(String::from("2")));
+
+        // the watchpoint is deleted once main returns
+        let result = context.cont();
+        assert!(
+            matches!(result, DebugCommandResult::WatchpointTriggered(ref w) if *w == watchpoint)
+        );
+        assert!(!context.is_watching(&watchpoint));
+        assert_eq!(context.get_watched_value(&watchpoint), None);
+
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::Done));
+    }
+
+    #[test]
+    fn test_step_back_and_reverse_continue() {
+        let fe_1 = FieldElement::one();
+        let w_x = Witness(1);
+        let w_y = Witness(2);
+        let w_z = Witness(3);
+
+        // This Brillig block is equivalent to: z = x + y
+        let brillig_bytecode = BrilligBytecode {
+            bytecode: vec![
+                BrilligOpcode::CalldataCopy {
+                    destination_address: MemoryAddress(0),
+                    size: 2,
+                    offset: 0,
+                },
+                BrilligOpcode::BinaryFieldOp {
+                    destination: MemoryAddress::from(0),
+                    op: BinaryFieldOp::Add,
+                    lhs: MemoryAddress::from(0),
+                    rhs: MemoryAddress::from(1),
+                },
+                BrilligOpcode::Stop { return_data_offset: 0, return_data_size: 1 },
+            ],
+        };
+        let opcodes = vec![Opcode::BrilligCall {
+            id: 0,
+            inputs: vec![
+                BrilligInputs::Single(Expression {
+                    linear_combinations: vec![(fe_1, w_x)],
+                    ..Expression::default()
+                }),
+                BrilligInputs::Single(Expression {
+                    linear_combinations: vec![(fe_1, w_y)],
+                    ..Expression::default()
+                }),
+            ],
+            outputs: vec![BrilligOutputs::Simple(w_z)],
+            predicate: None,
+        }];
+        let current_witness_index = 3;
+        let circuit = &Circuit { current_witness_index, opcodes, ..Circuit::default() };
+
+        let debug_artifact = &DebugArtifact { debug_symbols: vec![], file_map: BTreeMap::new() };
+
+        let initial_witness = BTreeMap::from([(w_x, fe_1), (w_y, fe_1)]).into();
+
+        let foreign_call_executor =
+            Box::new(DefaultDebugForeignCallExecutor::from_artifact(true, debug_artifact));
+        let brillig_funcs = &vec![brillig_bytecode];
+        let mut context = DebugContext::new(
+            &StubbedBlackBoxSolver,
+            circuit,
+            debug_artifact,
+            initial_witness,
+            foreign_call_executor,
+            brillig_funcs,
+        );
+        assert!(!context.can_step_back());
+
+        let result = context.step_into_opcode();
+        assert!(matches!(result, DebugCommandResult::Ok));
+        let result = context.step_into_opcode();
+        assert!(matches!(result, DebugCommandResult::Ok));
+        assert_eq!(
+            context.get_current_opcode_location(),
+            Some(OpcodeLocation::Brillig { acir_index: 0, brillig_index: 2 })
+        );
+
+        // step back a single opcode
+        assert!(context.can_step_back());
+        let result = context.step_back_opcode();
+        assert!(matches!(result, DebugCommandResult::Ok));
+        assert_eq!(
+            context.get_current_opcode_location(),
+            Some(OpcodeLocation::Brillig { acir_index: 0, brillig_index: 1 })
+        );
+
+        // run to the end of the program, which solves z
+        let breakpoint_location = OpcodeLocation::Brillig { acir_index: 0, brillig_index: 1 };
+        assert!(context.add_breakpoint(breakpoint_location));
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::Done));
+        assert_eq!(context.get_witness_map().get(&w_z), Some(&(fe_1 + fe_1)));
+
+        // running backwards stops at the breakpoint, before z is solved
+        let result = context.reverse_cont();
+        assert!(
+            matches!(result, DebugCommandResult::BreakpointReached(location) if location == breakpoint_location)
+        );
+        assert_eq!(context.get_current_opcode_location(), Some(breakpoint_location));
+        assert_eq!(context.get_witness_map().get(&w_z), None);
+
+        // and then to the start of the program
+        let result = context.reverse_cont();
+        assert!(matches!(result, DebugCommandResult::Ok));
+        assert_eq!(context.get_current_opcode_location(), Some(OpcodeLocation::Acir(0)));
+        assert!(!context.can_step_back());
+    }
+
+    #[test]
+    fn test_set_variables_held_in_brillig_memory() {
+        let u8_type = PrintableType::UnsignedInteger { width: 8 };
+        let point_type = PrintableType::Struct {
+            name: String::from("Point"),
+            fields: vec![
+                (String::from("x"), u8_type.clone()),
+                (String::from("y"), u8_type.clone()),
+            ],
+        };
+        let debug_artifact =
+            &debug_artifact(&["main"], &[("x", u8_type.clone()), ("p", point_type)]);
+
+        // unconstrained fn main() -> (u8, Point) { let x = 5; let p = Point { x: 10, y: 20 }; (x, p) }
+        let brillig_bytecode = BrilligBytecode {
+            bytecode: vec![
+                brillig_const(0, 0, 32),
+                brillig_debug_call("__debug_fn_enter", &[(0, 32)]),
+                brillig_const(1, 0, 32),
+                brillig_const(3, 5, 8),
+                brillig_debug_call("__debug_var_assign", &[(1, 32), (3, 8)]),
+                brillig_const(2, 1, 32),
+                brillig_const(4, 10, 8),
+                brillig_const(5, 20, 8),
+                brillig_const(6, 4, 32),
+                BrilligOpcode::ForeignCall {
+                    function: "__debug_var_assign".into(),
+                    destinations: vec![],
+                    destination_value_types: vec![],
+                    inputs: vec![
+                        ValueOrArray::MemoryAddress(MemoryAddress::from(2)),
+                        ValueOrArray::HeapArray(HeapArray {
+                            pointer: MemoryAddress::from(6),
+                            size: 2,
+                        }),
+                    ],
+                    input_value_types: vec![
+                        HeapValueType::Simple(32),
+                        HeapValueType::Array {
+                            value_types: vec![HeapValueType::Simple(8)],
+                            size: 2,
+                        },
+                    ],
+                },
+                BrilligOpcode::Stop { return_data_offset: 3, return_data_size: 3 },
+            ],
+        };
+        let outputs = (1..=3).map(|index| BrilligOutputs::Simple(Witness(index))).collect();
+        let opcodes = vec![Opcode::BrilligCall { id: 0, inputs: vec![], outputs, predicate: None }];
+        let brillig_funcs = &vec![brillig_bytecode];
+        let circuit = &Circuit { current_witness_index: 3, opcodes, ..Circuit::default() };
+
+        let foreign_call_executor =
+            Box::new(DefaultDebugForeignCallExecutor::from_artifact(true, debug_artifact));
+        let mut context = DebugContext::new(
+            &StubbedBlackBoxSolver,
+            circuit,
+            debug_artifact,
+            WitnessMap::new(),
+            foreign_call_executor,
+            brillig_funcs,
+        );
+
+        let stop = Some(OpcodeLocation::Brillig { acir_index: 0, brillig_index: 10 });
+        while context.get_current_opcode_location() != stop {
+            let result = context.step_into_opcode();
+            assert!(matches!(result, DebugCommandResult::Ok));
+        }
+
+        assert_eq!(context.set_variable("x", "x + 2"), Ok(()));
+        assert_eq!(context.set_variable("p.y", "x * 2"), Ok(()));
+        assert_eq!(
+            context.evaluate("p.y", None),
+            Ok((PrintableValue::Field(FieldElement::from(14u128)), u8_type))
+        );
+        assert!(context.set_variable("x", "256").is_err());
+        assert!(context.set_variable("p.z", "1").is_err());
+        assert!(context.set_variable("p", "1").is_err());
+
+        // the program returns the modified values
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::Done));
+        let witness_map = context.get_witness_map();
+        let returned: Vec<_> =
+            (1..=3).map(|index| witness_map.get(&Witness(index)).copied()).collect();
+        let expected = [7u128, 10, 14].map(|value| Some(FieldElement::from(value)));
+        assert_eq!(returned, expected);
+
+        // the Brillig memory is gone once the call finishes
+        assert!(context.set_variable("x", "1").is_err());
+    }
+
     #[test]
     fn test_address_opcode_location_mapping() {
         let brillig_bytecode = BrilligBytecode {
@@ -1,11 +1,12 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::{Read, Write};
 
 use acvm::acir::circuit::brillig::BrilligBytecode;
 use acvm::acir::circuit::{Circuit, OpcodeLocation};
-use acvm::acir::native_types::WitnessMap;
+use acvm::acir::native_types::{Witness, WitnessMap};
 use acvm::{BlackBoxFunctionSolver, FieldElement};
 
+use crate::breakpoints::{BreakpointCondition, Watchpoint};
 use crate::context::DebugCommandResult;
 use crate::context::DebugContext;
 use crate::foreign_calls::DefaultDebugForeignCallExecutor;
@@ -15,9 +16,11 @@ use dap::events::StoppedEventBody;
 use dap::prelude::Event;
 use dap::requests::{Command, Request, SetBreakpointsArguments};
 use dap::responses::{
-    ContinueResponse, DisassembleResponse, ResponseBody, ScopesResponse, SetBreakpointsResponse,
-    SetExceptionBreakpointsResponse, SetInstructionBreakpointsResponse, StackTraceResponse,
-    ThreadsResponse, VariablesResponse,
+    ContinueResponse, DataBreakpointInfoResponse, DisassembleResponse, EvaluateResponse,
+    ResponseBody, ScopesResponse, SetBreakpointsResponse, SetDataBreakpointsResponse,
+    SetExceptionBreakpointsResponse, SetFunctionBreakpointsResponse,
+    SetInstructionBreakpointsResponse, SetVariableResponse, StackTraceResponse, ThreadsResponse,
+    VariablesResponse,
 };
 use dap::server::Server;
 use dap::types::{
@@ -25,6 +28,7 @@ use dap::types::{
     StoppedEventReason, Thread, Variable,
 };
 use noirc_artifacts::debug::DebugArtifact;
+use noirc_printable_type::PrintableValueDisplay;
 
 use fm::FileId;
 use noirc_driver::CompiledProgram;
@@ -39,6 +43,9 @@ pub struct DapSession<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElem
     next_breakpoint_id: BreakpointId,
     instruction_breakpoints: Vec<(OpcodeLocation, BreakpointId)>,
     source_breakpoints: BTreeMap<FileId, Vec<(OpcodeLocation, BreakpointId)>>,
+    breakpoint_conditions: HashMap<BreakpointId, BreakpointCondition>,
+    function_breakpoints: Vec<(String, BreakpointId)>,
+    data_breakpoints: Vec<(Watchpoint, BreakpointId)>,
 }
 
 enum ScopeReferences {
@@ -82,6 +89,9 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
             next_breakpoint_id: 1,
             instruction_breakpoints: vec![],
             source_breakpoints: BTreeMap::new(),
+            breakpoint_conditions: HashMap::new(),
+            function_breakpoints: vec![],
+            data_breakpoints: vec![],
         }
     }
 
@@ -135,6 +145,15 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
                 Command::SetInstructionBreakpoints(_) => {
                     self.handle_set_instruction_breakpoints(req)?;
                 }
+                Command::SetFunctionBreakpoints(_) => {
+                    self.handle_set_function_breakpoints(req)?;
+                }
+                Command::DataBreakpointInfo(_) => {
+                    self.handle_data_breakpoint_info(req)?;
+                }
+                Command::SetDataBreakpoints(_) => {
+                    self.handle_set_data_breakpoints(req)?;
+                }
                 Command::Threads => {
                     self.server.respond(req.success(ResponseBody::Threads(ThreadsResponse {
                         threads: vec![Thread { id: 0, name: "main".to_string() }],
@@ -173,12 +192,29 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
                 Command::Continue(_) => {
                     self.handle_continue(req)?;
                 }
+                Command::StepBack(ref args) => {
+                    let granularity =
+                        args.granularity.as_ref().unwrap_or(&SteppingGranularity::Statement);
+                    match granularity {
+                        SteppingGranularity::Instruction => self.handle_step_back(req)?,
+                        _ => self.handle_next_back(req)?,
+                    }
+                }
+                Command::ReverseContinue(_) => {
+                    self.handle_reverse_continue(req)?;
+                }
                 Command::Scopes(_) => {
                     self.handle_scopes(req)?;
                 }
                 Command::Variables(ref _args) => {
                     self.handle_variables(req)?;
                 }
+                Command::Evaluate(_) => {
+                    self.handle_evaluate(req)?;
+                }
+                Command::SetVariable(_) => {
+                    self.handle_set_variable(req)?;
+                }
                 _ => {
                     eprintln!("ERROR: unhandled command: {:?}", req.command);
                 }
@@ -320,6 +356,27 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
         self.handle_execution_result(result)
     }
 
+    fn handle_step_back(&mut self, req: Request) -> Result<(), ServerError> {
+        let result = self.context.step_back_opcode();
+        eprintln!("INFO: stepped back by instruction with result {result:?}");
+        self.server.respond(req.ack()?)?;
+        self.handle_execution_result(result)
+    }
+
+    fn handle_next_back(&mut self, req: Request) -> Result<(), ServerError> {
+        let result = self.context.next_back();
+        eprintln!("INFO: stepped back by statement with result {result:?}");
+        self.server.respond(req.ack()?)?;
+        self.handle_execution_result(result)
+    }
+
+    fn handle_reverse_continue(&mut self, req: Request) -> Result<(), ServerError> {
+        let result = self.context.reverse_cont();
+        eprintln!("INFO: reverse continue with result {result:?}");
+        self.server.respond(req.ack()?)?;
+        self.handle_execution_result(result)
+    }
+
     fn find_breakpoints_at_location(&self, opcode_location: &OpcodeLocation) -> Vec<i64> {
         let mut result = vec![];
         for (location, id) in &self.instruction_breakpoints {
@@ -365,6 +422,40 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
                     hit_breakpoint_ids: Some(breakpoint_ids),
                 }))?;
             }
+            DebugCommandResult::FunctionBreakpointReached(function_name) => {
+                let breakpoint_ids = self
+                    .function_breakpoints
+                    .iter()
+                    .filter(|(name, _)| *name == function_name)
+                    .map(|(_, id)| *id)
+                    .collect();
+                self.server.send_event(Event::Stopped(StoppedEventBody {
+                    reason: StoppedEventReason::Function,
+                    description: Some(format!("Paused at function {function_name}")),
+                    thread_id: Some(0),
+                    preserve_focus_hint: Some(false),
+                    text: None,
+                    all_threads_stopped: Some(false),
+                    hit_breakpoint_ids: Some(breakpoint_ids),
+                }))?;
+            }
+            DebugCommandResult::WatchpointTriggered(watchpoint) => {
+                let breakpoint_ids = self
+                    .data_breakpoints
+                    .iter()
+                    .filter(|(data, _)| *data == watchpoint)
+                    .map(|(_, id)| *id)
+                    .collect();
+                self.server.send_event(Event::Stopped(StoppedEventBody {
+                    reason: StoppedEventReason::Data,
+                    description: Some(format!("Paused on change of {watchpoint}")),
+                    thread_id: Some(0),
+                    preserve_focus_hint: Some(false),
+                    text: None,
+                    all_threads_stopped: Some(false),
+                    hit_breakpoint_ids: Some(breakpoint_ids),
+                }))?;
+            }
             DebugCommandResult::Error(err) => {
                 self.server.send_event(Event::Stopped(StoppedEventBody {
                     reason: StoppedEventReason::Exception,
@@ -388,13 +479,36 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
 
     fn reinstall_breakpoints(&mut self) {
         self.context.clear_breakpoints();
-        for (location, _) in &self.instruction_breakpoints {
-            self.context.add_breakpoint(*location);
+        let breakpoints: Vec<(OpcodeLocation, BreakpointId)> = self
+            .instruction_breakpoints
+            .iter()
+            .chain(self.source_breakpoints.values().flatten())
+            .copied()
+            .collect();
+        // drop conditions of breakpoints which have been replaced
+        self.breakpoint_conditions.retain(|id, _| breakpoints.iter().any(|(_, other)| other == id));
+        for (location, id) in breakpoints {
+            match self.breakpoint_conditions.get(&id) {
+                Some(condition) => {
+                    self.context.add_conditional_breakpoint(location, condition.clone())
+                }
+                None => self.context.add_breakpoint(location),
+            };
         }
-        for breakpoints in self.source_breakpoints.values() {
-            for (location, _) in breakpoints {
-                self.context.add_breakpoint(*location);
-            }
+    }
+
+    /// Parses the optional condition of a breakpoint. Returns an unverified
+    /// breakpoint to respond with if the condition is invalid.
+    fn parse_breakpoint_condition(
+        condition: &Option<String>,
+    ) -> Result<Option<BreakpointCondition>, Breakpoint> {
+        match condition.as_deref().map(str::trim).filter(|condition| !condition.is_empty()) {
+            None => Ok(None),
+            Some(condition) => condition.parse().map(Some).map_err(|error| Breakpoint {
+                verified: false,
+                message: Some(format!("Invalid condition: {error}")),
+                ..Breakpoint::default()
+            }),
         }
     }
 
@@ -409,6 +523,10 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
             .breakpoints
             .iter()
             .map(|breakpoint| {
+                let condition = match Self::parse_breakpoint_condition(&breakpoint.condition) {
+                    Ok(condition) => condition,
+                    Err(unverified) => return unverified,
+                };
                 let offset = breakpoint.offset.unwrap_or(0);
                 let address = breakpoint.instruction_reference.parse::<i64>().unwrap_or(0) + offset;
                 let Ok(address): Result<usize, _> = address.try_into() else {
@@ -431,6 +549,9 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
                 };
                 let id = self.get_next_breakpoint_id();
                 breakpoints_to_set.push((location, id));
+                if let Some(condition) = condition {
+                    self.breakpoint_conditions.insert(id, condition);
+                }
                 Breakpoint {
                     id: Some(id),
                     verified: true,
@@ -476,6 +597,10 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
         let breakpoints = breakpoints
             .iter()
             .map(|breakpoint| {
+                let condition = match Self::parse_breakpoint_condition(&breakpoint.condition) {
+                    Ok(condition) => condition,
+                    Err(unverified) => return unverified,
+                };
                 let line = breakpoint.line;
                 let Some(location) = self.context.find_opcode_for_source_location(&file_id, line)
                 else {
@@ -501,6 +626,9 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
                 let instruction_reference = format!("{}", breakpoint_address);
                 let breakpoint_id = self.get_next_breakpoint_id();
                 breakpoints_to_set.push((location, breakpoint_id));
+                if let Some(condition) = condition {
+                    self.breakpoint_conditions.insert(breakpoint_id, condition);
+                }
                 Breakpoint {
                     id: Some(breakpoint_id),
                     verified: true,
@@ -530,6 +658,108 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
         Ok(())
     }
 
+    fn handle_set_function_breakpoints(&mut self, req: Request) -> Result<(), ServerError> {
+        let Command::SetFunctionBreakpoints(ref args) = req.command else {
+            unreachable!("handle_set_function_breakpoints called on a different request");
+        };
+
+        let mut breakpoints_to_set: Vec<(String, BreakpointId)> = vec![];
+        let breakpoints: Vec<Breakpoint> = args
+            .breakpoints
+            .iter()
+            .map(|breakpoint| {
+                if !self.context.is_valid_function_name(&breakpoint.name) {
+                    return Breakpoint {
+                        verified: false,
+                        message: Some(format!("Unknown function {}", breakpoint.name)),
+                        ..Breakpoint::default()
+                    };
+                }
+                let id = self.get_next_breakpoint_id();
+                breakpoints_to_set.push((breakpoint.name.clone(), id));
+                Breakpoint { id: Some(id), verified: true, ..Breakpoint::default() }
+            })
+            .collect();
+
+        self.context.clear_function_breakpoints();
+        for (function_name, _) in &breakpoints_to_set {
+            self.context.add_function_breakpoint(function_name.clone());
+        }
+        self.function_breakpoints = breakpoints_to_set;
+
+        self.server.respond(req.success(ResponseBody::SetFunctionBreakpoints(
+            SetFunctionBreakpointsResponse { breakpoints },
+        )))?;
+        Ok(())
+    }
+
+    fn handle_data_breakpoint_info(&mut self, req: Request) -> Result<(), ServerError> {
+        let Command::DataBreakpointInfo(ref args) = req.command else {
+            unreachable!("handle_data_breakpoint_info called on a different request");
+        };
+
+        // Data IDs use the same notation accepted by watchpoints in the REPL:
+        // `witness:_<index>` for witnesses and plain names for local variables
+        let scope = args.variables_reference.map(ScopeReferences::from);
+        let watchpoint = match scope {
+            Some(ScopeReferences::Locals) => Some(Watchpoint::Variable(args.name.clone())),
+            Some(ScopeReferences::WitnessMap) => args
+                .name
+                .strip_prefix('_')
+                .and_then(|index| index.parse().ok())
+                .map(|index| Watchpoint::Witness(Witness(index))),
+            _ => None,
+        };
+        let data_id = watchpoint.map(|watchpoint| watchpoint.to_string());
+        let description = match data_id {
+            Some(_) => format!("Changes of {}", args.name),
+            None => String::from("Data breakpoints are only supported on locals and witnesses"),
+        };
+
+        self.server.respond(req.success(ResponseBody::DataBreakpointInfo(
+            DataBreakpointInfoResponse {
+                data_id,
+                description,
+                access_types: None,
+                can_persist: Some(false),
+            },
+        )))?;
+        Ok(())
+    }
+
+    fn handle_set_data_breakpoints(&mut self, req: Request) -> Result<(), ServerError> {
+        let Command::SetDataBreakpoints(ref args) = req.command else {
+            unreachable!("handle_set_data_breakpoints called on a different request");
+        };
+
+        let mut breakpoints_to_set: Vec<(Watchpoint, BreakpointId)> = vec![];
+        let breakpoints: Vec<Breakpoint> = args
+            .breakpoints
+            .iter()
+            .map(|breakpoint| match breakpoint.data_id.parse::<Watchpoint>() {
+                Ok(watchpoint) => {
+                    let id = self.get_next_breakpoint_id();
+                    breakpoints_to_set.push((watchpoint, id));
+                    Breakpoint { id: Some(id), verified: true, ..Breakpoint::default() }
+                }
+                Err(error) => {
+                    Breakpoint { verified: false, message: Some(error), ..Breakpoint::default() }
+                }
+            })
+            .collect();
+
+        self.context.clear_watchpoints();
+        for (watchpoint, _) in &breakpoints_to_set {
+            self.context.add_watchpoint(watchpoint.clone());
+        }
+        self.data_breakpoints = breakpoints_to_set;
+
+        self.server.respond(req.success(ResponseBody::SetDataBreakpoints(
+            SetDataBreakpointsResponse { breakpoints },
+        )))?;
+        Ok(())
+    }
+
     fn handle_scopes(&mut self, req: Request) -> Result<(), ServerError> {
         self.server.respond(req.success(ResponseBody::Scopes(ScopesResponse {
             scopes: vec![
@@ -600,6 +830,77 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
             .respond(req.success(ResponseBody::Variables(VariablesResponse { variables })))?;
         Ok(())
     }
+
+    fn handle_evaluate(&mut self, req: Request) -> Result<(), ServerError> {
+        let Command::Evaluate(ref args) = req.command else {
+            unreachable!("handle_evaluate called on a different request");
+        };
+        // Frame IDs are the indexes in the source-level call stack, see
+        // build_stack_trace
+        let frame_index = args.frame_id.map(|frame_id| frame_id as usize);
+        match self.context.evaluate(&args.expression, frame_index) {
+            Ok((value, typ)) => {
+                let type_field = Some(format!("{typ:?}"));
+                let result = PrintableValueDisplay::Plain(value, typ).to_string();
+                self.server.respond(req.success(ResponseBody::Evaluate(EvaluateResponse {
+                    result,
+                    type_field,
+                    presentation_hint: None,
+                    variables_reference: 0,
+                    named_variables: None,
+                    indexed_variables: None,
+                    memory_reference: None,
+                })))?;
+            }
+            Err(error) => {
+                self.server.respond(req.error(&error))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_set_variable(&mut self, req: Request) -> Result<(), ServerError> {
+        let Command::SetVariable(ref args) = req.command else {
+            unreachable!("handle_set_variable called on a different request");
+        };
+        let scope: ScopeReferences = args.variables_reference.into();
+        let result = match scope {
+            ScopeReferences::Locals => self
+                .context
+                .set_variable(&args.name, &args.value)
+                .and_then(|_| self.context.evaluate(&args.name, None))
+                .map(|(value, typ)| PrintableValueDisplay::Plain(value, typ).to_string()),
+            ScopeReferences::WitnessMap => {
+                match args.name.strip_prefix('_').and_then(|index| index.parse::<u32>().ok()) {
+                    Some(index) => self
+                        .context
+                        .set_witness(Witness(index), &args.value)
+                        .map(|value| format!("{value:?}")),
+                    None => Err(format!("invalid witness `{}`", args.name)),
+                }
+            }
+            ScopeReferences::InvalidScope => {
+                Err(format!("unknown variables reference {}", args.variables_reference))
+            }
+        };
+        match result {
+            Ok(value) => {
+                self.server.respond(req.success(ResponseBody::SetVariable(
+                    SetVariableResponse {
+                        value,
+                        type_field: None,
+                        variables_reference: None,
+                        named_variables: None,
+                        indexed_variables: None,
+                    },
+                )))?;
+            }
+            Err(error) => {
+                self.server.respond(req.error(&error))?;
+            }
+        }
+        Ok(())
+    }
 }
 
 pub fn run_session<R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>>(
@@ -0,0 +1,708 @@
+use acvm::acir::native_types::{Witness, WitnessMap};
+use acvm::{AcirField, FieldElement};
+use noirc_artifacts::debug::StackFrame;
+use noirc_frontend::ast::{BinaryOpKind, Expression, ExpressionKind, Literal, UnaryOp};
+use noirc_frontend::parser::parse_expression;
+use noirc_printable_type::{PrintableType, PrintableValue};
+
+/// Evaluates simple Noir expressions (variables, member access, indexing,
+/// arithmetic and comparisons) over the variables visible in a stack frame and
+/// the current witness map, which can be referenced with the `_<index>`
+/// notation.
+pub(super) struct Evaluator<'a> {
+    frame: Option<&'a StackFrame<'a, FieldElement>>,
+    witness_map: &'a WitnessMap<FieldElement>,
+}
+
+/// A variable, or a field or element within one, which can be assigned to
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Place {
+    pub(super) variable: String,
+    /// Indexes of the struct fields, tuple fields and array elements leading from the
+    /// variable to the place, in the form expected by the `__debug_member_assign` calls
+    pub(super) indexes: Vec<u32>,
+    /// Position of the place within the flattened value of the variable
+    pub(super) offset: usize,
+    pub(super) typ: PrintableType,
+}
+
+/// Intermediate result of evaluating an expression. Integer literals have no
+/// type on their own and take the type of the value they are combined with.
+#[derive(Debug, Clone)]
+enum Value {
+    Typed(PrintableValue<FieldElement>, PrintableType),
+    Literal { magnitude: FieldElement, negative: bool },
+}
+
+impl Value {
+    fn boolean(value: bool) -> Value {
+        let field = if value { FieldElement::one() } else { FieldElement::zero() };
+        Value::Typed(PrintableValue::Field(field), PrintableType::Boolean)
+    }
+
+    fn into_typed(self) -> (PrintableValue<FieldElement>, PrintableType) {
+        match self {
+            Value::Typed(value, typ) => (value, typ),
+            Value::Literal { .. } => {
+                let value = self.coerce(&PrintableType::Field).expect("fields fit any literal");
+                (PrintableValue::Field(value), PrintableType::Field)
+            }
+        }
+    }
+
+    /// Returns the field representation of a scalar value of the given type
+    fn coerce(&self, expected: &PrintableType) -> Result<FieldElement, String> {
+        match self {
+            Value::Typed(PrintableValue::Field(value), typ) if typ == expected => Ok(*value),
+            Value::Typed(_, typ) => Err(format!(
+                "expected a value of type {}, found {}",
+                type_name(expected),
+                type_name(typ)
+            )),
+            Value::Literal { magnitude, negative } => match expected {
+                PrintableType::Field => Ok(if *negative { -*magnitude } else { *magnitude }),
+                PrintableType::UnsignedInteger { width } => {
+                    let value = magnitude
+                        .try_into_u128()
+                        .filter(|value| !*negative || *value == 0)
+                        .ok_or_else(|| out_of_range(self, expected))?;
+                    unsigned_to_field(value, *width).ok_or_else(|| out_of_range(self, expected))
+                }
+                PrintableType::SignedInteger { width } => {
+                    let value = magnitude
+                        .try_into_u128()
+                        .and_then(|value| i128::try_from(value).ok())
+                        .map(|value| if *negative { -value } else { value })
+                        .ok_or_else(|| out_of_range(self, expected))?;
+                    signed_to_field(value, *width).ok_or_else(|| out_of_range(self, expected))
+                }
+                PrintableType::Boolean => {
+                    Err(String::from("expected `true` or `false` for a boolean value"))
+                }
+                _ => Err(format!("expected a value of type {}", type_name(expected))),
+            },
+        }
+    }
+}
+
+impl<'a> Evaluator<'a> {
+    pub(super) fn new(
+        frame: Option<&'a StackFrame<'a, FieldElement>>,
+        witness_map: &'a WitnessMap<FieldElement>,
+    ) -> Self {
+        Self { frame, witness_map }
+    }
+
+    /// Evaluates the expression, returning its value along with its type
+    pub(super) fn evaluate(
+        &self,
+        input: &str,
+    ) -> Result<(PrintableValue<FieldElement>, PrintableType), String> {
+        let expression = parse(input)?;
+        self.evaluate_expression(&expression).map(Value::into_typed)
+    }
+
+    /// Evaluates the expression as a scalar of the given type, eg. the new value
+    /// for a variable or witness being edited
+    pub(super) fn evaluate_as(
+        &self,
+        input: &str,
+        typ: &PrintableType,
+    ) -> Result<FieldElement, String> {
+        let expression = parse(input)?;
+        self.evaluate_expression(&expression)?.coerce(typ)
+    }
+
+    /// Resolves an assignable expression such as `x`, `point.y` or `points[i].y`.
+    /// Only scalars can be assigned, and only within values of a fixed size.
+    pub(super) fn place(&self, input: &str) -> Result<Place, String> {
+        let expression = parse(input)?;
+        let place = self.place_of(&expression)?;
+        match place.typ {
+            PrintableType::Field
+            | PrintableType::Boolean
+            | PrintableType::UnsignedInteger { .. }
+            | PrintableType::SignedInteger { .. } => Ok(place),
+            _ => Err(format!("cannot assign to `{input}` of type {}", type_name(&place.typ))),
+        }
+    }
+
+    fn place_of(&self, expression: &Expression) -> Result<Place, String> {
+        match &expression.kind {
+            ExpressionKind::Variable(path, None) if path.segments.len() == 1 => {
+                let name = &path.segments[0].0.contents;
+                let (_, _, typ) = self
+                    .frame
+                    .and_then(|frame| {
+                        frame.variables.iter().find(|(var_name, ..)| *var_name == name.as_str())
+                    })
+                    .ok_or_else(|| format!("`{name}` is not in scope"))?;
+                Ok(Place {
+                    variable: name.clone(),
+                    indexes: vec![],
+                    offset: 0,
+                    typ: (*typ).clone(),
+                })
+            }
+            ExpressionKind::Parenthesized(expression) => self.place_of(expression),
+            ExpressionKind::MemberAccess(access) => {
+                let mut place = self.place_of(&access.lhs)?;
+                let name = &access.rhs.0.contents;
+                let (index, mut types) = match &place.typ {
+                    PrintableType::Struct { fields, .. } => {
+                        let index = fields
+                            .iter()
+                            .position(|(field_name, _)| field_name == name)
+                            .ok_or_else(|| format!("no field `{name}` in struct"))?;
+                        (index, fields.iter().map(|(_, typ)| typ.clone()).collect::<Vec<_>>())
+                    }
+                    PrintableType::Tuple { types } => {
+                        let index = name
+                            .parse::<usize>()
+                            .ok()
+                            .filter(|index| *index < types.len())
+                            .ok_or_else(|| {
+                                format!("no field `{name}` in tuple of length {}", types.len())
+                            })?;
+                        (index, types.clone())
+                    }
+                    typ => {
+                        return Err(format!(
+                            "cannot access field `{name}` of a value of type {}",
+                            type_name(typ)
+                        ))
+                    }
+                };
+                let preceding: Option<usize> = types[..index].iter().map(flattened_size).sum();
+                place.offset += preceding.ok_or_else(|| unsized_place(&place))?;
+                place.indexes.push(index as u32);
+                place.typ = types.swap_remove(index);
+                Ok(place)
+            }
+            ExpressionKind::Index(index) => {
+                let mut place = self.place_of(&index.collection)?;
+                let position = index_value(self.evaluate_expression(&index.index)?)?;
+                let PrintableType::Array { length, typ } = &place.typ else {
+                    return Err(format!(
+                        "cannot assign to an element of a value of type {}",
+                        type_name(&place.typ)
+                    ));
+                };
+                let position = position
+                    .try_into_u128()
+                    .filter(|position| *position < *length as u128)
+                    .ok_or_else(|| format!("index {position} out of bounds for length {length}"))?
+                    as usize;
+                let element_size = flattened_size(typ).ok_or_else(|| unsized_place(&place))?;
+                place.offset += position * element_size;
+                place.indexes.push(position as u32);
+                place.typ = (**typ).clone();
+                Ok(place)
+            }
+            _ => Err(format!("cannot assign to `{expression}`")),
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Result<Value, String> {
+        let variable = self
+            .frame
+            .and_then(|frame| frame.variables.iter().find(|(var_name, ..)| *var_name == name));
+        if let Some((_, value, typ)) = variable {
+            return Ok(Value::Typed((*value).clone(), (*typ).clone()));
+        }
+        let witness = name.strip_prefix('_').and_then(|index| index.parse::<u32>().ok());
+        match witness.and_then(|index| self.witness_map.get(&Witness(index))) {
+            Some(value) => Ok(Value::Typed(PrintableValue::Field(*value), PrintableType::Field)),
+            None => Err(format!("`{name}` is not in scope")),
+        }
+    }
+
+    fn evaluate_expression(&self, expression: &Expression) -> Result<Value, String> {
+        match &expression.kind {
+            ExpressionKind::Literal(Literal::Integer(magnitude, negative)) => {
+                Ok(Value::Literal { magnitude: *magnitude, negative: *negative })
+            }
+            ExpressionKind::Literal(Literal::Bool(value)) => Ok(Value::boolean(*value)),
+            ExpressionKind::Variable(path, None) if path.segments.len() == 1 => {
+                self.lookup(&path.segments[0].0.contents)
+            }
+            ExpressionKind::Parenthesized(expression) => self.evaluate_expression(expression),
+            ExpressionKind::MemberAccess(access) => {
+                let lhs = self.evaluate_expression(&access.lhs)?;
+                member(lhs, &access.rhs.0.contents)
+            }
+            ExpressionKind::Index(index) => {
+                let collection = self.evaluate_expression(&index.collection)?;
+                let index = self.evaluate_expression(&index.index)?;
+                element(collection, index)
+            }
+            ExpressionKind::Prefix(prefix) => {
+                let rhs = self.evaluate_expression(&prefix.rhs)?;
+                unary(&prefix.operator, rhs)
+            }
+            ExpressionKind::Infix(infix) => {
+                let lhs = self.evaluate_expression(&infix.lhs)?;
+                let rhs = self.evaluate_expression(&infix.rhs)?;
+                binary(infix.operator.contents, lhs, rhs)
+            }
+            _ => Err(format!("unsupported expression `{expression}`")),
+        }
+    }
+}
+
+fn parse(input: &str) -> Result<Expression, String> {
+    parse_expression(input.trim()).map_err(|errors| {
+        let messages: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+        format!("invalid expression `{input}`: {}", messages.join(", "))
+    })
+}
+
+fn member(value: Value, name: &str) -> Result<Value, String> {
+    match value {
+        Value::Typed(
+            PrintableValue::Struct(mut fields),
+            PrintableType::Struct { fields: types, .. },
+        ) => {
+            let typ = types.into_iter().find(|(field_name, _)| field_name == name);
+            match (fields.remove(name), typ) {
+                (Some(value), Some((_, typ))) => Ok(Value::Typed(value, typ)),
+                _ => Err(format!("no field `{name}` in struct")),
+            }
+        }
+        Value::Typed(
+            PrintableValue::Vec { mut array_elements, .. },
+            PrintableType::Tuple { mut types },
+        ) => match name.parse::<usize>() {
+            Ok(index) if index < types.len() && index < array_elements.len() => {
+                Ok(Value::Typed(array_elements.swap_remove(index), types.swap_remove(index)))
+            }
+            _ => Err(format!("no field `{name}` in tuple of length {}", types.len())),
+        },
+        value => Err(format!("cannot access field `{name}` of {}", value_type_name(&value))),
+    }
+}
+
+fn element(collection: Value, index: Value) -> Result<Value, String> {
+    let index = index_value(index)?;
+    let (mut elements, typ) = match collection {
+        Value::Typed(
+            PrintableValue::Vec { array_elements, .. },
+            PrintableType::Array { typ, .. },
+        )
+        | Value::Typed(PrintableValue::Vec { array_elements, .. }, PrintableType::Slice { typ }) => {
+            (array_elements, *typ)
+        }
+        value => return Err(format!("cannot index into {}", value_type_name(&value))),
+    };
+    match index.try_into_u128().filter(|index| *index < elements.len() as u128) {
+        Some(index) => Ok(Value::Typed(elements.swap_remove(index as usize), typ)),
+        None => Err(format!("index {index} out of bounds for length {}", elements.len())),
+    }
+}
+
+/// Returns the value of an array index. Any unsigned integer or field can be used
+/// as an index, as in Noir itself.
+fn index_value(index: Value) -> Result<FieldElement, String> {
+    match index {
+        Value::Typed(PrintableValue::Field(value), typ)
+            if matches!(typ, PrintableType::UnsignedInteger { .. } | PrintableType::Field) =>
+        {
+            Ok(value)
+        }
+        Value::Literal { magnitude, negative } if !negative || magnitude.is_zero() => Ok(magnitude),
+        _ => Err(String::from("array index must be a non-negative integer")),
+    }
+}
+
+fn unary(operator: &UnaryOp, rhs: Value) -> Result<Value, String> {
+    match (operator, rhs) {
+        (UnaryOp::Minus, Value::Literal { magnitude, negative }) => {
+            Ok(Value::Literal { magnitude, negative: !negative })
+        }
+        (UnaryOp::Minus, Value::Typed(PrintableValue::Field(value), typ)) => match typ {
+            PrintableType::Field => Ok(Value::Typed(PrintableValue::Field(-value), typ)),
+            PrintableType::SignedInteger { width } => {
+                let negated = field_to_signed(value, width).checked_neg();
+                let result = negated.and_then(|value| signed_to_field(value, width));
+                let result = result.ok_or("attempt to negate with overflow")?;
+                Ok(Value::Typed(PrintableValue::Field(result), typ))
+            }
+            _ => Err(format!("cannot negate a value of type {}", type_name(&typ))),
+        },
+        (UnaryOp::Not, Value::Typed(PrintableValue::Field(value), typ)) => {
+            let result = match typ {
+                PrintableType::Boolean => {
+                    if value.is_zero() {
+                        FieldElement::one()
+                    } else {
+                        FieldElement::zero()
+                    }
+                }
+                PrintableType::UnsignedInteger { width } => {
+                    unsigned_to_field(!value.to_u128() & mask(width), width).unwrap()
+                }
+                PrintableType::SignedInteger { width } => {
+                    signed_to_field(!field_to_signed(value, width), width).unwrap()
+                }
+                _ => {
+                    return Err(format!("cannot apply `!` to a value of type {}", type_name(&typ)))
+                }
+            };
+            Ok(Value::Typed(PrintableValue::Field(result), typ))
+        }
+        (operator, rhs) => Err(format!("cannot apply `{operator}` to {}", value_type_name(&rhs))),
+    }
+}
+
+fn binary(operator: BinaryOpKind, lhs: Value, rhs: Value) -> Result<Value, String> {
+    let typ = match (&lhs, &rhs) {
+        (Value::Typed(_, typ), _) | (_, Value::Typed(_, typ)) => typ.clone(),
+        _ => PrintableType::Field,
+    };
+    let lhs = lhs.coerce(&typ)?;
+    let rhs = rhs.coerce(&typ)?;
+    let overflow = || format!("attempt to apply `{}` with overflow", operator.as_string());
+
+    let result = match &typ {
+        PrintableType::Field => match operator {
+            BinaryOpKind::Add => lhs + rhs,
+            BinaryOpKind::Subtract => lhs - rhs,
+            BinaryOpKind::Multiply => lhs * rhs,
+            BinaryOpKind::Divide if rhs.is_zero() => return Err(String::from("division by zero")),
+            BinaryOpKind::Divide => lhs / rhs,
+            BinaryOpKind::Equal => return Ok(Value::boolean(lhs == rhs)),
+            BinaryOpKind::NotEqual => return Ok(Value::boolean(lhs != rhs)),
+            _ => return Err(unsupported(operator, &typ)),
+        },
+        PrintableType::Boolean => {
+            let (lhs, rhs) = (!lhs.is_zero(), !rhs.is_zero());
+            let result = match operator {
+                BinaryOpKind::Equal => lhs == rhs,
+                BinaryOpKind::NotEqual => lhs != rhs,
+                BinaryOpKind::And => lhs & rhs,
+                BinaryOpKind::Or => lhs | rhs,
+                BinaryOpKind::Xor => lhs ^ rhs,
+                _ => return Err(unsupported(operator, &typ)),
+            };
+            return Ok(Value::boolean(result));
+        }
+        PrintableType::UnsignedInteger { width } => {
+            let (lhs, rhs) = (lhs.to_u128(), rhs.to_u128());
+            if let Some(result) = compare(operator, lhs, rhs) {
+                return Ok(Value::boolean(result));
+            }
+            let result = match operator {
+                BinaryOpKind::Add => lhs.checked_add(rhs),
+                BinaryOpKind::Subtract => lhs.checked_sub(rhs),
+                BinaryOpKind::Multiply => lhs.checked_mul(rhs),
+                BinaryOpKind::Divide => lhs.checked_div(rhs),
+                BinaryOpKind::Modulo => lhs.checked_rem(rhs),
+                BinaryOpKind::And => Some(lhs & rhs),
+                BinaryOpKind::Or => Some(lhs | rhs),
+                BinaryOpKind::Xor => Some(lhs ^ rhs),
+                BinaryOpKind::ShiftLeft if rhs < *width as u128 => {
+                    Some((lhs << rhs) & mask(*width))
+                }
+                BinaryOpKind::ShiftRight if rhs < *width as u128 => Some(lhs >> rhs),
+                _ => return Err(unsupported(operator, &typ)),
+            };
+            result.and_then(|result| unsigned_to_field(result, *width)).ok_or_else(overflow)?
+        }
+        PrintableType::SignedInteger { width } => {
+            let (lhs, rhs) = (field_to_signed(lhs, *width), field_to_signed(rhs, *width));
+            if let Some(result) = compare(operator, lhs, rhs) {
+                return Ok(Value::boolean(result));
+            }
+            let result = match operator {
+                BinaryOpKind::Add => lhs.checked_add(rhs),
+                BinaryOpKind::Subtract => lhs.checked_sub(rhs),
+                BinaryOpKind::Multiply => lhs.checked_mul(rhs),
+                BinaryOpKind::Divide => lhs.checked_div(rhs),
+                BinaryOpKind::Modulo => lhs.checked_rem(rhs),
+                BinaryOpKind::And => Some(lhs & rhs),
+                BinaryOpKind::Or => Some(lhs | rhs),
+                BinaryOpKind::Xor => Some(lhs ^ rhs),
+                _ => return Err(unsupported(operator, &typ)),
+            };
+            result.and_then(|result| signed_to_field(result, *width)).ok_or_else(overflow)?
+        }
+        _ => return Err(unsupported(operator, &typ)),
+    };
+    Ok(Value::Typed(PrintableValue::Field(result), typ))
+}
+
+fn compare<T: PartialOrd>(operator: BinaryOpKind, lhs: T, rhs: T) -> Option<bool> {
+    match operator {
+        BinaryOpKind::Equal => Some(lhs == rhs),
+        BinaryOpKind::NotEqual => Some(lhs != rhs),
+        BinaryOpKind::Less => Some(lhs < rhs),
+        BinaryOpKind::LessEqual => Some(lhs <= rhs),
+        BinaryOpKind::Greater => Some(lhs > rhs),
+        BinaryOpKind::GreaterEqual => Some(lhs >= rhs),
+        _ => None,
+    }
+}
+
+fn mask(width: u32) -> u128 {
+    if width >= 128 {
+        u128::MAX
+    } else {
+        (1u128 << width) - 1
+    }
+}
+
+fn unsigned_to_field(value: u128, width: u32) -> Option<FieldElement> {
+    (value <= mask(width)).then(|| FieldElement::from(value))
+}
+
+fn field_to_signed(value: FieldElement, width: u32) -> i128 {
+    let unsigned = value.to_u128() & mask(width);
+    if width > 0 && width < 128 && unsigned >> (width - 1) == 1 {
+        unsigned as i128 - (1i128 << width)
+    } else {
+        unsigned as i128
+    }
+}
+
+fn signed_to_field(value: i128, width: u32) -> Option<FieldElement> {
+    if width == 0 || width >= 128 {
+        return None;
+    }
+    let min = -(1i128 << (width - 1));
+    let max = (1i128 << (width - 1)) - 1;
+    // Negative values are stored in two's complement
+    (min..=max).contains(&value).then(|| FieldElement::from((value as u128) & mask(width)))
+}
+
+/// Returns the number of fields a value of the given type is flattened into,
+/// or None if it depends on the value
+fn flattened_size(typ: &PrintableType) -> Option<usize> {
+    match typ {
+        PrintableType::Field
+        | PrintableType::Boolean
+        | PrintableType::UnsignedInteger { .. }
+        | PrintableType::SignedInteger { .. } => Some(1),
+        PrintableType::Unit => Some(0),
+        PrintableType::Array { length, typ } => Some(*length as usize * flattened_size(typ)?),
+        PrintableType::Tuple { types } => types.iter().map(flattened_size).sum(),
+        PrintableType::Struct { fields, .. } => {
+            fields.iter().map(|(_, typ)| flattened_size(typ)).sum()
+        }
+        PrintableType::String { length } => Some(*length as usize),
+        PrintableType::Function { env, .. } => Some(1 + flattened_size(env)?),
+        PrintableType::MutableReference { typ } => flattened_size(typ),
+        PrintableType::Slice { .. } => None,
+    }
+}
+
+fn unsized_place(place: &Place) -> String {
+    format!("cannot assign within `{}`, whose size is not fixed", place.variable)
+}
+
+fn type_name(typ: &PrintableType) -> String {
+    match typ {
+        PrintableType::Field => String::from("Field"),
+        PrintableType::Boolean => String::from("bool"),
+        PrintableType::UnsignedInteger { width } => format!("u{width}"),
+        PrintableType::SignedInteger { width } => format!("i{width}"),
+        PrintableType::Array { length, typ } => format!("[{}; {length}]", type_name(typ)),
+        PrintableType::Slice { typ } => format!("[{}]", type_name(typ)),
+        PrintableType::Tuple { types } => {
+            let types: Vec<String> = types.iter().map(type_name).collect();
+            format!("({})", types.join(", "))
+        }
+        PrintableType::Struct { name, .. } => name.clone(),
+        PrintableType::String { length } => format!("str<{length}>"),
+        PrintableType::Function { .. } => String::from("function"),
+        PrintableType::MutableReference { typ } => format!("&mut {}", type_name(typ)),
+        PrintableType::Unit => String::from("()"),
+    }
+}
+
+/// Returns a short description of a value for error messages
+fn value_type_name(value: &Value) -> String {
+    match value {
+        Value::Typed(_, typ) => format!("a value of type {}", type_name(typ)),
+        Value::Literal { .. } => String::from("an integer literal"),
+    }
+}
+
+fn out_of_range(value: &Value, typ: &PrintableType) -> String {
+    match value {
+        Value::Literal { magnitude, negative } => {
+            let sign = if *negative { "-" } else { "" };
+            format!("{sign}{magnitude} does not fit in type {}", type_name(typ))
+        }
+        Value::Typed(..) => format!("value does not fit in type {}", type_name(typ)),
+    }
+}
+
+fn unsupported(operator: BinaryOpKind, typ: &PrintableType) -> String {
+    format!("cannot apply `{}` to values of type {}", operator.as_string(), type_name(typ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn evaluate(
+        input: &str,
+        variables: Vec<(&str, &PrintableValue<FieldElement>, &PrintableType)>,
+    ) -> Result<(PrintableValue<FieldElement>, PrintableType), String> {
+        let frame = StackFrame { function_name: "main", function_params: vec![], variables };
+        let witness_map =
+            WitnessMap::from(BTreeMap::from([(Witness(3), FieldElement::from(7u128))]));
+        Evaluator::new(Some(&frame), &witness_map).evaluate(input)
+    }
+
+    fn field(value: u128) -> PrintableValue<FieldElement> {
+        PrintableValue::Field(FieldElement::from(value))
+    }
+
+    #[test]
+    fn evaluates_arithmetic_on_typed_values() {
+        let x = field(250);
+        let u8_type = PrintableType::UnsignedInteger { width: 8 };
+        let variables = || vec![("x", &x, &u8_type)];
+
+        assert_eq!(evaluate("x + 5", variables()), Ok((field(255), u8_type.clone())));
+        assert_eq!(evaluate("(x - 10) / 2", variables()), Ok((field(120), u8_type.clone())));
+        assert_eq!(evaluate("x > 200", variables()), Ok((field(1), PrintableType::Boolean)));
+        assert!(evaluate("x + 6", variables()).is_err());
+        assert!(evaluate("x + y", variables()).is_err());
+        // Untyped literals evaluate to fields
+        assert_eq!(evaluate("2 * _3", vec![]), Ok((field(14), PrintableType::Field)));
+    }
+
+    #[test]
+    fn evaluates_signed_integers_in_twos_complement() {
+        // -1 as an i8
+        let x = field(255);
+        let i8_type = PrintableType::SignedInteger { width: 8 };
+        let variables = || vec![("x", &x, &i8_type)];
+
+        assert_eq!(evaluate("x - 1", variables()), Ok((field(254), i8_type.clone())));
+        assert_eq!(evaluate("-x", variables()), Ok((field(1), i8_type.clone())));
+        assert_eq!(evaluate("x < 0", variables()), Ok((field(1), PrintableType::Boolean)));
+        assert!(evaluate("x + 200", variables()).is_err());
+    }
+
+    #[test]
+    fn evaluates_member_access_and_indexing() {
+        let u32_type = PrintableType::UnsignedInteger { width: 32 };
+        let point_type = PrintableType::Struct {
+            name: String::from("Point"),
+            fields: vec![
+                (String::from("x"), u32_type.clone()),
+                (String::from("y"), u32_type.clone()),
+            ],
+        };
+        let points_type = PrintableType::Array { length: 2, typ: Box::new(point_type.clone()) };
+        let point = |x, y| {
+            PrintableValue::Struct(BTreeMap::from([
+                (String::from("x"), field(x)),
+                (String::from("y"), field(y)),
+            ]))
+        };
+        let points =
+            PrintableValue::Vec { array_elements: vec![point(1, 2), point(3, 4)], is_slice: false };
+        let variables = || vec![("points", &points, &points_type)];
+
+        assert_eq!(evaluate("points[1]", variables()), Ok((point(3, 4), point_type.clone())));
+        assert_eq!(
+            evaluate("points[0].y + points[1].x", variables()),
+            Ok((field(5), u32_type.clone()))
+        );
+        assert!(evaluate("points[2]", variables()).is_err());
+        assert!(evaluate("points[0].z", variables()).is_err());
+    }
+
+    #[test]
+    fn indexes_with_typed_values() {
+        let u8_type = PrintableType::UnsignedInteger { width: 8 };
+        let array_type = PrintableType::Array { length: 3, typ: Box::new(u8_type.clone()) };
+        let array = PrintableValue::Vec {
+            array_elements: vec![field(5), field(6), field(7)],
+            is_slice: false,
+        };
+        let (i, j, k) = (field(1), field(2), field(3));
+        let i8_type = PrintableType::SignedInteger { width: 8 };
+        let variables = || {
+            vec![
+                ("array", &array, &array_type),
+                ("i", &i, &u8_type),
+                ("j", &j, &PrintableType::Field),
+                ("k", &k, &i8_type),
+            ]
+        };
+
+        assert_eq!(evaluate("array[i]", variables()), Ok((field(6), u8_type.clone())));
+        assert_eq!(evaluate("array[j]", variables()), Ok((field(7), u8_type.clone())));
+        assert_eq!(evaluate("array[i + 1]", variables()), Ok((field(7), u8_type.clone())));
+        assert!(evaluate("array[i + 2]", variables()).is_err());
+        assert!(evaluate("array[j + 1]", variables()).is_err());
+        assert!(evaluate("array[-j]", variables()).is_err());
+        // Signed integers can't be used as indexes
+        assert!(evaluate("array[k]", variables()).is_err());
+    }
+
+    #[test]
+    fn resolves_places_within_variables() {
+        let u32_type = PrintableType::UnsignedInteger { width: 32 };
+        let point_type = PrintableType::Struct {
+            name: String::from("Point"),
+            fields: vec![
+                (String::from("x"), u32_type.clone()),
+                (String::from("y"), u32_type.clone()),
+            ],
+        };
+        let pair_type = PrintableType::Tuple {
+            types: vec![
+                PrintableType::Boolean,
+                PrintableType::Array { length: 2, typ: Box::new(point_type) },
+            ],
+        };
+        let slice_type = PrintableType::Slice { typ: Box::new(u32_type.clone()) };
+        let (pair, slice, i) = (field(0), field(0), field(1));
+        let frame = StackFrame {
+            function_name: "main",
+            function_params: vec![],
+            variables: vec![
+                ("pair", &pair, &pair_type),
+                ("slice", &slice, &slice_type),
+                ("i", &i, &u32_type),
+            ],
+        };
+        let witness_map = WitnessMap::default();
+        let evaluator = Evaluator::new(Some(&frame), &witness_map);
+
+        assert_eq!(
+            evaluator.place("pair.1[i].y"),
+            Ok(Place {
+                variable: String::from("pair"),
+                indexes: vec![1, 1, 1],
+                offset: 4,
+                typ: u32_type.clone(),
+            })
+        );
+        assert_eq!(evaluator.place("i").map(|place| place.offset), Ok(0));
+        assert!(evaluator.place("pair.1[2].x").is_err());
+        assert!(evaluator.place("pair.1[0]").is_err());
+        assert!(evaluator.place("slice[0]").is_err());
+        assert!(evaluator.place("i + 1").is_err());
+    }
+
+    #[test]
+    fn coerces_literals_to_the_expected_type() {
+        let witness_map = WitnessMap::default();
+        let evaluator = Evaluator::new(None, &witness_map);
+        let i8_type = PrintableType::SignedInteger { width: 8 };
+
+        assert_eq!(evaluator.evaluate_as("-1", &i8_type), Ok(FieldElement::from(255u128)));
+        assert!(evaluator.evaluate_as("128", &i8_type).is_err());
+        assert_eq!(evaluator.evaluate_as("true", &PrintableType::Boolean), Ok(FieldElement::one()));
+        assert_eq!(evaluator.evaluate_as("-1", &PrintableType::Field), Ok(-FieldElement::one()));
+        assert!(evaluator.evaluate_as("1 +", &PrintableType::Field).is_err());
+    }
+}
@@ -39,6 +39,9 @@ impl DebugForeignCall {
 pub trait DebugForeignCallExecutor: ForeignCallExecutor<FieldElement> {
     fn get_variables(&self) -> Vec<StackFrame<FieldElement>>;
     fn current_stack_frame(&self) -> Option<StackFrame<FieldElement>>;
+    fn current_frame_var_id(&self, name: &str) -> Option<DebugVarId>;
+    /// Resets the tracked debug variables to the start of the execution
+    fn restart(&mut self, artifact: &DebugArtifact);
 }
 
 pub struct DefaultDebugForeignCallExecutor {
@@ -78,9 +81,18 @@ impl DebugForeignCallExecutor for DefaultDebugForeignCallExecutor {
     fn current_stack_frame(&self) -> Option<StackFrame<FieldElement>> {
         self.debug_vars.current_stack_frame()
     }
+
+    fn current_frame_var_id(&self, name: &str) -> Option<DebugVarId> {
+        self.debug_vars.current_frame_var_id(name)
+    }
+
+    fn restart(&mut self, artifact: &DebugArtifact) {
+        self.debug_vars = DebugVars::default();
+        self.load_artifact(artifact);
+    }
 }
 
-fn debug_var_id(value: &FieldElement) -> DebugVarId {
+pub(crate) fn debug_var_id(value: &FieldElement) -> DebugVarId {
     DebugVarId(value.to_u128() as u32)
 }
 
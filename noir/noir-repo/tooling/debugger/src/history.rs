@@ -0,0 +1,137 @@
+use acvm::acir::brillig::ForeignCallResult;
+use acvm::acir::native_types::Witness;
+use acvm::FieldElement;
+use noirc_errors::debug_info::DebugVarId;
+
+/// A change to the execution state made by the user, which needs to be
+/// applied again at the same point when the execution is replayed.
+#[derive(Debug, Clone)]
+pub(super) enum StateEdit {
+    Witness(Witness, FieldElement),
+    BrilligMemory {
+        ptr: usize,
+        value: FieldElement,
+        bit_size: u32,
+    },
+    /// Source-level variable value, as tracked by the debugger. The indexes
+    /// select the struct field, tuple field or array element being assigned.
+    Variable {
+        var_id: DebugVarId,
+        indexes: Vec<u32>,
+        value: FieldElement,
+    },
+}
+
+/// Record of the execution of a debugging session, used to move backwards by
+/// replaying the program from the start up to an earlier step.
+///
+/// Neither the ACVM nor the Brillig VM can be snapshotted, but their execution
+/// is deterministic given the results of the foreign calls and the edits made
+/// by the user, so those are recorded along with the number of steps taken.
+#[derive(Debug, Default)]
+pub(super) struct ExecutionHistory {
+    /// Number of opcode steps taken since the start of the execution
+    steps: usize,
+    /// Results of the (non-instrumentation) foreign calls resolved so far, in
+    /// order of execution
+    foreign_call_results: Vec<ForeignCallResult<FieldElement>>,
+    /// Number of recorded foreign call results consumed in the current
+    /// execution; when replaying this is behind `foreign_call_results.len()`
+    foreign_call_cursor: usize,
+    /// User edits, tagged with the step at which they were made
+    edits: Vec<(usize, StateEdit)>,
+}
+
+impl ExecutionHistory {
+    pub(super) fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub(super) fn record_step(&mut self) {
+        self.steps += 1;
+    }
+
+    /// Returns the recorded result of the next foreign call if we are
+    /// re-executing a previously recorded part of the execution
+    pub(super) fn next_recorded_foreign_call(&mut self) -> Option<ForeignCallResult<FieldElement>> {
+        let result = self.foreign_call_results.get(self.foreign_call_cursor).cloned()?;
+        self.foreign_call_cursor += 1;
+        Some(result)
+    }
+
+    pub(super) fn record_foreign_call(&mut self, result: ForeignCallResult<FieldElement>) {
+        self.foreign_call_results.push(result);
+        self.foreign_call_cursor += 1;
+    }
+
+    /// Records an edit at the current step. Editing the state invalidates the
+    /// rest of the recorded execution, since it may diverge from this point.
+    pub(super) fn record_edit(&mut self, edit: StateEdit) {
+        self.foreign_call_results.truncate(self.foreign_call_cursor);
+        self.edits.retain(|(step, _)| *step <= self.steps);
+        self.edits.push((self.steps, edit));
+    }
+
+    /// Returns the edits to apply at the current step
+    pub(super) fn current_edits(&self) -> Vec<StateEdit> {
+        self.edits
+            .iter()
+            .filter(|(step, _)| *step == self.steps)
+            .map(|(_, edit)| edit.clone())
+            .collect()
+    }
+
+    /// Goes back to the start of the execution, keeping the recorded foreign
+    /// call results and edits so they can be replayed
+    pub(super) fn rewind(&mut self) {
+        self.steps = 0;
+        self.foreign_call_cursor = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use acvm::AcirField;
+
+    fn result_with(value: u128) -> ForeignCallResult<FieldElement> {
+        ForeignCallResult { values: vec![FieldElement::from(value).into()] }
+    }
+
+    #[test]
+    fn replays_recorded_foreign_calls_after_rewind() {
+        let mut history = ExecutionHistory::default();
+        history.record_step();
+        history.record_foreign_call(result_with(1));
+        history.record_step();
+        history.record_foreign_call(result_with(2));
+        assert_eq!(history.next_recorded_foreign_call(), None);
+
+        history.rewind();
+        assert_eq!(history.steps(), 0);
+        assert_eq!(history.next_recorded_foreign_call(), Some(result_with(1)));
+        assert_eq!(history.next_recorded_foreign_call(), Some(result_with(2)));
+        assert_eq!(history.next_recorded_foreign_call(), None);
+    }
+
+    #[test]
+    fn edits_discard_recorded_future() {
+        let mut history = ExecutionHistory::default();
+        history.record_step();
+        history.record_foreign_call(result_with(1));
+        history.record_step();
+        history.record_edit(StateEdit::Witness(Witness(1), FieldElement::one()));
+        history.record_foreign_call(result_with(2));
+
+        history.rewind();
+        history.record_step();
+        history.record_edit(StateEdit::Witness(Witness(2), FieldElement::one()));
+        assert_eq!(history.next_recorded_foreign_call(), None);
+
+        history.rewind();
+        history.record_step();
+        assert!(matches!(history.current_edits().as_slice(), [StateEdit::Witness(Witness(2), _)]));
+        history.record_step();
+        assert!(history.current_edits().is_empty());
+    }
+}
@@ -1,7 +1,10 @@
+mod breakpoints;
 mod context;
 mod dap;
 pub mod errors;
+mod evaluation;
 mod foreign_calls;
+mod history;
 mod repl;
 mod source_code_printer;
 
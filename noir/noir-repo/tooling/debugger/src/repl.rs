@@ -1,3 +1,4 @@
+use crate::breakpoints::{BreakpointCondition, Watchpoint};
 use crate::context::{DebugCommandResult, DebugContext};
 
 use acvm::acir::circuit::brillig::BrilligBytecode;
@@ -200,6 +201,23 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ReplDebugger<'a, B> {
         }
     }
 
+    fn add_conditional_breakpoint_at(&mut self, location: OpcodeLocation, condition: String) {
+        let condition = match condition.parse::<BreakpointCondition>() {
+            Ok(condition) => condition,
+            Err(error) => {
+                println!("Invalid breakpoint condition: {error}");
+                return;
+            }
+        };
+        if !self.context.is_valid_opcode_location(&location) {
+            println!("Invalid opcode location {location}");
+        } else if self.context.add_conditional_breakpoint(location, condition.clone()) {
+            println!("Added breakpoint at opcode {location} if {condition}");
+        } else {
+            println!("Breakpoint at opcode {location} updated to stop if {condition}");
+        }
+    }
+
     fn delete_breakpoint_at(&mut self, location: OpcodeLocation) {
         if self.context.delete_breakpoint(&location) {
             println!("Breakpoint at opcode {location} deleted");
@@ -208,9 +226,57 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ReplDebugger<'a, B> {
         }
     }
 
+    fn add_function_breakpoint(&mut self, function_name: String) {
+        if !self.context.is_valid_function_name(&function_name) {
+            println!("Unknown function {function_name}");
+        } else if self.context.add_function_breakpoint(function_name.clone()) {
+            println!("Added breakpoint at function {function_name}");
+        } else {
+            println!("Breakpoint at function {function_name} already set");
+        }
+    }
+
+    fn delete_function_breakpoint(&mut self, function_name: String) {
+        if self.context.delete_function_breakpoint(&function_name) {
+            println!("Breakpoint at function {function_name} deleted");
+        } else {
+            println!("Breakpoint at function {function_name} not set");
+        }
+    }
+
+    fn add_watchpoint(&mut self, expression: String) {
+        let watchpoint = match expression.parse::<Watchpoint>() {
+            Ok(watchpoint) => watchpoint,
+            Err(error) => {
+                println!("Invalid watchpoint: {error}");
+                return;
+            }
+        };
+        if self.context.add_watchpoint(watchpoint.clone()) {
+            println!("Watching {watchpoint}");
+        } else {
+            println!("Already watching {watchpoint}");
+        }
+    }
+
+    fn delete_watchpoint(&mut self, expression: String) {
+        let Ok(watchpoint) = expression.parse::<Watchpoint>() else {
+            println!("Not watching {expression}");
+            return;
+        };
+        if self.context.delete_watchpoint(&watchpoint) {
+            println!("Stopped watching {watchpoint}");
+        } else {
+            println!("Not watching {watchpoint}");
+        }
+    }
+
     fn validate_in_progress(&self) -> bool {
         match self.last_result {
-            DebugCommandResult::Ok | DebugCommandResult::BreakpointReached(..) => true,
+            DebugCommandResult::Ok
+            | DebugCommandResult::BreakpointReached(..)
+            | DebugCommandResult::FunctionBreakpointReached(..)
+            | DebugCommandResult::WatchpointTriggered(..) => true,
             DebugCommandResult::Done => {
                 println!("Execution finished");
                 false
@@ -226,7 +292,24 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ReplDebugger<'a, B> {
     fn handle_debug_command_result(&mut self, result: DebugCommandResult) {
         match &result {
             DebugCommandResult::BreakpointReached(location) => {
-                println!("Stopped at breakpoint in opcode {}", location);
+                match self.context.get_breakpoint_condition(location) {
+                    Some(condition) => {
+                        println!("Stopped at breakpoint in opcode {} ({})", location, condition)
+                    }
+                    None => println!("Stopped at breakpoint in opcode {}", location),
+                }
+            }
+            DebugCommandResult::FunctionBreakpointReached(function_name) => {
+                println!("Stopped at breakpoint in function {}", function_name);
+            }
+            DebugCommandResult::WatchpointTriggered(watchpoint) => {
+                match self.context.get_watched_value(watchpoint) {
+                    Some(value) => println!("Watched {} changed to {}", watchpoint, value),
+                    None if !self.context.is_watching(watchpoint) => {
+                        println!("Watched {} went out of scope and was deleted", watchpoint)
+                    }
+                    None => println!("Watched {} is no longer available", watchpoint),
+                }
             }
             DebugCommandResult::Error(error) => {
                 println!("ERROR: {}", error);
@@ -280,9 +363,46 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ReplDebugger<'a, B> {
         }
     }
 
+    fn validate_can_step_back(&self) -> bool {
+        if self.context.can_step_back() {
+            true
+        } else {
+            println!("Already at the start of the execution");
+            false
+        }
+    }
+
+    fn next_back(&mut self) {
+        if self.validate_can_step_back() {
+            let result = self.context.next_back();
+            self.handle_debug_command_result(result);
+        }
+    }
+
+    fn step_back_opcode(&mut self) {
+        if self.validate_can_step_back() {
+            let result = self.context.step_back_opcode();
+            self.handle_debug_command_result(result);
+        }
+    }
+
+    fn reverse_cont(&mut self) {
+        if self.validate_can_step_back() {
+            println!("(Continuing execution backwards...)");
+            let result = self.context.reverse_cont();
+            self.handle_debug_command_result(result);
+        }
+    }
+
     fn restart_session(&mut self) {
-        let breakpoints: Vec<OpcodeLocation> =
-            self.context.iterate_breakpoints().copied().collect();
+        let breakpoints: Vec<(OpcodeLocation, Option<BreakpointCondition>)> = self
+            .context
+            .iterate_breakpoints()
+            .map(|location| (*location, self.context.get_breakpoint_condition(location).cloned()))
+            .collect();
+        let function_breakpoints: Vec<String> =
+            self.context.iterate_function_breakpoints().cloned().collect();
+        let watchpoints: Vec<Watchpoint> = self.context.iterate_watchpoints().cloned().collect();
         let foreign_call_executor =
             Box::new(DefaultDebugForeignCallExecutor::from_artifact(true, self.debug_artifact));
         self.context = DebugContext::new(
@@ -293,8 +413,19 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ReplDebugger<'a, B> {
             foreign_call_executor,
             self.unconstrained_functions,
         );
-        for opcode_location in breakpoints {
-            self.context.add_breakpoint(opcode_location);
+        for (opcode_location, condition) in breakpoints {
+            match condition {
+                Some(condition) => {
+                    self.context.add_conditional_breakpoint(opcode_location, condition)
+                }
+                None => self.context.add_breakpoint(opcode_location),
+            };
+        }
+        for function_name in function_breakpoints {
+            self.context.add_function_breakpoint(function_name);
+        }
+        for watchpoint in watchpoints {
+            self.context.add_watchpoint(watchpoint);
         }
         self.last_result = DebugCommandResult::Ok;
         println!("Restarted debugging session.");
@@ -456,6 +587,36 @@ pub fn run<B: BlackBoxFunctionSolver<FieldElement>>(
                 }
             },
         )
+        .add(
+            "back",
+            command! {
+                "step back until the previous source location is reached",
+                () => || {
+                    ref_context.borrow_mut().next_back();
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "reverse-into",
+            command! {
+                "step back to the previous opcode",
+                () => || {
+                    ref_context.borrow_mut().step_back_opcode();
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "reverse-continue",
+            command! {
+                "run backwards until the previous breakpoint or the start of the program",
+                () => || {
+                    ref_context.borrow_mut().reverse_cont();
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
         .add(
             "restart",
             command! {
@@ -486,6 +647,17 @@ pub fn run<B: BlackBoxFunctionSolver<FieldElement>>(
                 }
             },
         )
+        .add(
+            "break",
+            command! {
+                "add a conditional breakpoint at an opcode location (eg. `break 3 i == 5`)",
+                (LOCATION:OpcodeLocation, variable: String, operator: String, value: String) => |location, variable, operator, value| {
+                    let condition = format!("{variable} {operator} {value}");
+                    ref_context.borrow_mut().add_conditional_breakpoint_at(location, condition);
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
         .add(
             "delete",
             command! {
@@ -496,6 +668,46 @@ pub fn run<B: BlackBoxFunctionSolver<FieldElement>>(
                 }
             },
         )
+        .add(
+            "fbreak",
+            command! {
+                "add a breakpoint stopping when the named function is entered",
+                (function_name: String) => |function_name| {
+                    ref_context.borrow_mut().add_function_breakpoint(function_name);
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "fdelete",
+            command! {
+                "delete breakpoint on the named function",
+                (function_name: String) => |function_name| {
+                    ref_context.borrow_mut().delete_function_breakpoint(function_name);
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "watch",
+            command! {
+                "stop when a witness (eg. `witness:_3`) or variable changes value",
+                (expression: String) => |expression| {
+                    ref_context.borrow_mut().add_watchpoint(expression);
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "unwatch",
+            command! {
+                "delete a watchpoint on a witness or variable",
+                (expression: String) => |expression| {
+                    ref_context.borrow_mut().delete_watchpoint(expression);
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
         .add(
             "witness",
             command! {
@@ -21,7 +21,12 @@ use async_lsp::{
 use fm::{codespan_files as files, FileManager};
 use fxhash::FxHashSet;
 use lsp_types::{
-    request::{PrepareRenameRequest, References, Rename},
+    request::{
+        CallHierarchyIncomingCalls, CallHierarchyOutgoingCalls, CallHierarchyPrepare,
+        CodeActionRequest, Completion, DocumentSymbolRequest, HoverRequest, InlayHintRequest,
+        OnTypeFormatting, PrepareRenameRequest, RangeFormatting, References, Rename,
+        SemanticTokensFullRequest, SignatureHelpRequest, WorkspaceSymbolRequest,
+    },
     CodeLens,
 };
 use nargo::{
@@ -33,7 +38,10 @@ use nargo_toml::{find_file_manifest, resolve_workspace_from_toml, PackageSelecti
 use noirc_driver::{file_manager_with_stdlib, prepare_crate, NOIR_ARTIFACT_VERSION_STRING};
 use noirc_frontend::{
     graph::{CrateId, CrateName},
-    hir::{def_map::parse_file, Context, FunctionNameMatch, ParsedFiles},
+    hir::{
+        def_map::{parse_file, CrateDefMap},
+        Context, FunctionNameMatch, ParsedFiles,
+    },
     node_interner::NodeInterner,
     parser::ParserError,
     ParsedModule,
@@ -45,10 +53,14 @@ use notifications::{
     on_did_open_text_document, on_did_save_text_document, on_exit, on_initialized,
 };
 use requests::{
-    on_code_lens_request, on_formatting, on_goto_declaration_request, on_goto_definition_request,
-    on_goto_type_definition_request, on_initialize, on_prepare_rename_request,
-    on_profile_run_request, on_references_request, on_rename_request, on_shutdown,
-    on_test_run_request, on_tests_request,
+    on_call_hierarchy_incoming_calls_request, on_call_hierarchy_outgoing_calls_request,
+    on_code_action_request, on_code_lens_request, on_completion_request,
+    on_document_symbol_request, on_formatting, on_goto_declaration_request,
+    on_goto_definition_request, on_goto_type_definition_request, on_hover_request, on_initialize,
+    on_inlay_hint_request, on_prepare_call_hierarchy_request, on_prepare_rename_request,
+    on_profile_run_request, on_range_formatting, on_references_request, on_rename_request,
+    on_semantic_tokens_full_request, on_shutdown, on_signature_help_request, on_test_run_request,
+    on_tests_request, on_type_formatting, on_workspace_symbol_request, FunctionOpcodeCounts,
 };
 use serde_json::Value as JsonValue;
 use thiserror::Error;
@@ -81,6 +93,8 @@ pub struct LspState {
     input_files: HashMap<String, String>,
     cached_lenses: HashMap<String, Vec<CodeLens>>,
     cached_definitions: HashMap<String, NodeInterner>,
+    cached_def_maps: HashMap<String, BTreeMap<CrateId, CrateDefMap>>,
+    cached_opcode_counts: HashMap<String, FunctionOpcodeCounts>,
     cached_parsed_files: HashMap<PathBuf, (usize, (ParsedModule, Vec<ParserError>))>,
     parsing_cache_enabled: bool,
 }
@@ -97,6 +111,8 @@ impl LspState {
             input_files: HashMap::new(),
             cached_lenses: HashMap::new(),
             cached_definitions: HashMap::new(),
+            cached_def_maps: HashMap::new(),
+            cached_opcode_counts: HashMap::new(),
             open_documents_count: 0,
             cached_parsed_files: HashMap::new(),
             parsing_cache_enabled: true,
@@ -118,6 +134,8 @@ impl NargoLspService {
         router
             .request::<request::Initialize, _>(on_initialize)
             .request::<request::Formatting, _>(on_formatting)
+            .request::<RangeFormatting, _>(on_range_formatting)
+            .request::<OnTypeFormatting, _>(on_type_formatting)
             .request::<request::Shutdown, _>(on_shutdown)
             .request::<request::CodeLens, _>(on_code_lens_request)
             .request::<request::NargoTests, _>(on_tests_request)
@@ -129,6 +147,17 @@ impl NargoLspService {
             .request::<References, _>(on_references_request)
             .request::<PrepareRenameRequest, _>(on_prepare_rename_request)
             .request::<Rename, _>(on_rename_request)
+            .request::<HoverRequest, _>(on_hover_request)
+            .request::<Completion, _>(on_completion_request)
+            .request::<DocumentSymbolRequest, _>(on_document_symbol_request)
+            .request::<WorkspaceSymbolRequest, _>(on_workspace_symbol_request)
+            .request::<SemanticTokensFullRequest, _>(on_semantic_tokens_full_request)
+            .request::<InlayHintRequest, _>(on_inlay_hint_request)
+            .request::<SignatureHelpRequest, _>(on_signature_help_request)
+            .request::<CodeActionRequest, _>(on_code_action_request)
+            .request::<CallHierarchyPrepare, _>(on_prepare_call_hierarchy_request)
+            .request::<CallHierarchyIncomingCalls, _>(on_call_hierarchy_incoming_calls_request)
+            .request::<CallHierarchyOutgoingCalls, _>(on_call_hierarchy_outgoing_calls_request)
             .notification::<notification::Initialized>(on_initialized)
             .notification::<notification::DidChangeConfiguration>(on_did_change_configuration)
             .notification::<notification::DidOpenTextDocument>(on_did_open_text_document)
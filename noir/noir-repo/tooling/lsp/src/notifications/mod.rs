@@ -5,7 +5,9 @@ use nargo::insert_all_files_for_workspace_into_file_manager;
 use noirc_driver::{check_crate, file_manager_with_stdlib};
 use noirc_errors::{DiagnosticKind, FileDiagnostic};
 
-use crate::requests::collect_lenses_for_package;
+use crate::requests::{
+    collect_lenses_for_package, collect_opcode_count_lenses, count_opcodes_in_file,
+};
 use crate::types::{
     notification, Diagnostic, DiagnosticSeverity, DidChangeConfigurationParams,
     DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
@@ -81,7 +83,21 @@ pub(super) fn on_did_change_text_document(
         }
     };
 
-    let lenses = collect_lenses_for_package(&context, crate_id, &workspace, package, None);
+    let mut lenses = collect_lenses_for_package(&context, crate_id, &workspace, package, None);
+    if let Some(opcode_counts) =
+        state.cached_opcode_counts.get(&params.text_document.uri.to_string())
+    {
+        let file_id = context.crate_graph[crate_id].root_file_id;
+        let files = context.file_manager.as_file_map();
+        lenses.extend(collect_opcode_count_lenses(
+            &context.def_interner,
+            files,
+            file_id,
+            &workspace,
+            package,
+            opcode_counts,
+        ));
+    }
 
     state.cached_lenses.insert(params.text_document.uri.to_string(), lenses);
 
@@ -94,11 +110,13 @@ pub(super) fn on_did_close_text_document(
 ) -> ControlFlow<Result<(), async_lsp::Error>> {
     state.input_files.remove(&params.text_document.uri.to_string());
     state.cached_lenses.remove(&params.text_document.uri.to_string());
+    state.cached_opcode_counts.remove(&params.text_document.uri.to_string());
 
     state.open_documents_count -= 1;
 
     if state.open_documents_count == 0 {
         state.cached_definitions.clear();
+        state.cached_def_maps.clear();
     }
 
     ControlFlow::Continue(())
@@ -154,16 +172,44 @@ fn process_noir_document(
                 });
             }
 
-            let collected_lenses = crate::requests::collect_lenses_for_package(
+            let mut collected_lenses = crate::requests::collect_lenses_for_package(
                 &context,
                 crate_id,
                 &workspace,
                 package,
                 Some(&file_path),
             );
+
+            // Opcode counts are taken from the artifacts of the last compilation of the package
+            // containing the saved file. They are kept as they were if the file changed since.
+            let file_id = context
+                .file_manager
+                .name_to_id(file_path.clone())
+                .filter(|_| file_path.starts_with(&package.root_dir));
+            if let Some(file_id) = file_id {
+                if let Some(opcode_counts) =
+                    count_opcodes_in_file(&context, crate_id, &workspace, package, file_id)
+                {
+                    state.cached_opcode_counts.insert(document_uri.to_string(), opcode_counts);
+                }
+            }
+            if let (Some(file_id), Some(opcode_counts)) =
+                (file_id, state.cached_opcode_counts.get(&document_uri.to_string()))
+            {
+                let files = context.file_manager.as_file_map();
+                collected_lenses.extend(collect_opcode_count_lenses(
+                    &context.def_interner,
+                    files,
+                    file_id,
+                    &workspace,
+                    package,
+                    opcode_counts,
+                ));
+            }
             state.cached_lenses.insert(document_uri.to_string(), collected_lenses);
 
-            state.cached_definitions.insert(package_root_dir, context.def_interner);
+            state.cached_definitions.insert(package_root_dir.clone(), context.def_interner);
+            state.cached_def_maps.insert(package_root_dir, context.def_maps);
 
             let fm = &context.file_manager;
             let files = fm.as_file_map();
@@ -0,0 +1,235 @@
+use std::future::{self, Future};
+
+use async_lsp::ResponseError;
+use fm::{codespan_files::Files, FileMap};
+use lsp_types::{
+    CallHierarchyIncomingCall, CallHierarchyIncomingCallsParams, CallHierarchyItem,
+    CallHierarchyOutgoingCall, CallHierarchyOutgoingCallsParams, CallHierarchyPrepareParams, Range,
+    SymbolKind, TextDocumentIdentifier, TextDocumentPositionParams, Url,
+};
+use noirc_errors::{Location, Span};
+use noirc_frontend::{
+    macros_api::NodeInterner,
+    node_interner::{DefinitionKind, FuncId, ReferenceId},
+};
+
+use crate::{byte_span_to_range, LspState};
+
+use super::{hover::format_function, process_request, signature_help::has_self_parameter};
+
+pub(crate) fn on_prepare_call_hierarchy_request(
+    state: &mut LspState,
+    params: CallHierarchyPrepareParams,
+) -> impl Future<Output = Result<Option<Vec<CallHierarchyItem>>, ResponseError>> {
+    let result = process_request(
+        state,
+        params.text_document_position_params,
+        |location, interner, files| {
+            let func_id = find_function(location, interner)?;
+            Some(vec![call_hierarchy_item(func_id, interner, files)?])
+        },
+    );
+    future::ready(result)
+}
+
+pub(crate) fn on_call_hierarchy_incoming_calls_request(
+    state: &mut LspState,
+    params: CallHierarchyIncomingCallsParams,
+) -> impl Future<Output = Result<Option<Vec<CallHierarchyIncomingCall>>, ResponseError>> {
+    let result =
+        process_request(state, item_position(&params.item), |location, interner, files| {
+            let func_id = find_function(location, interner)?;
+            let references = interner.references_to(ReferenceId::Function(func_id));
+            let calls = sorted_by_location(interner.function_callers(func_id), interner)
+                .into_iter()
+                .filter_map(|caller| {
+                    let from = call_hierarchy_item(caller, interner, files)?;
+                    let from_ranges = call_ranges(caller, &references, interner, files);
+                    Some(CallHierarchyIncomingCall { from, from_ranges })
+                })
+                .collect();
+            Some(calls)
+        });
+    future::ready(result)
+}
+
+pub(crate) fn on_call_hierarchy_outgoing_calls_request(
+    state: &mut LspState,
+    params: CallHierarchyOutgoingCallsParams,
+) -> impl Future<Output = Result<Option<Vec<CallHierarchyOutgoingCall>>, ResponseError>> {
+    let result =
+        process_request(state, item_position(&params.item), |location, interner, files| {
+            let func_id = find_function(location, interner)?;
+            let calls = sorted_by_location(interner.function_callees(func_id), interner)
+                .into_iter()
+                .filter_map(|callee| {
+                    let to = call_hierarchy_item(callee, interner, files)?;
+                    let references = interner.references_to(ReferenceId::Function(callee));
+                    let from_ranges = call_ranges(func_id, &references, interner, files);
+                    Some(CallHierarchyOutgoingCall { to, from_ranges })
+                })
+                .collect();
+            Some(calls)
+        });
+    future::ready(result)
+}
+
+/// Items are resolved again from the position of their name, as the client sends them back as-is
+fn item_position(item: &CallHierarchyItem) -> TextDocumentPositionParams {
+    TextDocumentPositionParams {
+        text_document: TextDocumentIdentifier { uri: item.uri.clone() },
+        position: item.selection_range.start,
+    }
+}
+
+fn find_function(location: Location, interner: &NodeInterner) -> Option<FuncId> {
+    match interner.find_referenced(location)? {
+        ReferenceId::Function(func_id) => Some(func_id),
+        ReferenceId::Local(id) => match interner.definition(id).kind {
+            DefinitionKind::Function(func_id) => Some(func_id),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn call_hierarchy_item(
+    func_id: FuncId,
+    interner: &NodeInterner,
+    files: &FileMap,
+) -> Option<CallHierarchyItem> {
+    let name_location = interner.function_modifiers(&func_id).name_location;
+    let file_id = name_location.file;
+
+    // The item spans from the function name to the end of its body
+    let end = interner
+        .function_body_location(&func_id)
+        .filter(|body_location| body_location.file == file_id)
+        .map_or(name_location.span.end(), |body_location| body_location.span.end());
+    let span = Span::from(name_location.span.start()..end);
+
+    let uri = Url::from_file_path(files.name(file_id).ok()?.to_string()).ok()?;
+    let kind = if has_self_parameter(func_id, interner) {
+        SymbolKind::METHOD
+    } else {
+        SymbolKind::FUNCTION
+    };
+
+    Some(CallHierarchyItem {
+        name: interner.function_name(&func_id).to_string(),
+        kind,
+        tags: None,
+        detail: Some(format_function(func_id, interner)),
+        uri,
+        range: byte_span_to_range(files, file_id, span.into())?,
+        selection_range: byte_span_to_range(files, file_id, name_location.span.into())?,
+        data: None,
+    })
+}
+
+/// The ranges of the given references which are inside the body of `func_id`
+fn call_ranges(
+    func_id: FuncId,
+    references: &[Location],
+    interner: &NodeInterner,
+    files: &FileMap,
+) -> Vec<Range> {
+    let Some(body_location) = interner.function_body_location(&func_id) else {
+        return Vec::new();
+    };
+    references
+        .iter()
+        .filter(|location| body_location.contains(location))
+        .filter_map(|location| byte_span_to_range(files, location.file, location.span.into()))
+        .collect()
+}
+
+fn sorted_by_location(mut functions: Vec<FuncId>, interner: &NodeInterner) -> Vec<FuncId> {
+    functions.sort_by_key(|func_id| {
+        let location = interner.function_modifiers(func_id).name_location;
+        (location.file, location.span.start())
+    });
+    functions
+}
+
+#[cfg(test)]
+mod call_hierarchy_tests {
+    use crate::test_utils::{self, search_in_file};
+
+    use super::*;
+    use lsp_types::{PartialResultParams, Position, WorkDoneProgressParams};
+    use tokio::test;
+
+    async fn prepare_at(state: &mut LspState, uri: &Url, search_string: &str) -> CallHierarchyItem {
+        let range = search_in_file(uri.path(), search_string)[0];
+        let params = CallHierarchyPrepareParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                position: Position { line: range.start.line, character: range.start.character },
+            },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        };
+
+        let mut items = on_prepare_call_hierarchy_request(state, params)
+            .await
+            .expect("Could not execute on_prepare_call_hierarchy_request")
+            .expect("Expected a call hierarchy item");
+        assert_eq!(items.len(), 1);
+        items.remove(0)
+    }
+
+    #[test]
+    async fn test_prepare_call_hierarchy_from_reference() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("call_hierarchy").await;
+
+        let item = prepare_at(&mut state, &noir_text_document, "add_one").await;
+        assert_eq!(item.name, "add_one");
+        assert_eq!(item.kind, SymbolKind::FUNCTION);
+        assert_eq!(item.selection_range, search_in_file(noir_text_document.path(), "add_one")[1]);
+
+        let item = prepare_at(&mut state, &noir_text_document, "increment").await;
+        assert_eq!(item.kind, SymbolKind::METHOD);
+    }
+
+    #[test]
+    async fn test_call_hierarchy_incoming_calls() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("call_hierarchy").await;
+
+        let item = prepare_at(&mut state, &noir_text_document, "add_one").await;
+        let params = CallHierarchyIncomingCallsParams {
+            item,
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+        let calls = on_call_hierarchy_incoming_calls_request(&mut state, params)
+            .await
+            .expect("Could not execute on_call_hierarchy_incoming_calls_request")
+            .expect("Expected incoming calls");
+
+        let calls: Vec<_> =
+            calls.iter().map(|call| (call.from.name.as_str(), call.from_ranges.len())).collect();
+        assert_eq!(calls, vec![("increment", 1), ("add_two", 2)]);
+    }
+
+    #[test]
+    async fn test_call_hierarchy_outgoing_calls() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("call_hierarchy").await;
+
+        let item = prepare_at(&mut state, &noir_text_document, "main").await;
+        let params = CallHierarchyOutgoingCallsParams {
+            item,
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+        let calls = on_call_hierarchy_outgoing_calls_request(&mut state, params)
+            .await
+            .expect("Could not execute on_call_hierarchy_outgoing_calls_request")
+            .expect("Expected outgoing calls");
+
+        let names: Vec<_> = calls.iter().map(|call| call.to.name.as_str()).collect();
+        assert_eq!(names, vec!["increment", "add_two"]);
+
+        let method_call = search_in_file(noir_text_document.path(), "increment")[1];
+        assert_eq!(calls[0].from_ranges, vec![method_call]);
+    }
+}
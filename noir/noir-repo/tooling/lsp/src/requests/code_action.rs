@@ -0,0 +1,525 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    future::{self, Future},
+};
+
+use async_lsp::{ErrorCode, ResponseError};
+use fm::{codespan_files::Files, FileId, FileMap};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, CodeActionResponse,
+    Diagnostic, Range, TextEdit, WorkspaceEdit,
+};
+use noirc_errors::Span;
+use noirc_frontend::{
+    ast::{Ident, ItemVisibility},
+    graph::CrateId,
+    hir::{
+        def_collector::dc_crate::CompilationError,
+        def_map::{CrateDefMap, LocalModuleId},
+        resolution::{
+            errors::{PubPosition, ResolverError},
+            import::PathResolutionError,
+        },
+    },
+};
+
+use crate::{byte_span_to_range, LspState};
+
+use super::{completion::is_struct_module, process_document_with_errors};
+
+pub(crate) fn on_code_action_request(
+    state: &mut LspState,
+    params: CodeActionParams,
+) -> impl Future<Output = Result<Option<CodeActionResponse>, ResponseError>> {
+    future::ready(on_code_action_inner(state, params))
+}
+
+fn on_code_action_inner(
+    state: &mut LspState,
+    params: CodeActionParams,
+) -> Result<Option<CodeActionResponse>, ResponseError> {
+    let uri = params.text_document.uri.clone();
+    process_document_with_errors(state, &uri, |context, crate_id, file_id, errors| {
+        let files = context.file_manager.as_file_map();
+        let source = files.source(file_id).map_err(|err| {
+            ResponseError::new(ErrorCode::REQUEST_FAILED, format!("Could not read file: {err:?}"))
+        })?;
+
+        let mut fixes = QuickFixes {
+            def_maps: &context.def_maps,
+            crate_id,
+            files,
+            file_id,
+            source,
+            actions: Vec::new(),
+        };
+        for (error, error_file_id) in errors {
+            if *error_file_id != file_id {
+                continue;
+            }
+            let CompilationError::ResolverError(error) = error else {
+                continue;
+            };
+            let Some(span) = error_span(error) else {
+                continue;
+            };
+            let Some(range) = fixes.range(span) else {
+                continue;
+            };
+            if range.end < params.range.start || params.range.end < range.start {
+                continue;
+            }
+
+            // Link the fixes to the diagnostics published for the error, if the client sent them
+            let diagnostics: Vec<Diagnostic> = params
+                .context
+                .diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.range == range)
+                .cloned()
+                .collect();
+            fixes.collect(error, span, diagnostics);
+        }
+
+        if fixes.actions.is_empty() {
+            return Ok(None);
+        }
+        let actions = fixes
+            .actions
+            .into_iter()
+            .map(|(title, edits, diagnostics)| {
+                CodeActionOrCommand::CodeAction(CodeAction {
+                    title,
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: (!diagnostics.is_empty()).then_some(diagnostics),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(HashMap::from([(uri.clone(), edits)])),
+                        document_changes: None,
+                        change_annotations: None,
+                    }),
+                    ..CodeAction::default()
+                })
+            })
+            .collect();
+        Ok(Some(actions))
+    })
+}
+
+/// The span of the item a fixable error is reported on
+fn error_span(error: &ResolverError) -> Option<Span> {
+    match error {
+        ResolverError::UnusedVariable { ident }
+        | ResolverError::UnusedImport { ident }
+        | ResolverError::NecessaryPub { ident }
+        | ResolverError::UnnecessaryPub { ident, .. }
+        | ResolverError::OracleMarkedAsConstrained { ident }
+        | ResolverError::PathResolutionError(PathResolutionError::Unresolved(ident)) => {
+            Some(ident.span())
+        }
+        ResolverError::VariableNotDeclared { span, .. } => Some(*span),
+        _ => None,
+    }
+}
+
+type QuickFix = (String, Vec<TextEdit>, Vec<Diagnostic>);
+
+/// Builds the quick fixes for the errors found in a single file
+struct QuickFixes<'a> {
+    def_maps: &'a BTreeMap<CrateId, CrateDefMap>,
+    crate_id: CrateId,
+    files: &'a FileMap,
+    file_id: FileId,
+    source: &'a str,
+    actions: Vec<QuickFix>,
+}
+
+impl<'a> QuickFixes<'a> {
+    fn collect(&mut self, error: &ResolverError, span: Span, diagnostics: Vec<Diagnostic>) {
+        let fixes = match error {
+            ResolverError::UnusedVariable { ident } => self
+                .insert(span.start(), "_")
+                .map(|edit| vec![(format!("Prefix `{ident}` with an underscore"), vec![edit])])
+                .unwrap_or_default(),
+            ResolverError::UnusedImport { ident } => self
+                .remove_import(span)
+                .map(|edit| vec![(format!("Remove unused import `{ident}`"), vec![edit])])
+                .unwrap_or_default(),
+            ResolverError::NecessaryPub { .. } => self
+                .return_type_start(span)
+                .and_then(|index| self.insert(index, "pub "))
+                .map(|edit| vec![("Mark the return type as `pub`".to_string(), vec![edit])])
+                .unwrap_or_default(),
+            ResolverError::UnnecessaryPub { position: PubPosition::ReturnType, .. } => self
+                .return_type_start(span)
+                .and_then(|index| self.remove_pub(index))
+                .map(|edit| vec![("Remove `pub` from the return type".to_string(), vec![edit])])
+                .unwrap_or_default(),
+            ResolverError::UnnecessaryPub { position: PubPosition::Parameter, .. } => {
+                let edits = self.remove_pub_from_parameters(span);
+                if edits.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![("Remove `pub` from the parameters".to_string(), edits)]
+                }
+            }
+            ResolverError::OracleMarkedAsConstrained { .. } => self
+                .modifiers_start(span)
+                .and_then(|index| self.insert(index, "unconstrained "))
+                .map(|edit| vec![("Mark the oracle as `unconstrained`".to_string(), vec![edit])])
+                .unwrap_or_default(),
+            ResolverError::PathResolutionError(PathResolutionError::Unresolved(ident)) => {
+                self.import_fixes(&ident.0.contents)
+            }
+            ResolverError::VariableNotDeclared { name, .. } => self.import_fixes(name),
+            _ => Vec::new(),
+        };
+
+        for (title, edits) in fixes {
+            // Errors such as `pub` on several parameters are reported once per occurrence
+            if !self
+                .actions
+                .iter()
+                .any(|(other_title, other_edits, _)| *other_title == title && *other_edits == edits)
+            {
+                self.actions.push((title, edits, diagnostics.clone()));
+            }
+        }
+    }
+
+    /// Offers to import each item named `name` which is visible from the current crate
+    fn import_fixes(&self, name: &str) -> Vec<(String, Vec<TextEdit>)> {
+        let edit_position = self.use_insertion_point();
+        let paths = self.find_importable_items(name);
+        paths
+            .into_iter()
+            .filter_map(|path| {
+                let edit = self.insert(edit_position, &format!("use {path};\n"))?;
+                Some((format!("Import `{path}`"), vec![edit]))
+            })
+            .collect()
+    }
+
+    /// Searches the current crate and its direct dependencies for items defined
+    /// with the given name, returning the paths they can be imported with.
+    fn find_importable_items(&self, name: &str) -> BTreeSet<String> {
+        let name = Ident::from(name);
+        let def_maps = self.def_maps;
+        let Some(current_def_map) = def_maps.get(&self.crate_id) else {
+            return BTreeSet::new();
+        };
+
+        let mut crates = vec![(self.crate_id, "crate".to_string())];
+        for (dependency_name, module_id) in current_def_map.extern_prelude() {
+            crates.push((module_id.krate, dependency_name.clone()));
+        }
+
+        let mut paths = BTreeSet::new();
+        for (krate, crate_path) in crates {
+            let Some(def_map) = def_maps.get(&krate) else { continue };
+            for (index, module) in def_map.modules().iter() {
+                let is_struct_namespace = module.parent.map_or(false, |parent| {
+                    is_struct_module(def_map, parent, LocalModuleId(index))
+                });
+                if is_struct_namespace {
+                    continue;
+                }
+
+                let scope = module.scope();
+                let definitions = scope.definitions();
+                let per_ns = scope.find_name_for_trait_id(&name, &None);
+                for (module_def_id, visibility, _) in per_ns.types.into_iter().chain(per_ns.values)
+                {
+                    // Only items declared in this module, not the ones it imports
+                    if !definitions.contains(&module_def_id)
+                        || (krate != self.crate_id && visibility != ItemVisibility::Public)
+                    {
+                        continue;
+                    }
+
+                    let module_path =
+                        def_map.get_module_path_with_separator(index, module.parent, "::");
+                    if module_path.is_empty() {
+                        paths.insert(format!("{crate_path}::{name}"));
+                    } else {
+                        paths.insert(format!("{crate_path}::{module_path}::{name}"));
+                    }
+                }
+            }
+        }
+        paths
+    }
+
+    /// New imports are added before the first top-level `use` of the file, or at its start
+    fn use_insertion_point(&self) -> u32 {
+        let mut offset = 0;
+        for line in self.source.split_inclusive('\n') {
+            if line.starts_with("use ") {
+                return offset as u32;
+            }
+            offset += line.len();
+        }
+        0
+    }
+
+    /// Removes the import of the item whose name (or alias) has the given span: the item and
+    /// its separating comma if it is imported in a group, or else the whole `use` statement
+    fn remove_import(&self, name_span: Span) -> Option<TextEdit> {
+        let before = self.source.get(..name_span.start() as usize)?;
+        let after = self.source.get(name_span.end() as usize..)?;
+
+        // The last character before the item's path, which is `{` or `,` in a group
+        let delimiter = before
+            .rfind(|char: char| !(is_ident_char(char) || char == ':' || char.is_whitespace()));
+        let use_keyword = before
+            .match_indices("use")
+            .map(|(index, _)| index)
+            .filter(|index| {
+                let preceded = before[..*index].ends_with(is_ident_char);
+                let followed = before[index + 3..].starts_with(char::is_whitespace);
+                !preceded && followed
+            })
+            .last()?;
+
+        if let Some(delimiter) = delimiter.filter(|delimiter| *delimiter > use_keyword) {
+            let item = &before[delimiter + 1..];
+            let item_start = (before.len() - item.trim_start().len()) as u32;
+            let rest = after.trim_start();
+            if let Some(rest) = rest.strip_prefix(',') {
+                let end = (self.source.len() - rest.trim_start().len()) as u32;
+                return self.replace(Span::from(item_start..end), "");
+            }
+            if before[delimiter..].starts_with(',') {
+                return self.replace(Span::from(delimiter as u32..name_span.end()), "");
+            }
+        }
+
+        // Remove the whole statement, along with its line if nothing else is on it
+        let mut start = use_keyword;
+        let line_start = before[..start].rfind('\n').map_or(0, |index| index + 1);
+        if before[line_start..start].trim().is_empty() {
+            start = line_start;
+        }
+        let mut end = name_span.end() as usize + after.find(';')? + 1;
+        let rest_of_line = &self.source[end..];
+        if let Some(newline) = rest_of_line.find('\n') {
+            if rest_of_line[..newline].trim().is_empty() {
+                end += newline + 1;
+            }
+        }
+        self.replace(Span::from(start as u32..end as u32), "")
+    }
+
+    /// Returns the index of the return type of the function whose name has the given span
+    fn return_type_start(&self, name_span: Span) -> Option<u32> {
+        let parameters_end = self.parameters_span(name_span)?.end() as usize + 1;
+        let rest = self.source.get(parameters_end..)?;
+        let after_arrow = rest.trim_start().strip_prefix("->")?;
+        let return_type = after_arrow.trim_start();
+        Some((self.source.len() - return_type.len()) as u32)
+    }
+
+    /// Returns the span between the parentheses of the parameter list of the function
+    /// whose name has the given span
+    fn parameters_span(&self, name_span: Span) -> Option<Span> {
+        let after_name = name_span.end() as usize;
+        let open_paren = after_name + self.source.get(after_name..)?.find('(')?;
+        let mut depth = 0;
+        for (index, char) in self.source[open_paren..].char_indices() {
+            match char {
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(Span::from(
+                            (open_paren + 1) as u32..(open_paren + index) as u32,
+                        ));
+                    }
+                }
+                _ => (),
+            }
+        }
+        None
+    }
+
+    /// Returns the index of the first modifier (`pub`, `comptime`) before the `fn`
+    /// keyword of the function whose name has the given span
+    fn modifiers_start(&self, name_span: Span) -> Option<u32> {
+        let before_name = self.source.get(..name_span.start() as usize)?.trim_end();
+        let mut before = before_name.strip_suffix("fn")?;
+        loop {
+            let trimmed = before.trim_end();
+            let modifier = ["comptime", "pub(crate)", "pub"].iter().find_map(|modifier| {
+                trimmed.strip_suffix(modifier).filter(|rest| !rest.ends_with(is_ident_char))
+            });
+            match modifier {
+                Some(rest) => before = rest,
+                None => break,
+            }
+        }
+        let modifiers = self.source[before.len()..].trim_start();
+        Some((self.source.len() - modifiers.len()) as u32)
+    }
+
+    /// Removes the `pub` keyword starting at `index` along with the whitespace following it
+    fn remove_pub(&self, index: u32) -> Option<TextEdit> {
+        let rest = self.source.get(index as usize..)?.strip_prefix("pub")?;
+        let after = rest.trim_start();
+        if after.len() == rest.len() {
+            return None;
+        }
+        let end = (self.source.len() - after.len()) as u32;
+        self.replace(Span::from(index..end), "")
+    }
+
+    fn remove_pub_from_parameters(&self, name_span: Span) -> Vec<TextEdit> {
+        let Some(parameters) = self.parameters_span(name_span) else {
+            return Vec::new();
+        };
+        let text = &self.source[parameters.start() as usize..parameters.end() as usize];
+        text.match_indices("pub")
+            .filter(|(index, _)| text[..*index].trim_end().ends_with(':'))
+            .filter_map(|(index, _)| self.remove_pub(parameters.start() + index as u32))
+            .collect()
+    }
+
+    fn insert(&self, index: u32, text: &str) -> Option<TextEdit> {
+        self.replace(Span::from(index..index), text)
+    }
+
+    fn replace(&self, span: Span, text: &str) -> Option<TextEdit> {
+        Some(TextEdit { range: self.range(span)?, new_text: text.to_string() })
+    }
+
+    fn range(&self, span: Span) -> Option<Range> {
+        byte_span_to_range(self.files, self.file_id, span.into())
+    }
+}
+
+fn is_ident_char(char: char) -> bool {
+    char.is_ascii_alphanumeric() || char == '_'
+}
+
+#[cfg(test)]
+mod code_action_tests {
+    use crate::test_utils::{self, search_in_file};
+
+    use super::*;
+    use lsp_types::{
+        CodeActionContext, PartialResultParams, Position, TextDocumentIdentifier,
+        WorkDoneProgressParams,
+    };
+    use tokio::test;
+
+    /// Returns the title and edits of the quick fixes for the given occurrence of a string
+    async fn quick_fixes_at(
+        search_string: &str,
+        occurrence: usize,
+    ) -> Vec<(String, Vec<TextEdit>)> {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("code_action").await;
+
+        let range = search_in_file(noir_text_document.path(), search_string)[occurrence];
+        let params = CodeActionParams {
+            text_document: TextDocumentIdentifier { uri: noir_text_document.clone() },
+            range,
+            context: CodeActionContext::default(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        let actions = on_code_action_request(&mut state, params)
+            .await
+            .expect("Could not execute on_code_action_request")
+            .unwrap_or_default();
+        actions
+            .into_iter()
+            .map(|action| {
+                let CodeActionOrCommand::CodeAction(action) = action else {
+                    panic!("Expected a code action");
+                };
+                let mut changes = action.edit.unwrap().changes.unwrap();
+                let edits = changes.remove(&noir_text_document).unwrap();
+                (action.title, edits)
+            })
+            .collect()
+    }
+
+    fn edit(line: u32, start: u32, end: u32, new_text: &str) -> TextEdit {
+        TextEdit {
+            range: Range {
+                start: Position { line, character: start },
+                end: Position { line, character: end },
+            },
+            new_text: new_text.to_string(),
+        }
+    }
+
+    #[test]
+    async fn test_prefix_unused_variable() {
+        let fixes = quick_fixes_at("unused", 0).await;
+        assert_eq!(
+            fixes,
+            vec![("Prefix `unused` with an underscore".to_string(), vec![edit(25, 8, 8, "_")])]
+        );
+    }
+
+    #[test]
+    async fn test_fix_pub_visibility() {
+        let fixes = quick_fixes_at("helper", 0).await;
+        assert_eq!(
+            fixes,
+            vec![
+                ("Remove `pub` from the return type".to_string(), vec![edit(24, 27, 31, "")]),
+                ("Remove `pub` from the parameters".to_string(), vec![edit(24, 13, 17, "")]),
+            ]
+        );
+
+        let fixes = quick_fixes_at("main", 0).await;
+        assert_eq!(
+            fixes,
+            vec![("Mark the return type as `pub`".to_string(), vec![edit(29, 21, 21, "pub ")])]
+        );
+    }
+
+    #[test]
+    async fn test_mark_oracle_unconstrained() {
+        let fixes = quick_fixes_at("get_secret()", 0).await;
+        assert_eq!(
+            fixes,
+            vec![(
+                "Mark the oracle as `unconstrained`".to_string(),
+                vec![edit(22, 0, 0, "unconstrained ")]
+            )]
+        );
+    }
+
+    #[test]
+    async fn test_import_unresolved_item() {
+        let fixes = quick_fixes_at("double(x)", 0).await;
+        assert_eq!(
+            fixes,
+            vec![(
+                "Import `crate::math::double`".to_string(),
+                vec![edit(0, 0, 0, "use crate::math::double;\n")]
+            )]
+        );
+    }
+
+    #[test]
+    async fn test_remove_unused_import() {
+        let fixes = quick_fixes_at("triple", 0).await;
+        assert_eq!(
+            fixes,
+            vec![("Remove unused import `triple`".to_string(), vec![edit(0, 16, 24, "")])]
+        );
+
+        let fixes = quick_fixes_at("square", 0).await;
+        let whole_line = TextEdit {
+            range: Range {
+                start: Position { line: 1, character: 0 },
+                end: Position { line: 2, character: 0 },
+            },
+            new_text: String::new(),
+        };
+        assert_eq!(fixes, vec![("Remove unused import `square`".to_string(), vec![whole_line])]);
+    }
+}
@@ -1,10 +1,22 @@
-use std::future::{self, Future};
+use std::{
+    collections::BTreeMap,
+    future::{self, Future},
+    path::Path,
+};
 
+use acvm::acir::circuit::OpcodeLocation;
 use async_lsp::{ErrorCode, ResponseError};
 
+use fm::{FileId, FileMap};
 use nargo::{package::Package, workspace::Workspace};
-use noirc_driver::check_crate;
-use noirc_frontend::hir::FunctionNameMatch;
+use noirc_artifacts::{contract::ContractArtifact, program::ProgramArtifact};
+use noirc_driver::{check_crate, DebugFile};
+use noirc_errors::{
+    debug_info::{DebugInfo, OpCodesCount},
+    Location,
+};
+use noirc_frontend::{hir::FunctionNameMatch, macros_api::NodeInterner};
+use serde::de::DeserializeOwned;
 
 use crate::{
     byte_span_to_range, prepare_source, resolve_workspace_for_source_path,
@@ -25,6 +37,9 @@ const EXECUTE_CODELENS_TITLE: &str = "Execute";
 const PROFILE_COMMAND: &str = "nargo.profile";
 const PROFILE_CODELENS_TITLE: &str = "Profile";
 
+/// The opcode counts of the functions in a file, in source order, as of the last compilation
+pub(crate) type FunctionOpcodeCounts = Vec<(String, OpCodesCount)>;
+
 fn with_arrow(title: &str) -> String {
     format!("{ARROW} {title}")
 }
@@ -69,8 +84,22 @@ fn on_code_lens_request_inner(
     // because we can still get the test functions even if compilation fails
     let _ = check_crate(&mut context, crate_id, false, false, false);
 
-    let collected_lenses =
+    let mut collected_lenses =
         collect_lenses_for_package(&context, crate_id, &workspace, package, None);
+    if let Some(opcode_counts) =
+        state.cached_opcode_counts.get(&params.text_document.uri.to_string())
+    {
+        let file_id = context.crate_graph[crate_id].root_file_id;
+        let files = context.file_manager.as_file_map();
+        collected_lenses.extend(collect_opcode_count_lenses(
+            &context.def_interner,
+            files,
+            file_id,
+            &workspace,
+            package,
+            opcode_counts,
+        ));
+    }
 
     if collected_lenses.is_empty() {
         state.cached_lenses.remove(&params.text_document.uri.to_string());
@@ -237,3 +266,173 @@ pub(crate) fn collect_lenses_for_package(
 
     lenses
 }
+
+/// Attributes the opcodes of the package's artifacts, as saved by the last command which compiled
+/// it (such as `nargo compile`), to the functions in `file_id` whose body contains their source
+/// location. As the locations of an opcode include its call stack, the counts of a function
+/// include those of the functions it calls, each opcode being counted once per function.
+/// Returns `None` if there are no artifacts or if the file changed since they were compiled.
+pub(crate) fn count_opcodes_in_file(
+    context: &noirc_frontend::macros_api::HirContext,
+    crate_id: noirc_frontend::macros_api::CrateId,
+    workspace: &Workspace,
+    package: &Package,
+    file_id: FileId,
+) -> Option<FunctionOpcodeCounts> {
+    let (debug_infos, file_map) = read_debug_artifacts(context, crate_id, workspace, package)?;
+
+    // The locations of the artifacts refer to the files of the compilation which produced them
+    let path = context.file_manager.path(file_id)?;
+    let Some((artifact_file_id, debug_file)) =
+        file_map.iter().find(|(_, debug_file)| debug_file.path == path)
+    else {
+        // Files which no opcode comes from are left out of the artifacts
+        return Some(Vec::new());
+    };
+    if Some(debug_file.source.as_str()) != context.file_manager.fetch_file(file_id) {
+        return None;
+    }
+
+    let interner = &context.def_interner;
+    let opcode_counts = interner
+        .functions_in_file(file_id)
+        .into_iter()
+        .filter_map(|func_id| {
+            let body_span = interner.function_body_location(&func_id)?.span;
+            let body_location = Location::new(body_span, *artifact_file_id);
+            let count = count_opcodes_in_body(body_location, &debug_infos);
+
+            // Functions which weren't compiled into the program have no cost to report
+            if count.acir_size == 0 && count.brillig_size == 0 {
+                return None;
+            }
+            Some((interner.function_name(&func_id).to_string(), count))
+        })
+        .collect();
+    Some(opcode_counts)
+}
+
+/// Counts the opcodes with a location (of their call stack) in the given function body.
+/// Opcodes with several locations in the body, such as those of a lambda called where it's
+/// defined, are only counted once.
+fn count_opcodes_in_body(body_location: Location, debug_infos: &[DebugInfo]) -> OpCodesCount {
+    let mut count = OpCodesCount::default();
+    for debug_info in debug_infos {
+        for (opcode_location, locations) in &debug_info.locations {
+            if !locations.iter().any(|location| body_location.contains(location)) {
+                continue;
+            }
+            match opcode_location {
+                OpcodeLocation::Acir(_) => count.acir_size += 1,
+                OpcodeLocation::Brillig { .. } => count.brillig_size += 1,
+            }
+        }
+    }
+    count
+}
+
+/// Reads the debug information of the package's artifacts, along with the files it refers to
+fn read_debug_artifacts(
+    context: &noirc_frontend::macros_api::HirContext,
+    crate_id: noirc_frontend::macros_api::CrateId,
+    workspace: &Workspace,
+    package: &Package,
+) -> Option<(Vec<DebugInfo>, BTreeMap<FileId, DebugFile>)> {
+    if package.is_binary() {
+        let program: ProgramArtifact = read_artifact(&workspace.package_build_path(package))?;
+        Some((program.debug_symbols.debug_infos, program.file_map))
+    } else if package.is_contract() {
+        let mut debug_infos = Vec::new();
+        let mut file_map = BTreeMap::new();
+        for contract in context.get_all_contracts(&crate_id) {
+            let artifact_name = format!("{}-{}", package.name, contract.name);
+            let artifact_path =
+                workspace.target_directory_path().join(artifact_name).with_extension("json");
+            let contract: ContractArtifact = read_artifact(&artifact_path)?;
+            debug_infos.extend(
+                contract
+                    .functions
+                    .into_iter()
+                    .flat_map(|function| function.debug_symbols.debug_infos),
+            );
+            file_map.extend(contract.file_map);
+        }
+        Some((debug_infos, file_map))
+    } else {
+        None
+    }
+}
+
+fn read_artifact<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let artifact = std::fs::read(path).ok()?;
+    serde_json::from_slice(&artifact).ok()
+}
+
+/// Shows the opcode counts of the last compilation above each function of the file.
+/// Functions are matched by name and by their position among functions of the same name,
+/// so that the counts stay on the right function while the file is being edited.
+/// Clicking a count profiles the package.
+pub(crate) fn collect_opcode_count_lenses(
+    interner: &NodeInterner,
+    files: &FileMap,
+    file_id: FileId,
+    workspace: &Workspace,
+    package: &Package,
+    opcode_counts: &FunctionOpcodeCounts,
+) -> Vec<CodeLens> {
+    let mut seen_names: Vec<&str> = Vec::new();
+    let mut lenses = Vec::new();
+    for func_id in interner.functions_in_file(file_id) {
+        let name = interner.function_name(&func_id);
+        let occurrence = seen_names.iter().filter(|seen| **seen == name).count();
+        seen_names.push(name);
+
+        let Some((_, count)) =
+            opcode_counts.iter().filter(|(counted_name, _)| counted_name == name).nth(occurrence)
+        else {
+            continue;
+        };
+
+        let location = interner.function_modifiers(&func_id).name_location;
+        let range = byte_span_to_range(files, file_id, location.span.into()).unwrap_or_default();
+        let command = Command {
+            title: format!(
+                "ACIR opcodes: {}, Brillig opcodes: {}",
+                count.acir_size, count.brillig_size
+            ),
+            command: PROFILE_COMMAND.into(),
+            arguments: Some(package_selection_args(workspace, package)),
+        };
+        lenses.push(CodeLens { range, command: Some(command), data: None });
+    }
+    lenses
+}
+
+#[cfg(test)]
+mod code_lens_tests {
+    use super::*;
+    use noirc_errors::Span;
+
+    #[test]
+    fn counts_each_opcode_once_per_function() {
+        let file = FileId::default();
+        let body = Location::new(Span::from(10..100), file);
+        let lambda_definition = Location::new(Span::from(20..30), file);
+        let lambda_call = Location::new(Span::from(40..50), file);
+        let elsewhere = Location::new(Span::from(200..210), file);
+
+        let locations = BTreeMap::from([
+            (OpcodeLocation::Acir(0), vec![lambda_call, lambda_definition]),
+            (OpcodeLocation::Acir(1), vec![elsewhere]),
+            (
+                OpcodeLocation::Brillig { acir_index: 2, brillig_index: 0 },
+                vec![lambda_call, lambda_definition],
+            ),
+        ]);
+        let debug_info =
+            DebugInfo::new(locations, Default::default(), Default::default(), Default::default());
+
+        let count = count_opcodes_in_body(body, &[debug_info]);
+        assert_eq!((count.acir_size, count.brillig_size), (1, 1));
+    }
+}
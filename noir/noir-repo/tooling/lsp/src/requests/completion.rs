@@ -0,0 +1,679 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    future::{self, Future},
+};
+
+use async_lsp::{ErrorCode, ResponseError};
+use fm::codespan_files::Files;
+use lsp_types::{CompletionItem, CompletionItemKind, CompletionParams, CompletionResponse};
+use noirc_errors::{Location, Span};
+use noirc_frontend::{
+    ast::{Ident, ItemVisibility},
+    graph::CrateId,
+    hir::def_map::{CrateDefMap, LocalModuleId, ModuleData, ModuleDefId, ModuleId},
+    hir_def::{expr::HirExpression, stmt::HirPattern, stmt::HirStatement},
+    macros_api::NodeInterner,
+    node_interner::{DefinitionId, ExprId, FuncId, StructId},
+    Type,
+};
+
+use crate::LspState;
+
+use super::{hover::format_function, position_to_byte_index, process_document};
+
+/// Keywords offered when completing a plain identifier
+const KEYWORDS: &[&str] = &[
+    "as",
+    "assert",
+    "assert_eq",
+    "break",
+    "comptime",
+    "continue",
+    "contract",
+    "crate",
+    "dep",
+    "else",
+    "false",
+    "fn",
+    "for",
+    "global",
+    "if",
+    "impl",
+    "in",
+    "let",
+    "mod",
+    "mut",
+    "pub",
+    "struct",
+    "super",
+    "trait",
+    "true",
+    "type",
+    "unconstrained",
+    "use",
+    "where",
+];
+
+/// Attribute names offered after `#[`
+const ATTRIBUTES: &[&str] = &[
+    "abi",
+    "builtin",
+    "contract_library_method",
+    "deprecated",
+    "export",
+    "field",
+    "fold",
+    "foreign",
+    "no_predicates",
+    "oracle",
+    "recursive",
+    "test",
+];
+
+pub(crate) fn on_completion_request(
+    state: &mut LspState,
+    params: CompletionParams,
+) -> impl Future<Output = Result<Option<CompletionResponse>, ResponseError>> {
+    future::ready(on_completion_inner(state, params))
+}
+
+fn on_completion_inner(
+    state: &mut LspState,
+    params: CompletionParams,
+) -> Result<Option<CompletionResponse>, ResponseError> {
+    let text_document_position = params.text_document_position;
+    let uri = text_document_position.text_document.uri;
+
+    process_document(state, &uri, |context, crate_id, file_id| {
+        let files = context.file_manager.as_file_map();
+        let byte_index = position_to_byte_index(files, file_id, &text_document_position.position)
+            .map_err(|err| {
+            ResponseError::new(
+                ErrorCode::REQUEST_FAILED,
+                format!("Could not convert position to byte index. Error: {:?}", err),
+            )
+        })?;
+        let source = files.source(file_id).map_err(|err| {
+            ResponseError::new(ErrorCode::REQUEST_FAILED, format!("Could not read file: {err:?}"))
+        })?;
+
+        let location = Location::new(Span::single_char(byte_index as u32), file_id);
+        let completion = Completion {
+            def_maps: &context.def_maps,
+            interner: &context.def_interner,
+            crate_id,
+            location,
+            function: context.def_interner.find_function_containing(location),
+        };
+
+        let items = completion.complete(&source[..byte_index]);
+        Ok(Some(CompletionResponse::Array(items)))
+    })
+}
+
+/// The state needed to compute completions at a given location
+struct Completion<'a> {
+    def_maps: &'a BTreeMap<CrateId, CrateDefMap>,
+    interner: &'a NodeInterner,
+    crate_id: CrateId,
+    location: Location,
+    /// The function whose body contains the cursor, if any
+    function: Option<FuncId>,
+}
+
+impl<'a> Completion<'a> {
+    /// Computes the completion items given the source text that precedes the cursor
+    fn complete(&self, text: &str) -> Vec<CompletionItem> {
+        let rest = text.trim_end_matches(is_ident_char);
+        let prefix = &text[rest.len()..];
+
+        let mut items: Vec<CompletionItem> = if rest.ends_with("#[") {
+            ATTRIBUTES.iter().map(|name| simple_item(name, CompletionItemKind::KEYWORD)).collect()
+        } else if let Some(path) = rest.strip_suffix("::") {
+            self.complete_path(&split_trailing(path, "::"))
+        } else if rest.ends_with('.') && !rest.ends_with("..") {
+            self.complete_member(&split_trailing(&rest[..rest.len() - 1], "."))
+        } else if rest.ends_with(char::is_whitespace) && ends_with_word(rest.trim_end(), "use") {
+            self.complete_use()
+        } else {
+            self.complete_identifier()
+        };
+
+        items.retain(|item| item.label.starts_with(prefix));
+        let mut seen = HashSet::new();
+        items.retain(|item| seen.insert(item.label.clone()));
+        items
+    }
+
+    /// Completes `a::b::` by resolving the path relative to the current module
+    fn complete_path(&self, segments: &[&str]) -> Vec<CompletionItem> {
+        let mut items = Vec::new();
+        match self.resolve_path(segments) {
+            Some(ModuleDefId::ModuleId(module_id)) => {
+                let include_private = module_id.krate == self.crate_id;
+                self.push_module_items(
+                    module_id.module(self.def_maps),
+                    include_private,
+                    &mut items,
+                );
+            }
+            Some(ModuleDefId::TypeId(struct_id)) => {
+                self.push_struct_methods(struct_id, false, &mut items);
+            }
+            _ => (),
+        }
+        items
+    }
+
+    /// Completes `a.b.` with the fields and methods of the type of `a.b`
+    fn complete_member(&self, segments: &[&str]) -> Vec<CompletionItem> {
+        let mut items = Vec::new();
+        let Some((first, members)) = segments.split_first() else {
+            return items;
+        };
+        let Some(local) =
+            self.locals().into_iter().rev().find(|id| self.interner.definition_name(*id) == *first)
+        else {
+            return items;
+        };
+
+        let mut typ = self.interner.definition_type(local);
+        for member in members {
+            match member_type(&typ, member) {
+                Some(member_type) => typ = member_type,
+                None => return items,
+            }
+        }
+
+        match dereference(typ) {
+            Type::Struct(struct_type, generics) => {
+                let struct_id = struct_type.borrow().id;
+                for (name, typ) in struct_type.borrow().get_fields(&generics) {
+                    items.push(CompletionItem {
+                        detail: Some(typ.to_string()),
+                        ..simple_item(&name, CompletionItemKind::FIELD)
+                    });
+                }
+                self.push_struct_methods(struct_id, true, &mut items);
+            }
+            Type::Tuple(types) => {
+                for (index, typ) in types.iter().enumerate() {
+                    items.push(CompletionItem {
+                        detail: Some(typ.to_string()),
+                        ..simple_item(&index.to_string(), CompletionItemKind::FIELD)
+                    });
+                }
+            }
+            _ => (),
+        }
+        items
+    }
+
+    /// Completes the first segment of a `use` path
+    fn complete_use(&self) -> Vec<CompletionItem> {
+        let mut items = vec![
+            simple_item("crate", CompletionItemKind::KEYWORD),
+            simple_item("dep", CompletionItemKind::KEYWORD),
+            simple_item("super", CompletionItemKind::KEYWORD),
+        ];
+        for name in self.def_maps[&self.crate_id].extern_prelude().keys() {
+            items.push(simple_item(name, CompletionItemKind::MODULE));
+        }
+        self.push_module_items(self.current_module().module(self.def_maps), true, &mut items);
+        items
+    }
+
+    /// Completes a plain identifier with the locals in scope, the items visible in the
+    /// current module and the language keywords
+    fn complete_identifier(&self) -> Vec<CompletionItem> {
+        let mut items = Vec::new();
+        for local in self.locals().into_iter().rev() {
+            items.push(CompletionItem {
+                detail: Some(self.interner.definition_type(local).to_string()),
+                ..simple_item(self.interner.definition_name(local), CompletionItemKind::VARIABLE)
+            });
+        }
+        self.push_module_items(self.current_module().module(self.def_maps), true, &mut items);
+        for name in self.def_maps[&self.crate_id].extern_prelude().keys() {
+            items.push(simple_item(name, CompletionItemKind::MODULE));
+        }
+        items.extend(KEYWORDS.iter().map(|name| simple_item(name, CompletionItemKind::KEYWORD)));
+        items
+    }
+
+    fn resolve_path(&self, segments: &[&str]) -> Option<ModuleDefId> {
+        let current_module = self.current_module();
+        let (first, rest) = segments.split_first()?;
+
+        let mut module_id = match *first {
+            "crate" => {
+                ModuleId { krate: self.crate_id, local_id: self.def_maps[&self.crate_id].root() }
+            }
+            "super" => {
+                let parent = current_module.module(self.def_maps).parent?;
+                ModuleId { krate: self.crate_id, local_id: parent }
+            }
+            "dep" => return self.resolve_dependency_path(rest),
+            name => match find_in_module(current_module.module(self.def_maps), name) {
+                Some(ModuleDefId::ModuleId(module_id)) => module_id,
+                Some(ModuleDefId::TypeId(struct_id)) if rest.is_empty() => {
+                    return Some(ModuleDefId::TypeId(struct_id))
+                }
+                Some(_) => return None,
+                None => *self.def_maps[&self.crate_id].extern_prelude().get(name)?,
+            },
+        };
+
+        for (index, segment) in rest.iter().enumerate() {
+            match find_in_module(module_id.module(self.def_maps), segment)? {
+                ModuleDefId::ModuleId(id) => module_id = id,
+                ModuleDefId::TypeId(struct_id) if index == rest.len() - 1 => {
+                    return Some(ModuleDefId::TypeId(struct_id))
+                }
+                _ => return None,
+            }
+        }
+        Some(ModuleDefId::ModuleId(module_id))
+    }
+
+    /// Resolves a `dep::a::b` path, where the first segment is the dependency name
+    fn resolve_dependency_path(&self, segments: &[&str]) -> Option<ModuleDefId> {
+        let (first, rest) = segments.split_first()?;
+        let mut module_id = *self.def_maps[&self.crate_id].extern_prelude().get(*first)?;
+        for segment in rest {
+            match find_in_module(module_id.module(self.def_maps), segment)? {
+                ModuleDefId::ModuleId(id) => module_id = id,
+                _ => return None,
+            }
+        }
+        Some(ModuleDefId::ModuleId(module_id))
+    }
+
+    /// The module the cursor is in. Inside a function this is the module that declares
+    /// the function, otherwise it is the top-level module of the current file.
+    fn current_module(&self) -> ModuleId {
+        let def_map = &self.def_maps[&self.crate_id];
+        let module_id = |local_id| ModuleId { krate: self.crate_id, local_id };
+
+        if let Some(func_id) = self.function {
+            let declaring_module = def_map.modules().iter().find(|(_, module)| {
+                module.value_definitions().any(|id| id == ModuleDefId::FunctionId(func_id))
+            });
+            if let Some((index, module)) = declaring_module {
+                // Methods are declared in the module of their struct, which is only
+                // a namespace; items are resolved in the parent module instead.
+                let local_id = LocalModuleId(index);
+                return match module.parent {
+                    Some(parent) if is_struct_module(def_map, parent, local_id) => {
+                        module_id(parent)
+                    }
+                    _ => module_id(local_id),
+                };
+            }
+        }
+
+        let file_module = def_map.modules().iter().find(|(_, module)| {
+            module.location.file == self.location.file
+                && module
+                    .parent
+                    .map_or(true, |parent| def_map.file_id(parent) != self.location.file)
+        });
+        match file_module {
+            Some((index, _)) => module_id(LocalModuleId(index)),
+            None => module_id(def_map.root()),
+        }
+    }
+
+    /// Returns the local variables in scope at the cursor, in declaration order
+    fn locals(&self) -> Vec<DefinitionId> {
+        let mut locals = Vec::new();
+        let Some(func_id) = self.function else {
+            return locals;
+        };
+        for (pattern, _, _) in self.interner.function_meta(&func_id).parameters.iter() {
+            collect_pattern_definitions(pattern, &mut locals);
+        }
+        self.collect_expression_locals(self.interner.function(&func_id).as_expr(), &mut locals);
+
+        // Later definitions shadow earlier ones with the same name
+        let mut seen = HashSet::new();
+        let mut locals: Vec<_> = locals
+            .into_iter()
+            .rev()
+            .filter(|id| seen.insert(self.interner.definition_name(*id).to_string()))
+            .collect();
+        locals.reverse();
+        locals
+    }
+
+    /// Collects the definitions visible at the cursor, descending only into the
+    /// expressions which contain it.
+    fn collect_expression_locals(&self, expr_id: ExprId, locals: &mut Vec<DefinitionId>) {
+        if !self.interner.expr_span(&expr_id).contains(&self.location.span) {
+            return;
+        }
+
+        match self.interner.expression(&expr_id) {
+            HirExpression::Block(block) | HirExpression::Comptime(block) => {
+                for statement in block.statements() {
+                    let span = self.interner.statement_span(*statement);
+                    if span.start() > self.location.span.start() {
+                        break;
+                    }
+                    self.collect_statement_locals(
+                        self.interner.statement(statement),
+                        span.contains(&self.location.span),
+                        locals,
+                    );
+                }
+            }
+            HirExpression::If(if_expr) => {
+                self.collect_expression_locals(if_expr.consequence, locals);
+                if let Some(alternative) = if_expr.alternative {
+                    self.collect_expression_locals(alternative, locals);
+                }
+            }
+            HirExpression::Match(match_expr) => {
+                // A rule's bindings are only in scope within its own branch
+                for (pattern, branch) in &match_expr.rules {
+                    if self.interner.expr_span(branch).contains(&self.location.span) {
+                        collect_pattern_definitions(pattern, locals);
+                        self.collect_expression_locals(*branch, locals);
+                    }
+                }
+            }
+            HirExpression::Lambda(lambda) => {
+                for (pattern, _) in &lambda.parameters {
+                    collect_pattern_definitions(pattern, locals);
+                }
+                self.collect_expression_locals(lambda.body, locals);
+            }
+            HirExpression::Call(call) => {
+                for argument in call.arguments {
+                    self.collect_expression_locals(argument, locals);
+                }
+            }
+            HirExpression::MethodCall(call) => {
+                for argument in call.arguments {
+                    self.collect_expression_locals(argument, locals);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn collect_statement_locals(
+        &self,
+        statement: HirStatement,
+        contains_cursor: bool,
+        locals: &mut Vec<DefinitionId>,
+    ) {
+        match statement {
+            // A `let` only brings its pattern into scope once the statement is complete
+            HirStatement::Let(let_statement) if contains_cursor => {
+                self.collect_expression_locals(let_statement.expression, locals);
+            }
+            HirStatement::Let(let_statement) => {
+                collect_pattern_definitions(&let_statement.pattern, locals);
+            }
+            HirStatement::For(for_statement) if contains_cursor => {
+                locals.push(for_statement.identifier.id);
+                self.collect_expression_locals(for_statement.block, locals);
+            }
+            HirStatement::Assign(assign) => {
+                self.collect_expression_locals(assign.expression, locals);
+            }
+            HirStatement::Expression(expr_id) | HirStatement::Semi(expr_id) => {
+                self.collect_expression_locals(expr_id, locals);
+            }
+            _ => (),
+        }
+    }
+
+    fn push_module_items(
+        &self,
+        module: &ModuleData,
+        include_private: bool,
+        items: &mut Vec<CompletionItem>,
+    ) {
+        let scope = module.scope();
+        for (name, entries) in scope.types().iter().chain(scope.values()) {
+            for (trait_id, (module_def_id, visibility, _)) in entries {
+                // Trait methods are only reachable through the trait itself
+                if trait_id.is_some()
+                    || (!include_private && *visibility == ItemVisibility::Private)
+                {
+                    continue;
+                }
+                items.push(self.module_def_item(&name.to_string(), *module_def_id));
+            }
+        }
+    }
+
+    fn module_def_item(&self, name: &str, module_def_id: ModuleDefId) -> CompletionItem {
+        match module_def_id {
+            ModuleDefId::ModuleId(_) => simple_item(name, CompletionItemKind::MODULE),
+            ModuleDefId::FunctionId(func_id) => CompletionItem {
+                detail: Some(format_function(func_id, self.interner)),
+                ..simple_item(name, CompletionItemKind::FUNCTION)
+            },
+            ModuleDefId::TypeId(_) => simple_item(name, CompletionItemKind::STRUCT),
+            ModuleDefId::TypeAliasId(_) => simple_item(name, CompletionItemKind::CLASS),
+            ModuleDefId::TraitId(_) => simple_item(name, CompletionItemKind::INTERFACE),
+            ModuleDefId::GlobalId(global_id) => {
+                let global = self.interner.get_global(global_id);
+                CompletionItem {
+                    detail: Some(self.interner.definition_type(global.definition_id).to_string()),
+                    ..simple_item(name, CompletionItemKind::CONSTANT)
+                }
+            }
+        }
+    }
+
+    /// Pushes the methods of a struct, including the ones from trait impls. When
+    /// `self_only` is set, associated functions without a `self` parameter are skipped.
+    fn push_struct_methods(
+        &self,
+        struct_id: StructId,
+        self_only: bool,
+        items: &mut Vec<CompletionItem>,
+    ) {
+        for methods in self.interner.get_struct_methods(struct_id) {
+            for func_id in methods.direct.iter().chain(&methods.trait_impl_methods) {
+                if self_only && !self.has_self_parameter(*func_id) {
+                    continue;
+                }
+                items.push(CompletionItem {
+                    detail: Some(format_function(*func_id, self.interner)),
+                    ..simple_item(self.interner.function_name(func_id), CompletionItemKind::METHOD)
+                });
+            }
+        }
+    }
+
+    fn has_self_parameter(&self, func_id: FuncId) -> bool {
+        let func_meta = self.interner.function_meta(&func_id);
+        let Some((pattern, _, _)) = func_meta.parameters.iter().next() else {
+            return false;
+        };
+        let mut definitions = Vec::new();
+        collect_pattern_definitions(pattern, &mut definitions);
+        matches!(definitions.as_slice(), [id] if self.interner.definition_name(*id) == "self")
+    }
+}
+
+fn simple_item(label: &str, kind: CompletionItemKind) -> CompletionItem {
+    CompletionItem { label: label.to_string(), kind: Some(kind), ..CompletionItem::default() }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn ends_with_word(text: &str, word: &str) -> bool {
+    text.strip_suffix(word).map_or(false, |before| !before.ends_with(is_ident_char))
+}
+
+/// Splits the identifiers separated by `separator` at the end of `text`,
+/// e.g. `let x = foo::bar` gives `["foo", "bar"]` for `::`.
+fn split_trailing<'t>(text: &'t str, separator: &str) -> Vec<&'t str> {
+    let mut segments = Vec::new();
+    let mut rest = text;
+    loop {
+        let trimmed = rest.trim_end_matches(is_ident_char);
+        let segment = &rest[trimmed.len()..];
+        if segment.is_empty() {
+            break;
+        }
+        segments.push(segment);
+        match trimmed.strip_suffix(separator) {
+            Some(trimmed) => rest = trimmed,
+            None => break,
+        }
+    }
+    segments.reverse();
+    segments
+}
+
+fn find_in_module(module: &ModuleData, name: &str) -> Option<ModuleDefId> {
+    let per_ns = module.find_name(&Ident::from(name));
+    per_ns.types.or(per_ns.values).map(|(id, _, _)| id)
+}
+
+/// Returns true if `module` is the namespace module created for a struct declared in `parent`
+pub(super) fn is_struct_module(
+    def_map: &CrateDefMap,
+    parent: LocalModuleId,
+    module: LocalModuleId,
+) -> bool {
+    def_map.modules()[parent.0].type_definitions().any(
+        |id| matches!(id, ModuleDefId::TypeId(struct_id) if struct_id.local_module_id() == module),
+    )
+}
+
+fn collect_pattern_definitions(pattern: &HirPattern, definitions: &mut Vec<DefinitionId>) {
+    match pattern {
+        HirPattern::Identifier(ident) => definitions.push(ident.id),
+        HirPattern::Mutable(pattern, _) => collect_pattern_definitions(pattern, definitions),
+        HirPattern::Tuple(patterns, _) => {
+            for pattern in patterns {
+                collect_pattern_definitions(pattern, definitions);
+            }
+        }
+        HirPattern::Struct(_, fields, _) => {
+            for (_, pattern) in fields {
+                collect_pattern_definitions(pattern, definitions);
+            }
+        }
+        HirPattern::Variant(_, _, patterns, _) => {
+            for pattern in patterns {
+                collect_pattern_definitions(pattern, definitions);
+            }
+        }
+    }
+}
+
+fn dereference(typ: Type) -> Type {
+    match typ.follow_bindings() {
+        Type::MutableReference(element) => dereference(*element),
+        typ => typ,
+    }
+}
+
+/// The type of the field or tuple element `member` of `typ`
+fn member_type(typ: &Type, member: &str) -> Option<Type> {
+    match dereference(typ.clone()) {
+        Type::Struct(struct_type, generics) => struct_type
+            .borrow()
+            .get_fields(&generics)
+            .into_iter()
+            .find_map(|(name, typ)| (name == member).then_some(typ)),
+        Type::Tuple(types) => types.get(member.parse::<usize>().ok()?).cloned(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod completion_tests {
+    use crate::test_utils;
+
+    use super::*;
+    use lsp_types::{Position, TextDocumentIdentifier, TextDocumentPositionParams};
+    use tokio::test;
+
+    /// Inserts `text` on a new line right before the first line containing `before`
+    /// and requests completions at the end of the inserted text.
+    async fn complete_after(text: &str, before: &str) -> Vec<String> {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("completion").await;
+
+        let file_path = noir_text_document.to_file_path().unwrap();
+        let source = std::fs::read_to_string(file_path).unwrap();
+        let mut lines: Vec<&str> = source.lines().collect();
+        let line = lines.iter().position(|line| line.contains(before)).unwrap();
+        lines.insert(line, text);
+        state.input_files.insert(noir_text_document.to_string(), lines.join("\n"));
+
+        let params = CompletionParams {
+            text_document_position: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: noir_text_document },
+                position: Position { line: line as u32, character: text.len() as u32 },
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+            context: None,
+        };
+
+        let response = on_completion_request(&mut state, params)
+            .await
+            .expect("Could not execute on_completion_request")
+            .expect("Expected a completion response");
+
+        match response {
+            CompletionResponse::Array(items) => items.into_iter().map(|item| item.label).collect(),
+            CompletionResponse::List(list) => {
+                list.items.into_iter().map(|item| item.label).collect()
+            }
+        }
+    }
+
+    #[test]
+    async fn test_complete_struct_fields_and_methods() {
+        let labels = complete_after("    let value = point.", "assert(point.x").await;
+        assert!(labels.contains(&"x".to_string()));
+        assert!(labels.contains(&"y".to_string()));
+        assert!(labels.contains(&"length_squared".to_string()));
+        assert!(!labels.contains(&"new".to_string()));
+    }
+
+    #[test]
+    async fn test_complete_locals_in_scope() {
+        let labels = complete_after("    let value = ", "assert(point.x").await;
+        assert!(labels.contains(&"offset".to_string()));
+        assert!(labels.contains(&"scale".to_string()));
+        assert!(labels.contains(&"let".to_string()));
+        assert!(!labels.contains(&"length".to_string()));
+    }
+
+    #[test]
+    async fn test_complete_module_path() {
+        let labels = complete_after("    let value = geometry::", "assert(point.x").await;
+        assert!(labels.contains(&"Point".to_string()));
+        assert!(labels.contains(&"origin".to_string()));
+    }
+
+    #[test]
+    async fn test_complete_use_path() {
+        let labels = complete_after("use ", "use geometry::Point").await;
+        assert!(labels.contains(&"geometry".to_string()));
+        assert!(labels.contains(&"std".to_string()));
+        assert!(labels.contains(&"crate".to_string()));
+    }
+
+    #[test]
+    async fn test_complete_attribute() {
+        let labels = complete_after("#[", "fn main").await;
+        assert!(labels.contains(&"test".to_string()));
+        assert!(labels.contains(&"oracle".to_string()));
+        assert!(labels.contains(&"recursive".to_string()));
+    }
+}
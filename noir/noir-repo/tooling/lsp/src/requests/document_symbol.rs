@@ -0,0 +1,440 @@
+use std::{
+    collections::HashSet,
+    future::{self, Future},
+};
+
+use async_lsp::{ErrorCode, LanguageClient, ResponseError};
+use fm::codespan_files::SimpleFile;
+use lsp_types::{
+    DocumentSymbol, DocumentSymbolParams, DocumentSymbolResponse, LogMessageParams, MessageType,
+    SymbolInformation, SymbolKind, WorkspaceSymbolParams, WorkspaceSymbolResponse,
+};
+use nargo::insert_all_files_for_workspace_into_file_manager;
+use nargo_toml::{find_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_driver::{check_crate, file_manager_with_stdlib, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_errors::Span;
+use noirc_frontend::{
+    ast::{FunctionReturnType, Ident, NoirFunction, Pattern, TraitImplItem, TraitItem},
+    hir::def_map::ModuleDefId,
+    macros_api::NodeInterner,
+    node_interner::ReferenceId,
+    parser::{ItemKind, ParsedModule},
+};
+
+use crate::{byte_span_to_range, parse_diff, LspState};
+
+use super::to_lsp_location;
+
+pub(crate) fn on_document_symbol_request(
+    state: &mut LspState,
+    params: DocumentSymbolParams,
+) -> impl Future<Output = Result<Option<DocumentSymbolResponse>, ResponseError>> {
+    future::ready(on_document_symbol_inner(state, params))
+}
+
+fn on_document_symbol_inner(
+    state: &LspState,
+    params: DocumentSymbolParams,
+) -> Result<Option<DocumentSymbolResponse>, ResponseError> {
+    let uri = params.text_document.uri;
+    let source = match state.input_files.get(&uri.to_string()) {
+        Some(source) => source.clone(),
+        None => {
+            let file_path = uri.to_file_path().map_err(|_| {
+                ResponseError::new(ErrorCode::REQUEST_FAILED, "URI is not a valid file path")
+            })?;
+            std::fs::read_to_string(file_path).map_err(|err| {
+                ResponseError::new(ErrorCode::REQUEST_FAILED, format!("Could not read file: {err}"))
+            })?
+        }
+    };
+
+    // The outline is built from whatever the parser recovered, so it stays
+    // available while the file has syntax errors.
+    let (parsed_module, _errors) = noirc_frontend::parse_program(&source);
+    let file = SimpleFile::new(uri.to_string(), source.as_str());
+    let symbols = DocumentSymbolCollector { file: &file }.module_symbols(&parsed_module);
+
+    Ok(Some(DocumentSymbolResponse::Nested(symbols)))
+}
+
+struct DocumentSymbolCollector<'a> {
+    file: &'a SimpleFile<String, &'a str>,
+}
+
+impl<'a> DocumentSymbolCollector<'a> {
+    fn module_symbols(&self, module: &ParsedModule) -> Vec<DocumentSymbol> {
+        let mut symbols = Vec::new();
+        for item in &module.items {
+            let symbol = match &item.kind {
+                ItemKind::Function(function) => self.function_symbol(function, item.span),
+                ItemKind::Struct(noir_struct) => {
+                    let fields = noir_struct
+                        .fields
+                        .iter()
+                        .filter_map(|(name, typ)| {
+                            let span = typ.span.map_or(name.span(), |span| name.span().merge(span));
+                            self.symbol(name, SymbolKind::FIELD, Some(typ.to_string()), span, None)
+                        })
+                        .collect();
+                    self.symbol(
+                        &noir_struct.name,
+                        SymbolKind::STRUCT,
+                        None,
+                        item.span,
+                        Some(fields),
+                    )
+                }
+                ItemKind::Enum(noir_enum) => {
+                    let variants = noir_enum
+                        .variants
+                        .iter()
+                        .filter_map(|variant| {
+                            let name = &variant.name;
+                            let last_span = variant.parameters.last().and_then(|typ| typ.span);
+                            let span =
+                                last_span.map_or(name.span(), |span| name.span().merge(span));
+                            self.symbol(name, SymbolKind::ENUM_MEMBER, None, span, None)
+                        })
+                        .collect();
+                    self.symbol(&noir_enum.name, SymbolKind::ENUM, None, item.span, Some(variants))
+                }
+                ItemKind::Trait(noir_trait) => {
+                    let items = noir_trait
+                        .items
+                        .iter()
+                        .filter_map(|trait_item| match trait_item {
+                            TraitItem::Function { name, .. } => {
+                                self.symbol(name, SymbolKind::METHOD, None, name.span(), None)
+                            }
+                            TraitItem::Constant { name, typ, .. } => self.symbol(
+                                name,
+                                SymbolKind::CONSTANT,
+                                Some(typ.to_string()),
+                                name.span(),
+                                None,
+                            ),
+                            TraitItem::Type { name } => self.symbol(
+                                name,
+                                SymbolKind::TYPE_PARAMETER,
+                                None,
+                                name.span(),
+                                None,
+                            ),
+                        })
+                        .collect();
+                    self.symbol(
+                        &noir_trait.name,
+                        SymbolKind::INTERFACE,
+                        None,
+                        item.span,
+                        Some(items),
+                    )
+                }
+                ItemKind::Impl(type_impl) => {
+                    let methods = type_impl
+                        .methods
+                        .iter()
+                        .filter_map(|(method, span)| self.function_symbol(method, *span))
+                        .collect();
+                    let name =
+                        Ident::new(format!("impl {}", type_impl.object_type), type_impl.type_span);
+                    self.symbol(&name, SymbolKind::OBJECT, None, item.span, Some(methods))
+                }
+                ItemKind::TraitImpl(trait_impl) => {
+                    let items = trait_impl
+                        .items
+                        .iter()
+                        .filter_map(|impl_item| match impl_item {
+                            TraitImplItem::Function(function) => {
+                                self.function_symbol(function, function.span())
+                            }
+                            TraitImplItem::Constant(name, typ, _) => self.symbol(
+                                name,
+                                SymbolKind::CONSTANT,
+                                Some(typ.to_string()),
+                                name.span(),
+                                None,
+                            ),
+                            TraitImplItem::Type { name, alias } => self.symbol(
+                                name,
+                                SymbolKind::TYPE_PARAMETER,
+                                Some(alias.to_string()),
+                                name.span(),
+                                None,
+                            ),
+                        })
+                        .collect();
+                    let name = Ident::new(
+                        format!("impl {} for {}", trait_impl.trait_name, trait_impl.object_type),
+                        trait_impl.trait_name.span(),
+                    );
+                    self.symbol(&name, SymbolKind::OBJECT, None, item.span, Some(items))
+                }
+                ItemKind::TypeAlias(alias) => self.symbol(
+                    &alias.name,
+                    SymbolKind::TYPE_PARAMETER,
+                    Some(alias.typ.to_string()),
+                    item.span,
+                    None,
+                ),
+                ItemKind::Global(global) => pattern_name(&global.pattern).and_then(|name| {
+                    self.symbol(name, SymbolKind::CONSTANT, None, item.span, None)
+                }),
+                ItemKind::ModuleDecl(declaration) => {
+                    self.symbol(&declaration.ident, SymbolKind::MODULE, None, item.span, None)
+                }
+                ItemKind::Submodules(submodule) => {
+                    let kind =
+                        if submodule.is_contract { SymbolKind::CLASS } else { SymbolKind::MODULE };
+                    let children = self.module_symbols(&submodule.contents);
+                    self.symbol(&submodule.name, kind, None, item.span, Some(children))
+                }
+                ItemKind::Import(_) => None,
+            };
+            symbols.extend(symbol);
+        }
+        symbols
+    }
+
+    fn function_symbol(&self, function: &NoirFunction, span: Span) -> Option<DocumentSymbol> {
+        let def = &function.def;
+        let parameters: Vec<String> = def
+            .parameters
+            .iter()
+            .map(|parameter| format!("{}: {}", parameter.pattern, parameter.typ))
+            .collect();
+        let detail = match &def.return_type {
+            FunctionReturnType::Default(_) => format!("fn({})", parameters.join(", ")),
+            FunctionReturnType::Ty(typ) => format!("fn({}) -> {typ}", parameters.join(", ")),
+        };
+        self.symbol(function.name_ident(), SymbolKind::FUNCTION, Some(detail), span, None)
+    }
+
+    fn symbol(
+        &self,
+        name: &Ident,
+        kind: SymbolKind,
+        detail: Option<String>,
+        span: Span,
+        children: Option<Vec<DocumentSymbol>>,
+    ) -> Option<DocumentSymbol> {
+        let range = byte_span_to_range(self.file, (), span.into())?;
+        let selection_range = byte_span_to_range(self.file, (), name.span().into())?;
+
+        #[allow(deprecated)]
+        Some(DocumentSymbol {
+            name: name.to_string(),
+            detail,
+            kind,
+            tags: None,
+            deprecated: None,
+            range,
+            selection_range,
+            children,
+        })
+    }
+}
+
+fn pattern_name(pattern: &Pattern) -> Option<&Ident> {
+    match pattern {
+        Pattern::Identifier(name) => Some(name),
+        Pattern::Mutable(pattern, ..) => pattern_name(pattern),
+        _ => None,
+    }
+}
+
+pub(crate) fn on_workspace_symbol_request(
+    state: &mut LspState,
+    params: WorkspaceSymbolParams,
+) -> impl Future<Output = Result<Option<WorkspaceSymbolResponse>, ResponseError>> {
+    future::ready(on_workspace_symbol_inner(state, params))
+}
+
+fn on_workspace_symbol_inner(
+    state: &mut LspState,
+    params: WorkspaceSymbolParams,
+) -> Result<Option<WorkspaceSymbolResponse>, ResponseError> {
+    let root_path = state.root_path.as_deref().ok_or_else(|| {
+        ResponseError::new(ErrorCode::REQUEST_FAILED, "Could not find project root")
+    })?;
+
+    let toml_path = match find_package_manifest(root_path, root_path) {
+        Ok(toml_path) => toml_path,
+        Err(err) => {
+            let _ = state.client.log_message(LogMessageParams {
+                typ: MessageType::WARNING,
+                message: err.to_string(),
+            });
+            return Ok(None);
+        }
+    };
+
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        PackageSelection::All,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+    )
+    .map_err(|err| ResponseError::new(ErrorCode::REQUEST_FAILED, err))?;
+
+    let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
+    insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
+    let parsed_files = parse_diff(&workspace_file_manager, state);
+
+    let files = workspace_file_manager.as_file_map();
+    let mut symbols: Vec<SymbolInformation> = Vec::new();
+    let mut symbol_locations = HashSet::new();
+    for package in &workspace {
+        // Packages type-checked since they were last saved are not type-checked again
+        let package_root_path: String = package.root_dir.as_os_str().to_string_lossy().into();
+        let cached = state.cached_definitions.contains_key(&package_root_path)
+            && state.cached_def_maps.contains_key(&package_root_path);
+        if !cached {
+            let (mut context, crate_id) =
+                crate::prepare_package(&workspace_file_manager, &parsed_files, package);
+            // Symbols of items that failed to type-check are still useful, so errors are ignored
+            let _ = check_crate(&mut context, crate_id, false, false, false);
+
+            state.cached_definitions.insert(package_root_path.clone(), context.def_interner);
+            state.cached_def_maps.insert(package_root_path.clone(), context.def_maps);
+        }
+        let interner = &state.cached_definitions[&package_root_path];
+        let def_maps = &state.cached_def_maps[&package_root_path];
+
+        for def_map in def_maps.values() {
+            for (index, module) in def_map.modules().iter() {
+                let container_name =
+                    def_map.get_module_path_with_separator(index, module.parent, "::");
+                let definitions = module.definitions();
+
+                for (name, entries) in definitions.types().iter().chain(definitions.values()) {
+                    if !fuzzy_match(&params.query, &name.0.contents) {
+                        continue;
+                    }
+                    for (module_def_id, _, _) in entries.values() {
+                        let (kind, location) = match *module_def_id {
+                            ModuleDefId::ModuleId(id) => {
+                                (SymbolKind::MODULE, id.module(def_maps).location)
+                            }
+                            module_def_id => symbol_kind_and_location(module_def_id, interner),
+                        };
+                        // Dependencies are shared between packages, so they are only reported once
+                        if !symbol_locations.insert(location) {
+                            continue;
+                        }
+                        let Some(location) = to_lsp_location(files, location.file, location.span)
+                        else {
+                            continue;
+                        };
+
+                        #[allow(deprecated)]
+                        symbols.push(SymbolInformation {
+                            name: name.to_string(),
+                            kind,
+                            tags: None,
+                            deprecated: None,
+                            location,
+                            container_name: (!container_name.is_empty())
+                                .then(|| container_name.clone()),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(Some(WorkspaceSymbolResponse::Flat(symbols)))
+}
+
+fn symbol_kind_and_location(
+    module_def_id: ModuleDefId,
+    interner: &NodeInterner,
+) -> (SymbolKind, noirc_errors::Location) {
+    let (kind, reference) = match module_def_id {
+        ModuleDefId::FunctionId(id) => (SymbolKind::FUNCTION, ReferenceId::Function(id)),
+        ModuleDefId::TypeId(id) => (SymbolKind::STRUCT, ReferenceId::Struct(id)),
+        ModuleDefId::TypeAliasId(id) => (SymbolKind::TYPE_PARAMETER, ReferenceId::Alias(id)),
+        ModuleDefId::TraitId(id) => (SymbolKind::INTERFACE, ReferenceId::Trait(id)),
+        ModuleDefId::GlobalId(id) => (SymbolKind::CONSTANT, ReferenceId::Global(id)),
+        ModuleDefId::ModuleId(id) => (SymbolKind::MODULE, ReferenceId::Module(id)),
+    };
+    (kind, interner.reference_location(reference))
+}
+
+/// Returns true if all the characters of `query` appear in `name` in order, ignoring case
+fn fuzzy_match(query: &str, name: &str) -> bool {
+    let mut name = name.chars().flat_map(char::to_lowercase);
+    query.chars().flat_map(char::to_lowercase).all(|c| name.any(|n| n == c))
+}
+
+#[cfg(test)]
+mod document_symbol_tests {
+    use crate::test_utils;
+
+    use super::*;
+    use lsp_types::{PartialResultParams, TextDocumentIdentifier, WorkDoneProgressParams};
+    use tokio::test;
+
+    fn names(symbols: &[DocumentSymbol]) -> Vec<&str> {
+        symbols.iter().map(|symbol| symbol.name.as_str()).collect()
+    }
+
+    #[test]
+    async fn test_document_symbols_follow_module_structure() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("symbols").await;
+
+        let params = DocumentSymbolParams {
+            text_document: TextDocumentIdentifier { uri: noir_text_document },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+        let response = on_document_symbol_request(&mut state, params)
+            .await
+            .expect("Could not execute on_document_symbol_request")
+            .expect("Expected document symbols");
+        let DocumentSymbolResponse::Nested(symbols) = response else {
+            panic!("Expected nested document symbols");
+        };
+
+        assert_eq!(names(&symbols), vec!["shapes", "ORIGIN", "main"]);
+
+        let shapes = symbols[0].children.as_ref().unwrap();
+        assert_eq!(names(shapes), vec!["Square", "impl Square", "Area", "impl Area for Square"]);
+        assert_eq!(names(shapes[0].children.as_ref().unwrap()), vec!["side"]);
+        assert_eq!(names(shapes[1].children.as_ref().unwrap()), vec!["new"]);
+        assert_eq!(names(shapes[3].children.as_ref().unwrap()), vec!["area"]);
+    }
+
+    #[test]
+    async fn test_workspace_symbols_fuzzy_search() {
+        let (mut state, _) = test_utils::init_lsp_server("symbols").await;
+
+        let params = WorkspaceSymbolParams {
+            query: "sqr".to_string(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+        let response = on_workspace_symbol_request(&mut state, params)
+            .await
+            .expect("Could not execute on_workspace_symbol_request")
+            .expect("Expected workspace symbols");
+        let WorkspaceSymbolResponse::Flat(symbols) = response else {
+            panic!("Expected flat workspace symbols");
+        };
+
+        let square = symbols
+            .iter()
+            .find(|symbol| symbol.name == "Square")
+            .expect("Expected to find the Square struct");
+        assert_eq!(square.kind, SymbolKind::STRUCT);
+        assert_eq!(square.container_name.as_deref(), Some("shapes"));
+        assert!(symbols.iter().all(|symbol| fuzzy_match("sqr", &symbol.name)));
+    }
+
+    #[test]
+    async fn test_fuzzy_match() {
+        assert!(fuzzy_match("sqr", "Square"));
+        assert!(fuzzy_match("", "anything"));
+        assert!(!fuzzy_match("sqz", "Square"));
+    }
+}
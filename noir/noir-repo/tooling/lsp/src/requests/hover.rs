@@ -0,0 +1,326 @@
+use std::future::{self, Future};
+
+use async_lsp::ResponseError;
+use fm::{codespan_files::Files, FileMap};
+use lsp_types::{Hover, HoverContents, HoverParams, MarkupContent, MarkupKind};
+use noirc_errors::Location;
+use noirc_frontend::{
+    ast::ItemVisibility,
+    hir_def::{stmt::HirPattern, traits::TraitConstraint},
+    macros_api::NodeInterner,
+    node_interner::{DefinitionId, DefinitionKind, FuncId, GlobalId, ReferenceId},
+    Generics, Kind, Type,
+};
+
+use crate::LspState;
+
+use super::process_request;
+
+pub(crate) fn on_hover_request(
+    state: &mut LspState,
+    params: HoverParams,
+) -> impl Future<Output = Result<Option<Hover>, ResponseError>> {
+    let result = process_request(
+        state,
+        params.text_document_position_params,
+        |location, interner, files| {
+            // First try to find the item referenced at the location, same as go to definition,
+            // otherwise show the type of the expression under the cursor
+            let contents = interner
+                .find_referenced(location)
+                .and_then(|reference| format_reference(reference, location, interner, files))
+                .or_else(|| format_expression_type(location, interner))?;
+            Some(Hover {
+                contents: HoverContents::Markup(MarkupContent {
+                    kind: MarkupKind::Markdown,
+                    value: contents,
+                }),
+                range: None,
+            })
+        },
+    );
+    future::ready(result)
+}
+
+fn format_reference(
+    reference: ReferenceId,
+    location: Location,
+    interner: &NodeInterner,
+    files: &FileMap,
+) -> Option<String> {
+    let signature = match reference {
+        ReferenceId::Module(_) => format!("mod {}", word_at(files, location)?),
+        ReferenceId::Struct(id) => {
+            let struct_type = interner.get_struct(id);
+            let struct_type = struct_type.borrow();
+            let fields: String = struct_type
+                .get_fields_as_written()
+                .into_iter()
+                .map(|(name, typ)| format!("    {name}: {typ},\n"))
+                .collect();
+            let generics = format_generics(&struct_type.generics);
+            format!("struct {}{generics} {{\n{fields}}}", struct_type.name)
+        }
+        ReferenceId::Trait(id) => {
+            let trait_ = interner.get_trait(id);
+            format!("trait {}{}", trait_.name, format_generics(&trait_.generics))
+        }
+        ReferenceId::Global(id) => format_global(id, interner),
+        ReferenceId::Function(id) => format_function(id, interner),
+        ReferenceId::Alias(id) => {
+            let alias = interner.get_type_alias(id);
+            let alias = alias.borrow();
+            format!("type {}{} = {}", alias.name, format_generics(&alias.generics), alias.typ)
+        }
+        ReferenceId::Local(id) => format_definition(id, interner),
+        ReferenceId::Reference(..) => return None,
+    };
+
+    let mut contents = format!("```noir\n{signature}\n```");
+    if let Some(docs) = doc_comments(files, interner.reference_location(reference)) {
+        contents.push_str("\n\n");
+        contents.push_str(&docs);
+    }
+    Some(contents)
+}
+
+fn format_expression_type(location: Location, interner: &NodeInterner) -> Option<String> {
+    let index = interner.find_location_index(location)?;
+    match interner.id_type(index) {
+        Type::Error | Type::Unit => None,
+        typ => Some(format!("```noir\n{typ}\n```")),
+    }
+}
+
+fn format_definition(id: DefinitionId, interner: &NodeInterner) -> String {
+    let definition = interner.definition(id);
+    match definition.kind {
+        DefinitionKind::Function(func_id) => format_function(func_id, interner),
+        DefinitionKind::Global(global_id) => format_global(global_id, interner),
+        DefinitionKind::Local(_) => {
+            let mutable = if definition.mutable { "mut " } else { "" };
+            format!("let {mutable}{}: {}", definition.name, interner.definition_type(id))
+        }
+        DefinitionKind::GenericType(_) => definition.name.clone(),
+    }
+}
+
+fn format_global(id: GlobalId, interner: &NodeInterner) -> String {
+    let global = interner.get_global(id);
+    format!("global {}: {}", global.ident, interner.definition_type(global.definition_id))
+}
+
+pub(super) fn format_function(id: FuncId, interner: &NodeInterner) -> String {
+    let modifiers = interner.function_modifiers(&id);
+    let func_meta = interner.function_meta(&id);
+
+    let mut string = String::new();
+    string.push_str(match modifiers.visibility {
+        ItemVisibility::Public => "pub ",
+        ItemVisibility::PublicCrate => "pub(crate) ",
+        ItemVisibility::Private => "",
+    });
+    if modifiers.is_comptime {
+        string.push_str("comptime ");
+    }
+    if modifiers.is_unconstrained {
+        string.push_str("unconstrained ");
+    }
+    string.push_str("fn ");
+    string.push_str(&modifiers.name);
+    string.push_str(&format_generics(&func_meta.direct_generics));
+
+    let parameters: Vec<String> = func_meta
+        .parameters
+        .iter()
+        .map(|(pattern, typ, _)| format!("{}: {typ}", format_pattern(pattern, interner)))
+        .collect();
+    string.push_str(&format!("({})", parameters.join(", ")));
+
+    let return_type = func_meta.return_type();
+    if !matches!(return_type, Type::Unit) {
+        string.push_str(&format!(" -> {return_type}"));
+    }
+
+    if !func_meta.trait_constraints.is_empty() {
+        string.push_str("\nwhere");
+        for constraint in &func_meta.trait_constraints {
+            string.push_str(&format!("\n    {},", format_trait_constraint(constraint, interner)));
+        }
+    }
+    string
+}
+
+fn format_trait_constraint(constraint: &TraitConstraint, interner: &NodeInterner) -> String {
+    let trait_ = interner.get_trait(constraint.trait_id);
+    let generics = if constraint.trait_generics.is_empty() {
+        String::new()
+    } else {
+        let generics: Vec<String> =
+            constraint.trait_generics.iter().map(|typ| typ.to_string()).collect();
+        format!("<{}>", generics.join(", "))
+    };
+    format!("{}: {}{generics}", constraint.typ, trait_.name)
+}
+
+fn format_generics(generics: &Generics) -> String {
+    if generics.is_empty() {
+        return String::new();
+    }
+    let generics: Vec<String> = generics
+        .iter()
+        .map(|generic| match &generic.kind {
+            Kind::Normal => generic.name.to_string(),
+            Kind::Numeric(typ) => format!("let {}: {typ}", generic.name),
+        })
+        .collect();
+    format!("<{}>", generics.join(", "))
+}
+
+pub(super) fn format_pattern(pattern: &HirPattern, interner: &NodeInterner) -> String {
+    match pattern {
+        HirPattern::Identifier(ident) => interner.definition_name(ident.id).to_string(),
+        HirPattern::Mutable(pattern, _) => format!("mut {}", format_pattern(pattern, interner)),
+        HirPattern::Tuple(patterns, _) => {
+            let patterns: Vec<String> =
+                patterns.iter().map(|pattern| format_pattern(pattern, interner)).collect();
+            format!("({})", patterns.join(", "))
+        }
+        HirPattern::Struct(typ, fields, _) => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(name, pattern)| format!("{name}: {}", format_pattern(pattern, interner)))
+                .collect();
+            format!("{typ} {{ {} }}", fields.join(", "))
+        }
+        HirPattern::Variant(typ, variant_index, patterns, _) => {
+            let path = match typ.follow_bindings() {
+                Type::Struct(def, _) => {
+                    let def = def.borrow();
+                    match def.variant_name(*variant_index) {
+                        Some(variant) => format!("{}::{variant}", def.name),
+                        None => typ.to_string(),
+                    }
+                }
+                _ => typ.to_string(),
+            };
+            if patterns.is_empty() {
+                path
+            } else {
+                let patterns: Vec<String> =
+                    patterns.iter().map(|pattern| format_pattern(pattern, interner)).collect();
+                format!("{path}({})", patterns.join(", "))
+            }
+        }
+    }
+}
+
+/// Returns the identifier surrounding the given location in the source code
+fn word_at(files: &FileMap, location: Location) -> Option<&str> {
+    let source = files.source(location.file).ok()?;
+    let index = location.span.start() as usize;
+    let is_ident_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let start = source[..index]
+        .char_indices()
+        .rev()
+        .find(|(_, c)| !is_ident_char(*c))
+        .map_or(0, |(index, c)| index + c.len_utf8());
+    let end = source[index..].find(|c| !is_ident_char(c)).map_or(source.len(), |i| index + i);
+    (start < end).then(|| &source[start..end])
+}
+
+/// Collects the `///` doc comments right above the line of the given location,
+/// skipping over any attributes in between.
+fn doc_comments(files: &FileMap, location: Location) -> Option<String> {
+    let source = files.source(location.file).ok()?;
+    let index = location.span.start() as usize;
+    let line_start = source[..index].rfind('\n').map_or(0, |index| index + 1);
+
+    let mut lines = vec![];
+    for line in source[..line_start].lines().rev() {
+        let line = line.trim();
+        if let Some(comment) = line.strip_prefix("///") {
+            lines.push(comment.strip_prefix(' ').unwrap_or(comment));
+        } else if !line.starts_with("#[") {
+            break;
+        }
+    }
+    if lines.is_empty() {
+        return None;
+    }
+    lines.reverse();
+    Some(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod hover_tests {
+    use crate::test_utils::{self, search_in_file};
+
+    use super::*;
+    use lsp_types::{Position, TextDocumentIdentifier, TextDocumentPositionParams};
+    use tokio::test;
+
+    async fn hover_at(search_string: &str, occurrence: usize) -> String {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("hover").await;
+
+        let ranges = search_in_file(noir_text_document.path(), search_string);
+        let position: Position = ranges[occurrence].start;
+
+        let params = HoverParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: noir_text_document },
+                position,
+            },
+            work_done_progress_params: Default::default(),
+        };
+
+        let hover = on_hover_request(&mut state, params)
+            .await
+            .expect("Could not execute on_hover_request")
+            .expect("Expected a hover response");
+
+        match hover.contents {
+            HoverContents::Markup(markup) => markup.value,
+            _ => panic!("Expected markup hover contents"),
+        }
+    }
+
+    #[test]
+    async fn test_hover_on_local_variable() {
+        let contents = hover_at("total", 1).await;
+        assert_eq!(contents, "```noir\nlet total: Field\n```");
+    }
+
+    #[test]
+    async fn test_hover_on_global() {
+        let contents = hover_at("SCALE", 1).await;
+        assert_eq!(contents, "```noir\nglobal SCALE: Field\n```");
+    }
+
+    #[test]
+    async fn test_hover_on_function_shows_signature_and_docs() {
+        let contents = hover_at("sum", 1).await;
+        assert!(contents.starts_with("```noir\npub fn sum<T>(point: Point<T>) -> T\nwhere\n"));
+        assert!(contents.ends_with("```\n\nAdds up the coordinates of a point"));
+    }
+
+    #[test]
+    async fn test_word_at_after_multi_byte_characters() {
+        let mut files = FileMap::default();
+        let source = "let s = \"é\"; // ü→foo bar";
+        let file = files.add_file(std::path::PathBuf::from("main.nr").into(), source.to_string());
+
+        let index = source.find("oo").unwrap() as u32;
+        let location = Location::new(noirc_errors::Span::from(index..index + 2), file);
+        assert_eq!(word_at(&files, location), Some("foo"));
+    }
+
+    #[test]
+    async fn test_hover_on_struct_shows_fields_and_docs() {
+        let contents = hover_at("Point", 3).await;
+        assert_eq!(
+            contents,
+            "```noir\nstruct Point<T> {\n    x: T,\n    y: T,\n}\n```\n\nA point in the plane"
+        );
+    }
+}
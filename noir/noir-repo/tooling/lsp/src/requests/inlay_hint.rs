@@ -0,0 +1,381 @@
+use std::future::{self, Future};
+
+use async_lsp::{ErrorCode, ResponseError};
+use fm::{codespan_files::Files, FileId, FileMap};
+use lsp_types::{InlayHint, InlayHintKind, InlayHintLabel, InlayHintParams, Position};
+use noirc_errors::Span;
+use noirc_frontend::{
+    hir_def::{
+        expr::{HirArrayLiteral, HirExpression, HirLiteral},
+        stmt::{HirLValue, HirPattern, HirStatement},
+    },
+    macros_api::NodeInterner,
+    node_interner::{DefinitionId, DefinitionKind, ExprId, FuncId, StmtId},
+    Type,
+};
+
+use crate::{byte_span_to_range, LspState};
+
+use super::process_document;
+
+pub(crate) fn on_inlay_hint_request(
+    state: &mut LspState,
+    params: InlayHintParams,
+) -> impl Future<Output = Result<Option<Vec<InlayHint>>, ResponseError>> {
+    future::ready(on_inlay_hint_inner(state, params))
+}
+
+fn on_inlay_hint_inner(
+    state: &mut LspState,
+    params: InlayHintParams,
+) -> Result<Option<Vec<InlayHint>>, ResponseError> {
+    process_document(state, &params.text_document.uri, |context, _, file_id| {
+        let files = context.file_manager.as_file_map();
+        let source = files.source(file_id).map_err(|err| {
+            ResponseError::new(ErrorCode::REQUEST_FAILED, format!("Could not read file: {err:?}"))
+        })?;
+
+        let mut collector = InlayHintCollector {
+            interner: &context.def_interner,
+            files,
+            file_id,
+            source,
+            hints: Vec::new(),
+        };
+        for func_id in context.def_interner.functions_in_file(file_id) {
+            collector.collect_function(func_id);
+        }
+
+        let range = params.range;
+        let mut hints: Vec<InlayHint> = collector
+            .hints
+            .into_iter()
+            .filter(|hint| range.start <= hint.position && hint.position <= range.end)
+            .collect();
+        hints.sort_by_key(|hint| hint.position);
+        Ok(Some(hints))
+    })
+}
+
+/// Walks the elaborated functions of a file and collects hints from their resolved types
+struct InlayHintCollector<'a> {
+    interner: &'a NodeInterner,
+    files: &'a FileMap,
+    file_id: FileId,
+    source: &'a str,
+    hints: Vec<InlayHint>,
+}
+
+impl<'a> InlayHintCollector<'a> {
+    fn collect_function(&mut self, func_id: FuncId) {
+        self.collect_expression(self.interner.function(&func_id).as_expr());
+    }
+
+    fn collect_expression(&mut self, expr_id: ExprId) {
+        match self.interner.expression(&expr_id) {
+            HirExpression::Literal(literal) => match literal {
+                HirLiteral::Array(array) | HirLiteral::Slice(array) => match array {
+                    HirArrayLiteral::Standard(elements) => self.collect_expressions(&elements),
+                    HirArrayLiteral::Repeated { repeated_element, .. } => {
+                        self.collect_expression(repeated_element);
+                    }
+                },
+                HirLiteral::FmtStr(_, captures) => self.collect_expressions(&captures),
+                HirLiteral::Bool(_)
+                | HirLiteral::Integer(..)
+                | HirLiteral::Str(_)
+                | HirLiteral::Unit => (),
+            },
+            HirExpression::Block(block) | HirExpression::Comptime(block) => {
+                for statement in block.statements() {
+                    self.collect_statement(*statement);
+                }
+            }
+            HirExpression::Prefix(prefix) => self.collect_expression(prefix.rhs),
+            HirExpression::Infix(infix) => {
+                self.collect_expression(infix.lhs);
+                self.collect_expression(infix.rhs);
+            }
+            HirExpression::Index(index) => {
+                self.collect_expression(index.collection);
+                self.collect_expression(index.index);
+            }
+            HirExpression::Constructor(constructor) => {
+                for (_, field) in constructor.fields {
+                    self.collect_expression(field);
+                }
+            }
+            HirExpression::EnumConstructor(constructor) => {
+                self.collect_expressions(&constructor.arguments);
+            }
+            HirExpression::MemberAccess(member_access) => {
+                self.collect_expression(member_access.lhs);
+            }
+            HirExpression::Call(call) => {
+                self.collect_parameter_names(call.func, &call.arguments);
+                self.collect_expression(call.func);
+                self.collect_expressions(&call.arguments);
+            }
+            HirExpression::MethodCall(call) => {
+                self.collect_expression(call.object);
+                self.collect_expressions(&call.arguments);
+            }
+            HirExpression::Cast(cast) => self.collect_expression(cast.lhs),
+            HirExpression::If(if_expr) => {
+                self.collect_expression(if_expr.condition);
+                self.collect_expression(if_expr.consequence);
+                if let Some(alternative) = if_expr.alternative {
+                    self.collect_expression(alternative);
+                }
+            }
+            HirExpression::Match(match_expr) => {
+                self.collect_expression(match_expr.expression);
+                for (pattern, branch) in &match_expr.rules {
+                    self.collect_pattern_identifiers(pattern);
+                    self.collect_expression(*branch);
+                }
+            }
+            HirExpression::Tuple(elements) => self.collect_expressions(&elements),
+            HirExpression::Lambda(lambda) => {
+                for (pattern, _) in &lambda.parameters {
+                    self.collect_pattern_types(pattern);
+                }
+                self.collect_expression(lambda.body);
+            }
+            HirExpression::Ident(..)
+            | HirExpression::Quote(_)
+            | HirExpression::Unquote(_)
+            | HirExpression::Error => (),
+        }
+    }
+
+    fn collect_expressions(&mut self, expressions: &[ExprId]) {
+        for expr_id in expressions {
+            self.collect_expression(*expr_id);
+        }
+    }
+
+    fn collect_statement(&mut self, stmt_id: StmtId) {
+        match self.interner.statement(&stmt_id) {
+            HirStatement::Let(let_statement) => {
+                self.collect_pattern_types(&let_statement.pattern);
+                self.collect_expression(let_statement.expression);
+            }
+            HirStatement::Constrain(constrain) => {
+                self.collect_expression(constrain.0);
+                if let Some(message) = constrain.2 {
+                    self.collect_expression(message);
+                }
+            }
+            HirStatement::Assign(assign) => {
+                self.collect_lvalue(&assign.lvalue);
+                self.collect_expression(assign.expression);
+            }
+            HirStatement::For(for_statement) => {
+                self.collect_expression(for_statement.start_range);
+                self.collect_expression(for_statement.end_range);
+                self.collect_expression(for_statement.block);
+            }
+            HirStatement::Expression(expr_id) | HirStatement::Semi(expr_id) => {
+                self.collect_expression(expr_id);
+            }
+            HirStatement::Comptime(statement) => self.collect_statement(statement),
+            HirStatement::Break | HirStatement::Continue | HirStatement::Error => (),
+        }
+    }
+
+    fn collect_lvalue(&mut self, lvalue: &HirLValue) {
+        match lvalue {
+            HirLValue::Ident(..) => (),
+            HirLValue::MemberAccess { object, .. } => self.collect_lvalue(object),
+            HirLValue::Index { array, index, .. } => {
+                self.collect_lvalue(array);
+                self.collect_expression(*index);
+            }
+            HirLValue::Dereference { lvalue, .. } => self.collect_lvalue(lvalue),
+        }
+    }
+
+    /// Adds the inferred type after each identifier of a `let` or closure parameter
+    /// pattern, unless the pattern already has a type annotation.
+    fn collect_pattern_types(&mut self, pattern: &HirPattern) {
+        if self.is_followed_by_type_annotation(pattern.span()) {
+            return;
+        }
+        self.collect_pattern_identifiers(pattern);
+    }
+
+    fn collect_pattern_identifiers(&mut self, pattern: &HirPattern) {
+        match pattern {
+            HirPattern::Identifier(ident) => self.push_type_hint(ident.id, ident.location.span),
+            HirPattern::Mutable(pattern, _) => self.collect_pattern_identifiers(pattern),
+            HirPattern::Tuple(patterns, _) => {
+                for pattern in patterns {
+                    self.collect_pattern_identifiers(pattern);
+                }
+            }
+            // A hint right after a field name would read like a rename of the field
+            HirPattern::Struct(..) => (),
+            HirPattern::Variant(_, _, patterns, _) => {
+                for pattern in patterns {
+                    self.collect_pattern_identifiers(pattern);
+                }
+            }
+        }
+    }
+
+    fn push_type_hint(&mut self, id: DefinitionId, span: Span) {
+        // Identifiers introduced by macros don't appear as written in the source
+        let name = self.interner.definition_name(id);
+        if self.source_text(span) != Some(name) || name.starts_with('_') {
+            return;
+        }
+
+        let typ = self.interner.definition_type(id).follow_bindings();
+        if matches!(typ, Type::Error) {
+            return;
+        }
+
+        let Some(position) = self.position(span.end()) else { return };
+        self.hints.push(InlayHint {
+            position,
+            label: InlayHintLabel::String(format!(": {typ}")),
+            kind: Some(InlayHintKind::TYPE),
+            text_edits: None,
+            tooltip: None,
+            padding_left: None,
+            padding_right: None,
+            data: None,
+        });
+    }
+
+    /// Adds the name of the matching parameter before each argument of a call to a
+    /// named function. Method calls were desugared into function calls taking the
+    /// object as their first argument, which is not hinted.
+    fn collect_parameter_names(&mut self, func: ExprId, arguments: &[ExprId]) {
+        let HirExpression::Ident(ident, _) = self.interner.expression(&func) else {
+            return;
+        };
+        let DefinitionKind::Function(func_id) = self.interner.definition(ident.id).kind else {
+            return;
+        };
+        let func_meta = self.interner.function_meta(&func_id);
+
+        let func_start = self.interner.expr_span(&func).start();
+        for (index, ((pattern, _, _), argument)) in
+            func_meta.parameters.iter().zip(arguments).enumerate()
+        {
+            let argument_span = self.interner.expr_span(argument);
+            let is_method_object = index == 0 && argument_span.start() < func_start;
+            if is_method_object {
+                continue;
+            }
+
+            let Some(name) = parameter_name(pattern, self.interner) else { continue };
+            if name == "self"
+                || name.starts_with('_')
+                || self.source_text(argument_span) == Some(name)
+            {
+                continue;
+            }
+
+            let Some(position) = self.position(argument_span.start()) else { continue };
+            self.hints.push(InlayHint {
+                position,
+                label: InlayHintLabel::String(format!("{name}:")),
+                kind: Some(InlayHintKind::PARAMETER),
+                text_edits: None,
+                tooltip: None,
+                padding_left: None,
+                padding_right: Some(true),
+                data: None,
+            });
+        }
+    }
+
+    /// Returns true if the first non-whitespace character after `span` is a `:`
+    fn is_followed_by_type_annotation(&self, span: Span) -> bool {
+        self.source
+            .get(span.end() as usize..)
+            .map_or(false, |rest| rest.trim_start().starts_with(':'))
+    }
+
+    fn source_text(&self, span: Span) -> Option<&'a str> {
+        self.source.get(span.start() as usize..span.end() as usize)
+    }
+
+    fn position(&self, index: u32) -> Option<Position> {
+        let span = Span::from(index..index);
+        byte_span_to_range(self.files, self.file_id, span.into()).map(|range| range.start)
+    }
+}
+
+fn parameter_name<'i>(pattern: &HirPattern, interner: &'i NodeInterner) -> Option<&'i str> {
+    match pattern {
+        HirPattern::Identifier(ident) => Some(interner.definition_name(ident.id)),
+        HirPattern::Mutable(pattern, _) => parameter_name(pattern, interner),
+        HirPattern::Tuple(..) | HirPattern::Struct(..) | HirPattern::Variant(..) => None,
+    }
+}
+
+#[cfg(test)]
+mod inlay_hint_tests {
+    use crate::test_utils;
+
+    use super::*;
+    use lsp_types::{Range, TextDocumentIdentifier, WorkDoneProgressParams};
+    use tokio::test;
+
+    async fn inlay_hints() -> Vec<(Position, String, InlayHintKind)> {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("inlay_hints").await;
+
+        let params = InlayHintParams {
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            text_document: TextDocumentIdentifier { uri: noir_text_document },
+            range: Range {
+                start: Position { line: 0, character: 0 },
+                end: Position { line: u32::MAX, character: 0 },
+            },
+        };
+        let hints = on_inlay_hint_request(&mut state, params)
+            .await
+            .expect("Could not execute on_inlay_hint_request")
+            .expect("Expected inlay hints");
+
+        hints
+            .into_iter()
+            .map(|hint| {
+                let InlayHintLabel::String(label) = hint.label else {
+                    panic!("Expected a string label");
+                };
+                (hint.position, label, hint.kind.expect("Expected a hint kind"))
+            })
+            .collect()
+    }
+
+    fn labels(hints: &[(Position, String, InlayHintKind)], kind: InlayHintKind) -> Vec<&str> {
+        hints
+            .iter()
+            .filter(|(_, _, hint_kind)| *hint_kind == kind)
+            .map(|(_, label, _)| label.as_str())
+            .collect()
+    }
+
+    #[test]
+    async fn test_inlay_hints_for_inferred_types() {
+        let hints = inlay_hints().await;
+        assert_eq!(
+            labels(&hints, InlayHintKind::TYPE),
+            vec![": u32", ": Field", ": Field", ": Field"]
+        );
+    }
+
+    #[test]
+    async fn test_inlay_hints_for_parameter_names() {
+        let hints = inlay_hints().await;
+        assert_eq!(
+            labels(&hints, InlayHintKind::PARAMETER),
+            vec!["base:", "exponent:", "factor:", "value:", "f:"]
+        );
+    }
+}
@@ -5,7 +5,10 @@ use crate::{
     types::{CodeLensOptions, InitializeParams},
 };
 use async_lsp::{ErrorCode, ResponseError};
-use fm::{codespan_files::Error, FileMap, PathString};
+use fm::{
+    codespan_files::{Error, SimpleFile},
+    FileId, FileMap, PathString,
+};
 use lsp_types::{
     DeclarationCapability, Location, Position, TextDocumentPositionParams,
     TextDocumentSyncCapability, TextDocumentSyncKind, TypeDefinitionProviderCapability, Url,
@@ -14,7 +17,14 @@ use lsp_types::{
 use nargo::insert_all_files_for_workspace_into_file_manager;
 use nargo_fmt::Config;
 use noirc_driver::file_manager_with_stdlib;
-use noirc_frontend::macros_api::NodeInterner;
+use noirc_errors::Span;
+use noirc_frontend::{
+    graph::CrateId,
+    hir::{def_collector::dc_crate::CompilationError, Context},
+    macros_api::NodeInterner,
+    parser::ParserError,
+    ParsedModule,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -32,21 +42,38 @@ use crate::{
 // They are not attached to the `NargoLspService` struct so they can be unit tested with only `LspState`
 // and params passed in.
 
+mod call_hierarchy;
+mod code_action;
 mod code_lens_request;
+mod completion;
+mod document_symbol;
 mod goto_declaration;
 mod goto_definition;
+mod hover;
+mod inlay_hint;
 mod profile_run;
 mod references;
 mod rename;
+mod semantic_tokens;
+mod signature_help;
 mod test_run;
 mod tests;
 
 pub(crate) use {
-    code_lens_request::collect_lenses_for_package, code_lens_request::on_code_lens_request,
+    call_hierarchy::on_call_hierarchy_incoming_calls_request,
+    call_hierarchy::on_call_hierarchy_outgoing_calls_request,
+    call_hierarchy::on_prepare_call_hierarchy_request, code_action::on_code_action_request,
+    code_lens_request::collect_lenses_for_package, code_lens_request::collect_opcode_count_lenses,
+    code_lens_request::count_opcodes_in_file, code_lens_request::on_code_lens_request,
+    code_lens_request::FunctionOpcodeCounts, completion::on_completion_request,
+    document_symbol::on_document_symbol_request, document_symbol::on_workspace_symbol_request,
     goto_declaration::on_goto_declaration_request, goto_definition::on_goto_definition_request,
-    goto_definition::on_goto_type_definition_request, profile_run::on_profile_run_request,
+    goto_definition::on_goto_type_definition_request, hover::on_hover_request,
+    inlay_hint::on_inlay_hint_request, profile_run::on_profile_run_request,
     references::on_references_request, rename::on_prepare_rename_request,
-    rename::on_rename_request, test_run::on_test_run_request, tests::on_tests_request,
+    rename::on_rename_request, semantic_tokens::on_semantic_tokens_full_request,
+    signature_help::on_signature_help_request, test_run::on_test_run_request,
+    tests::on_tests_request,
 };
 
 /// LSP client will send initialization request after the server has started.
@@ -112,6 +139,13 @@ pub(crate) fn on_initialize(
                 text_document_sync: Some(text_document_sync),
                 code_lens_provider: code_lens,
                 document_formatting_provider: true,
+                document_range_formatting_provider: Some(lsp_types::OneOf::Left(true)),
+                document_on_type_formatting_provider: Some(
+                    lsp_types::DocumentOnTypeFormattingOptions {
+                        first_trigger_character: "}".to_string(),
+                        more_trigger_character: Some(vec![";".to_string()]),
+                    },
+                ),
                 nargo: Some(nargo),
                 definition_provider: Some(lsp_types::OneOf::Left(true)),
                 declaration_provider: Some(DeclarationCapability::Simple(true)),
@@ -127,6 +161,82 @@ pub(crate) fn on_initialize(
                         work_done_progress: None,
                     },
                 })),
+                hover_provider: Some(lsp_types::OneOf::Right(lsp_types::HoverOptions {
+                    work_done_progress_options: WorkDoneProgressOptions {
+                        work_done_progress: None,
+                    },
+                })),
+                completion_provider: Some(lsp_types::CompletionOptions {
+                    resolve_provider: Some(false),
+                    trigger_characters: Some(vec![
+                        ".".to_string(),
+                        ":".to_string(),
+                        "[".to_string(),
+                    ]),
+                    all_commit_characters: None,
+                    work_done_progress_options: WorkDoneProgressOptions {
+                        work_done_progress: None,
+                    },
+                    completion_item: None,
+                }),
+                document_symbol_provider: Some(lsp_types::OneOf::Right(
+                    lsp_types::DocumentSymbolOptions {
+                        label: None,
+                        work_done_progress_options: WorkDoneProgressOptions {
+                            work_done_progress: None,
+                        },
+                    },
+                )),
+                workspace_symbol_provider: Some(lsp_types::OneOf::Right(
+                    lsp_types::WorkspaceSymbolOptions {
+                        resolve_provider: Some(false),
+                        work_done_progress_options: WorkDoneProgressOptions {
+                            work_done_progress: None,
+                        },
+                    },
+                )),
+                semantic_tokens_provider: Some(
+                    lsp_types::SemanticTokensOptions {
+                        work_done_progress_options: WorkDoneProgressOptions {
+                            work_done_progress: None,
+                        },
+                        legend: semantic_tokens::semantic_tokens_legend(),
+                        range: Some(false),
+                        full: Some(lsp_types::SemanticTokensFullOptions::Bool(true)),
+                    }
+                    .into(),
+                ),
+                inlay_hint_provider: Some(lsp_types::OneOf::Right(
+                    lsp_types::InlayHintServerCapabilities::Options(lsp_types::InlayHintOptions {
+                        work_done_progress_options: WorkDoneProgressOptions {
+                            work_done_progress: None,
+                        },
+                        resolve_provider: Some(false),
+                    }),
+                )),
+                signature_help_provider: Some(lsp_types::SignatureHelpOptions {
+                    trigger_characters: Some(vec!["(".to_string(), ",".to_string()]),
+                    retrigger_characters: None,
+                    work_done_progress_options: WorkDoneProgressOptions {
+                        work_done_progress: None,
+                    },
+                }),
+                code_action_provider: Some(lsp_types::CodeActionProviderCapability::Options(
+                    lsp_types::CodeActionOptions {
+                        code_action_kinds: Some(vec![lsp_types::CodeActionKind::QUICKFIX]),
+                        work_done_progress_options: WorkDoneProgressOptions {
+                            work_done_progress: None,
+                        },
+                        resolve_provider: Some(false),
+                    },
+                )),
+                call_hierarchy_provider: Some(lsp_types::CallHierarchyServerCapability::Options(
+                    lsp_types::CallHierarchyOptions {
+                        work_done_progress_options: WorkDoneProgressOptions {
+                            work_done_progress: None,
+                        },
+                    },
+                )),
             },
             server_info: None,
         })
@@ -147,12 +257,16 @@ fn on_formatting_inner(
     let path = params.text_document.uri.to_string();
 
     if let Some(source) = state.input_files.get(&path) {
+        let config = read_format_config(&params.text_document.uri)?;
+
         let (module, errors) = noirc_frontend::parse_program(source);
-        if !errors.is_empty() {
-            return Ok(None);
+        if !errors.iter().all(ParserError::is_warning) {
+            // The items which could be parsed are still formatted
+            let range = Span::from(0..source.len() as u32);
+            return Ok(Some(format_range_edits(source, module, &errors, range, &config)));
         }
 
-        let new_text = nargo_fmt::format(source, module, &Config::default());
+        let new_text = nargo_fmt::format(source, module, &config);
 
         let start_position = Position { line: 0, character: 0 };
         let end_position = Position {
@@ -169,6 +283,82 @@ fn on_formatting_inner(
     }
 }
 
+pub(crate) fn on_range_formatting(
+    state: &mut LspState,
+    params: lsp_types::DocumentRangeFormattingParams,
+) -> impl Future<Output = Result<Option<Vec<lsp_types::TextEdit>>, ResponseError>> {
+    std::future::ready(on_range_formatting_inner(
+        state,
+        &params.text_document.uri,
+        params.range.start,
+        params.range.end,
+    ))
+}
+
+pub(crate) fn on_type_formatting(
+    state: &mut LspState,
+    params: lsp_types::DocumentOnTypeFormattingParams,
+) -> impl Future<Output = Result<Option<Vec<lsp_types::TextEdit>>, ResponseError>> {
+    // The item in which the character was typed is formatted
+    let position_params = params.text_document_position;
+    std::future::ready(on_range_formatting_inner(
+        state,
+        &position_params.text_document.uri,
+        position_params.position,
+        position_params.position,
+    ))
+}
+
+fn on_range_formatting_inner(
+    state: &LspState,
+    uri: &Url,
+    start: Position,
+    end: Position,
+) -> Result<Option<Vec<lsp_types::TextEdit>>, ResponseError> {
+    let Some(source) = state.input_files.get(&uri.to_string()) else {
+        return Ok(None);
+    };
+    let config = read_format_config(uri)?;
+
+    let file = SimpleFile::new(uri.to_string(), source.as_str());
+    let to_byte_index = |position| {
+        position_to_byte_index(&file, (), &position)
+            .map_err(|err| ResponseError::new(ErrorCode::REQUEST_FAILED, err.to_string()))
+    };
+    let range = Span::from(to_byte_index(start)? as u32..to_byte_index(end)? as u32);
+
+    let (module, errors) = noirc_frontend::parse_program(source);
+    Ok(Some(format_range_edits(source, module, &errors, range, &config)))
+}
+
+fn format_range_edits(
+    source: &str,
+    module: ParsedModule,
+    errors: &[ParserError],
+    range: Span,
+    config: &Config,
+) -> Vec<lsp_types::TextEdit> {
+    let file = SimpleFile::new("", source);
+    nargo_fmt::format_range(source, module, errors, range, config)
+        .into_iter()
+        .filter_map(|(span, new_text)| {
+            let range = crate::byte_span_to_range(&file, (), span.into())?;
+            Some(lsp_types::TextEdit { range, new_text })
+        })
+        .collect()
+}
+
+/// Reads the `noirfmt.toml` of the package containing the document, as `nargo fmt` would
+fn read_format_config(uri: &Url) -> Result<Config, ResponseError> {
+    let package_root =
+        uri.to_file_path().ok().and_then(|path| nargo_toml::find_package_root(&path).ok());
+    let Some(package_root) = package_root else {
+        return Ok(Config::default());
+    };
+    Config::read(&package_root)
+        .map_err(|err| ResponseError::new(ErrorCode::REQUEST_FAILED, err.to_string()))
+}
+
 pub(crate) fn position_to_byte_index<'a, F>(
     files: &'a F,
     file_id: F::FileId,
@@ -309,6 +499,65 @@ where
     Ok(callback(location, interner, files))
 }
 
+/// Type-checks the package containing the document at `uri` and calls `callback` with the
+/// resulting context. If the document is open, the editor's (possibly unsaved and incomplete)
+/// buffer is used instead of the file on disk.
+pub(crate) fn process_document<F, T>(
+    state: &mut LspState,
+    uri: &Url,
+    callback: F,
+) -> Result<T, ResponseError>
+where
+    F: FnOnce(&Context, CrateId, FileId) -> Result<T, ResponseError>,
+{
+    process_document_with_errors(state, uri, |context, crate_id, file_id, _| {
+        callback(context, crate_id, file_id)
+    })
+}
+
+/// Same as [process_document], but also passes the errors and warnings found while
+/// type-checking the package to `callback`.
+pub(crate) fn process_document_with_errors<F, T>(
+    state: &mut LspState,
+    uri: &Url,
+    callback: F,
+) -> Result<T, ResponseError>
+where
+    F: FnOnce(&Context, CrateId, FileId, &[(CompilationError, FileId)]) -> Result<T, ResponseError>,
+{
+    let file_path = uri.to_file_path().map_err(|_| {
+        ResponseError::new(ErrorCode::REQUEST_FAILED, "URI is not a valid file path")
+    })?;
+
+    let workspace = resolve_workspace_for_source_path(file_path.as_path())
+        .map_err(|err| ResponseError::new(ErrorCode::REQUEST_FAILED, err.to_string()))?;
+    let package = workspace.members.first().ok_or_else(|| {
+        ResponseError::new(ErrorCode::REQUEST_FAILED, "Selected workspace has no members")
+    })?;
+
+    // The file manager keeps the first source registered for a path, so adding the
+    // buffer first makes it take precedence over the file on disk.
+    let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
+    if let Some(source) = state.input_files.get(&uri.to_string()) {
+        workspace_file_manager.add_file_with_source(&file_path, source.clone());
+    }
+    insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
+    let parsed_files = parse_diff(&workspace_file_manager, state);
+
+    let (mut context, crate_id) =
+        crate::prepare_package(&workspace_file_manager, &parsed_files, package);
+
+    // The buffer is usually incomplete while typing, so errors are expected here
+    let errors = noirc_driver::collect_crate_errors(&mut context, crate_id, false, false);
+
+    let file_id =
+        context.file_manager.as_file_map().get_file_id(&PathString::from(file_path)).ok_or_else(
+            || ResponseError::new(ErrorCode::REQUEST_FAILED, "Could not find file in file manager"),
+        )?;
+
+    callback(&context, crate_id, file_id, &errors)
+}
+
 #[cfg(test)]
 mod initialization {
     use acvm::blackbox_solver::StubbedBlackBoxSolver;
@@ -362,3 +611,69 @@ mod character_to_line_offset_tests {
         assert_eq!(result, 10);
     }
 }
+
+#[cfg(test)]
+mod formatting_tests {
+    use lsp_types::{
+        DocumentFormattingParams, DocumentRangeFormattingParams, FormattingOptions, Range,
+        TextDocumentIdentifier, TextEdit,
+    };
+    use tokio::test;
+
+    use crate::test_utils;
+
+    use super::*;
+
+    const SOURCE: &str = "fn one() {\nlet x = 1;\n}\n\nfn two() {\nlet y = 2;\n}\n";
+
+    #[test]
+    async fn test_range_formatting_only_formats_overlapping_items() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("formatting").await;
+        state.input_files.insert(noir_text_document.to_string(), SOURCE.to_string());
+
+        let params = DocumentRangeFormattingParams {
+            text_document: TextDocumentIdentifier { uri: noir_text_document },
+            range: Range::new(Position::new(5, 0), Position::new(5, 3)),
+            options: FormattingOptions::default(),
+            work_done_progress_params: Default::default(),
+        };
+        let edits = on_range_formatting(&mut state, params)
+            .await
+            .expect("Could not execute on_range_formatting")
+            .expect("Expected edits");
+
+        // The package's `noirfmt.toml` sets the indentation to two spaces
+        assert_eq!(
+            edits,
+            vec![TextEdit {
+                range: Range::new(Position::new(4, 0), Position::new(6, 1)),
+                new_text: "fn two() {\n  let y = 2;\n}".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    async fn test_formatting_tolerates_parse_errors_in_other_items() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("formatting").await;
+        let source = "fn one() {\nlet x = 1;\n}\n\nfn two( {\n";
+        state.input_files.insert(noir_text_document.to_string(), source.to_string());
+
+        let params = DocumentFormattingParams {
+            text_document: TextDocumentIdentifier { uri: noir_text_document },
+            options: FormattingOptions::default(),
+            work_done_progress_params: Default::default(),
+        };
+        let edits = on_formatting(&mut state, params)
+            .await
+            .expect("Could not execute on_formatting")
+            .expect("Expected edits");
+
+        assert_eq!(
+            edits,
+            vec![TextEdit {
+                range: Range::new(Position::new(0, 0), Position::new(2, 1)),
+                new_text: "fn one() {\n  let x = 1;\n}".to_string(),
+            }]
+        );
+    }
+}
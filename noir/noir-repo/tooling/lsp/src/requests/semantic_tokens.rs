@@ -0,0 +1,290 @@
+use std::future::{self, Future};
+
+use async_lsp::{ErrorCode, ResponseError};
+use fm::{codespan_files::Files, FileId, FileMap};
+use lsp_types::{
+    SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokens, SemanticTokensLegend,
+    SemanticTokensParams, SemanticTokensResult,
+};
+use noirc_errors::{Location, Span};
+use noirc_frontend::{
+    lexer::Lexer,
+    macros_api::NodeInterner,
+    node_interner::{DefinitionKind, ReferenceId},
+    token::{Keyword, Token},
+};
+
+use crate::{byte_span_to_range, LspState};
+
+use super::process_document;
+
+/// The token types reported by the server, in the order of their index in the legend
+const TOKEN_TYPES: &[SemanticTokenType] = &[
+    SemanticTokenType::NAMESPACE,
+    SemanticTokenType::STRUCT,
+    SemanticTokenType::INTERFACE,
+    SemanticTokenType::TYPE,
+    SemanticTokenType::TYPE_PARAMETER,
+    SemanticTokenType::FUNCTION,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::KEYWORD,
+    SemanticTokenType::STRING,
+    SemanticTokenType::NUMBER,
+    SemanticTokenType::COMMENT,
+    SemanticTokenType::DECORATOR,
+];
+
+/// The token modifiers reported by the server, in the order of their bit in the legend
+const TOKEN_MODIFIERS: &[SemanticTokenModifier] =
+    &[SemanticTokenModifier::DECLARATION, SemanticTokenModifier::READONLY];
+
+const DECLARATION: u32 = 1 << 0;
+const READONLY: u32 = 1 << 1;
+
+pub(crate) fn semantic_tokens_legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: TOKEN_MODIFIERS.to_vec(),
+    }
+}
+
+pub(crate) fn on_semantic_tokens_full_request(
+    state: &mut LspState,
+    params: SemanticTokensParams,
+) -> impl Future<Output = Result<Option<SemanticTokensResult>, ResponseError>> {
+    future::ready(on_semantic_tokens_full_inner(state, params))
+}
+
+fn on_semantic_tokens_full_inner(
+    state: &mut LspState,
+    params: SemanticTokensParams,
+) -> Result<Option<SemanticTokensResult>, ResponseError> {
+    process_document(state, &params.text_document.uri, |context, _, file_id| {
+        let files = context.file_manager.as_file_map();
+        let source = files.source(file_id).map_err(|err| {
+            ResponseError::new(ErrorCode::REQUEST_FAILED, format!("Could not read file: {err:?}"))
+        })?;
+
+        let mut builder = SemanticTokensBuilder::new(files, file_id);
+        for token in Lexer::new(source).skip_comments(false).flatten() {
+            let span = token.to_span();
+            let classification = match token.token() {
+                Token::Ident(_) => {
+                    classify_identifier(Location::new(span, file_id), &context.def_interner)
+                }
+                Token::Keyword(keyword) => Some((keyword_token_type(*keyword), 0)),
+                Token::Bool(_) => Some((SemanticTokenType::KEYWORD, 0)),
+                Token::IntType(_) => Some((SemanticTokenType::TYPE, 0)),
+                Token::Int(_) => Some((SemanticTokenType::NUMBER, 0)),
+                Token::Str(_) | Token::RawStr(..) | Token::FmtStr(_) => {
+                    Some((SemanticTokenType::STRING, 0))
+                }
+                Token::LineComment(..) | Token::BlockComment(..) => {
+                    Some((SemanticTokenType::COMMENT, 0))
+                }
+                Token::Attribute(_) => Some((SemanticTokenType::DECORATOR, 0)),
+                _ => None,
+            };
+            if let Some((token_type, modifiers)) = classification {
+                builder.push(span, &token_type, modifiers);
+            }
+        }
+
+        Ok(Some(SemanticTokensResult::Tokens(builder.build())))
+    })
+}
+
+/// Classifies an identifier using the definition it resolves to. Identifiers which
+/// did not resolve (for example because of errors) are left to the client's grammar.
+fn classify_identifier(
+    location: Location,
+    interner: &NodeInterner,
+) -> Option<(SemanticTokenType, u32)> {
+    let referenced = interner.find_referenced(location)?;
+    let mut modifiers = match interner.reference_at_location(location) {
+        Some(ReferenceId::Reference(..)) | None => 0,
+        Some(_) => DECLARATION,
+    };
+
+    let token_type = match referenced {
+        ReferenceId::Module(_) => SemanticTokenType::NAMESPACE,
+        ReferenceId::Struct(_) => SemanticTokenType::STRUCT,
+        ReferenceId::Trait(_) => SemanticTokenType::INTERFACE,
+        ReferenceId::Alias(_) => SemanticTokenType::TYPE,
+        ReferenceId::Function(_) => SemanticTokenType::FUNCTION,
+        ReferenceId::Global(_) => {
+            modifiers |= READONLY;
+            SemanticTokenType::VARIABLE
+        }
+        ReferenceId::Local(id) => match interner.definition(id).kind {
+            DefinitionKind::Function(_) => SemanticTokenType::FUNCTION,
+            DefinitionKind::Global(_) => {
+                modifiers |= READONLY;
+                SemanticTokenType::VARIABLE
+            }
+            DefinitionKind::Local(_) => SemanticTokenType::VARIABLE,
+            DefinitionKind::GenericType(_) => SemanticTokenType::TYPE_PARAMETER,
+        },
+        ReferenceId::Reference(..) => return None,
+    };
+    Some((token_type, modifiers))
+}
+
+fn keyword_token_type(keyword: Keyword) -> SemanticTokenType {
+    match keyword {
+        Keyword::Bool
+        | Keyword::Char
+        | Keyword::Expr
+        | Keyword::Field
+        | Keyword::FormatString
+        | Keyword::Quoted
+        | Keyword::String
+        | Keyword::StructDefinition
+        | Keyword::TopLevelItem
+        | Keyword::TypeType => SemanticTokenType::TYPE,
+        _ => SemanticTokenType::KEYWORD,
+    }
+}
+
+/// Accumulates tokens in source order and encodes them relative to each other
+struct SemanticTokensBuilder<'a> {
+    files: &'a FileMap,
+    file_id: FileId,
+    tokens: Vec<SemanticToken>,
+    previous_line: u32,
+    previous_start: u32,
+}
+
+impl<'a> SemanticTokensBuilder<'a> {
+    fn new(files: &'a FileMap, file_id: FileId) -> Self {
+        Self { files, file_id, tokens: Vec::new(), previous_line: 0, previous_start: 0 }
+    }
+
+    fn push(&mut self, span: Span, token_type: &SemanticTokenType, modifiers: u32) {
+        let Some(range) = byte_span_to_range(self.files, self.file_id, span.into()) else {
+            return;
+        };
+        let token_type = TOKEN_TYPES
+            .iter()
+            .position(|typ| typ == token_type)
+            .expect("Token type should be part of the legend") as u32;
+
+        // Clients are not required to support tokens spanning several lines,
+        // so these are split at each line break.
+        for line in range.start.line..=range.end.line {
+            let start = if line == range.start.line { range.start.character } else { 0 };
+            let end =
+                if line == range.end.line { range.end.character } else { self.line_length(line) };
+            if end > start {
+                self.push_on_line(line, start, end - start, token_type, modifiers);
+            }
+        }
+    }
+
+    /// The length of the given line in UTF-16 code units, excluding the line break
+    fn line_length(&self, line: u32) -> u32 {
+        let Ok(source) = self.files.source(self.file_id) else { return 0 };
+        let Ok(line_range) = self.files.line_range(self.file_id, line as usize) else { return 0 };
+        let text = source[line_range].trim_end_matches(['\n', '\r']);
+        text.encode_utf16().count() as u32
+    }
+
+    fn push_on_line(
+        &mut self,
+        line: u32,
+        start: u32,
+        length: u32,
+        token_type: u32,
+        modifiers: u32,
+    ) {
+        let delta_line = line - self.previous_line;
+        let delta_start = if delta_line == 0 { start - self.previous_start } else { start };
+        self.tokens.push(SemanticToken {
+            delta_line,
+            delta_start,
+            length,
+            token_type,
+            token_modifiers_bitset: modifiers,
+        });
+        self.previous_line = line;
+        self.previous_start = start;
+    }
+
+    fn build(self) -> SemanticTokens {
+        SemanticTokens { result_id: None, data: self.tokens }
+    }
+}
+
+#[cfg(test)]
+mod semantic_tokens_tests {
+    use crate::test_utils::{self, search_in_file};
+
+    use super::*;
+    use lsp_types::{
+        PartialResultParams, Position, TextDocumentIdentifier, WorkDoneProgressParams,
+    };
+    use tokio::test;
+
+    /// Decodes the relative token positions back into absolute ones
+    fn decode(tokens: &[SemanticToken]) -> Vec<(Position, u32, &SemanticTokenType, u32)> {
+        let mut line = 0;
+        let mut start = 0;
+        tokens
+            .iter()
+            .map(|token| {
+                if token.delta_line > 0 {
+                    start = 0;
+                }
+                line += token.delta_line;
+                start += token.delta_start;
+                let token_type = &TOKEN_TYPES[token.token_type as usize];
+                (
+                    Position { line, character: start },
+                    token.length,
+                    token_type,
+                    token.token_modifiers_bitset,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    async fn test_semantic_tokens_classify_identifiers() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("symbols").await;
+
+        let params = SemanticTokensParams {
+            text_document: TextDocumentIdentifier { uri: noir_text_document.clone() },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+        let response = on_semantic_tokens_full_request(&mut state, params)
+            .await
+            .expect("Could not execute on_semantic_tokens_full_request")
+            .expect("Expected semantic tokens");
+        let SemanticTokensResult::Tokens(tokens) = response else {
+            panic!("Expected full semantic tokens");
+        };
+        let tokens = decode(&tokens.data);
+
+        let token_at = |search_string: &str, occurrence: usize| {
+            let position =
+                search_in_file(noir_text_document.path(), search_string)[occurrence].start;
+            tokens
+                .iter()
+                .find(|(token_position, ..)| *token_position == position)
+                .map(|(_, length, token_type, modifiers)| {
+                    (*length, (*token_type).clone(), *modifiers)
+                })
+                .unwrap_or_else(|| {
+                    panic!("No token for occurrence {occurrence} of {search_string}")
+                })
+        };
+
+        assert_eq!(token_at("Square", 0), (6, SemanticTokenType::STRUCT, DECLARATION));
+        assert_eq!(token_at("Square", 1), (6, SemanticTokenType::STRUCT, 0));
+        assert_eq!(token_at("ORIGIN", 1), (6, SemanticTokenType::VARIABLE, READONLY));
+        assert_eq!(token_at("shapes", 1), (6, SemanticTokenType::NAMESPACE, 0));
+        assert_eq!(token_at("fn", 0), (2, SemanticTokenType::KEYWORD, 0));
+        assert_eq!(token_at("// The", 0).1, SemanticTokenType::COMMENT);
+    }
+}
@@ -0,0 +1,286 @@
+use std::future::{self, Future};
+
+use async_lsp::{ErrorCode, ResponseError};
+use fm::codespan_files::Files;
+use lsp_types::{
+    ParameterInformation, ParameterLabel, SignatureHelp, SignatureHelpParams, SignatureInformation,
+};
+use noirc_errors::{Location, Span};
+use noirc_frontend::{
+    macros_api::NodeInterner,
+    node_interner::{DefinitionKind, FuncId, ReferenceId},
+    Type,
+};
+
+use crate::LspState;
+
+use super::{hover::format_pattern, position_to_byte_index, process_document};
+
+pub(crate) fn on_signature_help_request(
+    state: &mut LspState,
+    params: SignatureHelpParams,
+) -> impl Future<Output = Result<Option<SignatureHelp>, ResponseError>> {
+    future::ready(on_signature_help_inner(state, params))
+}
+
+fn on_signature_help_inner(
+    state: &mut LspState,
+    params: SignatureHelpParams,
+) -> Result<Option<SignatureHelp>, ResponseError> {
+    let position_params = params.text_document_position_params;
+    process_document(state, &position_params.text_document.uri, |context, _, file_id| {
+        let files = context.file_manager.as_file_map();
+        let source = files.source(file_id).map_err(|err| {
+            ResponseError::new(ErrorCode::REQUEST_FAILED, format!("Could not read file: {err:?}"))
+        })?;
+        let index = position_to_byte_index(files, file_id, &position_params.position)
+            .map_err(|err| ResponseError::new(ErrorCode::REQUEST_FAILED, err.to_string()))?;
+
+        let Some(call) = find_enclosing_call(&source[..index]) else {
+            return Ok(None);
+        };
+        let location = Location::new(Span::from(call.name_start..call.name_end), file_id);
+        let name = &source[call.name_start as usize..call.name_end as usize];
+        let Some(signature) = resolve_signature(name, location, &context.def_interner) else {
+            return Ok(None);
+        };
+
+        // The object of a method call is the `self` argument, which is not written in the parentheses
+        let mut active_parameter = call.argument_index;
+        if call.is_method_call && signature.has_self_parameter {
+            active_parameter += 1;
+        }
+
+        Ok(Some(SignatureHelp {
+            signatures: vec![signature.information],
+            active_signature: Some(0),
+            active_parameter: Some(active_parameter),
+        }))
+    })
+}
+
+/// The call surrounding the cursor, as found in the text before it
+#[derive(Debug, PartialEq, Eq)]
+struct EnclosingCall {
+    name_start: u32,
+    name_end: u32,
+    is_method_call: bool,
+    /// The index of the argument the cursor is in
+    argument_index: u32,
+}
+
+/// Scans backwards for the opening parenthesis of the call the cursor is in, skipping
+/// over nested brackets and counting the commas separating the previous arguments.
+fn find_enclosing_call(text: &str) -> Option<EnclosingCall> {
+    let mut depth = 0;
+    let mut argument_index = 0;
+    let mut open_paren = None;
+    for (index, char) in text.char_indices().rev() {
+        match char {
+            ')' | ']' | '}' => depth += 1,
+            '(' | '[' | '{' if depth > 0 => depth -= 1,
+            '(' => {
+                open_paren = Some(index);
+                break;
+            }
+            // The cursor is inside an array literal or a block rather than in a call
+            '[' | '{' | ';' => return None,
+            ',' if depth == 0 => argument_index += 1,
+            _ => (),
+        }
+    }
+
+    let before = skip_turbofish(text[..open_paren?].trim_end());
+    let name = &before[before.trim_end_matches(is_ident_char).len()..];
+    if name.is_empty() || name.starts_with(|char: char| char.is_ascii_digit()) {
+        return None;
+    }
+
+    let name_end = before.len();
+    let name_start = name_end - name.len();
+    let is_method_call = before[..name_start].trim_end().ends_with('.');
+    Some(EnclosingCall {
+        name_start: name_start as u32,
+        name_end: name_end as u32,
+        is_method_call,
+        argument_index,
+    })
+}
+
+/// Strips generics given with a turbofish, as in `foo::<Field>`, from the end of `text`
+fn skip_turbofish(text: &str) -> &str {
+    let Some(rest) = text.strip_suffix('>') else {
+        return text;
+    };
+    let mut depth = 1;
+    for (index, char) in rest.char_indices().rev() {
+        match char {
+            '>' => depth += 1,
+            '<' => {
+                depth -= 1;
+                if depth == 0 {
+                    return rest[..index].trim_end().strip_suffix("::").unwrap_or(text).trim_end();
+                }
+            }
+            _ => (),
+        }
+    }
+    text
+}
+
+fn is_ident_char(char: char) -> bool {
+    char.is_ascii_alphanumeric() || char == '_'
+}
+
+struct Signature {
+    information: SignatureInformation,
+    has_self_parameter: bool,
+}
+
+/// Resolves the callee through the name resolution results. Calls which are still being
+/// typed may not have been elaborated yet, in which case functions are looked up by name.
+fn resolve_signature(name: &str, location: Location, interner: &NodeInterner) -> Option<Signature> {
+    match interner.find_referenced(location) {
+        Some(ReferenceId::Function(func_id)) => Some(function_signature(func_id, interner)),
+        Some(ReferenceId::Local(id)) => match interner.definition(id).kind {
+            DefinitionKind::Function(func_id) => Some(function_signature(func_id, interner)),
+            DefinitionKind::Local(_) => {
+                closure_signature(name, interner.definition_type(id).follow_bindings())
+            }
+            DefinitionKind::Global(_) | DefinitionKind::GenericType(_) => None,
+        },
+        Some(_) => None,
+        None => interner.find_function(name).map(|func_id| function_signature(func_id, interner)),
+    }
+}
+
+fn function_signature(func_id: FuncId, interner: &NodeInterner) -> Signature {
+    let func_meta = interner.function_meta(&func_id);
+    let parameters = func_meta
+        .parameters
+        .iter()
+        .map(|(pattern, typ, _)| format!("{}: {typ}", format_pattern(pattern, interner)));
+    let has_self_parameter = has_self_parameter(func_id, interner);
+
+    let name = interner.function_name(&func_id);
+    let information = signature_information(name, parameters, func_meta.return_type());
+    Signature { information, has_self_parameter }
+}
+
+/// Whether the function is a method taking `self`, and can thus be called with the dot syntax
+pub(super) fn has_self_parameter(func_id: FuncId, interner: &NodeInterner) -> bool {
+    let func_meta = interner.function_meta(&func_id);
+    func_meta.parameters.iter().next().map_or(false, |(pattern, _, _)| {
+        format_pattern(pattern, interner).trim_start_matches("mut ") == "self"
+    })
+}
+
+fn closure_signature(name: &str, typ: Type) -> Option<Signature> {
+    let Type::Function(arguments, return_type, _) = typ else {
+        return None;
+    };
+    let parameters = arguments.iter().map(|typ| typ.to_string());
+    let information = signature_information(name, parameters, &return_type);
+    Some(Signature { information, has_self_parameter: false })
+}
+
+/// Builds a label such as `fn foo(x: Field, y: u8) -> bool`, with each parameter
+/// referring to its offsets in the label.
+fn signature_information(
+    name: &str,
+    parameters: impl Iterator<Item = String>,
+    return_type: &Type,
+) -> SignatureInformation {
+    let mut label = format!("fn {name}(");
+    let mut parameter_information = Vec::new();
+    for (index, parameter) in parameters.enumerate() {
+        if index > 0 {
+            label.push_str(", ");
+        }
+        let start = utf16_length(&label);
+        label.push_str(&parameter);
+        let end = utf16_length(&label);
+        parameter_information.push(ParameterInformation {
+            label: ParameterLabel::LabelOffsets([start, end]),
+            documentation: None,
+        });
+    }
+    label.push(')');
+    if !matches!(return_type, Type::Unit) {
+        label.push_str(&format!(" -> {return_type}"));
+    }
+
+    SignatureInformation {
+        label,
+        documentation: None,
+        parameters: Some(parameter_information),
+        active_parameter: None,
+    }
+}
+
+fn utf16_length(text: &str) -> u32 {
+    text.encode_utf16().count() as u32
+}
+
+#[cfg(test)]
+mod signature_help_tests {
+    use crate::test_utils::{self, search_in_file};
+
+    use super::*;
+    use lsp_types::{Position, TextDocumentIdentifier, TextDocumentPositionParams};
+    use tokio::test;
+
+    #[test]
+    async fn test_find_enclosing_call() {
+        let call = find_enclosing_call("let x = foo(a, bar(b, c), ").unwrap();
+        assert_eq!(
+            call,
+            EnclosingCall { name_start: 8, name_end: 11, is_method_call: false, argument_index: 2 }
+        );
+
+        let call = find_enclosing_call("point.scale::<u8>(").unwrap();
+        assert_eq!(
+            call,
+            EnclosingCall { name_start: 6, name_end: 11, is_method_call: true, argument_index: 0 }
+        );
+
+        assert_eq!(find_enclosing_call("foo(a); let y = [1, "), None);
+        assert_eq!(find_enclosing_call("let y = (1 + "), None);
+    }
+
+    async fn signature_help_at(search_string: &str) -> SignatureHelp {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("inlay_hints").await;
+
+        let range = search_in_file(noir_text_document.path(), search_string)[0];
+        let params = SignatureHelpParams {
+            context: None,
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: noir_text_document },
+                position: Position { line: range.end.line, character: range.end.character },
+            },
+            work_done_progress_params: Default::default(),
+        };
+
+        on_signature_help_request(&mut state, params)
+            .await
+            .expect("Could not execute on_signature_help_request")
+            .expect("Expected signature help")
+    }
+
+    #[test]
+    async fn test_signature_help_shows_active_parameter() {
+        let help = signature_help_at("power(3, ").await;
+        assert_eq!(help.signatures[0].label, "fn power(base: u32, exponent: u32) -> u32");
+        assert_eq!(help.active_parameter, Some(1));
+
+        let parameters = help.signatures[0].parameters.as_ref().unwrap();
+        assert_eq!(parameters[1].label, ParameterLabel::LabelOffsets([20, 33]));
+    }
+
+    #[test]
+    async fn test_signature_help_for_closure_parameter() {
+        let help = signature_help_at("f(").await;
+        assert_eq!(help.signatures[0].label, "fn f(Field) -> Field");
+        assert_eq!(help.active_parameter, Some(0));
+    }
+}
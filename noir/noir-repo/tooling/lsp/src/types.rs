@@ -1,7 +1,11 @@
 use fm::FileId;
 use lsp_types::{
-    DeclarationCapability, DefinitionOptions, OneOf, ReferencesOptions, RenameOptions,
-    TypeDefinitionProviderCapability,
+    CallHierarchyServerCapability, CodeActionProviderCapability, CompletionOptions,
+    DeclarationCapability, DefinitionOptions, DocumentOnTypeFormattingOptions,
+    DocumentRangeFormattingOptions, DocumentSymbolOptions, HoverOptions,
+    InlayHintServerCapabilities, OneOf, ReferencesOptions, RenameOptions,
+    SemanticTokensServerCapabilities, SignatureHelpOptions, TypeDefinitionProviderCapability,
+    WorkspaceSymbolOptions,
 };
 use noirc_driver::DebugFile;
 use noirc_errors::{debug_info::OpCodesCount, Location};
@@ -133,6 +137,15 @@ pub(crate) struct ServerCapabilities {
     /// The server provides document formatting.
     pub(crate) document_formatting_provider: bool,
 
+    /// The server provides document range formatting.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) document_range_formatting_provider:
+        Option<OneOf<bool, DocumentRangeFormattingOptions>>,
+
+    /// The server provides document formatting on typing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) document_on_type_formatting_provider: Option<DocumentOnTypeFormattingOptions>,
+
     /// The server handles and provides custom nargo messages.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) nargo: Option<NargoCapability>,
@@ -144,6 +157,42 @@ pub(crate) struct ServerCapabilities {
     /// The server provides references support.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) references_provider: Option<OneOf<bool, ReferencesOptions>>,
+
+    /// The server provides hover support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) hover_provider: Option<OneOf<bool, HoverOptions>>,
+
+    /// The server provides completion support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) completion_provider: Option<CompletionOptions>,
+
+    /// The server provides document symbol support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) document_symbol_provider: Option<OneOf<bool, DocumentSymbolOptions>>,
+
+    /// The server provides workspace symbol support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) workspace_symbol_provider: Option<OneOf<bool, WorkspaceSymbolOptions>>,
+
+    /// The server provides semantic tokens support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) semantic_tokens_provider: Option<SemanticTokensServerCapabilities>,
+
+    /// The server provides inlay hints support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) inlay_hint_provider: Option<OneOf<bool, InlayHintServerCapabilities>>,
+
+    /// The server provides signature help support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) signature_help_provider: Option<SignatureHelpOptions>,
+
+    /// The server provides code actions support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) code_action_provider: Option<CodeActionProviderCapability>,
+
+    /// The server provides call hierarchy support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) call_hierarchy_provider: Option<CallHierarchyServerCapability>,
 }
 
 #[derive(Debug, PartialEq, Clone, Default, Deserialize, Serialize)]
@@ -32,6 +32,19 @@ struct ProgramExecutor<'a, F, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecut
     // This is used to fetch the function we want to execute
     // and to resolve call stack locations across many function calls.
     current_function_index: usize,
+
+    // Whether to sample the call stack of each executed Brillig opcode
+    profiling_active: bool,
+
+    profiling_samples: Vec<ProfilingSample>,
+}
+
+/// A sample of the call stack taken for each Brillig opcode executed while profiling
+#[derive(Debug, Clone)]
+pub struct ProfilingSample {
+    /// The locations of the call stack across ACIR function calls, ending with the location
+    /// of the executed Brillig opcode
+    pub call_stack: Vec<ResolvedOpcodeLocation>,
 }
 
 impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
@@ -42,6 +55,7 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
         unconstrained_functions: &'a [BrilligBytecode<F>],
         blackbox_solver: &'a B,
         foreign_call_executor: &'a mut E,
+        profiling_active: bool,
     ) -> Self {
         ProgramExecutor {
             functions,
@@ -51,11 +65,13 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
             foreign_call_executor,
             call_stack: Vec::default(),
             current_function_index: 0,
+            profiling_active,
+            profiling_samples: Vec::new(),
         }
     }
 
-    fn finalize(self) -> WitnessStack<F> {
-        self.witness_stack
+    fn finalize(self) -> (WitnessStack<F>, Vec<ProfilingSample>) {
+        (self.witness_stack, self.profiling_samples)
     }
 
     #[tracing::instrument(level = "trace", skip_all)]
@@ -70,11 +86,31 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
             initial_witness,
             self.unconstrained_functions,
             &circuit.assert_messages,
+            self.profiling_active,
         );
 
         loop {
             let solver_status = acvm.solve();
 
+            if self.profiling_active {
+                let samples: Vec<_> = acvm
+                    .take_profiling_samples()
+                    .into_iter()
+                    .map(|sample| {
+                        let brillig_call_stack =
+                            sample.call_stack.into_iter().map(|opcode_location| {
+                                ResolvedOpcodeLocation {
+                                    acir_function_index: self.current_function_index,
+                                    opcode_location,
+                                }
+                            });
+                        let call_stack = self.call_stack.iter().copied().chain(brillig_call_stack);
+                        ProfilingSample { call_stack: call_stack.collect() }
+                    })
+                    .collect();
+                self.profiling_samples.extend(samples);
+            }
+
             match solver_status {
                 ACVMStatus::Solved => break,
                 ACVMStatus::InProgress => {
@@ -179,11 +215,45 @@ pub fn execute_program<F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCal
     blackbox_solver: &B,
     foreign_call_executor: &mut E,
 ) -> Result<WitnessStack<F>, NargoError<F>> {
+    let (witness_stack, _) = execute_program_inner(
+        program,
+        initial_witness,
+        blackbox_solver,
+        foreign_call_executor,
+        false,
+    )?;
+    Ok(witness_stack)
+}
+
+/// Executes the program like [execute_program], also sampling the call stack of each executed
+/// Brillig opcode.
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn execute_program_with_profiling<
+    F: AcirField,
+    B: BlackBoxFunctionSolver<F>,
+    E: ForeignCallExecutor<F>,
+>(
+    program: &Program<F>,
+    initial_witness: WitnessMap<F>,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut E,
+) -> Result<(WitnessStack<F>, Vec<ProfilingSample>), NargoError<F>> {
+    execute_program_inner(program, initial_witness, blackbox_solver, foreign_call_executor, true)
+}
+
+fn execute_program_inner<F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>(
+    program: &Program<F>,
+    initial_witness: WitnessMap<F>,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut E,
+    profiling_active: bool,
+) -> Result<(WitnessStack<F>, Vec<ProfilingSample>), NargoError<F>> {
     let mut executor = ProgramExecutor::new(
         &program.functions,
         &program.unconstrained_functions,
         blackbox_solver,
         foreign_call_executor,
+        profiling_active,
     );
     let main_witness = executor.execute_circuit(initial_witness)?;
     executor.witness_stack.push(0, main_witness);
@@ -61,7 +61,7 @@ const IGNORED_BRILLIG_TESTS: [&str; 11] = [
 /// Certain features are only available in the elaborator.
 /// We skip these tests for non-elaborator code since they are not
 /// expected to work there. This can be removed once the old code is removed.
-const IGNORED_NEW_FEATURE_TESTS: [&str; 8] = [
+const IGNORED_NEW_FEATURE_TESTS: [&str; 10] = [
     "macros",
     "wildcard_type",
     "type_definition_annotation",
@@ -70,6 +70,8 @@ const IGNORED_NEW_FEATURE_TESTS: [&str; 8] = [
     "comptime_traits",
     "comptime_slice_methods",
     "unquote_multiple_items_from_annotation",
+    "enums",
+    "trait_associated_items",
 ];
 
 fn read_test_cases(
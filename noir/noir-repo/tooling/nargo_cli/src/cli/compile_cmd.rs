@@ -117,6 +117,8 @@ pub(super) fn compile_workspace_full(
     insert_all_files_for_workspace_into_file_manager(workspace, &mut workspace_file_manager);
     let parsed_files = parse_all(&workspace_file_manager);
 
+    let compile_options =
+        &compile_options.with_ssa_dump_target_dir(&workspace.target_directory_path());
     let compiled_workspace =
         compile_workspace(&workspace_file_manager, &parsed_files, workspace, compile_options);
 
@@ -150,6 +150,12 @@ fn loop_uninitialized_dap<R: Read, W: Write>(
                     supports_disassemble_request: Some(true),
                     supports_instruction_breakpoints: Some(true),
                     supports_stepping_granularity: Some(true),
+                    supports_conditional_breakpoints: Some(true),
+                    supports_function_breakpoints: Some(true),
+                    supports_data_breakpoints: Some(true),
+                    supports_step_back: Some(true),
+                    supports_evaluate_for_hovers: Some(true),
+                    supports_set_variable: Some(true),
                     ..Default::default()
                 }));
                 server.respond(rsp)?;
@@ -101,7 +101,7 @@ pub(crate) fn compile_bin_package_for_debugging(
     let compile_options = CompileOptions {
         instrument_debug: !skip_instrumentation,
         force_brillig: !acir_mode,
-        ..compile_options
+        ..compile_options.with_ssa_dump_target_dir(&workspace.target_directory_path())
     };
 
     let compilation_result = if !skip_instrumentation {
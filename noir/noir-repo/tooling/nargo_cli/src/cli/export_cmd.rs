@@ -58,6 +58,9 @@ pub(crate) fn run(args: ExportCommand, config: NargoConfig) -> Result<(), CliErr
     insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
     let parsed_files = parse_all(&workspace_file_manager);
 
+    let compile_options =
+        args.compile_options.with_ssa_dump_target_dir(&workspace.target_directory_path());
+
     let library_packages: Vec<_> =
         workspace.into_iter().filter(|package| package.is_library()).collect();
 
@@ -69,7 +72,7 @@ pub(crate) fn run(args: ExportCommand, config: NargoConfig) -> Result<(), CliErr
                 &parsed_files,
                 &workspace,
                 package,
-                &args.compile_options,
+                &compile_options,
             )
         })
         .collect()
@@ -1,6 +1,11 @@
-use std::{fs::DirEntry, path::Path};
+use std::{
+    fs::DirEntry,
+    io::Read,
+    path::{Path, PathBuf},
+};
 
 use clap::Args;
+use fm::{FileId, FileManager};
 use nargo::{insert_all_files_for_workspace_into_file_manager, ops::report_errors};
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_driver::{file_manager_with_stdlib, NOIR_ARTIFACT_VERSION_STRING};
@@ -11,18 +16,72 @@ use crate::errors::CliError;
 
 use super::NargoConfig;
 
+/// The file argument standing for the standard input
+const STDIN_FILE: &str = "-";
+
 /// Format the Noir files in a workspace
 #[derive(Debug, Clone, Args)]
 pub(crate) struct FormatCommand {
     /// Run noirfmt in check mode
     #[arg(long)]
     check: bool,
+
+    /// Print a unified diff of the changes instead of writing them, exiting with an error if
+    /// there are any
+    #[arg(long, conflicts_with = "check")]
+    diff: bool,
+
+    /// Format these files instead of the whole workspace. Use `-` to format the standard input
+    /// and write the result to the standard output
+    files: Vec<PathBuf>,
+}
+
+/// What to do with the result of formatting a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FormatMode {
+    /// Overwrite the file with the formatted source
+    Write,
+    /// Print a summary of the changes
+    Check,
+    /// Print the changes as a unified diff
+    Diff,
 }
 
 pub(crate) fn run(args: FormatCommand, config: NargoConfig) -> Result<(), CliError> {
-    let check_mode = args.check;
+    let mode = if args.check {
+        FormatMode::Check
+    } else if args.diff {
+        FormatMode::Diff
+    } else {
+        FormatMode::Write
+    };
+
+    let fmt_config = nargo_fmt::Config::read(&config.program_dir)
+        .map_err(|err| CliError::Generic(err.to_string()))?;
 
-    let toml_path = get_package_manifest(&config.program_dir)?;
+    let has_changes = if args.files.is_empty() {
+        format_workspace(&config.program_dir, mode, &fmt_config)?
+    } else {
+        format_files(&args.files, mode, &fmt_config)?
+    };
+
+    if has_changes && mode != FormatMode::Write {
+        std::process::exit(1);
+    } else if mode == FormatMode::Check {
+        println!("No formatting changes were detected");
+    }
+
+    Ok(())
+}
+
+/// Formats all of the Noir files of the workspace, returning whether any of them changed.
+/// Files which can't be parsed are left untouched, and make this fail once the others are formatted.
+fn format_workspace(
+    program_dir: &Path,
+    mode: FormatMode,
+    fmt_config: &nargo_fmt::Config,
+) -> Result<bool, CliError> {
+    let toml_path = get_package_manifest(program_dir)?;
     let workspace = resolve_workspace_from_toml(
         &toml_path,
         PackageSelection::All,
@@ -32,71 +91,161 @@ pub(crate) fn run(args: FormatCommand, config: NargoConfig) -> Result<(), CliErr
     let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
     insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
 
-    let config = nargo_fmt::Config::read(&config.program_dir)
-        .map_err(|err| CliError::Generic(err.to_string()))?;
-
-    let mut check_exit_code_one = false;
+    let mut has_changes = false;
+    let mut unparsable_files = Vec::new();
 
     for package in &workspace {
         visit_noir_files(&package.root_dir.join("src"), &mut |entry| {
             let file_id = workspace_file_manager.name_to_id(entry.path().to_path_buf()).expect("The file should exist since we added all files in the package into the file manager");
 
-            let (parsed_module, errors) = parse_file(&workspace_file_manager, file_id);
-
-            let is_all_warnings = errors.iter().all(ParserError::is_warning);
-            if !is_all_warnings {
-                let errors = errors
-                    .into_iter()
-                    .map(|error| {
-                        let error = CustomDiagnostic::from(&error);
-                        error.in_file(file_id)
-                    })
-                    .collect();
-
-                let _ = report_errors::<()>(
-                    Err(errors),
-                    &workspace_file_manager,
-                    false,
-                    false,
-                );
+            let Some(formatted) = format_file(&workspace_file_manager, file_id, fmt_config) else {
+                unparsable_files.push(entry.path());
                 return Ok(());
-            }
+            };
 
             let original = workspace_file_manager.fetch_file(file_id).expect("The file should exist since we added all files in the package into the file manager");
-            let formatted = nargo_fmt::format(original, parsed_module, &config);
-
-            if check_mode {
-                let diff = similar_asserts::SimpleDiff::from_str(
-                    original,
-                    &formatted,
-                    "original",
-                    "formatted",
-                )
-                .to_string();
+            has_changes |= emit_formatted(&entry.path(), original, &formatted, mode)?;
+            Ok(())
+        })
+        .map_err(|error| CliError::Generic(error.to_string()))?;
+    }
+
+    if !unparsable_files.is_empty() {
+        return Err(parse_failure(&unparsable_files));
+    }
+    Ok(has_changes)
+}
+
+/// Formats the given files, which don't need to be part of a workspace, returning whether
+/// any of them changed. The standard input is formatted to the standard output, where nothing
+/// is written if it can't be parsed.
+fn format_files(
+    files: &[PathBuf],
+    mode: FormatMode,
+    fmt_config: &nargo_fmt::Config,
+) -> Result<bool, CliError> {
+    let mut file_manager = FileManager::new(Path::new(""));
+    let mut has_changes = false;
+    let mut unparsable_files = Vec::new();
+
+    for path in files {
+        let is_stdin = path.as_os_str() == STDIN_FILE;
+        let source = if is_stdin {
+            let mut source = String::new();
+            std::io::stdin().read_to_string(&mut source).map_err(|error| {
+                CliError::Generic(format!("Could not read the standard input: {error}"))
+            })?;
+            source
+        } else {
+            std::fs::read_to_string(path).map_err(|error| {
+                CliError::Generic(format!("Could not read {}: {error}", path.display()))
+            })?
+        };
 
-                if !diff.lines().next().is_some_and(|line| line.contains("Invisible differences")) {
-                    if !check_exit_code_one {
-                        check_exit_code_one = true;
-                    }
+        let file_id = file_manager
+            .add_file_with_source(path, source)
+            .expect("Adding a file to the file manager should not fail");
 
-                    println!("{diff}");
-                }
+        let Some(formatted) = format_file(&file_manager, file_id, fmt_config) else {
+            unparsable_files.push(path.clone());
+            continue;
+        };
 
-                Ok(())
+        let original = file_manager.fetch_file(file_id).expect("The file was just added");
+        if is_stdin && mode == FormatMode::Write {
+            print!("{formatted}");
+        } else {
+            has_changes |= emit_formatted(path, original, &formatted, mode)?;
+        }
+    }
+
+    if !unparsable_files.is_empty() {
+        return Err(parse_failure(&unparsable_files));
+    }
+    Ok(has_changes)
+}
+
+/// The error reported when some of the files to format could not be parsed
+fn parse_failure(unparsable_files: &[PathBuf]) -> CliError {
+    let files: Vec<String> = unparsable_files
+        .iter()
+        .map(|path| {
+            if path.as_os_str() == STDIN_FILE {
+                "the standard input".to_string()
             } else {
-                std::fs::write(entry.path(), formatted)
+                path.display().to_string()
             }
         })
-        .map_err(|error| CliError::Generic(error.to_string()))?;
-    }
+        .collect();
+    CliError::Generic(format!("Could not format {} because of parse errors", files.join(", ")))
+}
 
-    if check_exit_code_one {
-        std::process::exit(1);
-    } else if check_mode {
-        println!("No formatting changes were detected");
+/// Formats a file, reporting its parse errors and returning `None` if it can't be parsed
+fn format_file(
+    file_manager: &FileManager,
+    file_id: FileId,
+    fmt_config: &nargo_fmt::Config,
+) -> Option<String> {
+    let (parsed_module, errors) = parse_file(file_manager, file_id);
+
+    let is_all_warnings = errors.iter().all(ParserError::is_warning);
+    if !is_all_warnings {
+        let errors = errors
+            .into_iter()
+            .map(|error| {
+                let error = CustomDiagnostic::from(&error);
+                error.in_file(file_id)
+            })
+            .collect();
+
+        let _ = report_errors::<()>(Err(errors), file_manager, false, false);
+        return None;
     }
 
-    Ok(())
+    let original = file_manager.fetch_file(file_id).expect("The file should exist");
+    Some(nargo_fmt::format(original, parsed_module, fmt_config))
+}
+
+/// Writes or reports the formatted version of a file, returning whether it changed
+fn emit_formatted(
+    path: &Path,
+    original: &str,
+    formatted: &str,
+    mode: FormatMode,
+) -> std::io::Result<bool> {
+    match mode {
+        FormatMode::Write => {
+            if original != formatted {
+                std::fs::write(path, formatted)?;
+            }
+            Ok(false)
+        }
+        FormatMode::Check => {
+            let diff =
+                similar_asserts::SimpleDiff::from_str(original, formatted, "original", "formatted")
+                    .to_string();
+
+            if diff.lines().next().is_some_and(|line| line.contains("Invisible differences")) {
+                return Ok(false);
+            }
+
+            println!("{diff}");
+            Ok(true)
+        }
+        FormatMode::Diff => {
+            if original == formatted {
+                return Ok(false);
+            }
+
+            let path = path.display().to_string();
+            let diff = similar::TextDiff::from_lines(original, formatted)
+                .unified_diff()
+                .header(&path, &path)
+                .to_string();
+            print!("{diff}");
+            Ok(true)
+        }
+    }
 }
 
 fn visit_noir_files(
@@ -6,7 +6,9 @@ use iter_extended::vecmap;
 use nargo::package::Package;
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_artifacts::{debug::DebugArtifact, program::ProgramArtifact};
-use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_driver::{
+    AutoFoldedFunction, BrilligRegisterUsage, CompileOptions, NOIR_ARTIFACT_VERSION_STRING,
+};
 use noirc_errors::{debug_info::OpCodesCount, Location};
 use noirc_frontend::graph::CrateName;
 use prettytable::{row, table, Row};
@@ -24,6 +26,8 @@ use super::{
 /// Current information provided per circuit:
 /// 1. The number of ACIR opcodes
 /// 2. Counts the final number gates in the circuit used by a backend
+///
+/// The peak number of registers used by each unconstrained function is shown as well.
 #[derive(Debug, Clone, Args)]
 #[clap(visible_alias = "i")]
 pub(crate) struct InfoCommand {
@@ -102,14 +106,46 @@ pub(crate) fn run(args: InfoCommand, config: NargoConfig) -> Result<(), CliError
         if !info_report.programs.is_empty() {
             let mut program_table =
                 table!([Fm->"Package", Fm->"Function", Fm->"Expression Width", Fm->"ACIR Opcodes"]);
+            let mut auto_fold_table = table!([Fm->"Package", Fm->"Auto-folded Function", Fm->"Estimated Cost", Fm->"Call Count"]);
+            let mut register_table =
+                table!([Fm->"Package", Fm->"Unconstrained Function", Fm->"Peak Registers"]);
+            let has_auto_folded =
+                info_report.programs.iter().any(|program| !program.auto_folded.is_empty());
+            let has_unconstrained_functions = info_report
+                .programs
+                .iter()
+                .any(|program| !program.brillig_register_usage.is_empty());
 
             for program_info in info_report.programs {
+                for function in &program_info.auto_folded {
+                    auto_fold_table.add_row(row![
+                        Fm->format!("{}", program_info.package_name),
+                        Fc->format!("{}", function.name),
+                        format!("{}", function.estimated_cost),
+                        format!("{}", function.call_count),
+                    ]);
+                }
+
+                for function in &program_info.brillig_register_usage {
+                    register_table.add_row(row![
+                        Fm->format!("{}", program_info.package_name),
+                        Fc->format!("{}", function.name),
+                        format!("{}", function.peak_registers),
+                    ]);
+                }
+
                 let program_rows: Vec<Row> = program_info.into();
                 for row in program_rows {
                     program_table.add_row(row);
                 }
             }
             program_table.printstd();
+            if has_auto_folded {
+                auto_fold_table.printstd();
+            }
+            if has_unconstrained_functions {
+                register_table.printstd();
+            }
         }
     }
 
@@ -176,6 +212,10 @@ struct ProgramInfo {
     #[serde(skip)]
     expression_width: ExpressionWidth,
     functions: Vec<FunctionInfo>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    auto_folded: Vec<AutoFoldedFunction>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    brillig_register_usage: Vec<BrilligRegisterUsage>,
 }
 
 impl From<ProgramInfo> for Vec<Row> {
@@ -224,6 +264,8 @@ fn count_opcodes_and_gates_in_program(
     package: &Package,
     expression_width: ExpressionWidth,
 ) -> ProgramInfo {
+    let auto_folded = compiled_program.auto_folded;
+    let brillig_register_usage = compiled_program.brillig_register_usage;
     let functions = compiled_program
         .bytecode
         .functions
@@ -235,5 +277,11 @@ fn count_opcodes_and_gates_in_program(
         })
         .collect();
 
-    ProgramInfo { package_name: package.name.to_string(), expression_width, functions }
+    ProgramInfo {
+        package_name: package.name.to_string(),
+        expression_width,
+        functions,
+        auto_folded,
+        brillig_register_usage,
+    }
 }
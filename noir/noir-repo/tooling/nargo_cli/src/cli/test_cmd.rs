@@ -81,6 +81,9 @@ pub(crate) fn run(args: TestCommand, config: NargoConfig) -> Result<(), CliError
         None => FunctionNameMatch::Anything,
     };
 
+    let compile_options =
+        args.compile_options.with_ssa_dump_target_dir(&workspace.target_directory_path());
+
     let test_reports: Vec<Vec<(String, TestStatus)>> = workspace
         .into_iter()
         .par_bridge()
@@ -92,7 +95,7 @@ pub(crate) fn run(args: TestCommand, config: NargoConfig) -> Result<(), CliError
                 pattern,
                 args.show_output,
                 args.oracle_resolver.as_deref(),
-                &args.compile_options,
+                &compile_options,
             )
         })
         .collect::<Result<_, _>>()?;
@@ -0,0 +1,52 @@
+//! Checks that `nargo fmt` fails on files it can't parse instead of silently skipping them.
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use assert_fs::prelude::{FileWriteStr, PathChild};
+
+const UNPARSABLE_SOURCE: &str = "fn main(x: Field {\n    assert(x == 1);\n}\n";
+
+/// Creates a package to run `nargo fmt` in, as it looks for the package's manifest
+fn package_dir() -> assert_fs::TempDir {
+    let package_dir = assert_fs::TempDir::new().unwrap();
+    package_dir
+        .child("Nargo.toml")
+        .write_str(
+            "[package]\nname = \"fmt_test\"\ntype = \"bin\"\nauthors = [\"\"]\n\n[dependencies]\n",
+        )
+        .unwrap();
+    package_dir
+}
+
+#[test]
+fn fmt_stdin_fails_without_output_on_unparsable_input() {
+    let package_dir = package_dir();
+
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(&package_dir).arg("fmt").arg("-").write_stdin(UNPARSABLE_SOURCE);
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("Could not format the standard input"));
+}
+
+#[test]
+fn fmt_check_fails_on_unparsable_file() {
+    let package_dir = package_dir();
+    let file = package_dir.child("main.nr");
+    file.write_str(UNPARSABLE_SOURCE).unwrap();
+
+    for flag in ["--check", "--diff"] {
+        let mut cmd = Command::cargo_bin("nargo").unwrap();
+        cmd.current_dir(&package_dir).arg("fmt").arg(flag).arg(file.path());
+        cmd.assert()
+            .failure()
+            .stdout(predicate::str::contains("No formatting changes were detected").not())
+            .stderr(predicate::str::contains("because of parse errors"));
+    }
+
+    // The file is left untouched
+    assert_eq!(std::fs::read_to_string(file.path()).unwrap(), UNPARSABLE_SOURCE);
+}
@@ -67,7 +67,7 @@ fn generate_formatter_tests(test_file: &mut File, test_data_dir: &Path) {
 
         let (parsed_module, _errors) = noirc_frontend::parse_program(input);
 
-        let config = nargo_fmt::Config::of("{config}").unwrap();
+        let config = nargo_fmt::Config::of({config:?}).unwrap();
         let fmt_text = nargo_fmt::format(input, parsed_module, &config);
 
         if std::env::var("UPDATE_EXPECT").is_ok() {{
@@ -90,7 +90,7 @@ fn generate_formatter_tests(test_file: &mut File, test_data_dir: &Path) {
 
             let (parsed_module, _errors) = noirc_frontend::parse_program(expected_output);
 
-            let config = nargo_fmt::Config::of("{config}").unwrap();
+            let config = nargo_fmt::Config::of({config:?}).unwrap();
             let fmt_text = nargo_fmt::format(expected_output, parsed_module, &config);
 
             similar_asserts::assert_eq!(fmt_text, expected_output);
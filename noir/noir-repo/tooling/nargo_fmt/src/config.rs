@@ -50,6 +50,42 @@ config! {
     array_width: usize, 100, "Maximum width of an array literal before falling back to vertical formatting";
     fn_call_width: usize, 60, "Maximum width of the args of a function call before falling back to vertical formatting";
     single_line_if_else_max_width: usize, 50, "Maximum line length for single line if-else expressions";
+    reorder_imports: bool, false, "Sort consecutive imports, and the items of import lists, alphabetically";
+    group_imports: GroupImports, GroupImports::Preserve, "How to group consecutive imports";
+    imports_granularity: ImportsGranularity, ImportsGranularity::Preserve, "How to merge or split consecutive imports";
+    trailing_comma: TrailingComma, TrailingComma::Never, "When to add a trailing comma to lists";
+    fn_single_line: bool, false, "Put functions whose body is a single expression on a single line";
+    where_single_line: bool, false, "Put where clauses on the same line as the function signature when they fit";
+}
+
+/// How consecutive imports are grouped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum GroupImports {
+    /// Keep the imports in the order they are written
+    Preserve,
+    /// Split the imports in three groups, separated by an empty line: imports from `std`,
+    /// from dependencies, and from the current crate
+    StdExternalCrate,
+}
+
+/// How consecutive imports are merged or split
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum ImportsGranularity {
+    /// Keep the imports as they are written
+    Preserve,
+    /// Merge the imports from the same crate into a single `use`
+    Crate,
+    /// Split the imports so that each `use` imports a single item
+    Item,
+}
+
+/// When lists get a comma after their last element
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum TrailingComma {
+    Always,
+    Never,
+    /// Only when the list is split over several lines
+    Vertical,
 }
 
 impl Config {
@@ -25,13 +25,63 @@ mod rewrite;
 mod utils;
 mod visitor;
 
-use noirc_frontend::ParsedModule;
+use noirc_frontend::{hir::resolution::errors::Span, parser::ParserError, ParsedModule};
 use visitor::FmtVisitor;
 
-pub use config::Config;
+pub use config::{Config, GroupImports, ImportsGranularity, TrailingComma};
 
 pub fn format(source: &str, parsed_module: ParsedModule, config: &Config) -> String {
     let mut fmt = FmtVisitor::new(source, config);
     fmt.visit_file(parsed_module);
     fmt.finish()
 }
+
+/// Formats the top-level items of `source` overlapping `range`, returning each formatted item
+/// along with the span it replaces. Items containing parse errors, or preceded by a
+/// `noir-fmt:ignore` or `noirfmt: skip` comment, are left as they are, so that a file can be partially formatted
+/// while other parts of it are being edited.
+pub fn format_range(
+    source: &str,
+    parsed_module: ParsedModule,
+    errors: &[ParserError],
+    range: Span,
+    config: &Config,
+) -> Vec<(Span, String)> {
+    let mut edits = Vec::new();
+    let mut previous_item_end = 0;
+    for item in parsed_module.items {
+        let span = item.span;
+        let leading_text = &source[previous_item_end as usize..span.start() as usize];
+        previous_item_end = span.end();
+
+        let overlaps_range = span.start() <= range.end() && range.start() <= span.end();
+        let is_skipped =
+            leading_text.contains("noir-fmt:ignore") || utils::has_skip_directive(leading_text);
+        if !overlaps_range || is_skipped {
+            continue;
+        }
+
+        let has_errors = errors.iter().any(|error| {
+            !error.is_warning()
+                && span.start() <= error.span().end()
+                && error.span().start() <= span.end()
+        });
+        if has_errors {
+            continue;
+        }
+
+        // Top-level items don't depend on their surroundings, so each one is formatted on its own
+        let item_source = &source[span.start() as usize..span.end() as usize];
+        let (item_module, item_errors) = noirc_frontend::parse_program(item_source);
+        if !item_errors.iter().all(ParserError::is_warning) {
+            continue;
+        }
+
+        let formatted = format(item_source, item_module, config);
+        let formatted = formatted.trim_end();
+        if formatted != item_source {
+            edits.push((span, formatted.to_string()));
+        }
+    }
+    edits
+}
@@ -2,8 +2,9 @@ use noirc_frontend::{ast::Expression, hir::resolution::errors::Span, token::Toke
 
 use crate::{
     items::Item,
-    utils::FindToken,
+    utils::{first_line_width, FindToken},
     visitor::{expr::NewlineMode, FmtVisitor},
+    TrailingComma,
 };
 
 pub(crate) fn rewrite(
@@ -59,32 +60,44 @@ pub(crate) fn rewrite(
 
     visitor.indent.block_unindent(visitor.config);
 
-    let mut items_str = String::new();
-    let mut items = result.into_iter().peekable();
-    while let Some(next) = items.next() {
-        items_str.push_str(&next.leading);
-        if next.leading.contains('\n') && !next.value.is_empty() {
-            items_str.push_str(&indent_str);
-        }
-        items_str.push_str(&next.value);
-        items_str.push_str(&next.trailing);
-
-        if let Some(item) = items.peek() {
-            if !item.value.is_empty() {
-                items_str.push(',');
+    let join_items = |trailing_comma: bool| {
+        let mut items_str = String::new();
+        let mut items = result.iter().peekable();
+        while let Some(next) = items.next() {
+            items_str.push_str(&next.leading);
+            if next.leading.contains('\n') && !next.value.is_empty() {
+                items_str.push_str(&indent_str);
             }
-
-            if !item.leading.contains('\n') && !next.value.is_empty() {
-                items_str.push(' ');
+            items_str.push_str(&next.value);
+            items_str.push_str(&next.trailing);
+
+            if let Some(item) = items.peek() {
+                // The last element is followed by the item holding the comments before `]`
+                let is_last = item.value.is_empty();
+                if !is_last || (trailing_comma && !next.value.is_empty()) {
+                    items_str.push(',');
+                }
+
+                if !item.leading.contains('\n') && !next.value.is_empty() {
+                    items_str.push(' ');
+                }
             }
         }
+        items_str.trim().to_string()
+    };
+
+    let mut items_str = join_items(visitor.config.trailing_comma == TrailingComma::Always);
+    if visitor.config.trailing_comma == TrailingComma::Vertical
+        && (items_str.contains('\n') || first_line_width(&items_str) > visitor.shape().width)
+    {
+        items_str = join_items(true);
     }
 
     let open_bracket = if is_slice { "&[" } else { "[" };
     crate::visitor::expr::wrap_exprs(
         open_bracket,
         "]",
-        items_str.trim().into(),
+        items_str,
         nested_indent,
         visitor.shape(),
         NewlineMode::IfContainsNewLineAndWidth,
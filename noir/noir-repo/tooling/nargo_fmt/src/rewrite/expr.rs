@@ -3,11 +3,11 @@ use noirc_frontend::ast::{
 };
 use noirc_frontend::{macros_api::Span, token::Token};
 
-use crate::rewrite;
 use crate::visitor::{
     expr::{format_brackets, format_parens, NewlineMode},
     ExpressionType, FmtVisitor, Indent, Shape,
 };
+use crate::{rewrite, TrailingComma};
 
 pub(crate) fn rewrite_sub_expr(
     visitor: &FmtVisitor,
@@ -56,7 +56,7 @@ pub(crate) fn rewrite(
                 visitor.config.fn_call_width.into(),
                 visitor.fork(),
                 shape,
-                false,
+                visitor.config.trailing_comma,
                 call_expr.arguments,
                 args_span,
                 true,
@@ -79,7 +79,7 @@ pub(crate) fn rewrite(
                 visitor.config.fn_call_width.into(),
                 visitor.fork(),
                 shape,
-                false,
+                visitor.config.trailing_comma,
                 method_call_expr.arguments,
                 args_span,
                 true,
@@ -98,7 +98,12 @@ pub(crate) fn rewrite(
                 .span_before(index_expr.collection.span.end()..span.end(), Token::LeftBracket);
 
             let collection = rewrite_sub_expr(visitor, shape, index_expr.collection);
-            let index = format_brackets(visitor.fork(), false, vec![index_expr.index], index_span);
+            let index = format_brackets(
+                visitor.fork(),
+                TrailingComma::Never,
+                vec![index_expr.index],
+                index_span,
+            );
 
             format!("{collection}{index}")
         }
@@ -106,7 +111,8 @@ pub(crate) fn rewrite(
             None,
             visitor.fork(),
             shape,
-            exprs.len() == 1,
+            // A tuple with a single element needs the comma to not be read as parentheses
+            if exprs.len() == 1 { TrailingComma::Always } else { visitor.config.trailing_comma },
             exprs,
             span,
             true,
@@ -168,6 +174,7 @@ pub(crate) fn rewrite(
             format!("{path_string}{turbofish}")
         }
         ExpressionKind::Lambda(_) => visitor.slice(span).to_string(),
+        ExpressionKind::Match(_) => visitor.slice(span).to_string(),
         ExpressionKind::Quote(_) => visitor.slice(span).to_string(),
         ExpressionKind::Comptime(block, block_span) => {
             format!("comptime {}", rewrite_block(visitor, block, block_span))
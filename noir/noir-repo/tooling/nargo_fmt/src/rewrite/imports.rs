@@ -1,6 +1,7 @@
 use noirc_frontend::ast;
 
 use crate::{
+    config::{Config, GroupImports, ImportsGranularity},
     items::Item,
     visitor::{
         expr::{format_exprs, Tactic},
@@ -8,7 +9,7 @@ use crate::{
     },
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum UseSegment {
     Ident(String, Option<String>),
     List(Vec<UseTree>),
@@ -35,8 +36,14 @@ impl UseSegment {
                     })
                     .collect();
 
-                let list_str =
-                    format_exprs(visitor.config, Tactic::Mixed, false, items, nested_shape, true);
+                let list_str = format_exprs(
+                    visitor.config,
+                    Tactic::Mixed,
+                    visitor.config.trailing_comma,
+                    items,
+                    nested_shape,
+                    true,
+                );
 
                 if list_str.contains('\n') {
                     format!(
@@ -52,9 +59,26 @@ impl UseSegment {
             UseSegment::Crate => "crate".into(),
         }
     }
+
+    fn without_alias(self) -> UseSegment {
+        match self {
+            UseSegment::Ident(ident, _) => UseSegment::Ident(ident, None),
+            segment => segment,
+        }
+    }
+
+    /// The name segments are merged and sorted by, which ignores aliases
+    fn name(&self) -> &str {
+        match self {
+            UseSegment::Ident(ident, _) => ident,
+            UseSegment::List(_) => "{",
+            UseSegment::Dep => "dep",
+            UseSegment::Crate => "crate",
+        }
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct UseTree {
     path: Vec<UseSegment>,
 }
@@ -93,6 +117,132 @@ impl UseTree {
         result
     }
 
+    /// Splits a tree into one tree per imported item, e.g. `a::{b, c::d}` into `a::b` and `a::c::d`
+    fn flatten(self) -> Vec<UseTree> {
+        let mut path = self.path;
+        match path.pop() {
+            Some(UseSegment::List(list)) if !list.is_empty() => list
+                .into_iter()
+                .flat_map(UseTree::flatten)
+                .map(|tree| {
+                    let mut prefix = path.clone();
+                    prefix.extend(tree.path);
+                    UseTree { path: prefix }
+                })
+                .collect(),
+            last => {
+                path.extend(last);
+                vec![UseTree { path }]
+            }
+        }
+    }
+
+    /// Merges flattened trees sharing a prefix into a single tree, keeping the order in which
+    /// each prefix first appears
+    fn merge(trees: Vec<UseTree>) -> Vec<UseTree> {
+        // The first segment of each group, the trees importing that segment itself (which can't be
+        // part of a list, e.g. `a` next to `a::b`), and the remaining paths of the other trees
+        let mut groups: Vec<(UseSegment, Vec<UseSegment>, Vec<UseTree>)> = Vec::new();
+        for mut tree in trees {
+            if tree.path.is_empty() {
+                continue;
+            }
+            let first = tree.path.remove(0);
+            let index = match groups.iter().position(|(prefix, ..)| prefix.name() == first.name()) {
+                Some(index) => index,
+                None => {
+                    groups.push((first.without_alias(), Vec::new(), Vec::new()));
+                    groups.len() - 1
+                }
+            };
+
+            let (_, leaves, tails) = &mut groups[index];
+            if tree.path.is_empty() {
+                if !leaves.contains(&first) {
+                    leaves.push(first);
+                }
+            } else {
+                tails.push(tree);
+            }
+        }
+
+        let mut result = Vec::new();
+        for (prefix, leaves, tails) in groups {
+            result.extend(leaves.into_iter().map(|leaf| UseTree { path: vec![leaf] }));
+
+            let mut merged = UseTree::merge(tails);
+            match merged.len() {
+                0 => {}
+                1 => {
+                    let mut path = vec![prefix];
+                    path.append(&mut merged[0].path);
+                    result.push(UseTree { path });
+                }
+                _ => result.push(UseTree { path: vec![prefix, UseSegment::List(merged)] }),
+            }
+        }
+        result
+    }
+
+    /// Sorts the items of every list in the tree
+    fn sort_lists(&mut self) {
+        if let Some(UseSegment::List(list)) = self.path.last_mut() {
+            list.iter_mut().for_each(UseTree::sort_lists);
+            list.sort_by_cached_key(UseTree::sort_key);
+        }
+    }
+
+    fn sort_key(&self) -> String {
+        let names: Vec<_> = self.path.iter().map(UseSegment::name).collect();
+        names.join("::")
+    }
+
+    /// The group of the tree for `GroupImports::StdExternalCrate`
+    fn group(&self) -> usize {
+        match self.path.as_slice() {
+            [UseSegment::Ident(ident, _), ..] if ident == "std" => 0,
+            [UseSegment::Dep, UseSegment::Ident(ident, _), ..] if ident == "std" => 0,
+            [UseSegment::Crate, ..] => 2,
+            _ => 1,
+        }
+    }
+
+    /// Whether consecutive imports are rewritten together rather than one at a time
+    pub(crate) fn organizes_imports(config: &Config) -> bool {
+        config.reorder_imports
+            || config.group_imports != GroupImports::Preserve
+            || config.imports_granularity != ImportsGranularity::Preserve
+    }
+
+    /// Merges, splits, sorts and groups consecutive imports according to the config. Each of the
+    /// returned groups is meant to be separated by an empty line.
+    pub(crate) fn organize(trees: Vec<UseTree>, config: &Config) -> Vec<Vec<UseTree>> {
+        let mut trees = match config.imports_granularity {
+            ImportsGranularity::Preserve => trees,
+            ImportsGranularity::Item => trees.into_iter().flat_map(UseTree::flatten).collect(),
+            ImportsGranularity::Crate => {
+                UseTree::merge(trees.into_iter().flat_map(UseTree::flatten).collect())
+            }
+        };
+
+        if config.reorder_imports {
+            trees.iter_mut().for_each(UseTree::sort_lists);
+            trees.sort_by_cached_key(UseTree::sort_key);
+        }
+
+        match config.group_imports {
+            GroupImports::Preserve => vec![trees],
+            GroupImports::StdExternalCrate => {
+                let mut groups = vec![Vec::new(), Vec::new(), Vec::new()];
+                for tree in trees {
+                    groups[tree.group()].push(tree);
+                }
+                groups.retain(|group| !group.is_empty());
+                groups
+            }
+        }
+    }
+
     pub(crate) fn rewrite_top_level(&self, visitor: &FmtVisitor, shape: Shape) -> String {
         format!("use {};", self.rewrite(visitor, shape))
     }
@@ -24,6 +24,14 @@ pub(crate) fn comments(source: &str) -> impl Iterator<Item = String> + '_ {
     })
 }
 
+/// Whether `leading_text`, the source between an item and the previous one, contains a
+/// `// noirfmt: skip` comment asking for the item to be kept exactly as written.
+pub(crate) fn has_skip_directive(leading_text: &str) -> bool {
+    comments(leading_text).any(|comment| {
+        comment.trim().strip_prefix("noirfmt:").map_or(false, |setting| setting.trim() == "skip")
+    })
+}
+
 pub(crate) trait FindToken {
     fn find_token(&self, token: Token) -> Option<Span>;
     fn find_token_with(&self, f: impl Fn(&Token) -> bool) -> Option<Span>;
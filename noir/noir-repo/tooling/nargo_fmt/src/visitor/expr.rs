@@ -9,7 +9,7 @@ use crate::{
     items::{HasItem, Item, Items},
     rewrite,
     utils::{first_line_width, FindToken},
-    Config,
+    Config, TrailingComma,
 };
 
 impl FmtVisitor<'_> {
@@ -87,7 +87,7 @@ impl FmtVisitor<'_> {
             let exprs = format_exprs(
                 visitor.config,
                 Tactic::HorizontalVertical,
-                false,
+                visitor.config.trailing_comma,
                 exprs,
                 nested_indent,
                 true,
@@ -195,7 +195,7 @@ pub(crate) fn format_seq<T: HasItem>(
     prefix: &str,
     suffix: &str,
     visitor: FmtVisitor,
-    trailing_comma: bool,
+    trailing_comma: TrailingComma,
     exprs: Vec<T>,
     span: Span,
     tactic: Tactic,
@@ -214,7 +214,7 @@ pub(crate) fn format_seq<T: HasItem>(
 
 pub(crate) fn format_brackets(
     visitor: FmtVisitor,
-    trailing_comma: bool,
+    trailing_comma: TrailingComma,
     exprs: Vec<Expression>,
     span: Span,
 ) -> String {
@@ -239,7 +239,7 @@ pub(crate) fn format_parens(
     max_width: Option<usize>,
     visitor: FmtVisitor,
     shape: Shape,
-    trailing_comma: bool,
+    trailing_comma: TrailingComma,
     exprs: Vec<Expression>,
     span: Span,
     reduce: bool,
@@ -252,15 +252,41 @@ pub(crate) fn format_parens(
 pub(crate) fn format_exprs(
     config: &Config,
     tactic: Tactic,
-    trailing_comma: bool,
+    trailing_comma: TrailingComma,
     exprs: Vec<Item>,
     shape: Shape,
     reduce: bool,
+) -> String {
+    let tactic = tactic.definitive(&exprs, config.short_array_element_width_threshold, reduce);
+
+    match trailing_comma {
+        TrailingComma::Always => join_exprs(tactic, true, exprs, shape),
+        TrailingComma::Never => join_exprs(tactic, false, exprs, shape),
+        TrailingComma::Vertical => {
+            if tactic == DefinitiveTactic::Vertical {
+                return join_exprs(tactic, true, exprs, shape);
+            }
+
+            // Mixed lists only know whether they span several lines once they are joined
+            let result = join_exprs(tactic, false, exprs.clone(), shape);
+            if result.contains('\n') {
+                join_exprs(tactic, true, exprs, shape)
+            } else {
+                result
+            }
+        }
+    }
+}
+
+fn join_exprs(
+    tactic: DefinitiveTactic,
+    trailing_comma: bool,
+    exprs: Vec<Item>,
+    shape: Shape,
 ) -> String {
     let mut result = String::new();
     let indent_str = shape.indent.to_string();
 
-    let tactic = tactic.definitive(&exprs, config.short_array_element_width_threshold, reduce);
     let mut exprs = exprs.into_iter().enumerate().peekable();
     let mut line_len = 0;
     let mut prev_expr_trailing_comment = false;
@@ -1,12 +1,13 @@
 use crate::{
+    config::GroupImports,
     rewrite::{self, UseTree},
     utils::{
-        append_space_if_nonempty, last_line_contains_single_line_comment, last_line_used_width,
-        FindToken,
+        append_space_if_nonempty, has_skip_directive, last_line_contains_single_line_comment,
+        last_line_used_width, FindToken,
     },
     visitor::expr::{format_seq, NewlineMode},
 };
-use noirc_frontend::ast::{NoirFunction, Visibility};
+use noirc_frontend::ast::{BlockExpression, NoirFunction, Statement, StatementKind, Visibility};
 use noirc_frontend::{
     hir::resolution::errors::Span,
     parser::{Item, ItemKind},
@@ -54,7 +55,7 @@ impl super::FmtVisitor<'_> {
                 "<",
                 ">",
                 self.fork(),
-                false,
+                self.config.trailing_comma,
                 generics,
                 span,
                 HorizontalVertical,
@@ -89,7 +90,7 @@ impl super::FmtVisitor<'_> {
                 "(",
                 ")",
                 self.fork(),
-                false,
+                self.config.trailing_comma,
                 parameters,
                 params_span.into(),
                 tactic,
@@ -102,8 +103,61 @@ impl super::FmtVisitor<'_> {
         result.push_str(&return_type);
 
         let maybe_comment = self.slice(params_end..func_span.start());
+        let force_brace_newline = last_line_contains_single_line_comment(maybe_comment);
+
+        let mut result = result.trim_end().to_string();
+        if self.config.where_single_line && !func.def.where_clause.is_empty() {
+            result = self.join_where_clause(result);
+        }
+
+        (result, force_brace_newline)
+    }
+
+    /// Moves the where clause at the end of `signature` to its last line if it fits there
+    fn join_where_clause(&self, signature: String) -> String {
+        let Some(where_span) = signature.find_token(Token::Keyword(Keyword::Where)) else {
+            return signature;
+        };
+
+        let (head, clause) = signature.split_at(where_span.start() as usize);
+        if clause.contains("//") || clause.contains("/*") {
+            return signature;
+        }
+
+        let clause: Vec<_> = clause.split_whitespace().collect();
+        let line = format!("{} {}", head.trim_end(), clause.join(" ").trim_end_matches(','));
+
+        let used_width = last_line_used_width(&line, self.indent.width());
+        if used_width + 2 <= self.config.max_width {
+            line
+        } else {
+            signature
+        }
+    }
+
+    /// Formats a function whose body is a single expression on a single line, e.g.
+    /// `fn one() -> Field { 1 }`, if it fits and has no comments
+    fn format_fn_single_line(
+        &self,
+        fn_before_block: &str,
+        body: &BlockExpression,
+        body_span: Span,
+    ) -> Option<String> {
+        let [Statement { kind: StatementKind::Expression(expr), .. }] = body.statements.as_slice()
+        else {
+            return None;
+        };
+
+        let body_source = self.slice(body_span);
+        if body_source.contains("//") || body_source.contains("/*") {
+            return None;
+        }
 
-        (result.trim_end().to_string(), last_line_contains_single_line_comment(maybe_comment))
+        let expr = rewrite::sub_expr(self, self.shape(), expr.clone());
+        let line = format!("{fn_before_block} {{ {expr} }}");
+        let fits = !line.contains('\n')
+            && self.indent.width() + line.chars().count() <= self.config.max_width;
+        fits.then_some(line)
     }
 
     fn format_return_type(
@@ -145,7 +199,12 @@ impl super::FmtVisitor<'_> {
     }
 
     fn visit_module(&mut self, module: ParsedModule) {
-        for Item { kind, span } in module.items {
+        let mut items = module.items.into_iter().peekable();
+        while let Some(Item { kind, span }) = items.next() {
+            if self.visit_skipped_item(span) {
+                continue;
+            }
+
             match kind {
                 ItemKind::Function(func) => {
                     self.visit_function(span, func);
@@ -210,6 +269,48 @@ impl super::FmtVisitor<'_> {
                         self.last_position = span.end();
                     }
                 }
+                ItemKind::Import(use_tree) if UseTree::organizes_imports(self.config) => {
+                    let mut imports = vec![UseTree::from_ast(use_tree)];
+                    let mut end = span.end();
+
+                    // Only imports separated by whitespace are organized together, so that no
+                    // comment is moved. Empty lines start a new run unless imports are regrouped.
+                    while let Some(Item { kind: ItemKind::Import(_), span: next_span }) =
+                        items.peek()
+                    {
+                        let between = self.slice(end..next_span.start());
+                        let is_new_group = between.matches('\n').count() > 1
+                            && self.config.group_imports == GroupImports::Preserve;
+                        if !between.trim().is_empty() || is_new_group {
+                            break;
+                        }
+
+                        let Some(Item { kind: ItemKind::Import(use_tree), span }) = items.next()
+                        else {
+                            unreachable!("the next item is an import");
+                        };
+                        imports.push(UseTree::from_ast(use_tree));
+                        end = span.end();
+                    }
+
+                    let indent = self.indent.to_string_with_newline();
+                    let groups: Vec<_> = UseTree::organize(imports, self.config)
+                        .into_iter()
+                        .map(|group| {
+                            let group: Vec<_> = group
+                                .iter()
+                                .map(|tree| tree.rewrite_top_level(self, self.shape()))
+                                .collect();
+                            group.join(&indent)
+                        })
+                        .collect();
+
+                    self.push_rewrite(
+                        groups.join(&format!("\n{indent}")),
+                        (span.start()..end).into(),
+                    );
+                    self.last_position = end;
+                }
                 ItemKind::Import(use_tree) => {
                     let use_tree =
                         UseTree::from_ast(use_tree).rewrite_top_level(self, self.shape());
@@ -217,6 +318,7 @@ impl super::FmtVisitor<'_> {
                     self.last_position = span.end();
                 }
                 ItemKind::Struct(_)
+                | ItemKind::Enum(_)
                 | ItemKind::Trait(_)
                 | ItemKind::TraitImpl(_)
                 | ItemKind::TypeAlias(_)
@@ -229,7 +331,24 @@ impl super::FmtVisitor<'_> {
         }
     }
 
+    /// Keeps the item at `span` exactly as written if it is preceded by a `// noirfmt: skip`
+    /// comment. Unlike `noir-fmt:ignore`, this only ever applies to whole items.
+    fn visit_skipped_item(&mut self, span: Span) -> bool {
+        if !has_skip_directive(self.slice(self.last_position..span.start())) {
+            return false;
+        }
+
+        self.format_missing_indent(span.start(), true);
+        self.push_str(self.slice(span));
+        self.last_position = span.end();
+        true
+    }
+
     fn visit_function(&mut self, span: Span, func: NoirFunction) {
+        if self.visit_skipped_item(span) {
+            return;
+        }
+
         self.format_missing_indent(span.start(), true);
         if std::mem::take(&mut self.ignore_next_node) {
             self.push_str(self.slice(span));
@@ -238,6 +357,17 @@ impl super::FmtVisitor<'_> {
         }
         let (fn_before_block, force_brace_newline) =
             self.format_fn_before_block(func.clone(), span.start());
+
+        if self.config.fn_single_line && !force_brace_newline {
+            if let Some(line) =
+                self.format_fn_single_line(&fn_before_block, &func.def.body, func.def.span)
+            {
+                self.push_str(&line);
+                self.last_position = func.def.span.end();
+                return;
+            }
+        }
+
         self.push_str(&fn_before_block);
         self.push_str(if force_brace_newline { "\n" } else { " " });
         self.visit_block(func.def.body, func.def.span);
@@ -34,6 +34,15 @@ impl<F: AcirField> DebugVars<F> {
         self.frames.last().map(|(fn_id, frame)| self.build_stack_frame(fn_id, frame))
     }
 
+    /// Returns the id of the variable with the given name in the current stack frame
+    pub fn current_frame_var_id(&self, name: &str) -> Option<DebugVarId> {
+        let (_, frame) = self.frames.last()?;
+        frame
+            .keys()
+            .copied()
+            .find(|var_id| self.lookup_var(*var_id).is_some_and(|(var_name, _)| var_name == name))
+    }
+
     fn lookup_var(&self, var_id: DebugVarId) -> Option<(&str, &PrintableType)> {
         self.variables.get(&var_id).and_then(|debug_var| {
             let ptype = self.types.get(&debug_var.debug_type_id)?;
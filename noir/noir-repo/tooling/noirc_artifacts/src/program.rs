@@ -4,8 +4,8 @@ use acvm::acir::circuit::Program;
 use acvm::FieldElement;
 use fm::FileId;
 use noirc_abi::Abi;
-use noirc_driver::CompiledProgram;
 use noirc_driver::DebugFile;
+use noirc_driver::{AutoFoldedFunction, BrilligRegisterUsage, CompiledProgram};
 use noirc_errors::debug_info::ProgramDebugInfo;
 use serde::{Deserialize, Serialize};
 
@@ -37,6 +37,14 @@ pub struct ProgramArtifact {
     pub file_map: BTreeMap<FileId, DebugFile>,
 
     pub names: Vec<String>,
+
+    /// Functions which were automatically compiled as separate ACIR functions, as if marked `#[fold]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auto_folded: Vec<AutoFoldedFunction>,
+
+    /// The largest number of registers used at once by each unconstrained function.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub brillig_register_usage: Vec<BrilligRegisterUsage>,
 }
 
 impl From<CompiledProgram> for ProgramArtifact {
@@ -49,6 +57,8 @@ impl From<CompiledProgram> for ProgramArtifact {
             debug_symbols: ProgramDebugInfo { debug_infos: compiled_program.debug },
             file_map: compiled_program.file_map,
             names: compiled_program.names,
+            auto_folded: compiled_program.auto_folded,
+            brillig_register_usage: compiled_program.brillig_register_usage,
         }
     }
 }
@@ -64,6 +74,8 @@ impl From<ProgramArtifact> for CompiledProgram {
             file_map: program.file_map,
             warnings: vec![],
             names: program.names,
+            auto_folded: program.auto_folded,
+            brillig_register_usage: program.brillig_register_usage,
         }
     }
 }
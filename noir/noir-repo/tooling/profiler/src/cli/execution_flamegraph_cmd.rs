@@ -0,0 +1,247 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use acir::circuit::{Opcode, OpcodeLocation, Program};
+use acir::FieldElement;
+use bn254_blackbox_solver::Bn254BlackBoxSolver;
+use clap::Args;
+use color_eyre::eyre::{self, Context};
+
+use nargo::ops::{execute_program_with_profiling, DefaultForeignCallExecutor, ProfilingSample};
+use noirc_abi::input_parser::Format;
+use noirc_abi::MAIN_RETURN_NAME;
+use noirc_artifacts::debug::DebugArtifact;
+
+use crate::flamegraph::{FlamegraphGenerator, InfernoFlamegraphGenerator, Sample};
+use crate::fs::read_program_from_file;
+use crate::opcode_formatter::format_brillig_opcode;
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ExecutionFlamegraphCommand {
+    /// The path to the artifact JSON file
+    #[clap(long, short)]
+    artifact_path: String,
+
+    /// The path to the Prover.toml file with the inputs of the program
+    #[clap(long, short)]
+    prover_toml_path: String,
+
+    /// The output folder for the flamegraph svg files
+    #[clap(long, short)]
+    output: String,
+}
+
+pub(crate) fn run(args: ExecutionFlamegraphCommand) -> eyre::Result<()> {
+    run_with_generator(
+        &PathBuf::from(args.artifact_path),
+        &PathBuf::from(args.prover_toml_path),
+        &InfernoFlamegraphGenerator { count_name: "samples".to_string() },
+        &PathBuf::from(args.output),
+    )
+}
+
+fn run_with_generator<Generator: FlamegraphGenerator>(
+    artifact_path: &Path,
+    prover_toml_path: &Path,
+    flamegraph_generator: &Generator,
+    output_path: &Path,
+) -> eyre::Result<()> {
+    let mut program =
+        read_program_from_file(artifact_path).context("Error reading program from file")?;
+
+    let input_string =
+        std::fs::read_to_string(prover_toml_path).context("Error reading the inputs file")?;
+    let mut inputs_map = Format::Toml.parse(&input_string, &program.abi)?;
+    inputs_map.remove(MAIN_RETURN_NAME);
+    let initial_witness = program.abi.encode(&inputs_map, None)?;
+
+    let (_, profiling_samples) = execute_program_with_profiling(
+        &program.bytecode,
+        initial_witness,
+        &Bn254BlackBoxSolver,
+        &mut DefaultForeignCallExecutor::new(true, None),
+    )
+    .map_err(|error| eyre::eyre!("Error executing the program: {error}"))?;
+
+    let main_name = program.names.first().cloned().unwrap_or_else(|| "main".to_string());
+    let bytecode = std::mem::take(&mut program.bytecode);
+    let debug_artifact: DebugArtifact = program.into();
+
+    let samples = brillig_samples(profiling_samples, &bytecode, &debug_artifact);
+
+    flamegraph_generator.generate_flamegraph(
+        samples,
+        &debug_artifact,
+        artifact_path.to_str().unwrap(),
+        &main_name,
+        &Path::new(&output_path).join(Path::new(&format!("{}_brillig_trace.svg", &main_name))),
+    )?;
+
+    Ok(())
+}
+
+/// Counts the samples taken with the same call stack, attributing them to the source call stack
+/// and to the Brillig opcode which was executed
+fn brillig_samples(
+    profiling_samples: Vec<ProfilingSample>,
+    bytecode: &Program<FieldElement>,
+    debug_artifact: &DebugArtifact,
+) -> Vec<Sample> {
+    let mut counts: BTreeMap<Vec<(usize, OpcodeLocation)>, usize> = BTreeMap::new();
+    for sample in profiling_samples {
+        let call_stack = sample
+            .call_stack
+            .into_iter()
+            .map(|location| (location.acir_function_index, location.opcode_location))
+            .collect();
+        *counts.entry(call_stack).or_default() += 1;
+    }
+
+    counts
+        .into_iter()
+        .map(|(call_stack, count)| {
+            let opcode = match call_stack.last() {
+                Some(&(function_index, OpcodeLocation::Brillig { acir_index, brillig_index })) => {
+                    let circuit = &bytecode.functions[function_index];
+                    let Opcode::BrilligCall { id, .. } = &circuit.opcodes[acir_index] else {
+                        unreachable!("Brillig opcodes are only executed by Brillig calls");
+                    };
+                    let brillig_bytecode = &bytecode.unconstrained_functions[*id as usize];
+                    format_brillig_opcode(&brillig_bytecode.bytecode[brillig_index])
+                }
+                _ => "unknown".to_string(),
+            };
+
+            let call_stack = call_stack
+                .iter()
+                .flat_map(|(function_index, opcode_location)| {
+                    debug_artifact.debug_symbols[*function_index]
+                        .opcode_location(opcode_location)
+                        .unwrap_or_default()
+                })
+                .collect();
+
+            Sample { call_stack, opcode, count }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use acir::brillig::{MemoryAddress, Opcode as BrilligOpcode};
+    use acir::circuit::brillig::BrilligBytecode;
+    use acir::circuit::{Circuit, Opcode, OpcodeLocation, Program};
+    use acir::FieldElement;
+    use color_eyre::eyre::{self};
+    use fm::{codespan_files::Files, FileId};
+    use noirc_artifacts::program::ProgramArtifact;
+    use noirc_driver::DebugFile;
+    use noirc_errors::debug_info::{DebugInfo, ProgramDebugInfo};
+    use noirc_errors::{Location, Span};
+    use std::{collections::BTreeMap, path::Path};
+
+    use crate::flamegraph::{generate_folded_sorted_lines, Sample};
+
+    #[derive(Default)]
+    struct TestFlamegraphGenerator {}
+
+    impl super::FlamegraphGenerator for TestFlamegraphGenerator {
+        fn generate_flamegraph<'files>(
+            &self,
+            samples: Vec<Sample>,
+            files: &'files impl Files<'files, FileId = fm::FileId>,
+            _artifact_name: &str,
+            _function_name: &str,
+            output_path: &Path,
+        ) -> eyre::Result<()> {
+            // Write the collapsed stacks instead of rendering them
+            let folded_lines = generate_folded_sorted_lines(samples, files);
+            std::fs::write(output_path, folded_lines.join("\n")).unwrap();
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn smoke_test() {
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        let artifact_path = temp_dir.path().join("test.json");
+        let prover_toml_path = temp_dir.path().join("Prover.toml");
+
+        // A circuit calling an unconstrained function which sets a value and stops
+        let circuit = Circuit {
+            opcodes: vec![Opcode::BrilligCall {
+                id: 0,
+                inputs: Vec::new(),
+                outputs: Vec::new(),
+                predicate: None,
+            }],
+            ..Circuit::default()
+        };
+        let unconstrained_function = BrilligBytecode {
+            bytecode: vec![
+                BrilligOpcode::Const {
+                    destination: MemoryAddress::from(0),
+                    bit_size: 32,
+                    value: FieldElement::from(1_usize),
+                },
+                BrilligOpcode::Stop { return_data_offset: 0, return_data_size: 0 },
+            ],
+        };
+
+        let source = "fn main() { unconstrained_function() }";
+        let file_id = FileId::default();
+        let call_location = Location::new(Span::from(12..36), file_id);
+        let mut debug_info = DebugInfo::default();
+        for brillig_index in 0..2 {
+            debug_info.locations.insert(
+                OpcodeLocation::Brillig { acir_index: 0, brillig_index },
+                vec![call_location],
+            );
+        }
+        let debug_file = DebugFile { source: source.to_string(), path: "main.nr".into() };
+
+        let artifact = ProgramArtifact {
+            noir_version: "0.0.0".to_string(),
+            hash: 27,
+            abi: noirc_abi::Abi::default(),
+            bytecode: Program {
+                functions: vec![circuit],
+                unconstrained_functions: vec![unconstrained_function],
+            },
+            debug_symbols: ProgramDebugInfo { debug_infos: vec![debug_info] },
+            file_map: BTreeMap::from([(file_id, debug_file)]),
+            names: vec!["main".to_string()],
+            auto_folded: Vec::new(),
+            brillig_register_usage: Vec::new(),
+        };
+
+        // Write the artifact and an empty inputs file
+        let artifact_file = std::fs::File::create(&artifact_path).unwrap();
+        serde_json::to_writer(artifact_file, &artifact).unwrap();
+        std::fs::write(&prover_toml_path, "").unwrap();
+
+        let flamegraph_generator = TestFlamegraphGenerator::default();
+
+        super::run_with_generator(
+            &artifact_path,
+            &prover_toml_path,
+            &flamegraph_generator,
+            temp_dir.path(),
+        )
+        .expect("should run without errors");
+
+        // Check that each executed Brillig opcode was sampled under the call to the unconstrained function
+        let output_file = temp_dir.path().join("main_brillig_trace.svg");
+        let folded_lines = std::fs::read_to_string(output_file).unwrap();
+        let folded_lines: Vec<&str> = folded_lines.lines().collect();
+        assert_eq!(
+            folded_lines,
+            vec![
+                "main.nr:1:13::unconstrained_function();brillig::const 1",
+                "main.nr:1:13::unconstrained_function();brillig::stop 1",
+            ]
+        );
+    }
+}
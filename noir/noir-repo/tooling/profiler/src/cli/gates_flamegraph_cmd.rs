@@ -5,7 +5,7 @@ use color_eyre::eyre::{self, Context};
 
 use noirc_artifacts::debug::DebugArtifact;
 
-use crate::flamegraph::{FlamegraphGenerator, InfernoFlamegraphGenerator};
+use crate::flamegraph::{acir_samples, FlamegraphGenerator, InfernoFlamegraphGenerator};
 use crate::fs::read_program_from_file;
 use crate::gates_provider::{BackendGatesProvider, GatesProvider};
 
@@ -65,10 +65,14 @@ fn run_with_provider<Provider: GatesProvider, Generator: FlamegraphGenerator>(
             func_gates.circuit_size
         );
 
-        flamegraph_generator.generate_flamegraph(
+        let samples = acir_samples(
             func_gates.gates_per_opcode,
-            bytecode.opcodes,
+            &bytecode.opcodes,
             &debug_artifact.debug_symbols[func_idx],
+        );
+
+        flamegraph_generator.generate_flamegraph(
+            samples,
             &debug_artifact,
             artifact_path.to_str().unwrap(),
             &func_name,
@@ -81,7 +85,7 @@ fn run_with_provider<Provider: GatesProvider, Generator: FlamegraphGenerator>(
 
 #[cfg(test)]
 mod tests {
-    use acir::circuit::{Circuit, Opcode, Program};
+    use acir::circuit::{Circuit, Program};
     use color_eyre::eyre::{self};
     use fm::codespan_files::Files;
     use noirc_artifacts::program::ProgramArtifact;
@@ -91,6 +95,7 @@ mod tests {
         path::{Path, PathBuf},
     };
 
+    use crate::flamegraph::Sample;
     use crate::gates_provider::{BackendGatesReport, BackendGatesResponse, GatesProvider};
 
     struct TestGateProvider {
@@ -112,11 +117,9 @@ mod tests {
     struct TestFlamegraphGenerator {}
 
     impl super::FlamegraphGenerator for TestFlamegraphGenerator {
-        fn generate_flamegraph<'files, F>(
+        fn generate_flamegraph<'files>(
             &self,
-            _samples_per_opcode: Vec<usize>,
-            _opcodes: Vec<Opcode<F>>,
-            _debug_symbols: &DebugInfo,
+            _samples: Vec<Sample>,
             _files: &'files impl Files<'files, FileId = fm::FileId>,
             _artifact_name: &str,
             _function_name: &str,
@@ -144,6 +147,8 @@ mod tests {
             debug_symbols: ProgramDebugInfo { debug_infos: vec![DebugInfo::default()] },
             file_map: BTreeMap::default(),
             names: vec!["main".to_string()],
+            auto_folded: Vec::new(),
+            brillig_register_usage: Vec::new(),
         };
 
         // Write the artifact to a file
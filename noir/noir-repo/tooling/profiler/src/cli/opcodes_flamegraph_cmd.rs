@@ -5,7 +5,7 @@ use color_eyre::eyre::{self, Context};
 
 use noirc_artifacts::debug::DebugArtifact;
 
-use crate::flamegraph::{FlamegraphGenerator, InfernoFlamegraphGenerator};
+use crate::flamegraph::{acir_samples, FlamegraphGenerator, InfernoFlamegraphGenerator};
 use crate::fs::read_program_from_file;
 
 #[derive(Debug, Clone, Args)]
@@ -46,10 +46,14 @@ fn run_with_generator<Generator: FlamegraphGenerator>(
     {
         println!("Opcode count: {}", bytecode.opcodes.len());
 
-        flamegraph_generator.generate_flamegraph(
+        let samples = acir_samples(
             bytecode.opcodes.iter().map(|_op| 1).collect(),
-            bytecode.opcodes,
+            &bytecode.opcodes,
             &debug_artifact.debug_symbols[func_idx],
+        );
+
+        flamegraph_generator.generate_flamegraph(
+            samples,
             &debug_artifact,
             artifact_path.to_str().unwrap(),
             &func_name,
@@ -62,22 +66,22 @@ fn run_with_generator<Generator: FlamegraphGenerator>(
 
 #[cfg(test)]
 mod tests {
-    use acir::circuit::{Circuit, Opcode, Program};
+    use acir::circuit::{Circuit, Program};
     use color_eyre::eyre::{self};
     use fm::codespan_files::Files;
     use noirc_artifacts::program::ProgramArtifact;
     use noirc_errors::debug_info::{DebugInfo, ProgramDebugInfo};
     use std::{collections::BTreeMap, path::Path};
 
+    use crate::flamegraph::Sample;
+
     #[derive(Default)]
     struct TestFlamegraphGenerator {}
 
     impl super::FlamegraphGenerator for TestFlamegraphGenerator {
-        fn generate_flamegraph<'files, F>(
+        fn generate_flamegraph<'files>(
             &self,
-            _samples_per_opcode: Vec<usize>,
-            _opcodes: Vec<Opcode<F>>,
-            _debug_symbols: &DebugInfo,
+            _samples: Vec<Sample>,
             _files: &'files impl Files<'files, FileId = fm::FileId>,
             _artifact_name: &str,
             _function_name: &str,
@@ -105,6 +109,8 @@ mod tests {
             debug_symbols: ProgramDebugInfo { debug_infos: vec![DebugInfo::default()] },
             file_map: BTreeMap::default(),
             names: vec!["main".to_string()],
+            auto_folded: Vec::new(),
+            brillig_register_usage: Vec::new(),
         };
 
         // Write the artifact to a file
@@ -17,13 +17,37 @@ pub(crate) struct FoldedStackItem {
     pub(crate) nested_items: BTreeMap<String, FoldedStackItem>,
 }
 
+/// A number of samples attributed to an opcode, along with the source call stack of the opcode
+#[derive(Debug, Clone)]
+pub(crate) struct Sample {
+    pub(crate) call_stack: Vec<Location>,
+    pub(crate) opcode: String,
+    pub(crate) count: usize,
+}
+
+/// Attributes the given number of samples to each opcode of an ACIR circuit
+pub(crate) fn acir_samples<F>(
+    samples_per_opcode: Vec<usize>,
+    opcodes: &[Opcode<F>],
+    debug_symbols: &DebugInfo,
+) -> Vec<Sample> {
+    samples_per_opcode
+        .into_iter()
+        .enumerate()
+        .map(|(opcode_index, count)| Sample {
+            call_stack: debug_symbols
+                .opcode_location(&OpcodeLocation::Acir(opcode_index))
+                .unwrap_or_default(),
+            opcode: format_opcode(&opcodes[opcode_index]),
+            count,
+        })
+        .collect()
+}
+
 pub(crate) trait FlamegraphGenerator {
-    #[allow(clippy::too_many_arguments)]
-    fn generate_flamegraph<'files, F>(
+    fn generate_flamegraph<'files>(
         &self,
-        samples_per_opcode: Vec<usize>,
-        opcodes: Vec<Opcode<F>>,
-        debug_symbols: &DebugInfo,
+        samples: Vec<Sample>,
         files: &'files impl Files<'files, FileId = fm::FileId>,
         artifact_name: &str,
         function_name: &str,
@@ -36,18 +60,15 @@ pub(crate) struct InfernoFlamegraphGenerator {
 }
 
 impl FlamegraphGenerator for InfernoFlamegraphGenerator {
-    fn generate_flamegraph<'files, F>(
+    fn generate_flamegraph<'files>(
         &self,
-        samples_per_opcode: Vec<usize>,
-        opcodes: Vec<Opcode<F>>,
-        debug_symbols: &DebugInfo,
+        samples: Vec<Sample>,
         files: &'files impl Files<'files, FileId = fm::FileId>,
         artifact_name: &str,
         function_name: &str,
         output_path: &Path,
     ) -> eyre::Result<()> {
-        let folded_lines =
-            generate_folded_sorted_lines(samples_per_opcode, opcodes, debug_symbols, files);
+        let folded_lines = generate_folded_sorted_lines(samples, files);
 
         let flamegraph_file = std::fs::File::create(output_path)?;
         let flamegraph_writer = BufWriter::new(flamegraph_file);
@@ -72,28 +93,26 @@ impl FlamegraphGenerator for InfernoFlamegraphGenerator {
     }
 }
 
-fn generate_folded_sorted_lines<'files, F>(
-    samples_per_opcode: Vec<usize>,
-    opcodes: Vec<Opcode<F>>,
-    debug_symbols: &DebugInfo,
+pub(crate) fn generate_folded_sorted_lines<'files>(
+    samples: Vec<Sample>,
     files: &'files impl Files<'files, FileId = fm::FileId>,
 ) -> Vec<String> {
-    // Create a nested hashmap with the stack items, folding the gates for all the callsites that are equal
+    // Create a nested hashmap with the stack items, folding the samples for all the callsites that are equal
     let mut folded_stack_items = BTreeMap::new();
 
-    samples_per_opcode.into_iter().enumerate().for_each(|(opcode_index, gates)| {
-        let call_stack = debug_symbols.locations.get(&OpcodeLocation::Acir(opcode_index));
-        let location_names = if let Some(call_stack) = call_stack {
-            call_stack
+    samples.into_iter().for_each(|sample| {
+        let location_names = if sample.call_stack.is_empty() {
+            vec!["unknown".to_string()]
+        } else {
+            sample
+                .call_stack
                 .iter()
                 .map(|location| location_to_callsite_label(*location, files))
-                .chain(std::iter::once(format_opcode(&opcodes[opcode_index])))
+                .chain(std::iter::once(sample.opcode))
                 .collect::<Vec<String>>()
-        } else {
-            vec!["unknown".to_string()]
         };
 
-        add_locations_to_folded_stack_items(&mut folded_stack_items, location_names, gates);
+        add_locations_to_folded_stack_items(&mut folded_stack_items, location_names, sample.count);
     });
 
     to_folded_sorted_lines(&folded_stack_items, Default::default())
@@ -188,7 +207,7 @@ mod tests {
     use noirc_errors::{debug_info::DebugInfo, Location, Span};
     use std::{collections::BTreeMap, path::Path};
 
-    use super::generate_folded_sorted_lines;
+    use super::{acir_samples, generate_folded_sorted_lines};
 
     fn find_spans_for(source: &str, needle: &str) -> Vec<Span> {
         let mut spans = Vec::new();
@@ -290,9 +309,7 @@ mod tests {
         ];
 
         let actual_folded_sorted_lines = generate_folded_sorted_lines(
-            samples_per_opcode,
-            opcodes,
-            &debug_info,
+            acir_samples(samples_per_opcode, &opcodes, &debug_info),
             fm.as_file_map(),
         );
 
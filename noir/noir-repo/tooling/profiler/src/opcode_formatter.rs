@@ -1,3 +1,4 @@
+use acir::brillig::{BinaryFieldOp, BinaryIntOp, BlackBoxOp, Opcode as BrilligOpcode};
 use acir::circuit::{directives::Directive, opcodes::BlackBoxFuncCall, Opcode};
 
 fn format_blackbox_function<F>(call: &BlackBoxFuncCall<F>) -> String {
@@ -51,3 +52,89 @@ fn format_opcode_kind<F>(opcode: &Opcode<F>) -> String {
 pub(crate) fn format_opcode<F>(opcode: &Opcode<F>) -> String {
     format!("opcode::{}", format_opcode_kind(opcode))
 }
+
+fn format_brillig_black_box_op(op: &BlackBoxOp) -> String {
+    match op {
+        BlackBoxOp::AES128Encrypt { .. } => "aes128_encrypt".to_string(),
+        BlackBoxOp::Sha256 { .. } => "sha256".to_string(),
+        BlackBoxOp::Blake2s { .. } => "blake2s".to_string(),
+        BlackBoxOp::Blake3 { .. } => "blake3".to_string(),
+        BlackBoxOp::Keccak256 { .. } => "keccak256".to_string(),
+        BlackBoxOp::Keccakf1600 { .. } => "keccakf1600".to_string(),
+        BlackBoxOp::EcdsaSecp256k1 { .. } => "ecdsa_secp256k1".to_string(),
+        BlackBoxOp::EcdsaSecp256r1 { .. } => "ecdsa_secp256r1".to_string(),
+        BlackBoxOp::SchnorrVerify { .. } => "schnorr_verify".to_string(),
+        BlackBoxOp::PedersenCommitment { .. } => "pedersen_commitment".to_string(),
+        BlackBoxOp::PedersenHash { .. } => "pedersen_hash".to_string(),
+        BlackBoxOp::MultiScalarMul { .. } => "multi_scalar_mul".to_string(),
+        BlackBoxOp::EmbeddedCurveAdd { .. } => "embedded_curve_add".to_string(),
+        BlackBoxOp::BigIntAdd { .. } => "big_int_add".to_string(),
+        BlackBoxOp::BigIntSub { .. } => "big_int_sub".to_string(),
+        BlackBoxOp::BigIntMul { .. } => "big_int_mul".to_string(),
+        BlackBoxOp::BigIntDiv { .. } => "big_int_div".to_string(),
+        BlackBoxOp::BigIntFromLeBytes { .. } => "big_int_from_le_bytes".to_string(),
+        BlackBoxOp::BigIntToLeBytes { .. } => "big_int_to_le_bytes".to_string(),
+        BlackBoxOp::Poseidon2Permutation { .. } => "poseidon2_permutation".to_string(),
+        BlackBoxOp::Sha256Compression { .. } => "sha256_compression".to_string(),
+        BlackBoxOp::ToRadix { .. } => "to_radix".to_string(),
+    }
+}
+
+fn format_binary_field_op(op: &BinaryFieldOp) -> String {
+    match op {
+        BinaryFieldOp::Add => "add".to_string(),
+        BinaryFieldOp::Sub => "sub".to_string(),
+        BinaryFieldOp::Mul => "mul".to_string(),
+        BinaryFieldOp::Div => "fdiv".to_string(),
+        BinaryFieldOp::IntegerDiv => "div".to_string(),
+        BinaryFieldOp::Equals => "eq".to_string(),
+        BinaryFieldOp::LessThan => "lt".to_string(),
+        BinaryFieldOp::LessThanEquals => "lt_eq".to_string(),
+    }
+}
+
+fn format_binary_int_op(op: &BinaryIntOp) -> String {
+    match op {
+        BinaryIntOp::Add => "add".to_string(),
+        BinaryIntOp::Sub => "sub".to_string(),
+        BinaryIntOp::Mul => "mul".to_string(),
+        BinaryIntOp::Div => "div".to_string(),
+        BinaryIntOp::Equals => "eq".to_string(),
+        BinaryIntOp::LessThan => "lt".to_string(),
+        BinaryIntOp::LessThanEquals => "lt_eq".to_string(),
+        BinaryIntOp::And => "and".to_string(),
+        BinaryIntOp::Or => "or".to_string(),
+        BinaryIntOp::Xor => "xor".to_string(),
+        BinaryIntOp::Shl => "shl".to_string(),
+        BinaryIntOp::Shr => "shr".to_string(),
+    }
+}
+
+fn format_brillig_opcode_kind<F>(opcode: &BrilligOpcode<F>) -> String {
+    match opcode {
+        BrilligOpcode::BinaryFieldOp { op, .. } => format!("field::{}", format_binary_field_op(op)),
+        BrilligOpcode::BinaryIntOp { op, .. } => {
+            format!("int::{}", format_binary_int_op(op))
+        }
+        BrilligOpcode::BlackBox(op) => format!("blackbox::{}", format_brillig_black_box_op(op)),
+        BrilligOpcode::Call { .. } => "call".to_string(),
+        BrilligOpcode::CalldataCopy { .. } => "calldata_copy".to_string(),
+        BrilligOpcode::Cast { .. } => "cast".to_string(),
+        BrilligOpcode::ConditionalMov { .. } => "cmov".to_string(),
+        BrilligOpcode::Const { .. } => "const".to_string(),
+        BrilligOpcode::ForeignCall { function, .. } => format!("foreign_call::{function}"),
+        BrilligOpcode::Jump { .. } => "jump".to_string(),
+        BrilligOpcode::JumpIf { .. } => "jump_if".to_string(),
+        BrilligOpcode::JumpIfNot { .. } => "jump_if_not".to_string(),
+        BrilligOpcode::Load { .. } => "load".to_string(),
+        BrilligOpcode::Mov { .. } => "mov".to_string(),
+        BrilligOpcode::Return => "return".to_string(),
+        BrilligOpcode::Stop { .. } => "stop".to_string(),
+        BrilligOpcode::Store { .. } => "store".to_string(),
+        BrilligOpcode::Trap { .. } => "trap".to_string(),
+    }
+}
+
+pub(crate) fn format_brillig_opcode<F>(opcode: &BrilligOpcode<F>) -> String {
+    format!("brillig::{}", format_brillig_opcode_kind(opcode))
+}